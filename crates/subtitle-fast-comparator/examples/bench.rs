@@ -235,6 +235,7 @@ fn run_comparator_bench(
         let configuration = Configuration {
             backend: kind,
             preprocess,
+            temporal_decay: 0.0,
         };
         let comparator = configuration.create_comparator();
 
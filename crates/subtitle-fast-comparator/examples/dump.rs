@@ -135,6 +135,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         let configuration = Configuration {
             backend: args.comparator,
             preprocess,
+            temporal_decay: 0.0,
         };
         let comparator = configuration.create_comparator();
 
@@ -1,9 +1,10 @@
 use std::cell::UnsafeCell;
+use std::time::Duration;
 
 use rayon::prelude::*;
 use subtitle_fast_types::{RoiConfig, VideoFrame};
 
-use crate::comparators::SubtitleComparator;
+use crate::comparators::{SubtitleComparator, temporal_weight};
 use crate::pipeline::{ComparisonReport, FeatureBlob, PreprocessSettings, ReportMetric};
 
 const TAG: &str = "bitset-cover";
@@ -17,11 +18,60 @@ thread_local! {
 
 pub struct BitsetCoverComparator {
     settings: PreprocessSettings,
+    temporal_decay: f32,
 }
 
 impl BitsetCoverComparator {
     pub fn new(settings: PreprocessSettings) -> Self {
-        Self { settings }
+        Self {
+            settings,
+            temporal_decay: 0.0,
+        }
+    }
+
+    /// Decays similarity by `exp(-per_second * seconds)` in [`SubtitleComparator::compare_at`];
+    /// zero (the default) ignores time.
+    pub fn with_temporal_decay(mut self, per_second: f32) -> Self {
+        self.temporal_decay = per_second.max(0.0);
+        self
+    }
+
+    fn weighted_compare(
+        &self,
+        reference: &FeatureBlob,
+        candidate: &FeatureBlob,
+        weight: f32,
+    ) -> ComparisonReport {
+        let Some(reference) = reference.downcast::<BitsetFeatures>(TAG) else {
+            return ComparisonReport::new(0.0, false);
+        };
+        let Some(candidate) = candidate.downcast::<BitsetFeatures>(TAG) else {
+            return ComparisonReport::new(0.0, false);
+        };
+        let reference = reference.as_ref();
+        let candidate = candidate.as_ref();
+        let Some((similarity, miss_fraction, parallel)) =
+            self.compare_features(reference, candidate)
+        else {
+            return ComparisonReport::new(0.0, false);
+        };
+        let decayed = similarity * weight;
+        // A decayed similarity counts as that much extra miss.
+        let same =
+            miss_fraction <= MISS_THRESHOLD && (weight >= 1.0 || 1.0 - decayed <= MISS_THRESHOLD);
+        ComparisonReport::with_details(
+            decayed,
+            same,
+            vec![
+                ReportMetric::new("miss_fraction", miss_fraction),
+                ReportMetric::new("threshold_miss", MISS_THRESHOLD),
+                ReportMetric::new("tolerance_px", TOLERANCE_PX as f32),
+                ReportMetric::new("temporal_weight", weight),
+                ReportMetric::new("parallel_min_words", PARALLEL_MIN_WORDS as f32),
+                ReportMetric::new("parallel_used", parallel as i32 as f32),
+                ReportMetric::new("parallel_threads", rayon::current_num_threads() as f32),
+            ],
+        )
     }
 
     fn build_features(&self, frame: &VideoFrame, roi: &RoiConfig) -> Option<BitsetFeatures> {
@@ -136,32 +186,17 @@ impl SubtitleComparator for BitsetCoverComparator {
     }
 
     fn compare(&self, reference: &FeatureBlob, candidate: &FeatureBlob) -> ComparisonReport {
-        let Some(reference) = reference.downcast::<BitsetFeatures>(TAG) else {
-            return ComparisonReport::new(0.0, false);
-        };
-        let Some(candidate) = candidate.downcast::<BitsetFeatures>(TAG) else {
-            return ComparisonReport::new(0.0, false);
-        };
-        let reference = reference.as_ref();
-        let candidate = candidate.as_ref();
-        let Some((similarity, miss_fraction, parallel)) =
-            self.compare_features(reference, candidate)
-        else {
-            return ComparisonReport::new(0.0, false);
-        };
-        let same = miss_fraction <= MISS_THRESHOLD;
-        ComparisonReport::with_details(
-            similarity,
-            same,
-            vec![
-                ReportMetric::new("miss_fraction", miss_fraction),
-                ReportMetric::new("threshold_miss", MISS_THRESHOLD),
-                ReportMetric::new("tolerance_px", TOLERANCE_PX as f32),
-                ReportMetric::new("parallel_min_words", PARALLEL_MIN_WORDS as f32),
-                ReportMetric::new("parallel_used", parallel as i32 as f32),
-                ReportMetric::new("parallel_threads", rayon::current_num_threads() as f32),
-            ],
-        )
+        self.weighted_compare(reference, candidate, 1.0)
+    }
+
+    fn compare_at(
+        &self,
+        reference: &FeatureBlob,
+        candidate: &FeatureBlob,
+        time_delta: Duration,
+    ) -> ComparisonReport {
+        let weight = temporal_weight(self.temporal_decay, time_delta);
+        self.weighted_compare(reference, candidate, weight)
     }
 }
 
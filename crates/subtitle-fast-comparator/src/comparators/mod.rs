@@ -1,9 +1,13 @@
 pub mod bitset_cover;
+pub mod perceptual_hash;
 pub mod sparse_chamfer;
 
 pub use bitset_cover::BitsetCoverComparator;
+pub use perceptual_hash::PerceptualHashComparator;
 pub use sparse_chamfer::SparseChamferComparator;
 
+use std::time::Duration;
+
 use crate::pipeline::{ComparisonReport, FeatureBlob};
 use subtitle_fast_types::{RoiConfig, VideoFrame};
 
@@ -17,4 +21,39 @@ pub trait SubtitleComparator: Send + Sync {
 
     /// Compares two feature blobs and produces a similarity report.
     fn compare(&self, reference: &FeatureBlob, candidate: &FeatureBlob) -> ComparisonReport;
+
+    /// Compares two blobs captured `time_delta` apart.
+    ///
+    /// Comparators built with a temporal decay scale the similarity by
+    /// `exp(-decay * seconds)` before deciding `same_segment`, so the same picture seen far
+    /// apart in time is less likely to be joined into one subtitle. Without a decay this is
+    /// [`compare`](Self::compare).
+    fn compare_at(
+        &self,
+        reference: &FeatureBlob,
+        candidate: &FeatureBlob,
+        time_delta: Duration,
+    ) -> ComparisonReport {
+        let _ = time_delta;
+        self.compare(reference, candidate)
+    }
+
+    /// Decides whether two blobs show the same subtitle.
+    ///
+    /// The metric is [`ComparisonReport::similarity`], in `0.0..=1.0`, where 1.0 means
+    /// identical. The blobs match when it is at least `threshold`, so a threshold of 0.0
+    /// accepts everything and anything above 1.0 accepts nothing. Blobs from another comparator
+    /// score 0.0. Unlike `same_segment`, the comparator's own secondary checks are ignored;
+    /// callers pick the strictness.
+    fn is_match(&self, reference: &FeatureBlob, candidate: &FeatureBlob, threshold: f32) -> bool {
+        self.compare(reference, candidate).similarity >= threshold
+    }
+}
+
+/// Similarity weight for two frames `time_delta` apart under a decay of `per_second`.
+pub(crate) fn temporal_weight(per_second: f32, time_delta: Duration) -> f32 {
+    if per_second <= 0.0 {
+        return 1.0;
+    }
+    (-per_second * time_delta.as_secs_f32()).exp()
 }
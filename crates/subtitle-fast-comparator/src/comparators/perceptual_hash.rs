@@ -0,0 +1,116 @@
+use std::time::Duration;
+
+use subtitle_fast_types::{RoiConfig, VideoFrame};
+
+use crate::comparators::{SubtitleComparator, temporal_weight};
+use crate::pipeline::ops::resize_average;
+use crate::pipeline::preprocess::extract_masked_patch;
+use crate::pipeline::{ComparisonReport, FeatureBlob, PreprocessSettings, ReportMetric};
+
+const TAG: &str = "perceptual-hash";
+/// dHash grid: each of the 8 rows compares 9 neighbouring cells into 8 bits.
+const HASH_COLUMNS: usize = 9;
+const HASH_ROWS: usize = 8;
+const HASH_BITS: u32 = 64;
+/// Largest Hamming distance still treated as the same subtitle.
+const MAX_DISTANCE: u32 = 10;
+
+/// Difference hash of the luma-band mask, downsampled to 9x8 cells.
+///
+/// Extracting and comparing cost a few hundred operations regardless of ROI size, which makes
+/// it a cheap first-pass gate in front of the heavier comparators. It tolerates small shifts
+/// and blur but cannot tell apart lines whose coarse shape is alike.
+pub struct PerceptualHashComparator {
+    settings: PreprocessSettings,
+    temporal_decay: f32,
+}
+
+impl PerceptualHashComparator {
+    pub fn new(settings: PreprocessSettings) -> Self {
+        Self {
+            settings,
+            temporal_decay: 0.0,
+        }
+    }
+
+    /// Decays similarity by `exp(-per_second * seconds)` in [`SubtitleComparator::compare_at`];
+    /// zero (the default) ignores time.
+    pub fn with_temporal_decay(mut self, per_second: f32) -> Self {
+        self.temporal_decay = per_second.max(0.0);
+        self
+    }
+
+    fn hash(&self, frame: &VideoFrame, roi: &RoiConfig) -> Option<u64> {
+        let patch = extract_masked_patch(frame, roi, self.settings)?;
+        if patch.is_empty() {
+            return None;
+        }
+        let cells = resize_average(
+            &patch.masked,
+            patch.width,
+            patch.height,
+            HASH_COLUMNS,
+            HASH_ROWS,
+        );
+        let mut hash = 0u64;
+        for row in cells.chunks_exact(HASH_COLUMNS) {
+            for pair in row.windows(2) {
+                hash = (hash << 1) | u64::from(pair[0] < pair[1]);
+            }
+        }
+        Some(hash)
+    }
+
+    fn weighted_compare(
+        &self,
+        reference: &FeatureBlob,
+        candidate: &FeatureBlob,
+        weight: f32,
+    ) -> ComparisonReport {
+        let Some(reference) = reference.downcast::<u64>(TAG) else {
+            return ComparisonReport::new(0.0, false);
+        };
+        let Some(candidate) = candidate.downcast::<u64>(TAG) else {
+            return ComparisonReport::new(0.0, false);
+        };
+        let distance = (*reference ^ *candidate).count_ones();
+        let similarity = 1.0 - distance as f32 / HASH_BITS as f32;
+        let decayed = similarity * weight;
+        let min_similarity = 1.0 - MAX_DISTANCE as f32 / HASH_BITS as f32;
+        let same = distance <= MAX_DISTANCE && (weight >= 1.0 || decayed >= min_similarity);
+        ComparisonReport::with_details(
+            decayed,
+            same,
+            vec![
+                ReportMetric::new("hamming_distance", distance as f32),
+                ReportMetric::new("max_distance", MAX_DISTANCE as f32),
+                ReportMetric::new("temporal_weight", weight),
+            ],
+        )
+    }
+}
+
+impl SubtitleComparator for PerceptualHashComparator {
+    fn name(&self) -> &'static str {
+        TAG
+    }
+
+    fn extract(&self, frame: &VideoFrame, roi: &RoiConfig) -> Option<FeatureBlob> {
+        let hash = self.hash(frame, roi)?;
+        Some(FeatureBlob::new(TAG, hash))
+    }
+
+    fn compare(&self, reference: &FeatureBlob, candidate: &FeatureBlob) -> ComparisonReport {
+        self.weighted_compare(reference, candidate, 1.0)
+    }
+
+    fn compare_at(
+        &self,
+        reference: &FeatureBlob,
+        candidate: &FeatureBlob,
+        time_delta: Duration,
+    ) -> ComparisonReport {
+        let weight = temporal_weight(self.temporal_decay, time_delta);
+        self.weighted_compare(reference, candidate, weight)
+    }
+}
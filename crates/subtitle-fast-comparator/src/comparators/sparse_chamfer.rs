@@ -1,10 +1,11 @@
 use rayon::prelude::*;
 use std::cell::RefCell;
 use std::mem;
+use std::time::Duration;
 
 use subtitle_fast_types::{RoiConfig, VideoFrame};
 
-use crate::comparators::SubtitleComparator;
+use crate::comparators::{SubtitleComparator, temporal_weight};
 use crate::pipeline::ops::sobel_magnitude_into;
 use crate::pipeline::preprocess::extract_masked_patch;
 use crate::pipeline::{
@@ -395,11 +396,61 @@ impl Scratch {
 
 pub struct SparseChamferComparator {
     settings: PreprocessSettings,
+    temporal_decay: f32,
 }
 
 impl SparseChamferComparator {
     pub fn new(settings: PreprocessSettings) -> Self {
-        Self { settings }
+        Self {
+            settings,
+            temporal_decay: 0.0,
+        }
+    }
+
+    /// Decays similarity by `exp(-per_second * seconds)` in [`SubtitleComparator::compare_at`];
+    /// zero (the default) ignores time.
+    pub fn with_temporal_decay(mut self, per_second: f32) -> Self {
+        self.temporal_decay = per_second.max(0.0);
+        self
+    }
+
+    fn weighted_compare(
+        &self,
+        reference: &FeatureBlob,
+        candidate: &FeatureBlob,
+        weight: f32,
+    ) -> ComparisonReport {
+        let Some(reference) = reference.downcast::<SparseChamferFeatures>(TAG) else {
+            return ComparisonReport::new(0.0, false);
+        };
+        let Some(candidate) = candidate.downcast::<SparseChamferFeatures>(TAG) else {
+            return ComparisonReport::new(0.0, false);
+        };
+        let (cost, match_fraction, dx, dy) = self.search_best_shift(&reference, &candidate);
+        if !cost.is_finite() {
+            return ComparisonReport::new(0.0, false);
+        }
+        let diag = 0.5 * (reference.diag + candidate.diag);
+        let sigma = (SIGMA_SCALE * diag).max(1e-3);
+        let core_similarity = (-((cost / sigma).powi(2))).exp();
+        let stroke_delta = (reference.stroke_width - candidate.stroke_width).abs();
+        let stroke_penalty = (-(stroke_delta / STROKE_SIGMA).powi(2)).exp();
+        let similarity = core_similarity * stroke_penalty * weight;
+        let same = similarity >= SIM_THRESHOLD && match_fraction >= MATCH_THRESHOLD;
+        ComparisonReport::with_details(
+            similarity,
+            same,
+            vec![
+                ReportMetric::new("best_cost_px", cost),
+                ReportMetric::new("match_fraction", match_fraction),
+                ReportMetric::new("stroke_penalty", stroke_penalty),
+                ReportMetric::new("temporal_weight", weight),
+                ReportMetric::new("shift_dx", dx as f32),
+                ReportMetric::new("shift_dy", dy as f32),
+                ReportMetric::new("threshold_similarity", SIM_THRESHOLD),
+                ReportMetric::new("threshold_match", MATCH_THRESHOLD),
+            ],
+        )
     }
 
     fn with_scratch<F, R>(&self, f: F) -> R
@@ -760,35 +811,16 @@ impl SubtitleComparator for SparseChamferComparator {
     }
 
     fn compare(&self, reference: &FeatureBlob, candidate: &FeatureBlob) -> ComparisonReport {
-        let Some(reference) = reference.downcast::<SparseChamferFeatures>(TAG) else {
-            return ComparisonReport::new(0.0, false);
-        };
-        let Some(candidate) = candidate.downcast::<SparseChamferFeatures>(TAG) else {
-            return ComparisonReport::new(0.0, false);
-        };
-        let (cost, match_fraction, dx, dy) = self.search_best_shift(&reference, &candidate);
-        if !cost.is_finite() {
-            return ComparisonReport::new(0.0, false);
-        }
-        let diag = 0.5 * (reference.diag + candidate.diag);
-        let sigma = (SIGMA_SCALE * diag).max(1e-3);
-        let core_similarity = (-((cost / sigma).powi(2))).exp();
-        let stroke_delta = (reference.stroke_width - candidate.stroke_width).abs();
-        let stroke_penalty = (-(stroke_delta / STROKE_SIGMA).powi(2)).exp();
-        let similarity = core_similarity * stroke_penalty;
-        let same = similarity >= SIM_THRESHOLD && match_fraction >= MATCH_THRESHOLD;
-        ComparisonReport::with_details(
-            similarity,
-            same,
-            vec![
-                ReportMetric::new("best_cost_px", cost),
-                ReportMetric::new("match_fraction", match_fraction),
-                ReportMetric::new("stroke_penalty", stroke_penalty),
-                ReportMetric::new("shift_dx", dx as f32),
-                ReportMetric::new("shift_dy", dy as f32),
-                ReportMetric::new("threshold_similarity", SIM_THRESHOLD),
-                ReportMetric::new("threshold_match", MATCH_THRESHOLD),
-            ],
-        )
+        self.weighted_compare(reference, candidate, 1.0)
+    }
+
+    fn compare_at(
+        &self,
+        reference: &FeatureBlob,
+        candidate: &FeatureBlob,
+        time_delta: Duration,
+    ) -> ComparisonReport {
+        let weight = temporal_weight(self.temporal_decay, time_delta);
+        self.weighted_compare(reference, candidate, weight)
     }
 }
@@ -2,13 +2,16 @@ use std::fmt;
 use std::str::FromStr;
 use std::sync::Arc;
 
-use crate::comparators::{BitsetCoverComparator, SparseChamferComparator, SubtitleComparator};
+use crate::comparators::{
+    BitsetCoverComparator, PerceptualHashComparator, SparseChamferComparator, SubtitleComparator,
+};
 use crate::pipeline::PreprocessSettings;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum Backend {
     BitsetCover,
     SparseChamfer,
+    PerceptualHash,
 }
 
 impl Backend {
@@ -16,12 +19,46 @@ impl Backend {
         match self {
             Backend::BitsetCover => "bitset-cover",
             Backend::SparseChamfer => "sparse-chamfer",
+            Backend::PerceptualHash => "perceptual-hash",
         }
     }
 
+    /// Every comparator, in the order menus and help text list them. All of them are pure Rust,
+    /// so this is also what [`Backend::available`] returns on every platform.
+    pub fn all() -> Vec<Backend> {
+        vec![
+            Backend::BitsetCover,
+            Backend::SparseChamfer,
+            Backend::PerceptualHash,
+        ]
+    }
+
     pub fn available() -> Vec<Backend> {
         Configuration::available_backends()
     }
+
+    /// Instantiates this backend's comparator directly from preprocessing settings.
+    pub fn create_comparator(self, preprocess: PreprocessSettings) -> Arc<dyn SubtitleComparator> {
+        self.create_with_decay(preprocess, 0.0)
+    }
+
+    fn create_with_decay(
+        self,
+        preprocess: PreprocessSettings,
+        temporal_decay: f32,
+    ) -> Arc<dyn SubtitleComparator> {
+        match self {
+            Backend::BitsetCover => {
+                Arc::new(BitsetCoverComparator::new(preprocess).with_temporal_decay(temporal_decay))
+            }
+            Backend::SparseChamfer => Arc::new(
+                SparseChamferComparator::new(preprocess).with_temporal_decay(temporal_decay),
+            ),
+            Backend::PerceptualHash => Arc::new(
+                PerceptualHashComparator::new(preprocess).with_temporal_decay(temporal_decay),
+            ),
+        }
+    }
 }
 
 impl fmt::Display for Backend {
@@ -49,6 +86,7 @@ impl FromStr for Backend {
         match lower.as_str() {
             "bitset-cover" => Ok(Backend::BitsetCover),
             "sparse-chamfer" => Ok(Backend::SparseChamfer),
+            "perceptual-hash" => Ok(Backend::PerceptualHash),
             _ => Err(BackendParseError(lower)),
         }
     }
@@ -58,18 +96,19 @@ impl FromStr for Backend {
 pub struct Configuration {
     pub backend: Backend,
     pub preprocess: PreprocessSettings,
+    /// Per-second decay applied to similarity by
+    /// [`SubtitleComparator::compare_at`]; 0 ignores the time between frames.
+    pub temporal_decay: f32,
 }
 
 impl Configuration {
     pub fn available_backends() -> Vec<Backend> {
-        vec![Backend::BitsetCover, Backend::SparseChamfer]
+        Backend::all()
     }
 
     pub fn create_comparator(&self) -> Arc<dyn SubtitleComparator> {
-        match self.backend {
-            Backend::BitsetCover => Arc::new(BitsetCoverComparator::new(self.preprocess)),
-            Backend::SparseChamfer => Arc::new(SparseChamferComparator::new(self.preprocess)),
-        }
+        self.backend
+            .create_with_decay(self.preprocess, self.temporal_decay)
     }
 }
 
@@ -4,7 +4,9 @@ pub mod comparators;
 pub mod config;
 pub mod pipeline;
 
-pub use comparators::{BitsetCoverComparator, SparseChamferComparator, SubtitleComparator};
+pub use comparators::{
+    BitsetCoverComparator, PerceptualHashComparator, SparseChamferComparator, SubtitleComparator,
+};
 pub use config::{Backend, ComparatorKind, ComparatorKindParseError, Configuration};
 pub use pipeline::{ComparisonReport, FeatureBlob, PreprocessSettings, ReportMetric};
 
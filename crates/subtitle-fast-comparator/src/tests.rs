@@ -1,7 +1,12 @@
+use std::time::Duration;
+
 use subtitle_fast_types::{RoiConfig, VideoFrame};
 
-use crate::pipeline::PreprocessSettings;
-use crate::{BitsetCoverComparator, SparseChamferComparator, SubtitleComparator};
+use crate::pipeline::{FeatureBlob, PreprocessSettings};
+use crate::{
+    Backend, BitsetCoverComparator, ComparatorKind, Configuration, PerceptualHashComparator,
+    SparseChamferComparator, SubtitleComparator,
+};
 
 fn frame_from_pixels(width: usize, height: usize, data: &[u8]) -> VideoFrame {
     let uv_rows = height.div_ceil(2);
@@ -132,3 +137,203 @@ fn bitset_cover_detects_large_offset() {
     assert!(!report.same_segment);
     assert!(report.similarity < 0.9);
 }
+
+#[test]
+fn comparators_reject_blobs_from_other_backends() {
+    let settings = PreprocessSettings {
+        target: 210,
+        delta: 20,
+    };
+    let mut pixels = vec![10u8; 16 * 12];
+    for y in 4..8 {
+        for x in 3..13 {
+            pixels[y * 16 + x] = 210;
+        }
+    }
+    let frame = frame_from_pixels(16, 12, &pixels);
+    let roi = full_roi();
+
+    let bitset = Backend::BitsetCover.create_comparator(settings);
+    let chamfer = Backend::SparseChamfer.create_comparator(settings);
+    assert_eq!(bitset.name(), BitsetCoverComparator::new(settings).name());
+    assert_eq!(
+        chamfer.name(),
+        SparseChamferComparator::new(settings).name()
+    );
+
+    let bitset_blob = bitset.extract(&frame, &roi).unwrap();
+    let chamfer_blob = chamfer.extract(&frame, &roi).unwrap();
+    assert!(bitset.compare(&bitset_blob, &bitset_blob).same_segment);
+    assert!(chamfer.compare(&chamfer_blob, &chamfer_blob).same_segment);
+
+    let mixed = bitset.compare(&bitset_blob, &chamfer_blob);
+    assert!(!mixed.same_segment);
+    assert_eq!(mixed.similarity, 0.0);
+    let mixed = chamfer.compare(&chamfer_blob, &bitset_blob);
+    assert!(!mixed.same_segment);
+    assert_eq!(mixed.similarity, 0.0);
+}
+
+/// Checks `is_match` at, just below, and just above the pair's similarity.
+fn assert_threshold_semantics(
+    comparator: &dyn SubtitleComparator,
+    reference: &FeatureBlob,
+    candidate: &FeatureBlob,
+) {
+    let similarity = comparator.compare(reference, candidate).similarity;
+    assert!(
+        similarity > 0.05 && similarity < 0.95,
+        "{}: {similarity}",
+        comparator.name()
+    );
+    assert!(comparator.is_match(reference, candidate, similarity));
+    assert!(comparator.is_match(reference, candidate, similarity - 0.05));
+    assert!(!comparator.is_match(reference, candidate, similarity + 0.05));
+}
+
+#[test]
+fn is_match_compares_similarity_against_threshold() {
+    let settings = PreprocessSettings {
+        target: 220,
+        delta: 25,
+    };
+    let mut base = vec![10u8; 20 * 12];
+    for y in 4..8 {
+        for x in 3..15 {
+            base[y * 20 + x] = 230;
+        }
+    }
+    let mut partial = vec![10u8; 20 * 12];
+    for y in 4..8 {
+        for x in 3..9 {
+            partial[y * 20 + x] = 230;
+        }
+    }
+    let roi = full_roi();
+    let frame_base = frame_from_pixels(20, 12, &base);
+    let frame_partial = frame_from_pixels(20, 12, &partial);
+
+    let bitset = BitsetCoverComparator::new(settings);
+    let chamfer = SparseChamferComparator::new(settings);
+    for comparator in [&bitset as &dyn SubtitleComparator, &chamfer] {
+        let reference = comparator.extract(&frame_base, &roi).unwrap();
+        let candidate = comparator.extract(&frame_partial, &roi).unwrap();
+        assert_threshold_semantics(comparator, &reference, &candidate);
+    }
+}
+
+#[test]
+fn temporal_decay_lowers_similarity_of_identical_blobs_far_apart() {
+    let settings = PreprocessSettings {
+        target: 220,
+        delta: 25,
+    };
+    let mut pixels = vec![10u8; 24 * 14];
+    for y in 5..9 {
+        for x in 4..20 {
+            pixels[y * 24 + x] = 230;
+        }
+    }
+    let frame = frame_from_pixels(24, 14, &pixels);
+    let roi = full_roi();
+
+    for backend in Configuration::available_backends() {
+        let comparator = Configuration {
+            backend,
+            preprocess: settings,
+            temporal_decay: 0.5,
+        }
+        .create_comparator();
+        let blob = comparator.extract(&frame, &roi).unwrap();
+
+        let near = comparator.compare_at(&blob, &blob, Duration::ZERO);
+        let far = comparator.compare_at(&blob, &blob, Duration::from_secs(10));
+        assert!(near.same_segment, "{backend}");
+        assert!(!far.same_segment, "{backend}");
+        assert!(
+            far.similarity < near.similarity,
+            "{backend}: {} vs {}",
+            far.similarity,
+            near.similarity
+        );
+        assert_eq!(near.similarity, comparator.compare(&blob, &blob).similarity);
+
+        let undecayed = backend.create_comparator(settings);
+        let blob = undecayed.extract(&frame, &roi).unwrap();
+        let far = undecayed.compare_at(&blob, &blob, Duration::from_secs(10));
+        assert!(far.same_segment, "{backend}");
+    }
+}
+
+fn hamming_distance(report: &crate::ComparisonReport) -> f32 {
+    report
+        .details
+        .iter()
+        .find(|metric| metric.name == "hamming_distance")
+        .map(|metric| metric.value)
+        .expect("hamming_distance metric")
+}
+
+#[test]
+fn perceptual_hash_distance_tracks_crop_similarity() {
+    let comparator = PerceptualHashComparator::new(PreprocessSettings {
+        target: 220,
+        delta: 25,
+    });
+    let line = |dx: usize| {
+        let mut pixels = vec![10u8; 72 * 24];
+        for (start, end) in [(6, 20), (26, 34), (40, 62)] {
+            for y in 8..16 {
+                for x in start + dx..end + dx {
+                    pixels[y * 72 + x] = 230;
+                }
+            }
+        }
+        frame_from_pixels(72, 24, &pixels)
+    };
+    let mut unrelated = vec![10u8; 72 * 24];
+    for y in 0..24 {
+        for x in 0..72 {
+            if (x / 4 + y / 3) % 2 == 0 && x > y {
+                unrelated[y * 72 + x] = 230;
+            }
+        }
+    }
+    let roi = full_roi();
+    let base = comparator.extract(&line(0), &roi).unwrap();
+    let shifted = comparator.extract(&line(2), &roi).unwrap();
+    let unrelated = comparator
+        .extract(&frame_from_pixels(72, 24, &unrelated), &roi)
+        .unwrap();
+
+    let identical = comparator.compare(&base, &base);
+    assert_eq!(hamming_distance(&identical), 0.0);
+    assert_eq!(identical.similarity, 1.0);
+    assert!(identical.same_segment);
+
+    let nudged = comparator.compare(&base, &shifted);
+    assert!(hamming_distance(&nudged) <= 10.0, "{nudged:?}");
+    assert!(nudged.same_segment);
+
+    let different = comparator.compare(&base, &unrelated);
+    assert!(hamming_distance(&different) > 10.0, "{different:?}");
+    assert!(!different.same_segment);
+    assert!(different.similarity < nudged.similarity);
+}
+
+#[test]
+fn every_comparator_kind_round_trips_through_its_name() {
+    let kinds = ComparatorKind::all();
+    assert_eq!(kinds, Configuration::available_backends());
+    for kind in kinds {
+        assert_eq!(kind.as_str().parse::<ComparatorKind>().ok(), Some(kind));
+        assert_eq!(
+            kind.as_str()
+                .to_ascii_uppercase()
+                .parse::<ComparatorKind>()
+                .ok(),
+            Some(kind)
+        );
+    }
+    assert!("no-such-comparator".parse::<ComparatorKind>().is_err());
+}
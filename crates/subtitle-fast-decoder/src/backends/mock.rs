@@ -26,6 +26,10 @@ impl MockProvider {
     const DEFAULT_CHANNEL_CAPACITY: usize = 8;
     const FPS: f64 = 60.0;
 
+    fn frame_time(index: usize) -> Duration {
+        Duration::from_secs_f64(index as f64 / Self::FPS)
+    }
+
     fn emit_frames(
         &self,
         tx: Sender<DecoderResult<VideoFrame>>,
@@ -55,7 +59,7 @@ impl MockProvider {
             let uv_rows = (self.height as usize).div_ceil(2);
             let uv_stride = self.stride;
             let uv_plane = vec![128u8; uv_stride * uv_rows];
-            let pts = Some(Duration::from_millis((index * 16) as u64));
+            let pts = Some(Self::frame_time(index));
             if should_skip_frame(&mut pending_drop, index as u64, pts) {
                 index += 1;
                 continue;
@@ -108,8 +112,8 @@ impl DecoderProvider for MockProvider {
         use crate::core::VideoMetadata;
 
         VideoMetadata {
-            duration: Some(Duration::from_secs_f64((self.frame_count as f64) * 0.016)),
-            fps: Some(60.0),
+            duration: Some(Self::frame_time(self.frame_count)),
+            fps: Some(Self::FPS),
             width: Some(self.width),
             height: Some(self.height),
             total_frames: Some(self.frame_count as u64),
@@ -238,6 +242,27 @@ mod tests {
         assert_eq!(frame.uv_plane().len(), 640 * 180);
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn mock_backend_reports_frame_rate_and_duration() {
+        let config = crate::config::Configuration {
+            backend: crate::config::Backend::Mock,
+            input: None,
+            channel_capacity: None,
+            output_format: crate::config::OutputFormat::Nv12,
+            start_frame: None,
+        };
+        let decoder = Box::new(MockProvider::new(&config).unwrap()) as DynDecoderProvider;
+        let metadata = decoder.metadata();
+        assert_eq!(metadata.frame_rate(), Some(60.0));
+        assert_eq!(metadata.duration(), Some(Duration::from_secs(2)));
+        assert_eq!(metadata.frame_time(30), Some(Duration::from_millis(500)));
+
+        let (_controller, mut stream) = decoder.open().unwrap();
+        let _ = stream.next().await.unwrap().unwrap();
+        let second = stream.next().await.unwrap().unwrap();
+        assert_eq!(second.pts(), metadata.frame_time(1));
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn mock_backend_honors_start_frame() {
         let config = crate::config::Configuration {
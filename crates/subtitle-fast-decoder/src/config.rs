@@ -220,6 +220,14 @@ impl Configuration {
         Ok(config)
     }
 
+    /// This configuration decoding with `backend` instead, rejected up front when the backend
+    /// is not usable in this build instead of later in `create_provider`.
+    pub fn with_backend(mut self, backend: Backend) -> DecoderResult<Self> {
+        ensure_available(backend, &compiled_backends())?;
+        self.backend = backend;
+        Ok(self)
+    }
+
     pub fn available_backends() -> Vec<Backend> {
         compiled_backends()
     }
@@ -275,6 +283,22 @@ impl Configuration {
     }
 }
 
+fn ensure_available(backend: Backend, available: &[Backend]) -> DecoderResult<()> {
+    if available.contains(&backend) {
+        return Ok(());
+    }
+    let names: Vec<&str> = available.iter().map(Backend::as_str).collect();
+    let available = if names.is_empty() {
+        "none".to_string()
+    } else {
+        names.join(", ")
+    };
+    Err(DecoderError::configuration(format!(
+        "backend '{}' is not available in this build (available: {available})",
+        backend.as_str()
+    )))
+}
+
 fn default_backend() -> Backend {
     if github_ci_active() {
         return Backend::Mock;
@@ -291,3 +315,41 @@ fn github_ci_active() -> bool {
         .map(|value| !value.is_empty() && value != "false")
         .unwrap_or(false)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn with_backend_accepts_available_backends() {
+        assert!(ensure_available(Backend::Mock, &[Backend::Mock]).is_ok());
+        for backend in Configuration::available_backends() {
+            let config = Configuration {
+                start_frame: Some(42),
+                ..Configuration::default()
+            }
+            .with_backend(backend)
+            .expect("available backend");
+            assert_eq!(config.backend, backend);
+            assert_eq!(config.start_frame, Some(42));
+        }
+    }
+
+    #[test]
+    fn with_backend_rejects_unavailable_backend() {
+        match ensure_available(Backend::Mock, &[]) {
+            Err(DecoderError::Configuration { message }) => {
+                assert!(message.contains("'mock'"), "{message}");
+                assert!(message.contains("available: none"), "{message}");
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+        if !github_ci_active() {
+            assert!(
+                Configuration::default()
+                    .with_backend(Backend::Mock)
+                    .is_err()
+            );
+        }
+    }
+}
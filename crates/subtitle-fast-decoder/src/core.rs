@@ -93,6 +93,35 @@ impl VideoMetadata {
         }
     }
 
+    /// Frame rate from container info, derived from duration and frame count when missing.
+    pub fn frame_rate(&self) -> Option<f64> {
+        if let Some(fps) = self.fps.filter(|fps| fps.is_finite() && *fps > 0.0) {
+            return Some(fps);
+        }
+        let seconds = self.duration?.as_secs_f64();
+        let total = self.total_frames?;
+        if seconds <= 0.0 || total == 0 {
+            return None;
+        }
+        Some(total as f64 / seconds)
+    }
+
+    /// Stream duration from container info, derived from frame count and rate when missing.
+    pub fn duration(&self) -> Option<Duration> {
+        if let Some(duration) = self.duration.filter(|duration| !duration.is_zero()) {
+            return Some(duration);
+        }
+        let total = self.total_frames?;
+        let fps = self.fps.filter(|fps| fps.is_finite() && *fps > 0.0)?;
+        Some(Duration::from_secs_f64(total as f64 / fps))
+    }
+
+    /// Presentation time of `frame_index` assuming a constant frame rate.
+    pub fn frame_time(&self, frame_index: u64) -> Option<Duration> {
+        let fps = self.frame_rate()?;
+        Some(Duration::from_secs_f64(frame_index as f64 / fps))
+    }
+
     pub fn duration_ms(&self) -> Option<f64> {
         self.duration.map(|d| d.as_secs_f64() * 1000.0)
     }
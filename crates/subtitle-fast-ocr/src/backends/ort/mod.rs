@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Mutex;
@@ -6,14 +7,25 @@ use ndarray::{Array4, ArrayD, ArrayView2, Axis};
 use ort::session::{Session, builder::GraphOptimizationLevel};
 use ort::value::Tensor;
 
-use crate::{OcrEngine, OcrError, OcrRegion, OcrRequest, OcrResponse, OcrText};
+use crate::plane::resize_bilinear;
+use crate::request::clamp_to_plane;
+use crate::{
+    DecodeMode, InputPadding, LumaPlane, OcrCapabilities, OcrEngine, OcrError, OcrRegion,
+    OcrRequest, OcrResponse, OcrText, order_regions,
+};
 
 const DEFAULT_MODEL_PATH: &str = "models/ch_PP-OCRv5_rec_infer.onnx";
 const DEFAULT_DICT_PATH: &str = "models/ch_PP-OCRv5_rec_infer.txt";
 const DEFAULT_INPUT_HEIGHT: usize = 48;
 const DEFAULT_INPUT_WIDTH: usize = 320;
+const DEFAULT_DYNAMIC_MIN_WIDTH: usize = 64;
+const DEFAULT_DYNAMIC_MAX_WIDTH: usize = 1280;
+const DEFAULT_DYNAMIC_WIDTH_STRIDE: usize = 32;
 const DEFAULT_MEAN: f32 = 0.5;
 const DEFAULT_STD: f32 = 0.5;
+const DEFAULT_PROBABILITY_SAMPLE_ROWS: usize = 16;
+const PROBABILITY_RANGE_TOLERANCE: f32 = 0.05;
+const PROBABILITY_SUM_TOLERANCE: f32 = 0.05;
 
 #[derive(Debug, Clone)]
 pub struct OrtOcrConfig {
@@ -21,8 +33,62 @@ pub struct OrtOcrConfig {
     pub dictionary_path: PathBuf,
     pub input_height: usize,
     pub input_width: usize,
-    pub normalize_mean: f32,
-    pub normalize_std: f32,
+    /// Per-channel mean subtracted from the `[0, 1]` scaled pixel, in tensor channel order.
+    pub normalize_mean: [f32; 3],
+    /// Per-channel divisor applied after the mean; every entry must be finite and non-zero.
+    pub normalize_std: [f32; 3],
+    /// Rows sampled, spread across the sequence, when deciding whether the model emits
+    /// probabilities or raw logits; a majority of sampled rows decides for the whole tensor.
+    pub probability_sample_rows: usize,
+    /// Pick the input width per region from its aspect ratio instead of always using
+    /// `input_width`. Only valid for models exported with a dynamic width axis.
+    pub dynamic_width: Option<DynamicInputWidth>,
+    /// The dictionary file lists the CTC blank as its line `blank_index` rather than leaving it
+    /// implied. When `false`, the file holds only real tokens and the blank class is inserted
+    /// between them at `blank_index`.
+    pub dict_has_blank: bool,
+    /// Class the model uses for the CTC blank. Most PaddleOCR exports use 0; some models put
+    /// it last. Must be below the class count of the loaded dictionary.
+    pub blank_index: usize,
+    /// Keep empty dictionary lines as space tokens instead of skipping them. Some exported
+    /// dictionaries store the space class as a blank line; dropping it shifts every later class.
+    pub preserve_blank_tokens: bool,
+    /// Fill for input columns right of the scaled crop. `None` leaves them zero after
+    /// normalization, i.e. at the normalization mean.
+    pub input_padding: Option<InputPadding>,
+    /// How the model's class scores are decoded into text.
+    pub decode: DecodeMode,
+}
+
+/// Bounds for the per-region input width used with dynamic-shape models.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DynamicInputWidth {
+    pub min_width: usize,
+    pub max_width: usize,
+    /// Chosen widths are rounded to a multiple of this, matching the model's horizontal
+    /// downsampling.
+    pub stride: usize,
+}
+
+impl Default for DynamicInputWidth {
+    fn default() -> Self {
+        Self {
+            min_width: DEFAULT_DYNAMIC_MIN_WIDTH,
+            max_width: DEFAULT_DYNAMIC_MAX_WIDTH,
+            stride: DEFAULT_DYNAMIC_WIDTH_STRIDE,
+        }
+    }
+}
+
+impl OrtOcrConfig {
+    /// Default configuration using the same `mean`/`std` for every channel.
+    pub fn with_scalar_normalization(mean: f32, std: f32) -> Self {
+        Self {
+            normalize_mean: [mean; 3],
+            normalize_std: [std; 3],
+            ..Self::default()
+        }
+    }
 }
 
 impl Default for OrtOcrConfig {
@@ -32,8 +98,15 @@ impl Default for OrtOcrConfig {
             dictionary_path: PathBuf::from(DEFAULT_DICT_PATH),
             input_height: DEFAULT_INPUT_HEIGHT,
             input_width: DEFAULT_INPUT_WIDTH,
-            normalize_mean: DEFAULT_MEAN,
-            normalize_std: DEFAULT_STD,
+            normalize_mean: [DEFAULT_MEAN; 3],
+            normalize_std: [DEFAULT_STD; 3],
+            probability_sample_rows: DEFAULT_PROBABILITY_SAMPLE_ROWS,
+            dynamic_width: None,
+            dict_has_blank: false,
+            blank_index: 0,
+            preserve_blank_tokens: false,
+            input_padding: None,
+            decode: DecodeMode::Greedy,
         }
     }
 }
@@ -42,10 +115,15 @@ impl Default for OrtOcrConfig {
 pub struct OrtOcrEngine {
     session: Mutex<Session>,
     dictionary: Vec<String>,
+    blank_index: usize,
     input_height: usize,
     input_width: usize,
-    normalize_mean: f32,
-    normalize_std: f32,
+    normalize_mean: [f32; 3],
+    normalize_std: [f32; 3],
+    probability_sample_rows: usize,
+    dynamic_width: Option<DynamicInputWidth>,
+    input_padding: Option<InputPadding>,
+    decode: DecodeMode,
 }
 
 impl OrtOcrEngine {
@@ -59,7 +137,39 @@ impl OrtOcrEngine {
                 "ort OCR input dimensions must be non-zero",
             ));
         }
-        let dictionary = load_dictionary(&config.dictionary_path)?;
+        if config
+            .normalize_std
+            .iter()
+            .any(|std| *std == 0.0 || !std.is_finite())
+        {
+            return Err(OcrError::backend(format!(
+                "ort OCR normalization std must be finite and non-zero, got {:?}",
+                config.normalize_std
+            )));
+        }
+        if let Some(bounds) = config.dynamic_width
+            && (bounds.stride == 0 || bounds.min_width == 0 || bounds.min_width > bounds.max_width)
+        {
+            return Err(OcrError::backend(format!(
+                "ort OCR dynamic width needs a non-zero stride and 0 < min <= max, got {bounds:?}"
+            )));
+        }
+        if let Some(InputPadding::Value(value)) = config.input_padding
+            && !(0.0..=1.0).contains(&value)
+        {
+            return Err(OcrError::backend(format!(
+                "ort OCR padding value must be within [0, 1], got {value}"
+            )));
+        }
+        if config.decode == (DecodeMode::PrefixBeam { width: 0 }) {
+            return Err(OcrError::backend("ort OCR beam width must be non-zero"));
+        }
+        let dictionary = load_dictionary(
+            &config.dictionary_path,
+            config.dict_has_blank,
+            config.preserve_blank_tokens,
+            config.blank_index,
+        )?;
         let session = Session::builder()
             .map_err(|err| OcrError::backend(format!("failed to build ORT session: {err}")))?
             .with_optimization_level(GraphOptimizationLevel::Level3)
@@ -79,10 +189,15 @@ impl OrtOcrEngine {
         Ok(Self {
             session: Mutex::new(session),
             dictionary,
+            blank_index: config.blank_index,
             input_height: config.input_height,
             input_width: config.input_width,
             normalize_mean: config.normalize_mean,
             normalize_std: config.normalize_std,
+            probability_sample_rows: config.probability_sample_rows.max(1),
+            dynamic_width: config.dynamic_width,
+            input_padding: config.input_padding,
+            decode: config.decode,
         })
     }
 }
@@ -92,30 +207,37 @@ impl OcrEngine for OrtOcrEngine {
         "ort_ppocr"
     }
 
+    fn capabilities(&self) -> OcrCapabilities {
+        ort_capabilities()
+    }
+
     fn recognize(&self, request: &OcrRequest<'_>) -> Result<OcrResponse, OcrError> {
         let plane = request.plane();
         if plane.data().is_empty() {
             return Ok(OcrResponse::empty());
         }
 
+        let mut regions = request.regions().to_vec();
+        order_regions(&mut regions);
         let mut texts = Vec::new();
-        for region in request.regions() {
-            let Some(crop) = crop_region(
-                plane.data(),
-                plane.width(),
-                plane.height(),
-                plane.stride(),
-                region,
-            ) else {
+        for region in &regions {
+            let Some(crop) = crop_region(plane, region) else {
                 continue;
             };
 
+            let input_width = match self.dynamic_width {
+                Some(bounds) => {
+                    select_input_width(crop.width, crop.height, self.input_height, bounds)
+                }
+                None => self.input_width,
+            };
             let input = prepare_input_tensor(
                 &crop,
                 self.input_height,
-                self.input_width,
+                input_width,
                 self.normalize_mean,
                 self.normalize_std,
+                self.input_padding,
             )?;
 
             let tensor = Tensor::from_array(input)
@@ -148,7 +270,13 @@ impl OcrEngine for OrtOcrEngine {
                 continue;
             };
 
-            if let Some((text, confidence)) = decode_output(&output, &self.dictionary) {
+            if let Some((text, confidence)) = decode_output(
+                &output,
+                &self.dictionary,
+                self.blank_index,
+                self.probability_sample_rows,
+                self.decode,
+            ) {
                 let mut entry = OcrText::new(*region, text);
                 if let Some(value) = confidence {
                     entry = entry.with_confidence(value);
@@ -161,67 +289,58 @@ impl OcrEngine for OrtOcrEngine {
     }
 }
 
+/// Regions run through the session one at a time, and the scripts covered follow the loaded
+/// dictionary, so only the CTC confidence is advertised.
+fn ort_capabilities() -> OcrCapabilities {
+    OcrCapabilities {
+        emits_confidence: true,
+        ..OcrCapabilities::default()
+    }
+}
+
 struct Crop {
     data: Vec<u8>,
     width: usize,
     height: usize,
 }
 
-fn crop_region(
-    data: &[u8],
-    frame_width: u32,
-    frame_height: u32,
-    stride: usize,
-    region: &OcrRegion,
-) -> Option<Crop> {
-    let frame_width = frame_width as usize;
-    let frame_height = frame_height as usize;
-    if frame_width == 0 || frame_height == 0 {
-        return None;
-    }
-
-    let left = region.x.floor().clamp(0.0, frame_width as f32) as usize;
-    let top = region.y.floor().clamp(0.0, frame_height as f32) as usize;
-    let right = (region.x + region.width)
-        .ceil()
-        .clamp(left as f32, frame_width as f32) as usize;
-    let bottom = (region.y + region.height)
-        .ceil()
-        .clamp(top as f32, frame_height as f32) as usize;
-
-    let width = right.saturating_sub(left);
-    let height = bottom.saturating_sub(top);
-    if width == 0 || height == 0 {
-        return None;
-    }
-
-    let mut out = Vec::with_capacity(width * height);
-    for row in top..bottom {
-        let start = row.saturating_mul(stride).saturating_add(left);
-        let end = start.saturating_add(width);
-        if end > data.len() {
-            break;
-        }
-        out.extend_from_slice(&data[start..end]);
-    }
-
-    if out.len() != width * height {
-        return None;
-    }
+fn crop_region(plane: &LumaPlane<'_>, region: &OcrRegion) -> Option<Crop> {
+    let region = clamp_to_plane(region, plane.width(), plane.height())?;
+    let left = region.x.floor() as usize;
+    let top = region.y.floor() as usize;
+    let width = ((region.x + region.width).ceil() as usize).saturating_sub(left);
+    let height = ((region.y + region.height).ceil() as usize).saturating_sub(top);
 
+    let data = plane.crop_8bit(left, top, width, height)?;
     Some(Crop {
-        data: out,
+        data,
         width,
         height,
     })
 }
 
+/// Width the crop needs at `target_height`, rounded up to `bounds.stride` and kept within
+/// the bounds (themselves snapped inward to the stride).
+fn select_input_width(
+    crop_width: usize,
+    crop_height: usize,
+    target_height: usize,
+    bounds: DynamicInputWidth,
+) -> usize {
+    let stride = bounds.stride.max(1);
+    let min = bounds.min_width.max(1).div_ceil(stride) * stride;
+    let max = (bounds.max_width / stride * stride).max(min);
+    let scaled = (crop_width.max(1) * target_height).div_ceil(crop_height.max(1));
+    (scaled.div_ceil(stride) * stride).clamp(min, max)
+}
+
 fn prepare_input_tensor(
     crop: &Crop,
     target_height: usize,
     target_width: usize,
-    mean: f32,
-    std: f32,
+    mean: [f32; 3],
+    std: [f32; 3],
+    padding: Option<InputPadding>,
 ) -> Result<Array4<f32>, OcrError> {
     let width = crop.width.max(1);
     let height = crop.height.max(1);
@@ -233,75 +352,52 @@ fn prepare_input_tensor(
     let mut chw = vec![0.0f32; 3 * target_height * target_width];
     for y in 0..target_height {
         for x in 0..scaled_width {
-            let pixel = resized[y * scaled_width + x];
-            let value = (pixel / 255.0 - mean) / std;
+            let pixel = resized[y * scaled_width + x] / 255.0;
             for channel in 0..3 {
                 let offset = (channel * target_height + y) * target_width + x;
-                chw[offset] = value;
+                chw[offset] = (pixel - mean[channel]) / std[channel];
             }
         }
     }
-
-    Array4::from_shape_vec((1, 3, target_height, target_width), chw)
-        .map_err(|err| OcrError::backend(format!("failed to build OCR input tensor shape: {err}")))
-}
-
-fn resize_bilinear(
-    src: &[u8],
-    src_width: usize,
-    src_height: usize,
-    dst_width: usize,
-    dst_height: usize,
-) -> Vec<f32> {
-    if src_width == dst_width && src_height == dst_height {
-        return src.iter().map(|&v| v as f32).collect();
-    }
-
-    let mut dst = vec![0.0f32; dst_width * dst_height];
-    let scale_x = src_width as f32 / dst_width as f32;
-    let scale_y = src_height as f32 / dst_height as f32;
-
-    for y in 0..dst_height {
-        let src_y = (y as f32 + 0.5) * scale_y - 0.5;
-        let y0 = src_y.floor().clamp(0.0, (src_height - 1) as f32) as usize;
-        let y1 = (y0 + 1).min(src_height - 1);
-        let wy = src_y - y0 as f32;
-
-        for x in 0..dst_width {
-            let src_x = (x as f32 + 0.5) * scale_x - 0.5;
-            let x0 = src_x.floor().clamp(0.0, (src_width - 1) as f32) as usize;
-            let x1 = (x0 + 1).min(src_width - 1);
-            let wx = src_x - x0 as f32;
-
-            let v00 = src[y0 * src_width + x0] as f32;
-            let v01 = src[y0 * src_width + x1] as f32;
-            let v10 = src[y1 * src_width + x0] as f32;
-            let v11 = src[y1 * src_width + x1] as f32;
-
-            let v0 = v00 + (v01 - v00) * wx;
-            let v1 = v10 + (v11 - v10) * wx;
-            dst[y * dst_width + x] = v0 + (v1 - v0) * wy;
+    if let Some(padding) = padding
+        && scaled_width < target_width
+    {
+        let level = padding.level(&crop.data, width, height);
+        for channel in 0..3 {
+            let value = (level - mean[channel]) / std[channel];
+            for y in 0..target_height {
+                let row = (channel * target_height + y) * target_width;
+                chw[row + scaled_width..row + target_width].fill(value);
+            }
         }
     }
 
-    dst
+    Array4::from_shape_vec((1, 3, target_height, target_width), chw)
+        .map_err(|err| OcrError::backend(format!("failed to build OCR input tensor shape: {err}")))
 }
 
-fn load_dictionary(path: &Path) -> Result<Vec<String>, OcrError> {
+/// Loads the dictionary indexed by CTC class, with an empty entry at `blank_index`.
+fn load_dictionary(
+    path: &Path,
+    has_blank: bool,
+    preserve_blank_tokens: bool,
+    blank_index: usize,
+) -> Result<Vec<String>, OcrError> {
     let contents = fs::read_to_string(path).map_err(|err| {
         OcrError::backend(format!(
             "failed to read OCR dictionary {}: {err}",
             path.display()
         ))
     })?;
-    let mut dictionary = Vec::new();
-    for line in contents.lines() {
-        if line.is_empty() {
-            continue;
-        }
-        dictionary.push(line.to_string());
-    }
-    if dictionary.is_empty() {
+    let dictionary = parse_dictionary(&contents, has_blank, preserve_blank_tokens, blank_index)
+        .map_err(|classes| {
+            OcrError::backend(format!(
+                "ort OCR blank index {blank_index} is out of range for the {classes} classes of \
+                 OCR dictionary {}",
+                path.display()
+            ))
+        })?;
+    if dictionary.len() <= 1 {
         return Err(OcrError::backend(format!(
             "OCR dictionary at {} is empty",
             path.display()
@@ -310,15 +406,52 @@ fn load_dictionary(path: &Path) -> Result<Vec<String>, OcrError> {
     Ok(dictionary)
 }
 
-fn decode_output(output: &ArrayD<f32>, dictionary: &[String]) -> Option<(String, Option<f32>)> {
+/// Fails with the class count, blank included, when `blank_index` lies outside it.
+fn parse_dictionary(
+    contents: &str,
+    has_blank: bool,
+    preserve_blank_tokens: bool,
+    blank_index: usize,
+) -> Result<Vec<String>, usize> {
+    let mut dictionary: Vec<String> = contents
+        .lines()
+        .enumerate()
+        // Whatever the file names its blank (often an empty line), it only occupies its class.
+        .filter(|(line_index, _)| !(has_blank && *line_index == blank_index))
+        .filter_map(|(_, line)| match line {
+            "" if preserve_blank_tokens => Some(" ".to_string()),
+            "" => None,
+            token => Some(token.to_string()),
+        })
+        .collect();
+    if blank_index > dictionary.len() {
+        return Err(dictionary.len() + 1);
+    }
+    dictionary.insert(blank_index, String::new());
+    Ok(dictionary)
+}
+
+fn decode_output(
+    output: &ArrayD<f32>,
+    dictionary: &[String],
+    blank_index: usize,
+    probability_sample_rows: usize,
+    decode: DecodeMode,
+) -> Option<(String, Option<f32>)> {
     let view = output_to_time_major(output, dictionary.len())?;
-    let use_probabilities = is_probability_tensor(&view);
+    if view.shape()[1] <= blank_index {
+        return None;
+    }
+    // Decided once so every timestep uses the same confidence path.
+    let use_probabilities = is_probability_tensor(&view, probability_sample_rows);
+    if let DecodeMode::PrefixBeam { width } = decode {
+        return prefix_beam_decode(&view, dictionary, blank_index, use_probabilities, width);
+    }
 
     let mut text = String::new();
     let mut prev_idx = usize::MAX;
     let mut confidence_sum = 0.0f32;
     let mut confidence_count = 0u32;
-    let dict_len = dictionary.len();
 
     for row in view.axis_iter(Axis(0)) {
         let (mut best_idx, mut best_val) = (0usize, f32::NEG_INFINITY);
@@ -335,8 +468,8 @@ fn decode_output(output: &ArrayD<f32>, dictionary: &[String]) -> Option<(String,
             softmax_at(&row, best_idx)
         };
 
-        if best_idx != prev_idx && best_idx > 0 && best_idx <= dict_len {
-            text.push_str(&dictionary[best_idx - 1]);
+        if best_idx != prev_idx && best_idx != blank_index && best_idx < dictionary.len() {
+            text.push_str(&dictionary[best_idx]);
             confidence_sum += prob;
             confidence_count = confidence_count.saturating_add(1);
         }
@@ -356,13 +489,134 @@ fn decode_output(output: &ArrayD<f32>, dictionary: &[String]) -> Option<(String,
     Some((text, confidence))
 }
 
+/// Summed probabilities of a prefix's alignments ending in a blank and in its last class,
+/// both as natural logs.
+#[derive(Clone, Copy)]
+struct BeamScore {
+    blank: f64,
+    label: f64,
+}
+
+impl BeamScore {
+    const EMPTY: Self = Self {
+        blank: f64::NEG_INFINITY,
+        label: f64::NEG_INFINITY,
+    };
+
+    fn total(self) -> f64 {
+        log_add(self.blank, self.label)
+    }
+}
+
+/// CTC prefix-beam search. Classes past the dictionary are treated as non-emitting, like the
+/// blank. The confidence is the decoded text's probability, summed over its alignments, as a
+/// geometric mean per timestep, so it stays comparable across input widths.
+fn prefix_beam_decode(
+    view: &ArrayView2<'_, f32>,
+    dictionary: &[String],
+    blank_index: usize,
+    use_probabilities: bool,
+    width: usize,
+) -> Option<(String, Option<f32>)> {
+    let width = width.max(1);
+    let steps = view.shape()[0];
+    let mut beams: Vec<(Vec<usize>, BeamScore)> = vec![(
+        Vec::new(),
+        BeamScore {
+            blank: 0.0,
+            label: f64::NEG_INFINITY,
+        },
+    )];
+
+    for row in view.axis_iter(Axis(0)) {
+        let log_probs: Vec<f64> = if use_probabilities {
+            row.iter()
+                .map(|&value| f64::from(value.max(0.0)).ln())
+                .collect()
+        } else {
+            log_softmax(&row)
+        };
+        // Only the likeliest classes of the step can extend a prefix that stays in the beam.
+        let mut candidates: Vec<usize> = (0..dictionary.len().min(log_probs.len()))
+            .filter(|&class| class != blank_index)
+            .collect();
+        candidates.sort_by(|&a, &b| log_probs[b].total_cmp(&log_probs[a]).then(a.cmp(&b)));
+        candidates.truncate(width);
+
+        let mut next: HashMap<Vec<usize>, BeamScore> = HashMap::new();
+        for (prefix, score) in &beams {
+            let total = score.total();
+            let entry = next.entry(prefix.clone()).or_insert(BeamScore::EMPTY);
+            entry.blank = log_add(entry.blank, total + log_probs[blank_index]);
+            if let Some(&last) = prefix.last() {
+                // Repeating the last class without a blank in between collapses into it.
+                entry.label = log_add(entry.label, score.label + log_probs[last]);
+            }
+            for &class in &candidates {
+                // A repeat only starts a new character after a blank.
+                let from = if prefix.last() == Some(&class) {
+                    score.blank
+                } else {
+                    total
+                };
+                let mut extended = prefix.clone();
+                extended.push(class);
+                let entry = next.entry(extended).or_insert(BeamScore::EMPTY);
+                entry.label = log_add(entry.label, from + log_probs[class]);
+            }
+        }
+
+        let mut ranked: Vec<(Vec<usize>, BeamScore)> = next.into_iter().collect();
+        ranked.sort_by(|a, b| b.1.total().total_cmp(&a.1.total()).then(a.0.cmp(&b.0)));
+        ranked.truncate(width);
+        beams = ranked;
+    }
+
+    let (best, score) = beams.into_iter().next()?;
+    if best.is_empty() {
+        return None;
+    }
+    let text: String = best
+        .iter()
+        .map(|&class| dictionary[class].as_str())
+        .collect();
+    let confidence = (score.total() / steps.max(1) as f64).exp() as f32;
+    Some((text, Some(confidence)))
+}
+
+fn log_add(a: f64, b: f64) -> f64 {
+    if a == f64::NEG_INFINITY {
+        return b;
+    }
+    if b == f64::NEG_INFINITY {
+        return a;
+    }
+    let max = a.max(b);
+    max + ((a - max).exp() + (b - max).exp()).ln()
+}
+
+fn log_softmax(row: &ndarray::ArrayView1<'_, f32>) -> Vec<f64> {
+    let max = row
+        .iter()
+        .fold(f64::NEG_INFINITY, |max, &value| max.max(f64::from(value)));
+    let sum: f64 = row
+        .iter()
+        .map(|&value| (f64::from(value) - max).exp())
+        .sum();
+    let log_sum = max + sum.ln();
+    row.iter()
+        .map(|&value| f64::from(value) - log_sum)
+        .collect()
+}
+
+/// `classes` counts the blank; some models add one more class for a trailing space.
 fn output_to_time_major<'a>(
     output: &'a ArrayD<f32>,
-    dict_len: usize,
+    classes: usize,
 ) -> Option<ArrayView2<'a, f32>> {
     let shape = output.shape();
-    let classes_a = dict_len.saturating_add(1);
-    let classes_b = dict_len.saturating_add(2);
+    let classes_a = classes;
+    let classes_b = classes.saturating_add(1);
 
     match shape.len() {
         2 => {
@@ -396,30 +650,40 @@ fn output_to_time_major<'a>(
     }
 }
 
-fn is_probability_tensor(view: &ArrayView2<'_, f32>) -> bool {
-    let samples = view.shape()[0].min(3);
-    for idx in 0..samples {
-        let row = view.index_axis(Axis(0), idx);
-        let mut sum = 0.0f32;
-        let mut min = f32::INFINITY;
-        let mut max = f32::NEG_INFINITY;
-        for &value in row.iter() {
-            sum += value;
-            if value < min {
-                min = value;
-            }
-            if value > max {
-                max = value;
-            }
+fn is_probability_tensor(view: &ArrayView2<'_, f32>, sample_rows: usize) -> bool {
+    let rows = view.shape()[0];
+    if rows == 0 {
+        return true;
+    }
+    let samples = rows.min(sample_rows.max(1));
+    let mut votes = 0usize;
+    for sample in 0..samples {
+        // Spread samples over the sequence so blank-heavy leading rows cannot dominate.
+        let idx = sample * rows / samples;
+        if row_is_probability(&view.index_axis(Axis(0), idx)) {
+            votes += 1;
         }
-        if min < -0.05 || max > 1.05 {
-            return false;
+    }
+    votes * 2 > samples
+}
+
+fn row_is_probability(row: &ndarray::ArrayView1<'_, f32>) -> bool {
+    let mut sum = 0.0f32;
+    let mut min = f32::INFINITY;
+    let mut max = f32::NEG_INFINITY;
+    for &value in row.iter() {
+        sum += value;
+        if value < min {
+            min = value;
         }
-        if (sum - 1.0).abs() > 0.05 {
-            return false;
+        if value > max {
+            max = value;
         }
     }
-    true
+    if min < -PROBABILITY_RANGE_TOLERANCE || max > 1.0 + PROBABILITY_RANGE_TOLERANCE {
+        return false;
+    }
+    (sum - 1.0).abs() <= PROBABILITY_SUM_TOLERANCE
 }
 
 fn softmax_at(row: &ndarray::ArrayView1<'_, f32>, index: usize) -> f32 {
@@ -438,3 +702,291 @@ fn softmax_at(row: &ndarray::ArrayView1<'_, f32>, index: usize) -> f32 {
     }
     (row[index] - max).exp() / sum
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ort_reports_confidence_but_no_batching_or_char_boxes() {
+        let capabilities = ort_capabilities();
+        assert!(capabilities.emits_confidence);
+        assert!(!capabilities.supports_batch);
+        assert!(!capabilities.emits_char_boxes);
+        assert!(capabilities.supported_scripts.is_empty());
+    }
+
+    #[test]
+    fn per_channel_normalization_fills_each_plane() {
+        let crop = Crop {
+            data: vec![255, 0, 255, 0],
+            width: 2,
+            height: 2,
+        };
+        let tensor = prepare_input_tensor(
+            &crop,
+            2,
+            4,
+            [0.485, 0.456, 0.406],
+            [0.229, 0.224, 0.225],
+            None,
+        )
+        .expect("tensor");
+
+        assert_eq!(tensor.shape(), &[1, 3, 2, 4]);
+        let expected = |pixel: f32, channel: usize| {
+            (pixel - [0.485, 0.456, 0.406][channel]) / [0.229, 0.224, 0.225][channel]
+        };
+        for channel in 0..3 {
+            assert!((tensor[[0, channel, 0, 0]] - expected(1.0, channel)).abs() < 1e-5);
+            assert!((tensor[[0, channel, 0, 1]] - expected(0.0, channel)).abs() < 1e-5);
+            // Padding to the right of the scaled crop stays zero.
+            assert_eq!(tensor[[0, channel, 0, 3]], 0.0);
+        }
+    }
+
+    #[test]
+    fn padding_fills_the_columns_right_of_the_crop() {
+        // Dark text on a white border: the median border pixel is white.
+        let crop = Crop {
+            data: vec![255, 255, 255, 255, 0, 255, 255, 255, 255],
+            width: 3,
+            height: 3,
+        };
+        let fixed = prepare_input_tensor(
+            &crop,
+            3,
+            6,
+            [0.5; 3],
+            [0.5; 3],
+            Some(InputPadding::Value(0.0)),
+        )
+        .expect("tensor");
+        let border = prepare_input_tensor(
+            &crop,
+            3,
+            6,
+            [0.5; 3],
+            [0.5; 3],
+            Some(InputPadding::BorderMedian),
+        )
+        .expect("tensor");
+
+        for channel in 0..3 {
+            for y in 0..3 {
+                assert_eq!(fixed[[0, channel, y, 1]], if y == 1 { -1.0 } else { 1.0 });
+                for x in 3..6 {
+                    assert_eq!(fixed[[0, channel, y, x]], -1.0);
+                    assert_eq!(border[[0, channel, y, x]], 1.0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn dynamic_width_respects_bounds_and_stride() {
+        let bounds = DynamicInputWidth {
+            min_width: 50,
+            max_width: 1000,
+            stride: 32,
+        };
+        // 200x40 at height 48 scales to 240, rounded up to 256.
+        assert_eq!(select_input_width(200, 40, 48, bounds), 256);
+        // Exact multiples are kept.
+        assert_eq!(select_input_width(160, 24, 48, bounds), 320);
+        // Short text is raised to the minimum, snapped up to the stride.
+        assert_eq!(select_input_width(10, 40, 48, bounds), 64);
+        // Very wide banners stop at the maximum, snapped down to the stride.
+        assert_eq!(select_input_width(4000, 30, 48, bounds), 992);
+        for width in [1, 17, 333, 1919, 9000] {
+            let chosen = select_input_width(width, 31, 48, bounds);
+            assert_eq!(chosen % 32, 0);
+            assert!((64..=992).contains(&chosen));
+        }
+    }
+
+    fn tensor(rows: &[[f32; 3]]) -> ndarray::Array2<f32> {
+        let flat: Vec<f32> = rows.iter().flatten().copied().collect();
+        ndarray::Array2::from_shape_vec((rows.len(), 3), flat).expect("shape")
+    }
+
+    #[test]
+    fn drift_within_tolerance_counts_as_probabilities() {
+        let output = tensor(&[
+            [0.52, 0.25, 0.2799],
+            [-0.049, 0.549, 0.5],
+            [0.0, 0.0, 1.049],
+            [0.3, 0.3, 0.3],
+        ]);
+        assert!(is_probability_tensor(&output.view(), 16));
+    }
+
+    #[test]
+    fn minority_of_probability_like_rows_stays_logits() {
+        // Leading blank rows happen to look like probabilities; the rest are logits.
+        let output = tensor(&[
+            [1.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [4.2, -1.3, 0.7],
+            [3.9, -0.8, 1.1],
+            [5.1, -2.0, 0.2],
+            [2.8, -0.1, 0.4],
+        ]);
+        assert!(!is_probability_tensor(&output.view(), 16));
+        // Sampling only the first row reproduces the old misclassification.
+        assert!(is_probability_tensor(&output.view(), 1));
+    }
+
+    #[test]
+    fn rows_just_past_tolerance_are_rejected() {
+        assert!(!row_is_probability(&tensor(&[[-0.051, 0.551, 0.5]]).row(0)));
+        assert!(!row_is_probability(&tensor(&[[0.4, 0.3, 0.36]]).row(0)));
+        assert!(!row_is_probability(&tensor(&[[0.0, 0.0, 1.051]]).row(0)));
+    }
+
+    #[test]
+    fn dictionary_blank_handling_keeps_classes_aligned() {
+        // Classes: 0 blank, 1 "a", 2 "b", 3 "ch". Decodes to "a", "ch", "ch" again after a blank.
+        let output = ndarray::Array2::from_shape_vec(
+            (7, 4),
+            vec![
+                0.0, 5.0, 0.0, 0.0, //
+                0.0, 0.0, 0.0, 5.0, //
+                0.0, 0.0, 0.0, 5.0, //
+                5.0, 0.0, 0.0, 0.0, //
+                0.0, 0.0, 0.0, 5.0, //
+                5.0, 0.0, 0.0, 0.0, //
+                5.0, 0.0, 0.0, 0.0, //
+            ],
+        )
+        .expect("shape")
+        .into_dyn();
+
+        let without_blank = parse_dictionary("a\nb\nch\n", false, false, 0).unwrap();
+        let with_blank = parse_dictionary("\na\nb\nch\n", true, false, 0).unwrap();
+        let with_named_blank = parse_dictionary("<blank>\na\nb\nch\n", true, false, 0).unwrap();
+        for dictionary in [&without_blank, &with_blank, &with_named_blank] {
+            assert_eq!(dictionary.len(), 4);
+            let (text, _) =
+                decode_output(&output, dictionary, 0, 16, DecodeMode::Greedy).expect("text");
+            assert_eq!(text, "achch");
+        }
+
+        // Treating an explicit blank entry as a token shifts every character by one.
+        let misread = parse_dictionary("<blank>\na\nb\nch\n", false, false, 0).unwrap();
+        let (text, _) = decode_output(&output, &misread, 0, 16, DecodeMode::Greedy).expect("text");
+        assert_eq!(text, "<blank>bb");
+    }
+
+    #[test]
+    fn blank_at_a_non_zero_index_decodes_like_a_leading_blank() {
+        // Classes: 0 "a", 1 "b", 2 "ch", 3 blank; the same sequence as the leading-blank case.
+        let output = ndarray::Array2::from_shape_vec(
+            (7, 4),
+            vec![
+                5.0, 0.0, 0.0, 0.0, //
+                0.0, 0.0, 5.0, 0.0, //
+                0.0, 0.0, 5.0, 0.0, //
+                0.0, 0.0, 0.0, 5.0, //
+                0.0, 0.0, 5.0, 0.0, //
+                0.0, 0.0, 0.0, 5.0, //
+                0.0, 0.0, 0.0, 5.0, //
+            ],
+        )
+        .expect("shape")
+        .into_dyn();
+
+        let implied = parse_dictionary("a\nb\nch\n", false, false, 3).unwrap();
+        let listed = parse_dictionary("a\nb\nch\n<blank>\n", true, false, 3).unwrap();
+        assert_eq!(implied, ["a", "b", "ch", ""]);
+        assert_eq!(listed, implied);
+        for decode in [DecodeMode::Greedy, DecodeMode::PrefixBeam { width: 4 }] {
+            let (text, _) = decode_output(&output, &implied, 3, 16, decode).expect("text");
+            assert_eq!(text, "achch", "{decode:?}");
+        }
+
+        // Assuming the usual leading blank drops the "a", shifts every other class by one and
+        // reads the blank steps as text.
+        let leading = parse_dictionary("a\nb\nch\n", false, false, 0).unwrap();
+        let (text, _) = decode_output(&output, &leading, 0, 16, DecodeMode::Greedy).expect("text");
+        assert_eq!(text, "bchbch");
+
+        // The blank must be one of the dictionary's classes.
+        assert_eq!(parse_dictionary("a\nb\nch\n", false, false, 4), Err(4));
+    }
+
+    #[test]
+    fn preserved_blank_line_decodes_as_a_space() {
+        // Classes: 0 blank, 1 "a", 2 space (an empty line in the file), 3 "b".
+        let output = ndarray::Array2::from_shape_vec(
+            (4, 4),
+            vec![
+                0.0, 5.0, 0.0, 0.0, //
+                0.0, 0.0, 5.0, 0.0, //
+                0.0, 0.0, 0.0, 5.0, //
+                5.0, 0.0, 0.0, 0.0, //
+            ],
+        )
+        .expect("shape")
+        .into_dyn();
+
+        let preserved = parse_dictionary("a\n\nb\n", false, true, 0).unwrap();
+        assert_eq!(preserved, ["", "a", " ", "b"]);
+        let (text, _) =
+            decode_output(&output, &preserved, 0, 16, DecodeMode::Greedy).expect("text");
+        assert_eq!(text, "a b");
+        let with_blank = parse_dictionary("\na\n\nb\n", true, true, 0).unwrap();
+        assert_eq!(with_blank, preserved);
+
+        // Skipping the empty line moves "b" onto the space's class and leaves class 3 unmapped.
+        let skipped = parse_dictionary("a\n\nb\n", false, false, 0).unwrap();
+        let (text, _) = decode_output(&output, &skipped, 0, 16, DecodeMode::Greedy).expect("text");
+        assert_eq!(text, "ab");
+    }
+
+    #[test]
+    fn prefix_beam_recovers_text_split_across_alignments() {
+        // Classes: 0 blank, 1 "a", 2 "b". The blank wins the first two steps, but "a" holds
+        // more probability over all of its alignments than an empty start does.
+        let output = ndarray::Array2::from_shape_vec(
+            (3, 3),
+            vec![
+                0.4, 0.35, 0.25, //
+                0.4, 0.35, 0.25, //
+                0.1, 0.0, 0.9, //
+            ],
+        )
+        .expect("shape")
+        .into_dyn();
+        let dictionary = parse_dictionary("a\nb\n", false, false, 0).unwrap();
+
+        let (greedy, _) =
+            decode_output(&output, &dictionary, 0, 16, DecodeMode::Greedy).expect("text");
+        assert_eq!(greedy, "b");
+
+        let (beam, confidence) = decode_output(
+            &output,
+            &dictionary,
+            0,
+            16,
+            DecodeMode::PrefixBeam { width: 4 },
+        )
+        .expect("text");
+        assert_eq!(beam, "ab");
+        // P("ab") over its alignments is about 0.45; its per-step geometric mean about 0.77.
+        let confidence = confidence.expect("confidence");
+        assert!((0.7..0.85).contains(&confidence), "{confidence}");
+
+        // The same scores as logits decode the same way.
+        let logits = output.mapv(|value: f32| value.max(1e-6).ln());
+        let (beam, _) = decode_output(
+            &logits,
+            &dictionary,
+            0,
+            16,
+            DecodeMode::PrefixBeam { width: 4 },
+        )
+        .expect("text");
+        assert_eq!(beam, "ab");
+    }
+}
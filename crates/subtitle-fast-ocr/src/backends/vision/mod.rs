@@ -1,8 +1,9 @@
+use std::borrow::Cow;
 use std::ffi::{CStr, CString};
 use std::ptr;
 use std::slice;
 
-use crate::{OcrEngine, OcrError, OcrRegion, OcrRequest, OcrResponse, OcrText};
+use crate::{OcrCapabilities, OcrEngine, OcrError, OcrRegion, OcrRequest, OcrResponse, OcrText};
 
 #[repr(C)]
 #[derive(Clone, Copy)]
@@ -148,13 +149,28 @@ impl OcrEngine for VisionOcrEngine {
         "macos_vision"
     }
 
+    fn capabilities(&self) -> OcrCapabilities {
+        OcrCapabilities {
+            emits_confidence: true,
+            supported_scripts: self
+                .languages
+                .iter()
+                .map(|language| language.to_string_lossy().into_owned())
+                .collect(),
+            ..OcrCapabilities::default()
+        }
+    }
+
     fn recognize(&self, request: &OcrRequest<'_>) -> Result<OcrResponse, OcrError> {
         let plane = request.plane();
         let width = plane.width() as usize;
         let height = plane.height() as usize;
-        let stride = plane.stride();
-
-        let data = plane.data();
+        // Vision reads 8-bit luma, so deeper planes are scaled down into a packed copy.
+        let (data, stride) = if plane.bit_depth() > 8 {
+            (plane.to_8bit(), width)
+        } else {
+            (Cow::Borrowed(plane.data()), plane.stride())
+        };
         if data.is_empty() {
             return Ok(OcrResponse::empty());
         }
@@ -0,0 +1,14 @@
+/// Beam width used when a prefix-beam search is asked for without one.
+pub const DEFAULT_BEAM_WIDTH: usize = 8;
+
+/// How a CTC recognizer turns its per-timestep class scores into text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DecodeMode {
+    /// Best class at every timestep, with repeats and blanks collapsed. Fast, but it follows a
+    /// single alignment and can miss the text most alignments agree on.
+    #[default]
+    Greedy,
+    /// Prefix-beam search keeping the `width` most probable texts at every timestep, each
+    /// scored by the summed probability of all its alignments.
+    PrefixBeam { width: usize },
+}
@@ -2,15 +2,45 @@ use crate::error::OcrError;
 use crate::request::OcrRequest;
 use crate::response::OcrResponse;
 
+/// What an engine can do beyond plain single-request recognition.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OcrCapabilities {
+    /// [`OcrEngine::recognize_batch`] is cheaper than one `recognize` call per request.
+    pub supports_batch: bool,
+    /// Recognized texts carry a confidence.
+    pub emits_confidence: bool,
+    /// Recognized texts carry per-character boxes.
+    pub emits_char_boxes: bool,
+    /// Language or script tags the engine is limited to; empty when it is not restricted or
+    /// the set depends on the loaded model.
+    pub supported_scripts: Vec<String>,
+    /// The engine never returns text; it only stands in when no real backend is available.
+    pub reads_no_text: bool,
+}
+
 /// Common interface for all OCR engines.
 pub trait OcrEngine: Send + Sync {
     fn name(&self) -> &'static str;
 
+    fn capabilities(&self) -> OcrCapabilities {
+        OcrCapabilities::default()
+    }
+
     fn warm_up(&self) -> Result<(), OcrError> {
         Ok(())
     }
 
     fn recognize(&self, request: &OcrRequest<'_>) -> Result<OcrResponse, OcrError>;
+
+    /// Recognizes several requests, returning one response per request in order. Engines that
+    /// report [`OcrCapabilities::supports_batch`] override this; the default runs them one by
+    /// one.
+    fn recognize_batch(&self, requests: &[OcrRequest<'_>]) -> Result<Vec<OcrResponse>, OcrError> {
+        requests
+            .iter()
+            .map(|request| self.recognize(request))
+            .collect()
+    }
 }
 
 /// Placeholder OCR engine used while a real backend is not wired.
@@ -22,7 +52,35 @@ impl OcrEngine for NoopOcrEngine {
         "noop"
     }
 
+    fn capabilities(&self) -> OcrCapabilities {
+        OcrCapabilities {
+            reads_no_text: true,
+            ..OcrCapabilities::default()
+        }
+    }
+
     fn recognize(&self, _: &OcrRequest<'_>) -> Result<OcrResponse, OcrError> {
         Ok(OcrResponse::empty())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn noop_engine_claims_no_capabilities() {
+        let capabilities = NoopOcrEngine.capabilities();
+        assert!(!capabilities.supports_batch);
+        assert!(!capabilities.emits_confidence);
+        assert!(!capabilities.emits_char_boxes);
+        assert!(capabilities.supported_scripts.is_empty());
+        assert!(capabilities.reads_no_text);
+        assert!(
+            NoopOcrEngine
+                .recognize_batch(&[])
+                .expect("empty batch")
+                .is_empty()
+        );
+    }
+}
@@ -8,6 +8,10 @@ pub enum OcrError {
         "plane dimensions overflowed while validating stride * height (stride={stride}, height={height})"
     )]
     PlaneOverflow { stride: usize, height: u32 },
+    #[error("plane stride {stride} is smaller than width {width}")]
+    StrideTooSmall { stride: usize, width: u32 },
+    #[error("unsupported luma bit depth {bit_depth} (expected 8 to 16)")]
+    UnsupportedBitDepth { bit_depth: u8 },
     #[error("backend error: {message}")]
     Backend { message: String },
 }
@@ -1,5 +1,6 @@
 mod backend;
 mod backends;
+mod decode;
 mod engine;
 mod error;
 mod plane;
@@ -9,12 +10,13 @@ mod response;
 
 pub use backend::{Backend, Configuration};
 #[cfg(feature = "engine-ort")]
-pub use backends::ort::{OrtOcrConfig, OrtOcrEngine};
+pub use backends::ort::{DynamicInputWidth, OrtOcrConfig, OrtOcrEngine};
 #[cfg(all(feature = "engine-vision", target_os = "macos"))]
 pub use backends::vision::{VisionOcrConfig, VisionOcrEngine};
-pub use engine::{NoopOcrEngine, OcrEngine};
+pub use decode::{DEFAULT_BEAM_WIDTH, DecodeMode};
+pub use engine::{NoopOcrEngine, OcrCapabilities, OcrEngine};
 pub use error::OcrError;
-pub use plane::LumaPlane;
-pub use region::OcrRegion;
-pub use request::OcrRequest;
-pub use response::{OcrResponse, OcrText};
+pub use plane::{InputPadding, LumaPlane};
+pub use region::{OcrRegion, order_regions};
+pub use request::{OcrRequest, OcrRequestBuilder};
+pub use response::{OcrResponse, OcrText, TextDirection};
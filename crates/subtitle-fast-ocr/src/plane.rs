@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::fmt;
 use std::ops::Deref;
 
@@ -6,11 +7,15 @@ use subtitle_fast_types::VideoFrame;
 use crate::error::OcrError;
 
 /// Immutable view over a Y (luminance) plane.
+///
+/// Planes deeper than 8 bits store each sample as a little-endian `u16`; `stride` is always in
+/// bytes.
 #[derive(Clone)]
 pub struct LumaPlane<'a> {
     width: u32,
     height: u32,
     stride: usize,
+    bit_depth: u8,
     data: &'a [u8],
 }
 
@@ -21,6 +26,34 @@ impl<'a> LumaPlane<'a> {
         stride: usize,
         data: &'a [u8],
     ) -> Result<Self, OcrError> {
+        Self::from_raw(data, width, height, stride)
+    }
+
+    /// Builds a plane from caller-provided luma bytes, rejecting buffers that
+    /// are too short or whose rows are narrower than `width`.
+    pub fn from_raw(
+        data: &'a [u8],
+        width: u32,
+        height: u32,
+        stride: usize,
+    ) -> Result<Self, OcrError> {
+        Self::from_raw_with_depth(data, width, height, stride, 8)
+    }
+
+    /// Like [`LumaPlane::from_raw`] for 8 to 16-bit samples; rows must hold `width` samples.
+    pub fn from_raw_with_depth(
+        data: &'a [u8],
+        width: u32,
+        height: u32,
+        stride: usize,
+        bit_depth: u8,
+    ) -> Result<Self, OcrError> {
+        if !(8..=16).contains(&bit_depth) {
+            return Err(OcrError::UnsupportedBitDepth { bit_depth });
+        }
+        if stride < width as usize * bytes_per_sample(bit_depth) {
+            return Err(OcrError::StrideTooSmall { stride, width });
+        }
         let required = stride
             .checked_mul(height as usize)
             .ok_or(OcrError::PlaneOverflow { stride, height })?;
@@ -34,6 +67,7 @@ impl<'a> LumaPlane<'a> {
             width,
             height,
             stride,
+            bit_depth,
             data: &data[..required],
         })
     }
@@ -44,6 +78,7 @@ impl<'a> LumaPlane<'a> {
             width: frame.width(),
             height: frame.height(),
             stride: frame.stride(),
+            bit_depth: 8,
             data: frame.data(),
         }
     }
@@ -60,9 +95,164 @@ impl<'a> LumaPlane<'a> {
         self.stride
     }
 
+    pub fn bit_depth(&self) -> u8 {
+        self.bit_depth
+    }
+
     pub fn data(&self) -> &'a [u8] {
         self.data
     }
+
+    /// Copies a `width` x `height` block at (`left`, `top`) as 8-bit samples, scaling deeper
+    /// samples down to `0..=255`. Returns `None` if the block leaves the plane.
+    pub fn crop_8bit(
+        &self,
+        left: usize,
+        top: usize,
+        width: usize,
+        height: usize,
+    ) -> Option<Vec<u8>> {
+        if left + width > self.width as usize || top + height > self.height as usize {
+            return None;
+        }
+        let bytes = bytes_per_sample(self.bit_depth);
+        if height > 0 && (top + height - 1) * self.stride + (left + width) * bytes > self.data.len()
+        {
+            return None;
+        }
+        let max = (1u32 << self.bit_depth) - 1;
+        let mut out = Vec::with_capacity(width * height);
+        for row in top..top + height {
+            let start = row * self.stride + left * bytes;
+            let samples = &self.data[start..start + width * bytes];
+            if bytes == 1 {
+                out.extend_from_slice(samples);
+                continue;
+            }
+            out.extend(samples.chunks_exact(2).map(|pair| {
+                let value = u32::from(u16::from_le_bytes([pair[0], pair[1]])).min(max);
+                ((value * 255 + max / 2) / max) as u8
+            }));
+        }
+        Some(out)
+    }
+
+    /// Like [`LumaPlane::crop_8bit`], then scaled up bilinearly by `factor` in both directions,
+    /// e.g. to give OCR more pixels per glyph on tiny text.
+    pub fn crop_upscaled_8bit(
+        &self,
+        left: usize,
+        top: usize,
+        width: usize,
+        height: usize,
+        factor: usize,
+    ) -> Option<Vec<u8>> {
+        let crop = self.crop_8bit(left, top, width, height)?;
+        if factor <= 1 || width == 0 || height == 0 {
+            return Some(crop);
+        }
+        let scaled = resize_bilinear(&crop, width, height, width * factor, height * factor);
+        Some(
+            scaled
+                .into_iter()
+                .map(|value| value.round().clamp(0.0, 255.0) as u8)
+                .collect(),
+        )
+    }
+
+    /// The whole plane as tightly packed 8-bit rows; borrows when it already is.
+    pub fn to_8bit(&self) -> Cow<'a, [u8]> {
+        let (width, height) = (self.width as usize, self.height as usize);
+        if self.bit_depth == 8 && self.stride == width {
+            return Cow::Borrowed(self.data);
+        }
+        Cow::Owned(self.crop_8bit(0, 0, width, height).unwrap_or_default())
+    }
+}
+
+/// Bilinearly resamples a tightly packed 8-bit image, keeping fractional results.
+pub(crate) fn resize_bilinear(
+    src: &[u8],
+    src_width: usize,
+    src_height: usize,
+    dst_width: usize,
+    dst_height: usize,
+) -> Vec<f32> {
+    if src_width == dst_width && src_height == dst_height {
+        return src.iter().map(|&v| v as f32).collect();
+    }
+
+    let mut dst = vec![0.0f32; dst_width * dst_height];
+    let scale_x = src_width as f32 / dst_width as f32;
+    let scale_y = src_height as f32 / dst_height as f32;
+
+    for y in 0..dst_height {
+        let src_y = (y as f32 + 0.5) * scale_y - 0.5;
+        let y0 = src_y.floor().clamp(0.0, (src_height - 1) as f32) as usize;
+        let y1 = (y0 + 1).min(src_height - 1);
+        let wy = src_y - y0 as f32;
+
+        for x in 0..dst_width {
+            let src_x = (x as f32 + 0.5) * scale_x - 0.5;
+            let x0 = src_x.floor().clamp(0.0, (src_width - 1) as f32) as usize;
+            let x1 = (x0 + 1).min(src_width - 1);
+            let wx = src_x - x0 as f32;
+
+            let v00 = src[y0 * src_width + x0] as f32;
+            let v01 = src[y0 * src_width + x1] as f32;
+            let v10 = src[y1 * src_width + x0] as f32;
+            let v11 = src[y1 * src_width + x1] as f32;
+
+            let v0 = v00 + (v01 - v00) * wx;
+            let v1 = v10 + (v11 - v10) * wx;
+            dst[y * dst_width + x] = v0 + (v1 - v0) * wy;
+        }
+    }
+
+    dst
+}
+
+/// Fill for the part of a fixed-size model input that the scaled crop does not cover.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputPadding {
+    /// A fixed pixel level in `[0, 1]`, normalized like the crop itself (0 is black).
+    Value(f32),
+    /// The median of the crop's outermost pixels, so the padding continues its background.
+    BorderMedian,
+}
+
+impl InputPadding {
+    /// Pixel level in `[0, 1]` to pad a tightly packed 8-bit `crop` with.
+    pub fn level(self, crop: &[u8], width: usize, height: usize) -> f32 {
+        match self {
+            InputPadding::Value(value) => value.clamp(0.0, 1.0),
+            InputPadding::BorderMedian => f32::from(border_median(crop, width, height)) / 255.0,
+        }
+    }
+}
+
+fn border_median(crop: &[u8], width: usize, height: usize) -> u8 {
+    if width == 0 || height == 0 || crop.len() < width * height {
+        return 0;
+    }
+    let mut border: Vec<u8> = Vec::with_capacity(2 * (width + height));
+    for y in 0..height {
+        let row = &crop[y * width..(y + 1) * width];
+        if y == 0 || y + 1 == height {
+            border.extend_from_slice(row);
+        } else {
+            border.push(row[0]);
+            if width > 1 {
+                border.push(row[width - 1]);
+            }
+        }
+    }
+    let middle = border.len() / 2;
+    *border.select_nth_unstable(middle).1
+}
+
+fn bytes_per_sample(bit_depth: u8) -> usize {
+    if bit_depth > 8 { 2 } else { 1 }
 }
 
 impl fmt::Debug for LumaPlane<'_> {
@@ -71,6 +261,7 @@ impl fmt::Debug for LumaPlane<'_> {
             .field("width", &self.width)
             .field("height", &self.height)
             .field("stride", &self.stride)
+            .field("bit_depth", &self.bit_depth)
             .field("bytes", &self.data.len())
             .finish()
     }
@@ -83,3 +274,76 @@ impl Deref for LumaPlane<'_> {
         self.data
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_raw_accepts_padded_rows() {
+        let data = vec![7u8; 8 * 4];
+        let plane = LumaPlane::from_raw(&data, 6, 4, 8).expect("valid plane");
+        assert_eq!(plane.width(), 6);
+        assert_eq!(plane.height(), 4);
+        assert_eq!(plane.stride(), 8);
+        assert_eq!(plane.data().len(), 32);
+    }
+
+    #[test]
+    fn from_raw_rejects_short_buffer() {
+        let data = vec![0u8; 8 * 3];
+        let err = LumaPlane::from_raw(&data, 8, 4, 8).unwrap_err();
+        assert!(matches!(
+            err,
+            OcrError::InsufficientPlaneData {
+                provided: 24,
+                required: 32
+            }
+        ));
+    }
+
+    #[test]
+    fn ten_bit_crop_scales_to_eight_bit_range() {
+        let (width, height, stride) = (4usize, 2usize, 10usize);
+        let samples: [u16; 8] = [0, 64, 512, 1023, 940, 1, 2047, 256];
+        let mut data = vec![0xAAu8; stride * height];
+        for (idx, sample) in samples.iter().enumerate() {
+            let (row, col) = (idx / width, idx % width);
+            let offset = row * stride + col * 2;
+            data[offset..offset + 2].copy_from_slice(&sample.to_le_bytes());
+        }
+
+        let plane = LumaPlane::from_raw_with_depth(&data, width as u32, height as u32, stride, 10)
+            .expect("valid 10-bit plane");
+        assert_eq!(plane.bit_depth(), 10);
+        assert_eq!(
+            plane.crop_8bit(0, 0, width, height).expect("crop"),
+            vec![0, 16, 128, 255, 234, 0, 255, 64]
+        );
+        assert_eq!(plane.crop_8bit(2, 1, 2, 1).expect("crop"), vec![255, 64]);
+        assert!(plane.crop_8bit(3, 0, 2, 1).is_none());
+        assert_eq!(plane.to_8bit().len(), width * height);
+
+        let err = LumaPlane::from_raw_with_depth(&data, 6, 2, stride, 10).unwrap_err();
+        assert!(matches!(
+            err,
+            OcrError::StrideTooSmall {
+                stride: 10,
+                width: 6
+            }
+        ));
+    }
+
+    #[test]
+    fn from_raw_rejects_stride_below_width() {
+        let data = vec![0u8; 64];
+        let err = LumaPlane::from_raw(&data, 10, 4, 8).unwrap_err();
+        assert!(matches!(
+            err,
+            OcrError::StrideTooSmall {
+                stride: 8,
+                width: 10
+            }
+        ));
+    }
+}
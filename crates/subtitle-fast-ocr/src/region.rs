@@ -1 +1 @@
-pub use subtitle_fast_types::OcrRegion;
+pub use subtitle_fast_types::{OcrRegion, order_regions};
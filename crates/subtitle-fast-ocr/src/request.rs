@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use crate::plane::LumaPlane;
 use crate::region::OcrRegion;
 
@@ -5,19 +7,105 @@ use crate::region::OcrRegion;
 #[derive(Debug)]
 pub struct OcrRequest<'a> {
     plane: LumaPlane<'a>,
-    regions: &'a [OcrRegion],
+    regions: Cow<'a, [OcrRegion]>,
 }
 
 impl<'a> OcrRequest<'a> {
     pub fn new(plane: LumaPlane<'a>, regions: &'a [OcrRegion]) -> Self {
-        Self { plane, regions }
+        Self {
+            plane,
+            regions: Cow::Borrowed(regions),
+        }
+    }
+
+    /// Collects regions one at a time, clamping each to the plane; see [`OcrRequestBuilder`].
+    pub fn builder(plane: LumaPlane<'a>) -> OcrRequestBuilder<'a> {
+        OcrRequestBuilder {
+            plane,
+            regions: Vec::new(),
+        }
     }
 
     pub fn plane(&self) -> &LumaPlane<'a> {
         &self.plane
     }
 
-    pub fn regions(&self) -> &'a [OcrRegion] {
-        self.regions
+    pub fn regions(&self) -> &[OcrRegion] {
+        &self.regions
+    }
+}
+
+/// Builds an [`OcrRequest`] that owns its regions.
+///
+/// Regions reaching past the plane are clamped to its bounds, and ones left with no area, such
+/// as a box entirely outside the frame, are dropped, so engines only see pixels that exist.
+#[derive(Debug)]
+pub struct OcrRequestBuilder<'a> {
+    plane: LumaPlane<'a>,
+    regions: Vec<OcrRegion>,
+}
+
+impl<'a> OcrRequestBuilder<'a> {
+    pub fn region(mut self, region: OcrRegion) -> Self {
+        let (width, height) = (self.plane.width(), self.plane.height());
+        self.regions.extend(clamp_to_plane(&region, width, height));
+        self
+    }
+
+    pub fn regions(self, regions: impl IntoIterator<Item = OcrRegion>) -> Self {
+        regions.into_iter().fold(self, Self::region)
+    }
+
+    pub fn build(self) -> OcrRequest<'a> {
+        OcrRequest {
+            plane: self.plane,
+            regions: Cow::Owned(self.regions),
+        }
+    }
+}
+
+/// Part of `region` inside a `width` x `height` plane; `None` when nothing of it is.
+pub(crate) fn clamp_to_plane(region: &OcrRegion, width: u32, height: u32) -> Option<OcrRegion> {
+    let (width, height) = (width as f32, height as f32);
+    let left = region.x.clamp(0.0, width);
+    let top = region.y.clamp(0.0, height);
+    let right = (region.x + region.width).clamp(left, width);
+    let bottom = (region.y + region.height).clamp(top, height);
+    (right > left && bottom > top).then(|| OcrRegion::new(left, top, right - left, bottom - top))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_clamps_regions_to_the_plane() {
+        let data = vec![0u8; 100 * 40];
+        let plane = || LumaPlane::from_raw(&data, 100, 40, 100).expect("valid plane");
+
+        assert!(OcrRequest::builder(plane()).build().regions().is_empty());
+
+        let line = OcrRegion::new(10.0, 20.0, 50.0, 12.0);
+        let single = OcrRequest::builder(plane()).region(line).build();
+        assert_eq!(single.regions(), [line]);
+
+        let several = OcrRequest::builder(plane())
+            .region(line)
+            .regions([
+                // Runs off the right and bottom edges.
+                OcrRegion::new(80.0, 30.0, 40.0, 20.0),
+                // Entirely outside the plane.
+                OcrRegion::new(150.0, 5.0, 20.0, 10.0),
+                OcrRegion::new(-5.0, 2.0, 15.0, 6.0),
+            ])
+            .build();
+        assert_eq!(
+            several.regions(),
+            [
+                line,
+                OcrRegion::new(80.0, 30.0, 20.0, 10.0),
+                OcrRegion::new(0.0, 2.0, 10.0, 6.0),
+            ]
+        );
     }
 }
@@ -1 +1 @@
-pub use subtitle_fast_types::{OcrResponse, OcrText};
+pub use subtitle_fast_types::{OcrResponse, OcrText, TextDirection};
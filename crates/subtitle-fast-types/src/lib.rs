@@ -4,14 +4,20 @@
 //! validator, comparator, OCR, and CLI crates. Keep it backend-agnostic and
 //! avoid platform-specific dependencies so all crates can depend on it without
 //! pulling native SDKs or heavy features.
+//!
+//! The `serde` feature derives `Serialize`/`Deserialize` for the geometry and
+//! detection types. Their field names are written to exports and sidecars, so
+//! they are stable: renaming one is a format change.
 
+use std::cmp::Ordering;
 use std::ffi::c_void;
 use std::fmt;
 use std::ptr::NonNull;
 use std::sync::Arc;
 use std::time::Duration;
 
-use serde::Serialize;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 pub type DecoderResult<T> = Result<T, DecoderError>;
@@ -193,6 +199,17 @@ impl VideoFrame {
         })
     }
 
+    /// A tightly packed NV12 frame of uniform `luma` with neutral chroma, such as a black frame.
+    pub fn filled(width: u32, height: u32, luma: u8) -> DecoderResult<Self> {
+        let y_stride = width as usize;
+        let uv_stride = y_stride.div_ceil(2) * 2;
+        let y_plane = vec![luma; y_stride.saturating_mul(height as usize)];
+        let uv_plane = vec![128; uv_stride.saturating_mul(nv12_uv_rows(height))];
+        Self::from_nv12_owned(
+            width, height, y_stride, uv_stride, None, None, y_plane, uv_plane,
+        )
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn from_native_handle(
         width: u32,
@@ -356,6 +373,13 @@ pub enum DecoderError {
 
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
+
+    /// A stage after decoding failed; `source` is that stage's own error.
+    #[error("{stage} error: {source}")]
+    Stage {
+        stage: FailedStage,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
 }
 
 impl DecoderError {
@@ -375,26 +399,61 @@ impl DecoderError {
             message: message.into(),
         }
     }
+
+    pub fn stage(
+        stage: FailedStage,
+        source: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+    ) -> Self {
+        Self::Stage {
+            stage,
+            source: source.into(),
+        }
+    }
+}
+
+/// Pipeline stage a [`DecoderError::Stage`] came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailedStage {
+    Detection,
+    Ocr,
+}
+
+impl fmt::Display for FailedStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            FailedStage::Detection => "subtitle detection",
+            FailedStage::Ocr => "ocr",
+        })
+    }
 }
 
+/// Rectangle in frame fractions, serialized as `{ "x", "y", "width", "height" }`.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RoiConfig {
+    /// Left edge, from 0 at the frame's left to 1 at its right.
     pub x: f32,
+    /// Top edge, from 0 at the frame's top to 1 at its bottom.
     pub y: f32,
     pub width: f32,
     pub height: f32,
 }
 
-#[derive(Debug, Clone, Serialize)]
+/// Detected region in frame pixels, serialized as
+/// `{ "x", "y", "width", "height", "score" }`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DetectionRegion {
     pub x: f32,
     pub y: f32,
     pub width: f32,
     pub height: f32,
+    /// Detector confidence, from 0 to 1.
     pub score: f32,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct SubtitleDetectionResult {
     pub has_subtitle: bool,
     pub max_score: f32,
@@ -428,13 +487,72 @@ impl OcrRegion {
             height,
         }
     }
+
+    /// Reading order: top to bottom by vertical center, then left to right by `x`.
+    ///
+    /// Overlapping or nested boxes that tie on both keys are ordered by area, largest first,
+    /// then by top edge and width, so the result never depends on the order an engine
+    /// reported them in.
+    pub fn reading_order(&self, other: &Self) -> Ordering {
+        let center = |region: &Self| region.y + region.height * 0.5;
+        center(self)
+            .total_cmp(&center(other))
+            .then_with(|| self.x.total_cmp(&other.x))
+            .then_with(|| self.overlap_tiebreak(other))
+    }
+
+    /// The tiebreak of [`OcrRegion::reading_order`] on its own, for callers that order by a
+    /// different primary key such as right-to-left `x`.
+    pub fn overlap_tiebreak(&self, other: &Self) -> Ordering {
+        let area = |region: &Self| region.width * region.height;
+        area(other)
+            .total_cmp(&area(self))
+            .then_with(|| self.y.total_cmp(&other.y))
+            .then_with(|| self.width.total_cmp(&other.width))
+    }
+}
+
+/// Sorts `regions` into [`OcrRegion::reading_order`].
+pub fn order_regions(regions: &mut [OcrRegion]) {
+    regions.sort_by(OcrRegion::reading_order);
+}
+
+/// Reading direction of a line of recognized text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TextDirection {
+    #[default]
+    LeftToRight,
+    RightToLeft,
+}
+
+impl TextDirection {
+    /// Direction used by an ISO 15924 script code such as `Arab` or `Latn`.
+    pub fn from_script(script: &str) -> Self {
+        const RTL_SCRIPTS: &[&str] = &[
+            "adlm", "arab", "hebr", "mand", "nkoo", "rohg", "samr", "syrc", "thaa",
+        ];
+        let script = script.trim().to_ascii_lowercase();
+        if RTL_SCRIPTS.contains(&script.as_str()) {
+            Self::RightToLeft
+        } else {
+            Self::LeftToRight
+        }
+    }
+
+    pub fn is_rtl(self) -> bool {
+        matches!(self, Self::RightToLeft)
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct OcrText {
     pub region: OcrRegion,
     pub text: String,
+    /// Recognition confidence of this fragment, 0-1. Merging averages it per visual line, so a
+    /// multi-line cue keeps a separate value for each line.
     pub confidence: Option<f32>,
+    /// ISO 15924 script code reported by the engine, when known.
+    pub script: Option<String>,
 }
 
 impl OcrText {
@@ -443,6 +561,7 @@ impl OcrText {
             region,
             text,
             confidence: None,
+            script: None,
         }
     }
 
@@ -450,6 +569,16 @@ impl OcrText {
         self.confidence = Some(value);
         self
     }
+
+    pub fn with_script(mut self, script: impl Into<String>) -> Self {
+        self.script = Some(script.into());
+        self
+    }
+
+    /// Reading direction implied by the reported script, if any.
+    pub fn direction(&self) -> Option<TextDirection> {
+        self.script.as_deref().map(TextDirection::from_script)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -466,3 +595,82 @@ impl OcrResponse {
         Self { texts: Vec::new() }
     }
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roi_config_round_trips_with_stable_field_names() {
+        for roi in [
+            RoiConfig {
+                x: 0.0,
+                y: 0.0,
+                width: 1.0,
+                height: 1.0,
+            },
+            RoiConfig {
+                x: 1.0,
+                y: 1.0,
+                width: 0.0,
+                height: 0.0,
+            },
+            RoiConfig {
+                x: 0.125,
+                y: 0.75,
+                width: 0.8,
+                height: 0.2,
+            },
+        ] {
+            let json = serde_json::to_value(roi).expect("serialize");
+            let mut fields: Vec<&str> = json
+                .as_object()
+                .expect("object")
+                .keys()
+                .map(String::as_str)
+                .collect();
+            fields.sort_unstable();
+            assert_eq!(fields, ["height", "width", "x", "y"]);
+            let restored: RoiConfig = serde_json::from_value(json).expect("deserialize");
+            assert_eq!(restored, roi);
+        }
+    }
+
+    #[test]
+    fn detection_region_round_trips_with_stable_field_names() {
+        let json = r#"{"x":0.0,"y":1.0,"width":1920.0,"height":0.0,"score":1.0}"#;
+        let region: DetectionRegion = serde_json::from_str(json).expect("deserialize");
+        assert_eq!(
+            (
+                region.x,
+                region.y,
+                region.width,
+                region.height,
+                region.score
+            ),
+            (0.0, 1.0, 1920.0, 0.0, 1.0)
+        );
+        assert_eq!(serde_json::to_string(&region).expect("serialize"), json);
+
+        let region = DetectionRegion {
+            x: 12.5,
+            y: 900.0,
+            width: 640.25,
+            height: 48.0,
+            score: 0.0,
+        };
+        let restored: DetectionRegion =
+            serde_json::from_str(&serde_json::to_string(&region).expect("serialize"))
+                .expect("deserialize");
+        assert_eq!(
+            (
+                restored.x,
+                restored.y,
+                restored.width,
+                restored.height,
+                restored.score
+            ),
+            (12.5, 900.0, 640.25, 48.0, 0.0)
+        );
+    }
+}
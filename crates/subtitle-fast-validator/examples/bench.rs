@@ -130,7 +130,11 @@ fn main() -> Result<(), Box<dyn Error>> {
                     width: 1.0,
                     height: 1.0,
                 };
-                config.luma_band = LumaBandConfig { target, delta };
+                config.luma_band = LumaBandConfig {
+                    target,
+                    delta,
+                    softness: 0,
+                };
 
                 let detector = build_bench_detector(kind, config)?;
                 let start = Instant::now();
@@ -139,7 +139,11 @@ fn main() -> Result<(), Box<dyn Error>> {
                     width: 1.0,
                     height: 1.0,
                 };
-                config.luma_band = LumaBandConfig { target, delta };
+                config.luma_band = LumaBandConfig {
+                    target,
+                    delta,
+                    softness: 0,
+                };
                 let roi = config.roi;
                 let detector = build_dump_detector(kind, config)?;
                 let result = detector.detect(&frame)?;
@@ -345,6 +349,7 @@ fn detector_label(kind: SubtitleDetectorKind) -> &'static str {
         SubtitleDetectorKind::IntegralBand => "integral",
         SubtitleDetectorKind::ProjectionBand => "projection",
         SubtitleDetectorKind::MacVision => "vision",
+        SubtitleDetectorKind::Fusion => "fusion",
         SubtitleDetectorKind::Auto => "auto",
     }
 }
@@ -1,4 +1,8 @@
-use crate::subtitle_detection::{DEFAULT_DELTA, DEFAULT_TARGET, RoiConfig, SubtitleDetectorKind};
+use crate::subtitle_detection::{
+    DEFAULT_AUTO_DETECTOR_PRIORITY, DEFAULT_DELTA, DEFAULT_LINE_VALLEY_DEPTH, DEFAULT_TARGET,
+    FusionPolicy, RegionSizeConfig, RoiConfig, RoiRounding, ShearCompensation,
+    SubtitleDetectorKind,
+};
 
 #[derive(Clone, Debug, Default)]
 pub struct FrameValidatorConfig {
@@ -11,6 +15,24 @@ pub struct SubtitleDetectionOptions {
     pub roi: Option<RoiConfig>,
     pub detector: SubtitleDetectorKind,
     pub luma_band: LumaBandOptions,
+    pub min_region: RegionSizeConfig,
+    pub roi_clip_margin: f32,
+    /// Vision: how the clip ROI snaps to whole pixels, optionally against pixel centres.
+    pub roi_rounding: RoiRounding,
+    pub roi_half_pixel: bool,
+    /// Vision: drop text observations reported below this confidence.
+    pub min_detection_confidence: f32,
+    /// Fusion: how Vision's boxes are combined with the luma-band regions.
+    pub fusion_policy: FusionPolicy,
+    pub shear_compensation: Option<ShearCompensation>,
+    /// Projection-band: split bands holding several stacked lines into one region per line.
+    pub split_lines: bool,
+    /// How far below the band's peak row mass a row must drop to separate two lines.
+    pub line_valley_depth: f32,
+    /// Keep only this many highest-scoring regions per frame; `None` keeps every region.
+    pub max_regions: Option<usize>,
+    /// Backends tried in order when `detector` is `Auto`.
+    pub auto_priority: Vec<SubtitleDetectorKind>,
 }
 
 impl Default for SubtitleDetectionOptions {
@@ -20,6 +42,17 @@ impl Default for SubtitleDetectionOptions {
             roi: None,
             detector: SubtitleDetectorKind::ProjectionBand,
             luma_band: LumaBandOptions::default(),
+            min_region: RegionSizeConfig::default(),
+            roi_clip_margin: 0.0,
+            roi_rounding: RoiRounding::Outward,
+            roi_half_pixel: false,
+            min_detection_confidence: 0.0,
+            fusion_policy: FusionPolicy::Intersection,
+            shear_compensation: None,
+            split_lines: false,
+            line_valley_depth: DEFAULT_LINE_VALLEY_DEPTH,
+            max_regions: None,
+            auto_priority: DEFAULT_AUTO_DETECTOR_PRIORITY.to_vec(),
         }
     }
 }
@@ -28,6 +61,8 @@ impl Default for SubtitleDetectionOptions {
 pub struct LumaBandOptions {
     pub target: u8,
     pub delta: u8,
+    /// Feather band membership over this many luma levels; 0 is a hard window.
+    pub softness: u8,
 }
 
 impl Default for LumaBandOptions {
@@ -35,6 +70,7 @@ impl Default for LumaBandOptions {
         Self {
             target: DEFAULT_TARGET,
             delta: DEFAULT_DELTA,
+            softness: 0,
         }
     }
 }
@@ -2,6 +2,7 @@ use crate::config::SubtitleDetectionOptions;
 use crate::subtitle_detection::{
     LumaBandConfig, RoiConfig, SubtitleDetectionConfig, SubtitleDetectionError,
     SubtitleDetectionResult, SubtitleDetector, SubtitleDetectorKind, build_detector,
+    log_region_debug,
 };
 use std::time::Duration;
 use subtitle_fast_types::VideoFrame;
@@ -11,6 +12,7 @@ static REGION_MARGIN_PX: u32 = 5;
 pub(crate) struct SubtitleDetectionPipeline {
     state: Mutex<SubtitleDetectionState>,
     enabled: bool,
+    max_regions: Option<usize>,
 }
 
 impl SubtitleDetectionPipeline {
@@ -21,6 +23,7 @@ impl SubtitleDetectionPipeline {
 
         Some(Self {
             enabled: options.enabled,
+            max_regions: options.max_regions,
             state: Mutex::new(SubtitleDetectionState::new(options)),
         })
     }
@@ -37,6 +40,10 @@ impl SubtitleDetectionPipeline {
             SubtitleDetectionResult::empty()
         };
 
+        if let Some(max_regions) = self.max_regions {
+            cap_regions(&mut detection, max_regions);
+        }
+
         if detection.has_subtitle {
             inflate_regions(
                 &mut detection,
@@ -108,10 +115,21 @@ impl SubtitleDetectionState {
             detector_config.luma_band = LumaBandConfig {
                 target: self.options.luma_band.target,
                 delta: self.options.luma_band.delta,
+                softness: self.options.luma_band.softness,
             };
             if let Some(roi) = desired_roi {
                 detector_config.roi = roi;
             }
+            detector_config.min_region = self.options.min_region;
+            detector_config.roi_clip_margin = self.options.roi_clip_margin;
+            detector_config.roi_rounding = self.options.roi_rounding;
+            detector_config.roi_half_pixel = self.options.roi_half_pixel;
+            detector_config.min_detection_confidence = self.options.min_detection_confidence;
+            detector_config.fusion_policy = self.options.fusion_policy;
+            detector_config.shear_compensation = self.options.shear_compensation;
+            detector_config.split_lines = self.options.split_lines;
+            detector_config.line_valley_depth = self.options.line_valley_depth;
+            detector_config.auto_priority = self.options.auto_priority.clone();
             match build_detector(detector_kind, detector_config) {
                 Ok(detector) => {
                     self.detector = Some(detector);
@@ -160,6 +178,33 @@ impl SubtitleDetectionState {
     }
 }
 
+/// Keeps the `max_regions` highest-scoring regions, in their original order.
+fn cap_regions(result: &mut SubtitleDetectionResult, max_regions: usize) {
+    if result.regions.len() <= max_regions {
+        return;
+    }
+    let mut ranked: Vec<usize> = (0..result.regions.len()).collect();
+    ranked.sort_by(|&a, &b| result.regions[b].score.total_cmp(&result.regions[a].score));
+    let mut keep = vec![false; result.regions.len()];
+    for &index in &ranked[..max_regions] {
+        keep[index] = true;
+    }
+    for (region, _) in result.regions.iter().zip(&keep).filter(|(_, kept)| !**kept) {
+        log_region_debug(
+            "pipeline",
+            "drop-over-cap",
+            region.x as usize,
+            region.y as usize,
+            region.width as usize,
+            region.height as usize,
+            region.score,
+        );
+    }
+    let mut keep = keep.into_iter();
+    result.regions.retain(|_| keep.next().unwrap_or(false));
+    result.has_subtitle = !result.regions.is_empty();
+}
+
 fn inflate_regions(
     result: &mut SubtitleDetectionResult,
     frame_width: usize,
@@ -220,3 +265,38 @@ fn log_init_failure(kind: SubtitleDetectorKind, err: &SubtitleDetectionError) {
         kind.as_str()
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use subtitle_fast_types::DetectionRegion;
+
+    #[test]
+    fn cap_keeps_highest_scoring_regions_in_order() {
+        // Scores 0.00..0.95 are spread out of order so the kept regions are not simply a prefix.
+        let regions: Vec<DetectionRegion> = (0..20)
+            .map(|index| DetectionRegion {
+                x: 0.0,
+                y: index as f32 * 10.0,
+                width: 100.0,
+                height: 8.0,
+                score: ((index * 7) % 20) as f32 / 20.0,
+            })
+            .collect();
+        let mut result = SubtitleDetectionResult {
+            has_subtitle: true,
+            max_score: 0.95,
+            regions,
+        };
+
+        cap_regions(&mut result, 5);
+
+        let scores: Vec<f32> = result.regions.iter().map(|region| region.score).collect();
+        assert_eq!(scores, [0.75, 0.8, 0.85, 0.9, 0.95]);
+        assert!(result.regions.windows(2).all(|pair| pair[0].y < pair[1].y));
+        assert!(result.has_subtitle);
+
+        cap_regions(&mut result, 5);
+        assert_eq!(result.regions.len(), 5);
+    }
+}
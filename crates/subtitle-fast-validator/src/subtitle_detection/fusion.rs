@@ -0,0 +1,256 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::{
+    DetectionRegion, SubtitleDetectionConfig, SubtitleDetectionError, SubtitleDetectionResult,
+    SubtitleDetector,
+};
+#[cfg(all(feature = "detector-vision", target_os = "macos"))]
+use super::{ProjectionBandDetector, VisionTextDetector};
+use subtitle_fast_types::VideoFrame;
+
+/// How the fusion detector combines luma-band regions with Vision's text boxes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FusionPolicy {
+    /// Keep Vision's boxes only where a luma-band region overlaps them, so text Vision finds
+    /// in the picture but outside the subtitle colour band is dropped.
+    #[default]
+    Intersection,
+    /// Keep every Vision box, plus the luma-band regions no Vision box overlaps.
+    Union,
+}
+
+impl FusionPolicy {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FusionPolicy::Intersection => "intersection",
+            FusionPolicy::Union => "union",
+        }
+    }
+}
+
+impl fmt::Display for FusionPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for FusionPolicy {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "intersection" | "intersect" => Ok(FusionPolicy::Intersection),
+            "union" => Ok(FusionPolicy::Union),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Runs a luma-band detector and a text-localizing detector on every frame and combines
+/// their regions according to a [`FusionPolicy`]; geometry always comes from the localizer.
+pub struct FusionDetector {
+    band: Box<dyn SubtitleDetector>,
+    text: Box<dyn SubtitleDetector>,
+    policy: FusionPolicy,
+}
+
+impl FusionDetector {
+    /// Fuses the projection-band detector with Vision text detection.
+    #[cfg(all(feature = "detector-vision", target_os = "macos"))]
+    pub fn new(config: SubtitleDetectionConfig) -> Result<Self, SubtitleDetectionError> {
+        let policy = config.fusion_policy;
+        Ok(Self::from_detectors(
+            Box::new(ProjectionBandDetector::new(config.clone())?),
+            Box::new(VisionTextDetector::new(config)?),
+            policy,
+        ))
+    }
+
+    pub fn from_detectors(
+        band: Box<dyn SubtitleDetector>,
+        text: Box<dyn SubtitleDetector>,
+        policy: FusionPolicy,
+    ) -> Self {
+        Self { band, text, policy }
+    }
+}
+
+impl SubtitleDetector for FusionDetector {
+    #[cfg(all(feature = "detector-vision", target_os = "macos"))]
+    fn ensure_available(config: &SubtitleDetectionConfig) -> Result<(), SubtitleDetectionError> {
+        ProjectionBandDetector::ensure_available(config)?;
+        VisionTextDetector::ensure_available(config)
+    }
+
+    #[cfg(not(all(feature = "detector-vision", target_os = "macos")))]
+    fn ensure_available(_config: &SubtitleDetectionConfig) -> Result<(), SubtitleDetectionError> {
+        Err(SubtitleDetectionError::Unsupported { backend: "fusion" })
+    }
+
+    fn detect(
+        &self,
+        frame: &VideoFrame,
+    ) -> Result<SubtitleDetectionResult, SubtitleDetectionError> {
+        let band = self.band.detect(frame)?;
+        let text = self.text.detect(frame)?;
+        let regions = fuse_regions(self.policy, &band.regions, &text.regions);
+        Ok(SubtitleDetectionResult {
+            has_subtitle: !regions.is_empty(),
+            max_score: regions
+                .iter()
+                .map(|region| region.score)
+                .fold(0.0, f32::max),
+            regions,
+        })
+    }
+}
+
+/// Combines luma-band `band` regions with localized `text` regions under `policy`.
+pub fn fuse_regions(
+    policy: FusionPolicy,
+    band: &[DetectionRegion],
+    text: &[DetectionRegion],
+) -> Vec<DetectionRegion> {
+    let agreed = text
+        .iter()
+        .filter(|text| band.iter().any(|band| overlaps(text, band)));
+    match policy {
+        FusionPolicy::Intersection => agreed.cloned().collect(),
+        FusionPolicy::Union => {
+            let unmatched = band
+                .iter()
+                .filter(|band| !text.iter().any(|text| overlaps(text, band)));
+            text.iter().chain(unmatched).cloned().collect()
+        }
+    }
+}
+
+fn overlaps(a: &DetectionRegion, b: &DetectionRegion) -> bool {
+    a.x < b.x + b.width && b.x < a.x + a.width && a.y < b.y + b.height && b.y < a.y + a.height
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(x: f32, y: f32, width: f32, height: f32, score: f32) -> DetectionRegion {
+        DetectionRegion {
+            x,
+            y,
+            width,
+            height,
+            score,
+        }
+    }
+
+    fn boxes(regions: &[DetectionRegion]) -> Vec<(f32, f32, f32, f32, f32)> {
+        regions
+            .iter()
+            .map(|region| {
+                (
+                    region.x,
+                    region.y,
+                    region.width,
+                    region.height,
+                    region.score,
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the same regions for every frame.
+    struct Stub(Vec<DetectionRegion>);
+
+    impl SubtitleDetector for Stub {
+        fn ensure_available(
+            _config: &SubtitleDetectionConfig,
+        ) -> Result<(), SubtitleDetectionError> {
+            Ok(())
+        }
+
+        fn detect(
+            &self,
+            _frame: &VideoFrame,
+        ) -> Result<SubtitleDetectionResult, SubtitleDetectionError> {
+            Ok(SubtitleDetectionResult {
+                has_subtitle: !self.0.is_empty(),
+                max_score: self.0.iter().map(|region| region.score).fold(0.0, f32::max),
+                regions: self.0.clone(),
+            })
+        }
+    }
+
+    fn frame() -> VideoFrame {
+        VideoFrame::filled(64, 64, 0).expect("frame")
+    }
+
+    // A subtitle band at the bottom, with Vision boxing the two words inside it and a sign
+    // in the picture above it.
+    fn band() -> Vec<DetectionRegion> {
+        vec![region(100.0, 900.0, 1200.0, 80.0, 0.6)]
+    }
+
+    fn text() -> Vec<DetectionRegion> {
+        vec![
+            region(300.0, 910.0, 200.0, 60.0, 0.9),
+            region(520.0, 912.0, 240.0, 58.0, 0.8),
+            region(1500.0, 200.0, 300.0, 90.0, 0.95),
+        ]
+    }
+
+    #[test]
+    fn intersection_keeps_vision_boxes_inside_a_luma_band() {
+        let fusion = FusionDetector::from_detectors(
+            Box::new(Stub(band())),
+            Box::new(Stub(text())),
+            FusionPolicy::Intersection,
+        );
+        let result = fusion.detect(&frame()).expect("detect");
+        assert_eq!(boxes(&result.regions), boxes(&text()[..2]));
+        assert!(result.has_subtitle);
+        assert_eq!(result.max_score, 0.9);
+
+        // Vision finding nothing inside the band leaves nothing, however bright the band.
+        let signs_only = vec![text()[2].clone()];
+        assert!(fuse_regions(FusionPolicy::Intersection, &band(), &signs_only).is_empty());
+        let fusion = FusionDetector::from_detectors(
+            Box::new(Stub(band())),
+            Box::new(Stub(signs_only)),
+            FusionPolicy::Intersection,
+        );
+        let result = fusion.detect(&frame()).expect("detect");
+        assert!(!result.has_subtitle);
+        assert_eq!(result.max_score, 0.0);
+    }
+
+    #[test]
+    fn union_adds_luma_bands_vision_missed() {
+        let extra_band = region(100.0, 40.0, 800.0, 60.0, 0.5);
+        let bands = vec![band()[0].clone(), extra_band.clone()];
+        let fusion = FusionDetector::from_detectors(
+            Box::new(Stub(bands)),
+            Box::new(Stub(text())),
+            FusionPolicy::Union,
+        );
+        let result = fusion.detect(&frame()).expect("detect");
+        // The bottom band is already covered by Vision's boxes; the top one is not.
+        let mut expected = text();
+        expected.push(extra_band);
+        assert_eq!(boxes(&result.regions), boxes(&expected));
+        assert_eq!(result.max_score, 0.95);
+
+        // Touching edges do not count as agreement.
+        let touching = region(100.0, 980.0, 400.0, 40.0, 0.7);
+        assert!(fuse_regions(FusionPolicy::Intersection, &band(), &[touching]).is_empty());
+    }
+
+    #[test]
+    fn policy_names_round_trip() {
+        for policy in [FusionPolicy::Intersection, FusionPolicy::Union] {
+            assert_eq!(policy.as_str().parse::<FusionPolicy>(), Ok(policy));
+        }
+        assert_eq!(" Union ".parse::<FusionPolicy>(), Ok(FusionPolicy::Union));
+        assert!("xor".parse::<FusionPolicy>().is_err());
+    }
+}
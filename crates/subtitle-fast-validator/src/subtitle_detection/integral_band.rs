@@ -6,9 +6,8 @@ use std::arch::is_aarch64_feature_detected;
 use std::arch::is_x86_feature_detected;
 
 use super::{
-    DetectionRegion, LumaBandConfig, MIN_REGION_HEIGHT_PX, MIN_REGION_WIDTH_PX, RoiConfig,
-    SubtitleDetectionConfig, SubtitleDetectionError, SubtitleDetectionResult, SubtitleDetector,
-    log_region_debug,
+    DetectionRegion, LumaBandConfig, RoiConfig, SubtitleDetectionConfig, SubtitleDetectionError,
+    SubtitleDetectionResult, SubtitleDetector, log_region_debug,
 };
 use subtitle_fast_types::VideoFrame;
 
@@ -22,6 +21,9 @@ const Y_MERGE_TOL: usize = 10;
 const IOU_MERGE: f32 = 0.15;
 const NEAR_GAP: usize = 16;
 const MAX_OUTPUT_REGIONS: usize = 5;
+/// Integral-image units of one fully in-band pixel; small enough that an 8K frame's sum still
+/// fits in a `u32`.
+const WEIGHT_ONE: u32 = 64;
 
 #[derive(Clone, Copy)]
 struct RoiRect {
@@ -77,7 +79,8 @@ impl SubtitleDetector for IntegralBandDetector {
             return Ok(result);
         }
 
-        let mut mask = threshold_mask(y_plane, self.config.stride, self.roi, self.config.luma_band);
+        let luma_band = self.config.luma_band;
+        let mut mask = threshold_mask(y_plane, self.config.stride, self.roi, luma_band);
         rlsa_horizontal(&mut mask, self.roi.width, self.roi.height, RLSA_H_GAP);
         rlsa_vertical(&mut mask, self.roi.width, self.roi.height, RLSA_V_GAP);
 
@@ -91,7 +94,9 @@ impl SubtitleDetector for IntegralBandDetector {
             return Ok(result);
         }
 
-        let integral = integral_image(&mask, self.roi.width, self.roi.height);
+        let weights = (luma_band.softness > 0)
+            .then(|| band_weights(y_plane, self.config.stride, self.roi, luma_band));
+        let integral = integral_image(&mask, weights.as_deref(), self.roi.width, self.roi.height);
         let frame_area = self
             .config
             .frame_width
@@ -108,7 +113,7 @@ impl SubtitleDetector for IntegralBandDetector {
             if width == 0 || height == 0 {
                 continue;
             }
-            if height < MIN_REGION_HEIGHT_PX {
+            if height < self.config.min_region.min_height_px {
                 log_region_debug(
                     "integral",
                     "reject_short_component",
@@ -120,7 +125,7 @@ impl SubtitleDetector for IntegralBandDetector {
                 );
                 continue;
             }
-            if width < MIN_REGION_WIDTH_PX {
+            if width < self.config.min_region.min_width_px {
                 log_region_debug(
                     "integral",
                     "reject_narrow_component",
@@ -257,8 +262,16 @@ fn threshold_mask(data: &[u8], stride: usize, roi: RoiRect, params: LumaBandConf
         return mask;
     }
 
-    let lo = params.target.saturating_sub(params.delta);
-    let hi = params.target.saturating_add(params.delta);
+    // Feathered pixels join the mask so they can extend components; `band_weights` then scores
+    // them by how far outside the hard window they sit.
+    let lo = params
+        .target
+        .saturating_sub(params.delta)
+        .saturating_sub(params.softness);
+    let hi = params
+        .target
+        .saturating_add(params.delta)
+        .saturating_add(params.softness);
 
     #[cfg(target_arch = "x86_64")]
     {
@@ -583,7 +596,27 @@ fn connected_components(mask: &[u8], width: usize, height: usize) -> Vec<Compone
     stats.into_iter().flatten().collect()
 }
 
-fn integral_image(mask: &[u8], width: usize, height: usize) -> Vec<u32> {
+/// Band membership of every ROI pixel in `1..=WEIGHT_ONE` units, zero outside the feathered
+/// window.
+fn band_weights(data: &[u8], stride: usize, roi: RoiRect, params: LumaBandConfig) -> Vec<u8> {
+    let mut weights = vec![0u8; roi.width * roi.height];
+    for row in 0..roi.height {
+        let src_offset = (roi.y + row) * stride + roi.x;
+        let src = &data[src_offset..src_offset + roi.width];
+        let dst = &mut weights[row * roi.width..(row + 1) * roi.width];
+        for (value, out) in src.iter().zip(dst.iter_mut()) {
+            let weight = params.weight(*value);
+            if weight > 0.0 {
+                *out = ((weight * WEIGHT_ONE as f32).round() as u8).max(1);
+            }
+        }
+    }
+    weights
+}
+
+/// Summed-area table of the mask in `WEIGHT_ONE` units per pixel. With `weights`, set pixels
+/// count their band membership; pixels only set by run-length smoothing count in full.
+fn integral_image(mask: &[u8], weights: Option<&[u8]>, width: usize, height: usize) -> Vec<u32> {
     let stride = width + 1;
     let mut integral = vec![0u32; stride * (height + 1)];
     for y in 0..height {
@@ -591,20 +624,28 @@ fn integral_image(mask: &[u8], width: usize, height: usize) -> Vec<u32> {
         let src_offset = y * width;
         let dst_offset = (y + 1) * stride;
         for x in 0..width {
-            row_sum += mask[src_offset + x] as u32;
+            let idx = src_offset + x;
+            if mask[idx] != 0 {
+                row_sum += match weights {
+                    Some(weights) if weights[idx] != 0 => weights[idx] as u32,
+                    _ => WEIGHT_ONE,
+                };
+            }
             integral[dst_offset + x + 1] = integral[dst_offset - stride + x + 1] + row_sum;
         }
     }
     integral
 }
 
-fn rect_sum(integral: &[u32], width: usize, x0: usize, y0: usize, x1: usize, y1: usize) -> u32 {
+/// Pixel-equivalent hits inside `[x0, x1) x [y0, y1)`.
+fn rect_sum(integral: &[u32], width: usize, x0: usize, y0: usize, x1: usize, y1: usize) -> f32 {
     let stride = width + 1;
     let idx = |x: usize, y: usize| -> usize { y * stride + x };
-    integral[idx(x1, y1)]
+    let sum = integral[idx(x1, y1)]
         .wrapping_sub(integral[idx(x0, y1)])
         .wrapping_sub(integral[idx(x1, y0)])
-        .wrapping_add(integral[idx(x0, y0)])
+        .wrapping_add(integral[idx(x0, y0)]);
+    sum as f32 / WEIGHT_ONE as f32
 }
 
 fn evaluate_region(
@@ -618,7 +659,7 @@ fn evaluate_region(
 ) -> (f32, f32, f32) {
     let x1 = x + w;
     let y1 = y + h;
-    let hits = rect_sum(integral, width, x, y, x1, y1) as f32;
+    let hits = rect_sum(integral, width, x, y, x1, y1);
     let area = (w * h) as f32 + 1e-6;
     let fill = hits / area;
 
@@ -636,7 +677,7 @@ fn evaluate_region(
             let mut xx = x;
             while xx < x1 {
                 let xb = cmp::min(xx + dx, x1);
-                let value = rect_sum(integral, width, xx, yy, xb, yb) as f32;
+                let value = rect_sum(integral, width, xx, yy, xb, yb);
                 count += 1.0;
                 let delta = value - mean;
                 mean += delta / count;
@@ -762,3 +803,152 @@ fn merge_candidates(a: &Candidate, b: &Candidate, integral: &[u32], width: usize
         score,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WIDTH: usize = 640;
+    const HEIGHT: usize = 360;
+
+    /// A glyph-like text line near the bottom plus a bright logo block near the top.
+    fn frame_with_line_and_logo() -> VideoFrame {
+        let mut y_plane = vec![0u8; WIDTH * HEIGHT];
+        for y in 300..324 {
+            for x in 100..540 {
+                if x % 12 < 9 {
+                    y_plane[y * WIDTH + x] = 230;
+                }
+            }
+        }
+        for y in 20..60 {
+            for x in 480..620 {
+                y_plane[y * WIDTH + x] = 230;
+            }
+        }
+        VideoFrame::from_nv12_owned(
+            WIDTH as u32,
+            HEIGHT as u32,
+            WIDTH,
+            WIDTH,
+            None,
+            None,
+            y_plane,
+            vec![128u8; WIDTH * HEIGHT / 2],
+        )
+        .expect("frame")
+    }
+
+    /// `(x, y, width, height, score)` of every detected region.
+    fn detect_regions(roi: RoiConfig) -> Vec<(f32, f32, f32, f32, f32)> {
+        let mut config = SubtitleDetectionConfig::for_frame(WIDTH, HEIGHT, WIDTH);
+        config.roi = roi;
+        let detector = IntegralBandDetector::new(config).expect("detector");
+        detector
+            .detect(&frame_with_line_and_logo())
+            .expect("detect")
+            .regions
+            .iter()
+            .map(|region| {
+                (
+                    region.x,
+                    region.y,
+                    region.width,
+                    region.height,
+                    region.score,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn roi_limited_detection_matches_full_frame_inside_roi() {
+        let roi = RoiConfig {
+            x: 0.0,
+            y: 0.75,
+            width: 1.0,
+            height: 0.25,
+        };
+        let roi_top = roi.y * HEIGHT as f32;
+        let full_frame = detect_regions(RoiConfig {
+            x: 0.0,
+            y: 0.0,
+            width: 1.0,
+            height: 1.0,
+        });
+        assert!(
+            full_frame.iter().any(|region| region.1 < roi_top),
+            "logo outside the ROI should be detected on the full frame: {full_frame:?}"
+        );
+
+        let inside: Vec<_> = full_frame
+            .into_iter()
+            .filter(|region| region.1 >= roi_top)
+            .collect();
+        let roi_limited = detect_regions(roi);
+        assert!(!roi_limited.is_empty());
+        assert_eq!(roi_limited, inside);
+    }
+
+    /// A glyph-like text line whose top and bottom three rows fade out like anti-aliased edges,
+    /// dropping just below the hard band.
+    fn feathered_line_frame() -> VideoFrame {
+        let mut y_plane = vec![0u8; WIDTH * HEIGHT];
+        let fade = [216u8, 213, 210];
+        for x in (100..540).filter(|x| x % 12 < 9) {
+            for y in 300..324 {
+                y_plane[y * WIDTH + x] = 230;
+            }
+            for (step, &value) in fade.iter().enumerate() {
+                y_plane[(299 - step) * WIDTH + x] = value;
+                y_plane[(324 + step) * WIDTH + x] = value;
+            }
+        }
+        VideoFrame::from_nv12_owned(
+            WIDTH as u32,
+            HEIGHT as u32,
+            WIDTH,
+            WIDTH,
+            None,
+            None,
+            y_plane,
+            vec![128u8; WIDTH * HEIGHT / 2],
+        )
+        .expect("frame")
+    }
+
+    #[test]
+    fn feathered_band_weights_anti_aliased_rows() {
+        let frame = feathered_line_frame();
+        let hard = SubtitleDetectionConfig::for_frame(WIDTH, HEIGHT, WIDTH);
+        let mut soft = hard.clone();
+        soft.luma_band.softness = 12;
+
+        let line = |config: SubtitleDetectionConfig| {
+            let detector = IntegralBandDetector::new(config).expect("detector");
+            let result = detector.detect(&frame).expect("detect");
+            let region = result.regions.first().expect("text line detected").clone();
+            (region.y, region.height)
+        };
+        assert_eq!(line(hard.clone()), (300.0, 24.0));
+        assert_eq!(line(soft.clone()), (297.0, 30.0));
+
+        let roi = compute_roi_rect(WIDTH, HEIGHT, soft.roi).expect("roi");
+        let mask = threshold_mask(frame.data(), WIDTH, roi, soft.luma_band);
+        let weights = band_weights(frame.data(), WIDTH, roi, soft.luma_band);
+        let binary = integral_image(&mask, None, WIDTH, HEIGHT);
+        let feathered = integral_image(&mask, Some(&weights), WIDTH, HEIGHT);
+
+        let row_hits = |integral: &[u32], y: usize| rect_sum(integral, WIDTH, 0, y, WIDTH, y + 1);
+        let stroke_pixels = (100..540).filter(|x| x % 12 < 9).count() as f32;
+        assert_eq!(row_hits(&binary, 310), stroke_pixels);
+        assert_eq!(row_hits(&feathered, 310), stroke_pixels);
+        for (row, expected) in [(299, 11.0 / 13.0), (298, 8.0 / 13.0), (297, 5.0 / 13.0)] {
+            assert_eq!(row_hits(&binary, row), stroke_pixels);
+            let hits = row_hits(&feathered, row);
+            assert!((hits - stroke_pixels * expected).abs() < stroke_pixels / 64.0);
+            assert_eq!(hits, row_hits(&feathered, 623 - row));
+        }
+        assert_eq!(row_hits(&feathered, 50), 0.0);
+    }
+}
@@ -1,13 +1,16 @@
 use std::env;
 use std::fmt;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
 use subtitle_fast_types::VideoFrame;
 use thiserror::Error;
 
 pub use subtitle_fast_types::{DetectionRegion, RoiConfig, SubtitleDetectionResult};
 
+pub mod fusion;
 pub mod integral_band;
 pub mod projection_band;
+pub use fusion::{FusionDetector, FusionPolicy};
 pub use integral_band::IntegralBandDetector;
 pub use projection_band::ProjectionBandDetector;
 
@@ -20,16 +23,22 @@ pub const DEFAULT_TARGET: u8 = 230;
 pub const DEFAULT_DELTA: u8 = 12;
 pub const MIN_REGION_HEIGHT_PX: usize = 24;
 pub const MIN_REGION_WIDTH_PX: usize = 24;
+/// Share of a band's peak row mass a row must fall below to count as a gap between lines.
+pub const DEFAULT_LINE_VALLEY_DEPTH: f32 = 0.9;
 const REGION_DEBUG_ENV: &str = "REGION_DEBUG";
 
+static REGION_DEBUG: AtomicBool = AtomicBool::new(false);
+
+/// Order in which `Auto` tries detector backends when no priority is configured.
 #[cfg(target_os = "macos")]
-const AUTO_DETECTOR_PRIORITY: &[SubtitleDetectorKind] = &[
+pub const DEFAULT_AUTO_DETECTOR_PRIORITY: &[SubtitleDetectorKind] = &[
     SubtitleDetectorKind::ProjectionBand,
     SubtitleDetectorKind::IntegralBand,
 ];
 
+/// Order in which `Auto` tries detector backends when no priority is configured.
 #[cfg(not(target_os = "macos"))]
-const AUTO_DETECTOR_PRIORITY: &[SubtitleDetectorKind] = &[
+pub const DEFAULT_AUTO_DETECTOR_PRIORITY: &[SubtitleDetectorKind] = &[
     SubtitleDetectorKind::ProjectionBand,
     SubtitleDetectorKind::IntegralBand,
 ];
@@ -47,6 +56,16 @@ fn backend_for_kind(kind: SubtitleDetectorKind) -> Option<&'static dyn DetectorB
                 None
             }
         }
+        SubtitleDetectorKind::Fusion => {
+            #[cfg(all(feature = "detector-vision", target_os = "macos"))]
+            {
+                Some(&FUSION_BACKEND)
+            }
+            #[cfg(not(all(feature = "detector-vision", target_os = "macos")))]
+            {
+                None
+            }
+        }
         SubtitleDetectorKind::IntegralBand => Some(&INTEGRAL_BAND_BACKEND),
         SubtitleDetectorKind::ProjectionBand => Some(&PROJECTION_BAND_BACKEND),
     }
@@ -56,6 +75,122 @@ fn backend_for_kind(kind: SubtitleDetectorKind) -> Option<&'static dyn DetectorB
 pub struct LumaBandConfig {
     pub target: u8,
     pub delta: u8,
+    /// Luma levels past either edge of `[target - delta, target + delta]` over which band
+    /// membership fades linearly to zero. Zero keeps the hard window.
+    pub softness: u8,
+}
+
+impl LumaBandConfig {
+    /// Trapezoidal band membership of `value`: 1 inside the window, ramping down to 0 across
+    /// `softness` levels outside it.
+    pub fn weight(&self, value: u8) -> f32 {
+        let lo = self.target.saturating_sub(self.delta);
+        let hi = self.target.saturating_add(self.delta);
+        let distance = if value < lo {
+            lo - value
+        } else if value > hi {
+            value - hi
+        } else {
+            return 1.0;
+        };
+        if distance > self.softness {
+            return 0.0;
+        }
+        1.0 - distance as f32 / (self.softness as f32 + 1.0)
+    }
+}
+
+/// Smallest region, in pixels, a band detector reports as a subtitle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegionSizeConfig {
+    pub min_width_px: usize,
+    pub min_height_px: usize,
+}
+
+impl Default for RegionSizeConfig {
+    fn default() -> Self {
+        Self {
+            min_width_px: MIN_REGION_WIDTH_PX,
+            min_height_px: MIN_REGION_HEIGHT_PX,
+        }
+    }
+}
+
+/// Shear angles tried by the projection-band detector before taking row projections.
+///
+/// Angles run from `-max_angle_deg` to `max_angle_deg` in `step_deg` increments; the angle
+/// whose row projection has the sharpest peaks wins, so text on a slanted baseline collapses
+/// back into a narrow band.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShearCompensation {
+    pub max_angle_deg: f32,
+    pub step_deg: f32,
+}
+
+impl Default for ShearCompensation {
+    fn default() -> Self {
+        Self {
+            max_angle_deg: 12.0,
+            step_deg: 1.0,
+        }
+    }
+}
+
+/// How the Vision detector snaps the fractional edges of its clip ROI to whole pixels.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RoiRounding {
+    /// Floor the start and ceil the end, so a partly covered pixel is always kept.
+    #[default]
+    Outward,
+    Floor,
+    Round,
+    Ceil,
+}
+
+impl RoiRounding {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RoiRounding::Outward => "outward",
+            RoiRounding::Floor => "floor",
+            RoiRounding::Round => "round",
+            RoiRounding::Ceil => "ceil",
+        }
+    }
+
+    /// Pixel span `[start, end)` for edges at `start` and `end` pixels. With `half_pixel` the
+    /// edges are read against pixel centres: both move half a pixel toward the origin before
+    /// snapping, so with `Ceil` a pixel is inside exactly when its centre is.
+    pub fn snap(self, start: f32, end: f32, half_pixel: bool) -> (isize, isize) {
+        let shift = if half_pixel { 0.5 } else { 0.0 };
+        let (start, end) = (start - shift, end - shift);
+        let (start, end) = match self {
+            RoiRounding::Outward => (start.floor(), end.ceil()),
+            RoiRounding::Floor => (start.floor(), end.floor()),
+            RoiRounding::Round => (start.round(), end.round()),
+            RoiRounding::Ceil => (start.ceil(), end.ceil()),
+        };
+        (start as isize, end as isize)
+    }
+}
+
+impl fmt::Display for RoiRounding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RoiRounding {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "outward" => Ok(RoiRounding::Outward),
+            "floor" => Ok(RoiRounding::Floor),
+            "round" => Ok(RoiRounding::Round),
+            "ceil" => Ok(RoiRounding::Ceil),
+            _ => Err(()),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -98,6 +233,29 @@ impl DetectorBackend for VisionBackend {
 #[cfg(all(feature = "detector-vision", target_os = "macos"))]
 static VISION_BACKEND: VisionBackend = VisionBackend;
 
+#[cfg(all(feature = "detector-vision", target_os = "macos"))]
+struct FusionBackend;
+
+#[cfg(all(feature = "detector-vision", target_os = "macos"))]
+impl DetectorBackend for FusionBackend {
+    fn ensure_available(
+        &self,
+        config: &SubtitleDetectionConfig,
+    ) -> Result<(), SubtitleDetectionError> {
+        FusionDetector::ensure_available(config)
+    }
+
+    fn build(
+        &self,
+        config: SubtitleDetectionConfig,
+    ) -> Result<Box<dyn SubtitleDetector>, SubtitleDetectionError> {
+        Ok(Box::new(FusionDetector::new(config)?))
+    }
+}
+
+#[cfg(all(feature = "detector-vision", target_os = "macos"))]
+static FUSION_BACKEND: FusionBackend = FusionBackend;
+
 struct IntegralBandBackend;
 
 impl DetectorBackend for IntegralBandBackend {
@@ -156,6 +314,26 @@ pub struct SubtitleDetectionConfig {
     pub stride: usize,
     pub roi: RoiConfig,
     pub luma_band: LumaBandConfig,
+    /// Regions smaller than this are discarded by the band detectors.
+    pub min_region: RegionSizeConfig,
+    /// Fraction of the frame a detected region may extend past the ROI before clipping.
+    pub roi_clip_margin: f32,
+    /// Vision only: how the clip ROI's edges snap to whole pixels.
+    pub roi_rounding: RoiRounding,
+    /// Vision only: snap the clip ROI's edges against pixel centres; see [`RoiRounding::snap`].
+    pub roi_half_pixel: bool,
+    /// Vision only: text observations below this confidence are dropped; 0 keeps them all.
+    pub min_detection_confidence: f32,
+    /// Fusion only: how Vision's boxes are combined with the luma-band regions.
+    pub fusion_policy: FusionPolicy,
+    /// Projection-band only: search for a shear that straightens slanted text. Off when `None`.
+    pub shear_compensation: Option<ShearCompensation>,
+    /// Projection-band only: cut bands holding several stacked lines at the valleys between them.
+    pub split_lines: bool,
+    /// Rows whose mass is at most `1 - line_valley_depth` of the band's peak row separate lines.
+    pub line_valley_depth: f32,
+    /// Backends `Auto` tries, first available wins; empty uses [`DEFAULT_AUTO_DETECTOR_PRIORITY`].
+    pub auto_priority: Vec<SubtitleDetectorKind>,
 }
 
 impl SubtitleDetectionConfig {
@@ -173,7 +351,18 @@ impl SubtitleDetectionConfig {
             luma_band: LumaBandConfig {
                 target: DEFAULT_TARGET,
                 delta: DEFAULT_DELTA,
+                softness: 0,
             },
+            min_region: RegionSizeConfig::default(),
+            roi_clip_margin: 0.0,
+            roi_rounding: RoiRounding::Outward,
+            roi_half_pixel: false,
+            min_detection_confidence: 0.0,
+            fusion_policy: FusionPolicy::Intersection,
+            shear_compensation: None,
+            split_lines: false,
+            line_valley_depth: DEFAULT_LINE_VALLEY_DEPTH,
+            auto_priority: DEFAULT_AUTO_DETECTOR_PRIORITY.to_vec(),
         }
     }
 }
@@ -195,7 +384,16 @@ impl Configuration {
 }
 
 pub fn preflight_detection(kind: SubtitleDetectorKind) -> Result<(), SubtitleDetectionError> {
-    let probe_config = build_probe_config();
+    preflight_detection_with_priority(kind, DEFAULT_AUTO_DETECTOR_PRIORITY)
+}
+
+/// Like [`preflight_detection`], with `Auto` trying the backends in `auto_priority` order.
+pub fn preflight_detection_with_priority(
+    kind: SubtitleDetectorKind,
+    auto_priority: &[SubtitleDetectorKind],
+) -> Result<(), SubtitleDetectionError> {
+    let mut probe_config = build_probe_config();
+    probe_config.auto_priority = auto_priority.to_vec();
     match kind {
         SubtitleDetectorKind::Auto => preflight_auto(&probe_config),
         SubtitleDetectorKind::MacVision => {
@@ -207,6 +405,9 @@ pub fn preflight_detection(kind: SubtitleDetectorKind) -> Result<(), SubtitleDet
         SubtitleDetectorKind::ProjectionBand => {
             ensure_backend_available(SubtitleDetectorKind::ProjectionBand, &probe_config)
         }
+        SubtitleDetectorKind::Fusion => {
+            ensure_backend_available(SubtitleDetectorKind::Fusion, &probe_config)
+        }
     }
 }
 
@@ -216,7 +417,7 @@ fn build_probe_config() -> SubtitleDetectionConfig {
 
 fn preflight_auto(probe_config: &SubtitleDetectionConfig) -> Result<(), SubtitleDetectionError> {
     let mut last_err: Option<SubtitleDetectionError> = None;
-    for &candidate in auto_backend_priority() {
+    for &candidate in auto_backend_priority(probe_config) {
         match ensure_backend_available(candidate, probe_config) {
             Ok(()) => return Ok(()),
             Err(err) => {
@@ -239,6 +440,8 @@ pub enum SubtitleDetectorKind {
     MacVision,
     IntegralBand,
     ProjectionBand,
+    /// Projection-band and Vision together, combined per [`FusionPolicy`]; macOS only.
+    Fusion,
 }
 
 impl SubtitleDetectorKind {
@@ -248,6 +451,7 @@ impl SubtitleDetectorKind {
             SubtitleDetectorKind::MacVision => "macos-vision",
             SubtitleDetectorKind::IntegralBand => "integral-band",
             SubtitleDetectorKind::ProjectionBand => "projection-band",
+            SubtitleDetectorKind::Fusion => "fusion",
         }
     }
 
@@ -276,6 +480,8 @@ impl FromStr for SubtitleDetectorKind {
             }
             #[cfg(all(feature = "detector-vision", target_os = "macos"))]
             "vision" | "macos-vision" => Ok(SubtitleDetectorKind::MacVision),
+            #[cfg(all(feature = "detector-vision", target_os = "macos"))]
+            "fusion" => Ok(SubtitleDetectorKind::Fusion),
             _ => Err(SubtitleDetectionError::Unsupported {
                 backend: "unknown-detector",
             }),
@@ -309,12 +515,21 @@ pub fn build_detector(
     }
 }
 
-fn auto_backend_priority() -> &'static [SubtitleDetectorKind] {
-    AUTO_DETECTOR_PRIORITY
+fn auto_backend_priority(config: &SubtitleDetectionConfig) -> &[SubtitleDetectorKind] {
+    if config.auto_priority.is_empty() {
+        DEFAULT_AUTO_DETECTOR_PRIORITY
+    } else {
+        &config.auto_priority
+    }
+}
+
+/// Turns the per-region trace on for the whole process, as if `REGION_DEBUG` were set.
+pub fn set_region_debug(enabled: bool) {
+    REGION_DEBUG.store(enabled, Ordering::Relaxed);
 }
 
 fn region_debug_enabled() -> bool {
-    env::var_os(REGION_DEBUG_ENV).is_some()
+    REGION_DEBUG.load(Ordering::Relaxed) || env::var_os(REGION_DEBUG_ENV).is_some()
 }
 
 pub(crate) fn log_region_debug(
@@ -338,8 +553,15 @@ pub(crate) fn log_region_debug(
 fn build_auto(
     config: SubtitleDetectionConfig,
 ) -> Result<Box<dyn SubtitleDetector>, SubtitleDetectionError> {
+    select_auto(config).map(|(_, detector)| detector)
+}
+
+/// Builds the first usable backend in the configured priority, along with its kind.
+fn select_auto(
+    config: SubtitleDetectionConfig,
+) -> Result<(SubtitleDetectorKind, Box<dyn SubtitleDetector>), SubtitleDetectionError> {
     let mut last_err: Option<SubtitleDetectionError> = None;
-    for &candidate in auto_backend_priority() {
+    for &candidate in auto_backend_priority(&config) {
         let Some(backend) = backend_for_kind(candidate) else {
             let err = SubtitleDetectionError::Unsupported {
                 backend: candidate.as_str(),
@@ -354,7 +576,7 @@ fn build_auto(
         let candidate_config = config.clone();
         match backend.ensure_available(&candidate_config) {
             Ok(()) => match backend.build(candidate_config) {
-                Ok(detector) => return Ok(detector),
+                Ok(detector) => return Ok((candidate, detector)),
                 Err(err) => {
                     eprintln!(
                         "auto subtitle detector candidate '{}' failed to initialize: {err}",
@@ -396,6 +618,7 @@ pub fn available_detector_kinds() -> Vec<SubtitleDetectorKind> {
         SubtitleDetectorKind::IntegralBand,
         SubtitleDetectorKind::ProjectionBand,
         SubtitleDetectorKind::MacVision,
+        SubtitleDetectorKind::Fusion,
     ];
     let mut available = Vec::new();
     for kind in candidates {
@@ -405,3 +628,55 @@ pub fn available_detector_kinds() -> Vec<SubtitleDetectorKind> {
     }
     available
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn auto_priority_decides_which_backend_is_built_first() {
+        let config = SubtitleDetectionConfig::for_frame(640, 360, 640);
+        let (default_kind, _) = select_auto(config.clone()).expect("default auto detector");
+        assert_eq!(default_kind, SubtitleDetectorKind::ProjectionBand);
+
+        let reordered = SubtitleDetectionConfig {
+            auto_priority: vec![
+                SubtitleDetectorKind::IntegralBand,
+                SubtitleDetectorKind::ProjectionBand,
+            ],
+            ..config
+        };
+        let (kind, _) = select_auto(reordered).expect("reordered auto detector");
+        assert_eq!(kind, SubtitleDetectorKind::IntegralBand);
+    }
+
+    #[test]
+    fn roi_rounding_modes_snap_a_fractional_roi_differently() {
+        // x = 0.2003, width = 0.5994 on a 1000 px wide frame: edges at 200.3 and 799.7.
+        let (start, end) = (0.2003f32 * 1000.0, (0.2003f32 + 0.5994) * 1000.0);
+        let snapped: Vec<_> = [
+            RoiRounding::Outward,
+            RoiRounding::Floor,
+            RoiRounding::Round,
+            RoiRounding::Ceil,
+        ]
+        .into_iter()
+        .map(|mode| (mode.as_str(), mode.snap(start, end, false)))
+        .collect();
+        assert_eq!(
+            snapped,
+            [
+                ("outward", (200, 800)),
+                ("floor", (200, 799)),
+                ("round", (200, 800)),
+                ("ceil", (201, 800)),
+            ]
+        );
+
+        // Against pixel centres, pixel 200 (centre 200.5) and pixel 799 (centre 799.5) are in.
+        assert_eq!(RoiRounding::Ceil.snap(start, end, true), (200, 800));
+        assert_eq!(RoiRounding::Round.snap(start, end, true), (200, 799));
+        assert_eq!("Round".parse::<RoiRounding>(), Ok(RoiRounding::Round));
+        assert!("nearest".parse::<RoiRounding>().is_err());
+    }
+}
@@ -4,7 +4,7 @@ use std::ops::Range;
 const BYTE_BITS: usize = 8;
 
 use super::{
-    DetectionRegion, LumaBandConfig, MIN_REGION_HEIGHT_PX, MIN_REGION_WIDTH_PX, RoiConfig,
+    DetectionRegion, LumaBandConfig, RegionSizeConfig, RoiConfig, ShearCompensation,
     SubtitleDetectionConfig, SubtitleDetectionError, SubtitleDetectionResult, SubtitleDetector,
     log_region_debug,
 };
@@ -58,6 +58,10 @@ impl PackedMask {
         BitIter::new(self.row(y), self.width)
     }
 
+    fn bit(&self, x: usize, y: usize) -> bool {
+        self.data[y * self.stride + x / BYTE_BITS] & (1u8 << (x % BYTE_BITS)) != 0
+    }
+
     fn set_bit(&mut self, x: usize, y: usize) {
         let idx = y * self.stride + x / BYTE_BITS;
         let mask = 1u8 << (x % BYTE_BITS);
@@ -197,16 +201,22 @@ pub struct ProjectionBandDetector {
     config: SubtitleDetectionConfig,
     roi: RoiRect,
     required_len: usize,
+    shear_slopes: Vec<f32>,
 }
 
 impl ProjectionBandDetector {
     pub fn new(config: SubtitleDetectionConfig) -> Result<Self, SubtitleDetectionError> {
         let required_len = required_len(&config)?;
         let roi = compute_roi_rect(config.frame_width, config.frame_height, config.roi)?;
+        let shear_slopes = config
+            .shear_compensation
+            .map(shear_slopes)
+            .unwrap_or_default();
         Ok(Self {
             config,
             roi,
             required_len,
+            shear_slopes,
         })
     }
 
@@ -214,21 +224,47 @@ impl ProjectionBandDetector {
         threshold_mask(self.roi, data, self.config.stride, self.config.luma_band)
     }
 
-    fn find_candidates(&self, mask: &PackedMask) -> Vec<RegionCandidate> {
-        let width = self.roi.width;
-        let height = self.roi.height;
+    /// Band mass of each mask row: its set bits plus, with a soft band, the partial weight of
+    /// feathered pixels just outside the hard window that the mask left unset.
+    fn row_projection(&self, mask: &PackedMask, data: &[u8], shear: Option<&Shear>) -> Vec<f32> {
+        let mut rows: Vec<f32> = (0..mask.height)
+            .map(|y| mask.count_ones_row(y) as f32)
+            .collect();
+        let band = self.config.luma_band;
+        if band.softness == 0 {
+            return rows;
+        }
+        for y in 0..self.roi.height {
+            let offset = (self.roi.y + y) * self.config.stride + self.roi.x;
+            for (x, &value) in data[offset..offset + self.roi.width].iter().enumerate() {
+                let weight = band.weight(value);
+                if weight <= 0.0 || weight >= 1.0 {
+                    continue;
+                }
+                let row = shear.map_or(y, |shear| shear.row_for(x, y));
+                if !mask.bit(x, row) {
+                    rows[row] += weight;
+                }
+            }
+        }
+        rows
+    }
+
+    fn find_candidates(&self, mask: &PackedMask, row_mass: &[f32]) -> Vec<RegionCandidate> {
+        let width = mask.width;
+        let height = mask.height;
         if width == 0 || height == 0 {
             return Vec::new();
         }
         let mut min_area_px =
             (width as f32 * height as f32 * MIN_REGION_AREA_RATIO).ceil() as usize;
-        min_area_px = min_area_px.max(MIN_REGION_WIDTH_PX * MIN_REGION_HEIGHT_PX);
+        let min_region = self.config.min_region;
+        min_area_px = min_area_px.max(min_region.min_width_px * min_region.min_height_px);
         let mut row_density = vec![0f32; height];
         let mut total_density = 0f32;
         let width_f = width.max(1) as f32;
-        for (y, density) in row_density.iter_mut().enumerate().take(height) {
-            let ones = mask.count_ones_row(y);
-            *density = ones as f32 / width_f;
+        for (density, mass) in row_density.iter_mut().zip(row_mass) {
+            *density = mass / width_f;
             total_density += *density;
         }
         let avg_density = total_density / height.max(1) as f32;
@@ -249,7 +285,7 @@ impl ProjectionBandDetector {
             if end - start < MIN_BAND_HEIGHT {
                 continue;
             }
-            let mut band_candidates = analyze_band(mask, start..end, min_area_px);
+            let mut band_candidates = analyze_band(mask, start..end, min_area_px, min_region);
             candidates.append(&mut band_candidates);
         }
         candidates.sort_by(|a, b| {
@@ -279,16 +315,39 @@ impl SubtitleDetector for ProjectionBandDetector {
             });
         }
         let mut mask = self.threshold_mask(data);
+        let shear = best_shear(&mask, &self.shear_slopes);
+        if let Some(shear) = shear.as_ref() {
+            mask = shear.apply(&mask);
+        }
+        // Vertical bridging closes the gaps between stacked lines, so valleys are measured on
+        // the mask as thresholded.
+        let unbridged = self.config.split_lines.then(|| mask.clone());
         gap_bridge_horizontal(&mut mask, H_GAP);
         gap_bridge_vertical(&mut mask, V_GAP);
-        let mut local_candidates = self.find_candidates(&mask);
+        let row_mass = self.row_projection(&mask, data, shear.as_ref());
+        let mut local_candidates = self.find_candidates(&mask, &row_mass);
         if local_candidates.is_empty() {
-            let width = self.roi.width.max(1);
-            let height = self.roi.height.max(1);
+            let width = mask.width.max(1);
+            let height = mask.height.max(1);
             let mut min_area_px =
                 (width as f32 * height as f32 * MIN_REGION_AREA_RATIO).ceil() as usize;
-            min_area_px = min_area_px.max(MIN_REGION_WIDTH_PX * MIN_REGION_HEIGHT_PX);
-            local_candidates = rle_candidates(&mask, min_area_px);
+            let min_region = self.config.min_region;
+            min_area_px = min_area_px.max(min_region.min_width_px * min_region.min_height_px);
+            local_candidates = rle_candidates(&mask, min_area_px, min_region);
+        }
+        if let Some(unbridged) = unbridged.as_ref() {
+            let min_height = self.config.min_region.min_height_px.max(1);
+            local_candidates = local_candidates
+                .iter()
+                .flat_map(|cand| {
+                    split_lines(unbridged, cand, self.config.line_valley_depth, min_height)
+                })
+                .collect();
+        }
+        if let Some(shear) = shear.as_ref() {
+            for cand in &mut local_candidates {
+                shear.unshear_candidate(cand, self.roi.height);
+            }
         }
         if local_candidates.is_empty() {
             return Ok(SubtitleDetectionResult::empty());
@@ -336,10 +395,92 @@ fn candidate_mass(candidate: &RegionCandidate) -> f32 {
     candidate.score * area as f32
 }
 
-fn analyze_band(mask: &PackedMask, band: Range<usize>, min_area_px: usize) -> Vec<RegionCandidate> {
+/// Cut a candidate at rows whose mass, within its columns, falls to `1 - valley_depth` of the
+/// peak row. Stretches shorter than `min_height` stay attached to the line above them so
+/// accents and descenders are not split off; a band without such a valley is returned whole.
+fn split_lines(
+    mask: &PackedMask,
+    cand: &RegionCandidate,
+    valley_depth: f32,
+    min_height: usize,
+) -> Vec<RegionCandidate> {
+    let columns = cand.x..cand.x + cand.width;
+    let rows = cand.y..(cand.y + cand.height).min(mask.height);
+    let profile: Vec<usize> = rows
+        .clone()
+        .map(|y| mask.row_iter(y).filter(|x| columns.contains(x)).count())
+        .collect();
+    let peak = profile.iter().copied().max().unwrap_or(0);
+    let valley = peak as f32 * (1.0 - valley_depth.clamp(0.0, 1.0));
+    let mut lines: Vec<Range<usize>> = Vec::new();
+    let mut start = None;
+    for (offset, &mass) in profile.iter().enumerate() {
+        let y = rows.start + offset;
+        match (mass as f32 > valley, start) {
+            (true, None) => start = Some(y),
+            (false, Some(line_start)) => {
+                push_line(&mut lines, line_start..y, min_height);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(line_start) = start {
+        push_line(&mut lines, line_start..rows.end, min_height);
+    }
+    if lines.len() < 2 {
+        return vec![cand.clone()];
+    }
+    lines
+        .into_iter()
+        .filter_map(|line| {
+            let mut left = usize::MAX;
+            let mut right = 0usize;
+            for y in line.clone() {
+                for x in mask.row_iter(y).filter(|x| columns.contains(x)) {
+                    left = left.min(x);
+                    right = right.max(x + 1);
+                }
+            }
+            if left >= right {
+                return None;
+            }
+            log_region_debug(
+                "projection",
+                "split_line",
+                left,
+                line.start,
+                right - left,
+                line.len(),
+                cand.score,
+            );
+            Some(RegionCandidate {
+                x: left,
+                y: line.start,
+                width: right - left,
+                height: line.len(),
+                score: cand.score,
+            })
+        })
+        .collect()
+}
+
+fn push_line(lines: &mut Vec<Range<usize>>, line: Range<usize>, min_height: usize) {
+    match lines.last_mut() {
+        Some(last) if last.len() < min_height || line.len() < min_height => last.end = line.end,
+        _ => lines.push(line),
+    }
+}
+
+fn analyze_band(
+    mask: &PackedMask,
+    band: Range<usize>,
+    min_area_px: usize,
+    min_region: RegionSizeConfig,
+) -> Vec<RegionCandidate> {
     let width = mask.width;
     let height = band.end.saturating_sub(band.start);
-    if height == 0 || height < MIN_REGION_HEIGHT_PX {
+    if height == 0 || height < min_region.min_height_px {
         log_region_debug(
             "projection",
             "reject_band_short",
@@ -441,7 +582,7 @@ fn analyze_band(mask: &PackedMask, band: Range<usize>, min_area_px: usize) -> Ve
         if fill < MIN_FILL {
             continue;
         }
-        if seg_width < MIN_REGION_WIDTH_PX {
+        if seg_width < min_region.min_width_px {
             log_region_debug(
                 "projection",
                 "reject_narrow_band",
@@ -473,6 +614,104 @@ fn analyze_band(mask: &PackedMask, band: Range<usize>, min_area_px: usize) -> Ve
     candidates
 }
 
+/// Row offsets that undo a vertical shear of `slope` pixels per column.
+struct Shear {
+    slope: f32,
+    offset: usize,
+    extra_rows: usize,
+}
+
+impl Shear {
+    fn new(slope: f32, width: usize) -> Self {
+        let last = shear_shift(slope, width.saturating_sub(1));
+        Self {
+            slope,
+            offset: last.max(0) as usize,
+            extra_rows: last.unsigned_abs(),
+        }
+    }
+
+    fn row_for(&self, x: usize, y: usize) -> usize {
+        (y as isize - shear_shift(self.slope, x) + self.offset as isize) as usize
+    }
+
+    fn apply(&self, mask: &PackedMask) -> PackedMask {
+        let mut sheared = PackedMask::new(mask.width, mask.height + self.extra_rows);
+        for y in 0..mask.height {
+            for x in mask.row_iter(y) {
+                sheared.set_bit(x, self.row_for(x, y));
+            }
+        }
+        sheared
+    }
+
+    /// Map a candidate found in sheared space back to a bounding box in the original mask.
+    fn unshear_candidate(&self, cand: &mut RegionCandidate, height: usize) {
+        let first = shear_shift(self.slope, cand.x);
+        let last = shear_shift(self.slope, cand.x + cand.width.saturating_sub(1));
+        let top = cand.y as isize - self.offset as isize + first.min(last);
+        let bottom = (cand.y + cand.height) as isize - self.offset as isize + first.max(last);
+        let top = top.clamp(0, height as isize) as usize;
+        let bottom = bottom.clamp(top as isize, height as isize) as usize;
+        cand.y = top;
+        cand.height = bottom - top;
+    }
+}
+
+fn shear_shift(slope: f32, x: usize) -> isize {
+    (x as f32 * slope).round() as isize
+}
+
+fn shear_slopes(shear: ShearCompensation) -> Vec<f32> {
+    if !shear.step_deg.is_finite()
+        || shear.step_deg <= 0.0
+        || !shear.max_angle_deg.is_finite()
+        || shear.max_angle_deg <= 0.0
+    {
+        return Vec::new();
+    }
+    let max_angle = shear.max_angle_deg.min(45.0);
+    let steps = (max_angle / shear.step_deg).floor() as i32;
+    (-steps..=steps)
+        .filter(|&step| step != 0)
+        .map(|step| (step as f32 * shear.step_deg).to_radians().tan())
+        .collect()
+}
+
+/// Sum of squared row counts; concentrated rows score higher than the same pixels spread out.
+fn projection_sharpness(mask: &PackedMask, shear: Option<&Shear>) -> u64 {
+    let rows = mask.height + shear.map_or(0, |shear| shear.extra_rows);
+    let mut counts = vec![0u32; rows];
+    for y in 0..mask.height {
+        for x in mask.row_iter(y) {
+            let row = shear.map_or(y, |shear| shear.row_for(x, y));
+            counts[row] += 1;
+        }
+    }
+    counts
+        .iter()
+        .map(|&count| u64::from(count) * u64::from(count))
+        .sum()
+}
+
+/// Pick the shear whose row projection is sharpest, or `None` when the unsheared mask wins.
+fn best_shear(mask: &PackedMask, slopes: &[f32]) -> Option<Shear> {
+    if slopes.is_empty() || mask.is_empty() {
+        return None;
+    }
+    let mut best_score = projection_sharpness(mask, None);
+    let mut best = None;
+    for &slope in slopes {
+        let shear = Shear::new(slope, mask.width);
+        let score = projection_sharpness(mask, Some(&shear));
+        if score > best_score {
+            best_score = score;
+            best = Some(shear);
+        }
+    }
+    best
+}
+
 fn threshold_mask(roi: RoiRect, data: &[u8], stride: usize, params: LumaBandConfig) -> PackedMask {
     let mut mask = PackedMask::new(roi.width, roi.height);
     if mask.is_empty() {
@@ -556,7 +795,11 @@ unsafe fn threshold_pack_row_sse2(src: &[u8], dst: &mut [u8], lo: u8, hi: u8) ->
     x
 }
 
-fn rle_candidates(mask: &PackedMask, min_area_px: usize) -> Vec<RegionCandidate> {
+fn rle_candidates(
+    mask: &PackedMask,
+    min_area_px: usize,
+    min_region: RegionSizeConfig,
+) -> Vec<RegionCandidate> {
     let stats = connected_components(mask);
     let mut candidates = Vec::new();
     for comp in stats {
@@ -578,7 +821,7 @@ fn rle_candidates(mask: &PackedMask, min_area_px: usize) -> Vec<RegionCandidate>
             );
             continue;
         }
-        if h < MIN_REGION_HEIGHT_PX {
+        if h < min_region.min_height_px {
             log_region_debug(
                 "projection",
                 "reject_short_component",
@@ -590,7 +833,7 @@ fn rle_candidates(mask: &PackedMask, min_area_px: usize) -> Vec<RegionCandidate>
             );
             continue;
         }
-        if w < MIN_REGION_WIDTH_PX {
+        if w < min_region.min_width_px {
             log_region_debug(
                 "projection",
                 "reject_narrow_component",
@@ -896,4 +1139,169 @@ mod tests {
         let rows = mask_to_rows(&mask);
         assert_eq!(rows, vec![vec![1, 0], vec![0, 0], vec![0, 0], vec![1, 0]]);
     }
+
+    /// A 20-row band at full brightness whose top and bottom three rows fade out like
+    /// anti-aliased glyph edges, dropping just below the hard band.
+    fn feathered_band_frame(width: usize, height: usize) -> VideoFrame {
+        let mut y_plane = vec![0u8; width * height];
+        let fade = [216u8, 213, 210];
+        for x in 40..width - 40 {
+            for y in 100..120 {
+                y_plane[y * width + x] = 230;
+            }
+            for (step, &value) in fade.iter().enumerate() {
+                y_plane[(99 - step) * width + x] = value;
+                y_plane[(120 + step) * width + x] = value;
+            }
+        }
+        let uv_plane = vec![128u8; width * height.div_ceil(2)];
+        VideoFrame::from_nv12_owned(
+            width as u32,
+            height as u32,
+            width,
+            width,
+            None,
+            None,
+            y_plane,
+            uv_plane,
+        )
+        .expect("frame")
+    }
+
+    #[test]
+    fn feathered_band_weights_anti_aliased_rows() {
+        let (width, height) = (320, 240);
+        let frame = feathered_band_frame(width, height);
+        let hard = SubtitleDetectionConfig::for_frame(width, height, width);
+        let mut soft = hard.clone();
+        soft.luma_band.softness = 12;
+
+        let projection = |config: SubtitleDetectionConfig| {
+            let detector = ProjectionBandDetector::new(config).expect("detector");
+            let mask = detector.threshold_mask(frame.data());
+            detector.row_projection(&mask, frame.data(), None)
+        };
+        let binary = projection(hard);
+        let feathered = projection(soft);
+
+        let band_width = (width - 80) as f32;
+        for y in 100..120 {
+            assert_eq!(binary[y], band_width);
+            assert_eq!(feathered[y], band_width);
+        }
+        for (row, expected) in [(99, 11.0 / 13.0), (98, 8.0 / 13.0), (97, 5.0 / 13.0)] {
+            assert_eq!(binary[row], 0.0);
+            assert!((feathered[row] - band_width * expected).abs() < 1e-2);
+            assert_eq!(feathered[row], feathered[219 - row]);
+        }
+        assert_eq!(binary[50], 0.0);
+        assert_eq!(feathered[50], 0.0);
+    }
+
+    /// Two 30-row lines of text separated by a 6-row dark gap, close enough for the vertical
+    /// gap bridge to merge them into one band.
+    fn two_line_frame(width: usize, height: usize) -> VideoFrame {
+        let mut y_plane = vec![0u8; width * height];
+        for (rows, columns) in [(100..130, 40..width - 40), (136..166, 60..width - 60)] {
+            for y in rows {
+                for x in columns.clone() {
+                    y_plane[y * width + x] = 230;
+                }
+            }
+        }
+        let uv_plane = vec![128u8; width * height.div_ceil(2)];
+        VideoFrame::from_nv12_owned(
+            width as u32,
+            height as u32,
+            width,
+            width,
+            None,
+            None,
+            y_plane,
+            uv_plane,
+        )
+        .expect("frame")
+    }
+
+    #[test]
+    fn split_lines_cuts_two_line_band_at_the_valley() {
+        let (width, height) = (320, 240);
+        let frame = two_line_frame(width, height);
+
+        let config = SubtitleDetectionConfig::for_frame(width, height, width);
+        let whole = ProjectionBandDetector::new(config.clone()).expect("detector");
+        let result = whole.detect(&frame).expect("detect");
+        assert_eq!(result.regions.len(), 1, "{:?}", result.regions);
+        assert!(result.regions[0].height >= 60.0, "{:?}", result.regions);
+
+        let mut config = config;
+        config.split_lines = true;
+        let split = ProjectionBandDetector::new(config).expect("detector");
+        let mut regions = split.detect(&frame).expect("detect").regions;
+        regions.sort_by(|a, b| a.y.total_cmp(&b.y));
+        assert_eq!(regions.len(), 2, "{regions:?}");
+        let bounds: Vec<_> = regions
+            .iter()
+            .map(|r| (r.x, r.y, r.width, r.height))
+            .collect();
+        assert_eq!(
+            bounds,
+            [
+                (40.0, 100.0, (width - 80) as f32, 30.0),
+                (60.0, 136.0, (width - 120) as f32, 30.0)
+            ]
+        );
+    }
+
+    fn sheared_band_frame(width: usize, height: usize, slope: f32) -> VideoFrame {
+        let mut y_plane = vec![0u8; width * height];
+        let band_height = 30;
+        for x in 40..width - 40 {
+            let top = 40 + shear_shift(slope, x - 40) as usize;
+            for y in top..top + band_height {
+                y_plane[y * width + x] = 230;
+            }
+        }
+        let uv_plane = vec![128u8; width * height.div_ceil(2)];
+        VideoFrame::from_nv12_owned(
+            width as u32,
+            height as u32,
+            width,
+            width,
+            None,
+            None,
+            y_plane,
+            uv_plane,
+        )
+        .expect("frame")
+    }
+
+    #[test]
+    fn shear_compensation_recovers_slanted_band() {
+        let (width, height) = (640, 240);
+        let slope = 12f32.to_radians().tan();
+        let frame = sheared_band_frame(width, height, slope);
+
+        let config = SubtitleDetectionConfig::for_frame(width, height, width);
+        let plain = ProjectionBandDetector::new(config.clone()).expect("detector");
+        assert!(!plain.detect(&frame).expect("detect").has_subtitle);
+
+        let mut config = config;
+        config.shear_compensation = Some(ShearCompensation {
+            max_angle_deg: 15.0,
+            step_deg: 1.0,
+        });
+        let sheared = ProjectionBandDetector::new(config).expect("detector");
+        let result = sheared.detect(&frame).expect("detect");
+        assert!(result.has_subtitle);
+        let region = &result.regions[0];
+        let drift = shear_shift(slope, width - 81) as f32;
+        assert!((region.x - 40.0).abs() <= 1.0, "{region:?}");
+        assert!(
+            (region.width - (width - 80) as f32).abs() <= 1.0,
+            "{region:?}"
+        );
+        assert!((region.y - 40.0).abs() <= 1.0, "{region:?}");
+        assert!((region.height - (30.0 + drift)).abs() <= 2.0, "{region:?}");
+    }
 }
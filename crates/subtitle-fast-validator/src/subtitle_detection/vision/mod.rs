@@ -2,7 +2,7 @@ use std::ffi::{CStr, c_char};
 use std::slice;
 
 use super::{
-    DetectionRegion, RoiConfig, SubtitleDetectionConfig, SubtitleDetectionError,
+    DetectionRegion, RoiConfig, RoiRounding, SubtitleDetectionConfig, SubtitleDetectionError,
     SubtitleDetectionResult, SubtitleDetector,
 };
 use subtitle_fast_types::VideoFrame;
@@ -10,7 +10,7 @@ use subtitle_fast_types::VideoFrame;
 #[derive(Debug, Clone)]
 pub struct VisionTextDetector {
     config: SubtitleDetectionConfig,
-    roi: RoiRect,
+    clip: RoiRect,
     required_bytes: usize,
 }
 
@@ -107,10 +107,17 @@ impl VisionTextDetector {
                 required: required_bytes,
             });
         }
-        let roi = compute_roi_rect(config.frame_width, config.frame_height, config.roi)?;
+        let clip_roi = expand_roi(config.roi, config.roi_clip_margin);
+        let clip = compute_roi_rect(
+            config.frame_width,
+            config.frame_height,
+            clip_roi,
+            config.roi_rounding,
+            config.roi_half_pixel,
+        )?;
         Ok(Self {
             config,
-            roi,
+            clip,
             required_bytes,
         })
     }
@@ -151,19 +158,17 @@ impl SubtitleDetector for VisionTextDetector {
             return Err(SubtitleDetectionError::Vision(message));
         }
 
-        let mut regions = Vec::new();
-        let mut max_score = 0.0f32;
-        for region in owned.regions() {
-            if let Some(clipped) = clip_region(
-                region,
-                self.roi,
-                self.config.frame_width,
-                self.config.frame_height,
-            ) {
-                max_score = max_score.max(clipped.score);
-                regions.push(clipped);
-            }
-        }
+        let regions = collect_regions(
+            owned.regions(),
+            self.clip,
+            self.config.frame_width,
+            self.config.frame_height,
+            self.config.min_detection_confidence,
+        );
+        let max_score = regions
+            .iter()
+            .map(|region| region.score)
+            .fold(0.0f32, f32::max);
 
         let has_subtitle = !regions.is_empty();
         let result = SubtitleDetectionResult {
@@ -176,15 +181,38 @@ impl SubtitleDetector for VisionTextDetector {
     }
 }
 
+/// Grows the ROI by `margin` (a frame fraction) on every side; frame bounds are applied later.
+fn expand_roi(roi: RoiConfig, margin: f32) -> RoiConfig {
+    let margin = if margin.is_finite() {
+        margin.max(0.0)
+    } else {
+        0.0
+    };
+    RoiConfig {
+        x: roi.x - margin,
+        y: roi.y - margin,
+        width: roi.width + margin * 2.0,
+        height: roi.height + margin * 2.0,
+    }
+}
+
 fn compute_roi_rect(
     frame_width: usize,
     frame_height: usize,
     roi: RoiConfig,
+    rounding: RoiRounding,
+    half_pixel: bool,
 ) -> Result<RoiRect, SubtitleDetectionError> {
-    let start_x = (roi.x * frame_width as f32).floor() as isize;
-    let start_y = (roi.y * frame_height as f32).floor() as isize;
-    let end_x = ((roi.x + roi.width) * frame_width as f32).ceil() as isize;
-    let end_y = ((roi.y + roi.height) * frame_height as f32).ceil() as isize;
+    let (start_x, end_x) = rounding.snap(
+        roi.x * frame_width as f32,
+        (roi.x + roi.width) * frame_width as f32,
+        half_pixel,
+    );
+    let (start_y, end_y) = rounding.snap(
+        roi.y * frame_height as f32,
+        (roi.y + roi.height) * frame_height as f32,
+        half_pixel,
+    );
 
     let start_x = start_x.clamp(0, frame_width as isize);
     let start_y = start_y.clamp(0, frame_height as isize);
@@ -205,6 +233,21 @@ fn compute_roi_rect(
     })
 }
 
+/// Clips every observation at or above `min_confidence` to the ROI, dropping the ones left
+/// without area.
+fn collect_regions(
+    raw: &[CVisionRegion],
+    roi: RoiRect,
+    frame_width: usize,
+    frame_height: usize,
+    min_confidence: f32,
+) -> Vec<DetectionRegion> {
+    raw.iter()
+        .filter(|region| region.confidence >= min_confidence)
+        .filter_map(|region| clip_region(region, roi, frame_width, frame_height))
+        .collect()
+}
+
 fn clip_region(
     region: &CVisionRegion,
     roi: RoiRect,
@@ -244,3 +287,74 @@ fn clip_region(
         score: region.confidence.max(0.0),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAME_W: usize = 1000;
+    const FRAME_H: usize = 500;
+
+    fn edge_region() -> CVisionRegion {
+        // Straddles the ROI's right edge at x = 800.
+        CVisionRegion {
+            x: 700.0,
+            y: 420.0,
+            width: 160.0,
+            height: 40.0,
+            confidence: 0.9,
+        }
+    }
+
+    fn clip_for(margin: f32) -> RoiRect {
+        let roi = RoiConfig {
+            x: 0.2,
+            y: 0.8,
+            width: 0.6,
+            height: 0.2,
+        };
+        compute_roi_rect(
+            FRAME_W,
+            FRAME_H,
+            expand_roi(roi, margin),
+            RoiRounding::Outward,
+            false,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn region_is_truncated_at_roi_edge_without_margin() {
+        let clipped = clip_region(&edge_region(), clip_for(0.0), FRAME_W, FRAME_H).unwrap();
+        assert_eq!(clipped.width, 100.0);
+    }
+
+    #[test]
+    fn margin_keeps_full_region_width() {
+        let clipped = clip_region(&edge_region(), clip_for(0.1), FRAME_W, FRAME_H).unwrap();
+        assert_eq!(clipped.x, 700.0);
+        assert_eq!(clipped.width, 160.0);
+        assert_eq!(clipped.height, 40.0);
+    }
+
+    #[test]
+    fn observations_below_the_confidence_floor_are_dropped() {
+        let faint = CVisionRegion {
+            x: 300.0,
+            y: 430.0,
+            width: 200.0,
+            height: 30.0,
+            confidence: 0.2,
+        };
+        let raw = [faint, edge_region()];
+
+        let kept = collect_regions(&raw, clip_for(0.0), FRAME_W, FRAME_H, 0.5);
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].x, 700.0);
+        assert!((kept[0].score - 0.9).abs() < 1e-6);
+
+        // The default floor keeps every observation.
+        let all = collect_regions(&raw, clip_for(0.0), FRAME_W, FRAME_H, 0.0);
+        assert_eq!(all.len(), 2);
+    }
+}
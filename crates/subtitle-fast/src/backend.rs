@@ -1,30 +1,60 @@
-use std::time::Instant;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
 
-use futures_util::StreamExt;
-use indicatif::{ProgressBar, ProgressStyle};
+use futures_util::{Stream, StreamExt};
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
 use subtitle_fast_decoder::{Backend, Configuration};
 use subtitle_fast_types::DecoderError;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
 
+use crate::frame_timing;
+use crate::metadata;
+use crate::script_summary;
 use crate::stage;
+use crate::watch::{WATCH_POLL_INTERVAL, WatchedDir};
+use crate::{log_error, log_info};
 
 const COL_AVG: &str = "\x1b[33m"; // yellow-ish for averages
 const COL_COUNT: &str = "\x1b[36m"; // cyan-ish for counts
 const COL_RESET: &str = "\x1b[0m";
 
+/// Stacks the progress bars of batch files running at the same time; unset for single runs.
+static BATCH_PROGRESS: OnceLock<MultiProgress> = OnceLock::new();
+
 #[derive(Clone)]
 pub struct ExecutionPlan {
     pub config: Configuration,
     pub backend_locked: bool,
     pub pipeline: stage::PipelineConfig,
+    /// Skip writing output and print a stage timing summary when the run ends.
+    pub bench: bool,
+    /// Only run full-frame detection and write a region heatmap PNG here.
+    pub heatmap: Option<PathBuf>,
 }
 
 pub async fn run(plan: ExecutionPlan) -> Result<(), DecoderError> {
+    let (cancel_tx, cancel) = watch::channel(false);
+    let _interrupt_signal = SignalTaskGuard(Some(spawn_interrupt_signal(cancel_tx)));
+    run_until(plan, cancel).await
+}
+
+/// Runs `plan` until it completes or `cancel` turns true, which writes the cues committed so far.
+async fn run_until(
+    plan: ExecutionPlan,
+    mut cancel: watch::Receiver<bool>,
+) -> Result<(), DecoderError> {
     let ExecutionPlan {
         config,
         backend_locked,
         pipeline,
+        bench,
+        heatmap,
     } = plan;
 
+    let started = Instant::now();
     let available = Configuration::available_backends();
     if available.is_empty() {
         return Err(DecoderError::configuration(
@@ -44,12 +74,18 @@ pub async fn run(plan: ExecutionPlan) -> Result<(), DecoderError> {
         }
 
         let provider_started = Instant::now();
-        let provider_result = attempt_config.create_provider();
+        let provider_result = create_with_retry(
+            &attempt_config,
+            pipeline.decoder_init_retries,
+            pipeline.decoder_init_backoff,
+            Configuration::create_provider,
+        )
+        .await;
         let provider_elapsed = provider_started.elapsed();
 
         let provider = match provider_result {
             Ok(provider) => {
-                eprintln!(
+                log_info!(
                     "initialized decoder backend '{}' in {:.2?}",
                     attempt_config.backend.as_str(),
                     provider_elapsed
@@ -57,7 +93,7 @@ pub async fn run(plan: ExecutionPlan) -> Result<(), DecoderError> {
                 provider
             }
             Err(err) => {
-                eprintln!(
+                log_error!(
                     "decoder backend '{}' failed to initialize in {:.2?}: {err}",
                     attempt_config.backend.as_str(),
                     provider_elapsed
@@ -66,7 +102,7 @@ pub async fn run(plan: ExecutionPlan) -> Result<(), DecoderError> {
                     && let Some(next_backend) = select_next_backend(&available, &tried)
                 {
                     let failed_backend = attempt_config.backend;
-                    eprintln!(
+                    log_info!(
                         "backend {failed} failed to initialize ({reason}); trying {next}",
                         failed = failed_backend.as_str(),
                         reason = err,
@@ -79,22 +115,77 @@ pub async fn run(plan: ExecutionPlan) -> Result<(), DecoderError> {
             }
         };
 
+        if let Some(path) = heatmap.as_deref() {
+            let outcome = match stage::build_detection_stream(provider, &pipeline) {
+                Ok(detection) => write_heatmap(detection, path, &mut cancel).await,
+                Err(err) => Err((err, 0)),
+            };
+            match outcome {
+                Ok(RunEnd::Completed) => return Ok(()),
+                Ok(RunEnd::Interrupted) => {
+                    return Err(DecoderError::configuration("interrupted"));
+                }
+                Err((err, seen)) => {
+                    if seen == 0
+                        && !backend_locked
+                        && let Some(next_backend) = select_next_backend(&available, &tried)
+                    {
+                        log_info!(
+                            "backend {failed} failed to decode ({err}); trying {next}",
+                            failed = attempt_config.backend.as_str(),
+                            next = next_backend.as_str()
+                        );
+                        attempt_config.backend = next_backend;
+                        continue;
+                    }
+                    return Err(err);
+                }
+            }
+        }
+
         let pipeline_result = stage::build_pipeline(provider, &pipeline);
 
         let outcome = match pipeline_result {
-            Ok(pipeline_streams) => drive_pipeline(pipeline_streams, &pipeline.output.path).await,
+            Ok(pipeline_streams) => {
+                drive_pipeline(pipeline_streams, &pipeline.output, bench, &mut cancel).await
+            }
             Err(err) => Err((err, 0)),
         };
 
         match outcome {
-            Ok(()) => return Ok(()),
+            Ok(drained) => {
+                if bench {
+                    print!("{}", bench_summary(&drained.progress));
+                } else if drained.end == RunEnd::Completed
+                    && crate::log::enabled(crate::log::LogLevel::Info)
+                {
+                    print!(
+                        "{}",
+                        run_summary(
+                            &drained,
+                            started.elapsed(),
+                            attempt_config.backend.as_str(),
+                            pipeline.ocr.engine.name(),
+                            &pipeline.output.path,
+                        )
+                    );
+                }
+                return match drained.end {
+                    RunEnd::Completed => Ok(()),
+                    RunEnd::Interrupted if bench => Err(DecoderError::configuration("interrupted")),
+                    RunEnd::Interrupted => Err(DecoderError::configuration(format!(
+                        "interrupted; wrote the cues committed so far to {}",
+                        pipeline.output.path.display()
+                    ))),
+                };
+            }
             Err((err, seen)) => {
                 if seen == 0
                     && !backend_locked
                     && let Some(next_backend) = select_next_backend(&available, &tried)
                 {
                     let failed_backend = attempt_config.backend;
-                    eprintln!(
+                    log_info!(
                         "backend {failed} failed to decode ({reason}); trying {next}",
                         failed = failed_backend.as_str(),
                         reason = err,
@@ -109,6 +200,149 @@ pub async fn run(plan: ExecutionPlan) -> Result<(), DecoderError> {
     }
 }
 
+/// Runs every plan, up to `jobs` at a time, and reports each file's outcome. Plans built with
+/// [`PipelineConfig::for_batch`](stage::PipelineConfig::for_batch) share one OCR engine. A
+/// failed file does not stop the others; the batch fails if any did. One Ctrl+C interrupts every
+/// running file and starts no further ones.
+pub async fn run_batch(plans: Vec<ExecutionPlan>, jobs: usize) -> Result<(), DecoderError> {
+    let (cancel_tx, cancel) = watch::channel(false);
+    let _interrupt_signal = SignalTaskGuard(Some(spawn_interrupt_signal(cancel_tx)));
+    run_batch_until(plans, jobs, cancel).await.result
+}
+
+/// What a batch did before it ended or was cancelled.
+struct BatchOutcome {
+    /// Inputs that ran to the end, whether they succeeded or failed.
+    finished: Vec<PathBuf>,
+    result: Result<(), DecoderError>,
+}
+
+async fn run_batch_until(
+    plans: Vec<ExecutionPlan>,
+    jobs: usize,
+    cancel: watch::Receiver<bool>,
+) -> BatchOutcome {
+    let total = plans.len();
+    if jobs > 1 {
+        BATCH_PROGRESS.get_or_init(MultiProgress::new);
+    }
+    let pending = cancel.clone();
+    let mut runs = futures_util::stream::iter(plans.into_iter().enumerate())
+        .take_while(move |_| std::future::ready(!*pending.borrow()))
+        .map(|(index, plan)| {
+            let cancel = cancel.clone();
+            async move {
+                let input = plan.config.input.clone().unwrap_or_default();
+                let output = plan.pipeline.output.path.clone();
+                let result = run_until(plan, cancel.clone()).await;
+                let interrupted = result.is_err() && *cancel.borrow();
+                (index, input, output, result, interrupted)
+            }
+        })
+        .buffer_unordered(jobs.max(1));
+
+    let mut started = 0;
+    let mut failed = 0;
+    let mut finished = Vec::new();
+    while let Some((index, input, output, result, interrupted)) = runs.next().await {
+        started += 1;
+        match result {
+            Ok(()) => log_info!(
+                "[{}/{total}] {} -> {}",
+                index + 1,
+                input.display(),
+                output.display()
+            ),
+            Err(err) => {
+                failed += 1;
+                log_error!("[{}/{total}] {} failed: {err}", index + 1, input.display());
+            }
+        }
+        if !interrupted {
+            finished.push(input);
+        }
+    }
+
+    let result = if started < total {
+        Err(DecoderError::configuration(format!(
+            "interrupted; {} of {total} inputs were not started",
+            total - started
+        )))
+    } else if failed > 0 {
+        Err(DecoderError::configuration(format!(
+            "{failed} of {total} inputs failed"
+        )))
+    } else {
+        Ok(())
+    };
+    BatchOutcome { finished, result }
+}
+
+/// Polls `watched` until Ctrl+C, running each video that has finished being written as one
+/// batch of the plans `plans_for` builds. A video that ran to the end is not handed out again,
+/// even if it failed. Ctrl+C also interrupts the running batch; the partial subtitles of the
+/// videos it cut short are removed so the next watch processes them again. The watch itself
+/// only fails when the directory can no longer be read.
+pub async fn run_watch(
+    mut watched: WatchedDir,
+    jobs: usize,
+    plans_for: impl Fn(&[PathBuf]) -> Result<Vec<ExecutionPlan>, DecoderError>,
+) -> Result<(), DecoderError> {
+    log_info!(
+        "watching {} for new videos (Ctrl+C to stop)",
+        watched.dir().display()
+    );
+    let (cancel_tx, mut cancel) = watch::channel(false);
+    let _interrupt_signal = SignalTaskGuard(Some(spawn_interrupt_signal(cancel_tx)));
+    let mut poll = tokio::time::interval(WATCH_POLL_INTERVAL);
+    loop {
+        tokio::select! {
+            _ = poll.tick() => {}
+            _ = cancelled(&mut cancel) => return Ok(()),
+        }
+        let ready = watched.poll()?;
+        if ready.is_empty() {
+            continue;
+        }
+        let plans = match plans_for(&ready) {
+            Ok(plans) => plans,
+            Err(err) => {
+                log_error!("{err}");
+                ready.iter().for_each(|video| watched.mark_done(video));
+                continue;
+            }
+        };
+        let outputs: Vec<(PathBuf, PathBuf)> = plans
+            .iter()
+            .map(|plan| {
+                let input = plan.config.input.clone().unwrap_or_default();
+                (input, plan.pipeline.output.path.clone())
+            })
+            .collect();
+
+        let outcome = run_batch_until(plans, jobs, cancel.clone()).await;
+        outcome
+            .finished
+            .iter()
+            .for_each(|video| watched.mark_done(video));
+        if let Err(err) = outcome.result {
+            log_error!("{err}");
+        }
+        if *cancel.borrow() {
+            for (input, output) in outputs {
+                if !outcome.finished.contains(&input) && std::fs::remove_file(&output).is_ok() {
+                    log_info!(
+                        "removed the partial {} so {} is processed again",
+                        output.display(),
+                        input.display()
+                    );
+                }
+            }
+            return Ok(());
+        }
+    }
+}
+
 pub fn display_available_backends() {
     let names: Vec<&'static str> = Configuration::available_backends()
         .iter()
@@ -126,6 +360,54 @@ pub fn parse_backend(value: &str) -> Result<Backend, DecoderError> {
     Backend::from_str(value)
 }
 
+/// Runs `create` for `config`, retrying up to `retries` more times on a transient failure with a
+/// backoff that starts at `backoff` and doubles, so a briefly busy device keeps its backend
+/// instead of falling back to another one. Permanent failures return at once.
+pub(crate) async fn create_with_retry<T>(
+    config: &Configuration,
+    retries: u32,
+    backoff: Duration,
+    create: impl Fn(&Configuration) -> Result<T, DecoderError>,
+) -> Result<T, DecoderError> {
+    let mut delay = backoff;
+    let mut attempt = 0;
+    loop {
+        match create(config) {
+            Ok(value) => return Ok(value),
+            Err(err) if attempt < retries && is_transient(&err) => {
+                attempt += 1;
+                log_info!(
+                    "decoder backend '{}' failed to initialize ({err}); retry {attempt}/{retries} in {delay:.2?}",
+                    config.backend.as_str()
+                );
+                tokio::time::sleep(delay).await;
+                delay = delay.saturating_mul(2);
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Whether retrying may help: a backend or I/O failure can clear up, while an unsupported backend,
+/// a configuration error or a missing or unreadable input cannot.
+fn is_transient(err: &DecoderError) -> bool {
+    match err {
+        DecoderError::BackendFailure { .. } => true,
+        DecoderError::Io(err) => !matches!(
+            err.kind(),
+            std::io::ErrorKind::NotFound
+                | std::io::ErrorKind::PermissionDenied
+                | std::io::ErrorKind::InvalidInput
+                | std::io::ErrorKind::InvalidData
+                | std::io::ErrorKind::Unsupported
+        ),
+        DecoderError::Unsupported { .. }
+        | DecoderError::Configuration { .. }
+        | DecoderError::InvalidFrame { .. }
+        | DecoderError::Stage { .. } => false,
+    }
+}
+
 fn select_next_backend(available: &[Backend], tried: &[Backend]) -> Option<Backend> {
     available
         .iter()
@@ -133,21 +415,82 @@ fn select_next_backend(available: &[Backend], tried: &[Backend]) -> Option<Backe
         .find(|backend| !tried.contains(backend))
 }
 
+/// How a pipeline run that wrote its output ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RunEnd {
+    Completed,
+    /// Stopped early by Ctrl+C; the output holds the cues committed before the signal.
+    Interrupted,
+}
+
 async fn drive_pipeline(
     pipeline: stage::PipelineOutputs,
-    output_path: &std::path::Path,
-) -> Result<(), (DecoderError, u64)> {
+    output: &stage::OutputPipelineConfig,
+    bench: bool,
+    cancel: &mut watch::Receiver<bool>,
+) -> Result<Drained, (DecoderError, u64)> {
+    let pause = pipeline.handle.pause_sender();
+    let pause_updates = pause.subscribe();
+    let _pause_signals = SignalTaskGuard(spawn_pause_signals(pause));
+    drain_pipeline(
+        pipeline.stream,
+        pipeline.total_frames,
+        pause_updates,
+        output,
+        bench,
+        cancel,
+    )
+    .await
+}
+
+/// What [`drain_pipeline`] saw of a run that got as far as writing its output.
+struct Drained {
+    end: RunEnd,
+    /// Last progress snapshot the pipeline sent.
+    progress: stage::PipelineProgress,
+    /// Cues committed by the end of the run.
+    cues: usize,
+    /// Non-whitespace characters across those cues.
+    characters: usize,
+}
+
+/// Collects subtitle updates until the stream ends or `cancel` fires, then writes every cue
+/// committed so far unless `bench` is set.
+async fn drain_pipeline(
+    mut stream: Pin<Box<dyn Stream<Item = stage::PipelineResult> + Send>>,
+    total_frames: Option<u64>,
+    mut pause_updates: watch::Receiver<bool>,
+    output: &stage::OutputPipelineConfig,
+    bench: bool,
+    cancel: &mut watch::Receiver<bool>,
+) -> Result<Drained, (DecoderError, u64)> {
     let mut processed = 0;
+    let mut latest = stage::PipelineProgress::default();
     let mut subtitles: Vec<stage::MergedSubtitle> = Vec::new();
-    let mut stream = pipeline.stream;
-    let mut progress = PipelineProgressBar::new("detect", pipeline.total_frames);
+    let mut progress = PipelineProgressBar::new("detect", total_frames);
+    let mut end = RunEnd::Completed;
 
-    while let Some(event) = stream.next().await {
+    loop {
+        let event = tokio::select! {
+            event = stream.next() => event,
+            _ = cancelled(cancel) => {
+                end = RunEnd::Interrupted;
+                break;
+            }
+            Ok(()) = pause_updates.changed() => {
+                progress.set_paused(*pause_updates.borrow_and_update());
+                continue;
+            }
+        };
+        let Some(event) = event else {
+            break;
+        };
         match event {
             Ok(update) => {
                 processed = processed.max(update.progress.samples_seen);
                 progress.update(&update.progress);
                 apply_updates(&mut subtitles, &update.updates);
+                latest = update.progress;
             }
             Err(err) => {
                 let mapped = stage::pipeline_error_to_frame(err);
@@ -157,14 +500,244 @@ async fn drive_pipeline(
         }
     }
 
+    match end {
+        RunEnd::Completed => progress.finish(processed),
+        RunEnd::Interrupted => progress.fail("interrupted"),
+    }
+    // Dropping the stream stops the decoder from pulling further frames.
+    drop(stream);
+    if let Some(summary) = script_summary::summarize_scripts(&subtitles) {
+        log_info!("recognized scripts: {summary}");
+    }
+    if !bench {
+        sort_and_write(output, &subtitles, latest.latest_time)
+            .await
+            .map_err(|err| (err, processed))?;
+    }
+    let characters = subtitles
+        .iter()
+        .flat_map(|subtitle| &subtitle.lines)
+        .map(|line| line.text.chars().filter(|ch| !ch.is_whitespace()).count())
+        .sum();
+    Ok(Drained {
+        end,
+        progress: latest,
+        cues: subtitles.len(),
+        characters,
+    })
+}
+
+/// Accumulates every full-frame detection into a [`stage::heatmap::RegionHeatmap`] and writes it
+/// to `path` once the video ends.
+async fn write_heatmap(
+    detection: stage::StreamBundle<stage::detector::DetectionSampleResult>,
+    path: &Path,
+    cancel: &mut watch::Receiver<bool>,
+) -> Result<RunEnd, (DecoderError, u64)> {
+    let stage::StreamBundle {
+        mut stream,
+        total_frames,
+    } = detection;
+    let mut progress = PipelineProgressBar::new("heatmap", total_frames);
+    let mut heatmap: Option<stage::heatmap::RegionHeatmap> = None;
+    let mut processed = 0;
+
+    loop {
+        let event = tokio::select! {
+            event = stream.next() => event,
+            _ = cancelled(cancel) => {
+                progress.fail("interrupted");
+                return Ok(RunEnd::Interrupted);
+            }
+        };
+        let Some(event) = event else {
+            break;
+        };
+        match event {
+            Ok(sample) => {
+                let frame = sample.sample.frame();
+                let index = sample.sample.frame_index();
+                processed = processed.max(index.saturating_add(1));
+                progress.set_frame(index);
+                heatmap
+                    .get_or_insert_with(|| {
+                        stage::heatmap::RegionHeatmap::new(frame.width(), frame.height())
+                    })
+                    .add(&sample.detection.regions);
+            }
+            Err(err) => {
+                let mapped = stage::detector_error_to_frame(err);
+                progress.fail(&mapped.to_string());
+                return Err((mapped, processed));
+            }
+        }
+    }
     progress.finish(processed);
-    sort_and_write(output_path, &subtitles)
-        .await
-        .map_err(|err| (err, processed))
+    drop(stream);
+
+    let Some(heatmap) = heatmap else {
+        return Err((
+            DecoderError::configuration("no frames were sampled; heatmap not written"),
+            processed,
+        ));
+    };
+    heatmap.write_png(path).map_err(|err| {
+        (
+            DecoderError::configuration(format!(
+                "failed to write heatmap {}: {err}",
+                path.display()
+            )),
+            processed,
+        )
+    })?;
+    log_info!(
+        "wrote {}x{} region heatmap from {} samples to {}",
+        heatmap.width(),
+        heatmap.height(),
+        heatmap.samples(),
+        path.display()
+    );
+    Ok(RunEnd::Completed)
+}
+
+/// Plain-text report of a `--bench` run, built from the final pipeline progress.
+fn bench_summary(progress: &stage::PipelineProgress) -> String {
+    let rows = [
+        (
+            "frames",
+            progress
+                .latest_frame_index
+                .max(progress.samples_seen)
+                .to_string(),
+        ),
+        ("samples", progress.samples_seen.to_string()),
+        ("decode fps", format!("{:.1}", progress.fps)),
+        ("detect", format!("{} / sample", format_ms(progress.det_ms))),
+        ("segment", format!("{} / frame", format_ms(progress.seg_ms))),
+        ("ocr", format!("{} / region", format_ms(progress.ocr_ms))),
+        (
+            "cues",
+            format!(
+                "{} ({} merged, {} ocr-empty)",
+                progress.cues, progress.merged, progress.ocr_empty
+            ),
+        ),
+    ];
+    let mut summary = String::from("benchmark summary\n");
+    for (label, value) in rows {
+        summary.push_str(&format!("  {label:<10}  {value}\n"));
+    }
+    summary
+}
+
+/// Plain-text recap of a completed run, printed to stdout once its output is written.
+fn run_summary(
+    drained: &Drained,
+    elapsed: Duration,
+    backend: &str,
+    ocr_backend: &str,
+    output: &Path,
+) -> String {
+    let rows = [
+        ("cues", drained.cues.to_string()),
+        ("characters", drained.characters.to_string()),
+        ("duration", format!("{:.2}s", elapsed.as_secs_f64())),
+        ("avg fps", format!("{:.1}", drained.progress.fps)),
+        ("backend", backend.to_string()),
+        ("ocr", ocr_backend.to_string()),
+        ("output", output.display().to_string()),
+    ];
+    let mut summary = String::from("run summary\n");
+    for (label, value) in rows {
+        summary.push_str(&format!("  {label:<10}  {value}\n"));
+    }
+    summary
+}
+
+/// How a job-control signal changes the pipeline pause flag.
+#[derive(Clone, Copy, Debug)]
+enum PauseSignal {
+    /// SIGTSTP (Ctrl+Z): pause a running pipeline or resume a paused one.
+    Toggle,
+    /// SIGCONT: always resume.
+    Resume,
+}
+
+fn apply_pause_signal(pause: &watch::Sender<bool>, signal: PauseSignal) {
+    match signal {
+        PauseSignal::Toggle => pause.send_modify(|paused| *paused = !*paused),
+        PauseSignal::Resume => {
+            pause.send_if_modified(|paused| std::mem::replace(paused, false));
+        }
+    }
+}
+
+/// Route Ctrl+Z to the pause flag instead of suspending the process. Only installed when
+/// stderr is a terminal, since that is where the progress bar reports the paused state.
+#[cfg(unix)]
+fn spawn_pause_signals(pause: watch::Sender<bool>) -> Option<JoinHandle<()>> {
+    use std::io::IsTerminal;
+    use tokio::signal::unix::{SignalKind, signal};
+
+    if !std::io::stderr().is_terminal() {
+        return None;
+    }
+    let mut stop = signal(SignalKind::from_raw(libc::SIGTSTP)).ok()?;
+    let mut resume = signal(SignalKind::from_raw(libc::SIGCONT)).ok()?;
+    Some(tokio::spawn(async move {
+        loop {
+            let action = tokio::select! {
+                Some(()) = stop.recv() => PauseSignal::Toggle,
+                Some(()) = resume.recv() => PauseSignal::Resume,
+                else => return,
+            };
+            apply_pause_signal(&pause, action);
+        }
+    }))
+}
+
+#[cfg(not(unix))]
+fn spawn_pause_signals(_pause: watch::Sender<bool>) -> Option<JoinHandle<()>> {
+    None
+}
+
+/// Turn the first Ctrl+C into a graceful stop that still writes the committed cues; a second
+/// Ctrl+C exits immediately.
+fn spawn_interrupt_signal(cancel: watch::Sender<bool>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_err() {
+            // Ctrl+C keeps its default behavior; hold the sender so the run is never cancelled.
+            return std::future::pending().await;
+        }
+        log_info!("interrupted: writing the cues committed so far (Ctrl+C again to abort)");
+        let _ = cancel.send(true);
+        if tokio::signal::ctrl_c().await.is_ok() {
+            std::process::exit(130);
+        }
+    })
+}
+
+/// Resolves once `cancel` turns true; never resolves if the signal can no longer fire.
+async fn cancelled(cancel: &mut watch::Receiver<bool>) {
+    let closed = cancel.wait_for(|cancelled| *cancelled).await.is_err();
+    if closed {
+        std::future::pending::<()>().await;
+    }
+}
+
+struct SignalTaskGuard(Option<JoinHandle<()>>);
+
+impl Drop for SignalTaskGuard {
+    fn drop(&mut self) {
+        if let Some(task) = self.0.take() {
+            task.abort();
+        }
+    }
 }
 
 struct PipelineProgressBar {
     bar: ProgressBar,
+    label: &'static str,
     total_frames: Option<u64>,
     finished: bool,
 }
@@ -183,22 +756,36 @@ impl PipelineProgressBar {
                 bar
             }
         };
+        let bar = match BATCH_PROGRESS.get() {
+            Some(multi) => multi.add(bar),
+            None => bar,
+        };
         bar.set_prefix(label);
+        if !crate::log::enabled(crate::log::LogLevel::Info) {
+            bar.set_draw_target(ProgressDrawTarget::hidden());
+        }
 
         Self {
             bar,
+            label,
             total_frames,
             finished: false,
         }
     }
 
-    fn update(&mut self, progress: &stage::PipelineProgress) {
-        if let Some(total) = self.total_frames {
-            let next = std::cmp::min(progress.latest_frame_index.saturating_add(1), total);
-            self.bar.set_position(next);
+    fn set_paused(&mut self, paused: bool) {
+        if self.finished {
+            return;
+        }
+        if paused {
+            self.bar.set_prefix("paused (Ctrl+Z to resume)");
         } else {
-            self.bar.inc(1);
+            self.bar.set_prefix(self.label);
         }
+    }
+
+    fn update(&mut self, progress: &stage::PipelineProgress) {
+        self.set_frame(progress.latest_frame_index);
 
         let det = format_ms(progress.det_ms);
         let seg = format_ms(progress.seg_ms);
@@ -216,6 +803,15 @@ impl PipelineProgressBar {
         self.bar.set_message(format!("{avg_line}\n{counts_line}"));
     }
 
+    fn set_frame(&mut self, frame_index: u64) {
+        match self.total_frames {
+            Some(total) => self
+                .bar
+                .set_position(std::cmp::min(frame_index.saturating_add(1), total)),
+            None => self.bar.inc(1),
+        }
+    }
+
     fn fail(&mut self, reason: &str) {
         if self.finished {
             return;
@@ -290,13 +886,41 @@ fn apply_updates(subtitles: &mut Vec<stage::MergedSubtitle>, updates: &[stage::S
     }
 }
 
+/// Writes `subtitles` to the output path, or with split tracks each track's cues to its own
+/// `.trackN` file, along with the sidecars asked for. `stream_end` bounds the trailing gap
+/// marker.
 async fn sort_and_write(
-    output_path: &std::path::Path,
+    output: &stage::OutputPipelineConfig,
     subtitles: &[stage::MergedSubtitle],
+    stream_end: Duration,
 ) -> Result<(), DecoderError> {
-    let mut ordered = subtitles.to_vec();
-    stage::sort_subtitles(&mut ordered);
-    let contents = stage::render_srt(&ordered);
+    if output.tracks.is_empty() {
+        return write_track(output, &output.path, subtitles.to_vec(), stream_end).await;
+    }
+    for (index, region) in output.tracks.iter().enumerate() {
+        let cues = subtitles
+            .iter()
+            .filter(|subtitle| {
+                subtitle
+                    .lines
+                    .iter()
+                    .any(|line| line.region == Some(*region))
+            })
+            .cloned()
+            .collect();
+        write_track(output, &track_path(&output.path, index), cues, stream_end).await?;
+    }
+    Ok(())
+}
+
+async fn write_track(
+    output: &stage::OutputPipelineConfig,
+    output_path: &Path,
+    mut ordered: Vec<stage::MergedSubtitle>,
+    stream_end: Duration,
+) -> Result<(), DecoderError> {
+    output.layout.apply(&mut ordered);
+    let contents = encode_output(output, output.format.render(&ordered))?;
 
     if let Some(parent) = output_path.parent().filter(|p| !p.as_os_str().is_empty())
         && let Err(err) = tokio::fs::create_dir_all(parent).await
@@ -307,12 +931,427 @@ async fn sort_and_write(
         )));
     }
 
-    tokio::fs::write(output_path, contents)
+    write_subtitle_file(output_path, contents).await?;
+
+    if let Some(threshold_ms) = output.gap_markers_ms {
+        let gaps = stage::find_gaps(&ordered, Duration::from_millis(threshold_ms), stream_end);
+        let contents = encode_output(output, stage::render_gap_srt(&gaps))?;
+        write_subtitle_file(&gap_markers_path(output_path), contents).await?;
+    }
+    if output.emit_metadata {
+        // JSON is always UTF-8 regardless of the subtitle encoding.
+        let contents = metadata::render_metadata(&ordered).into_bytes();
+        write_subtitle_file(&metadata_path(output_path), contents).await?;
+    }
+    if output.timing_frames {
+        let contents = frame_timing::render_frame_timings(&ordered).into_bytes();
+        write_subtitle_file(&frame_timings_path(output_path), contents).await?;
+    }
+    Ok(())
+}
+
+fn encode_output(
+    output: &stage::OutputPipelineConfig,
+    contents: String,
+) -> Result<Vec<u8>, DecoderError> {
+    output.encoding.encode(&contents).map_err(|err| {
+        DecoderError::configuration(format!(
+            "failed to encode subtitle file {}: {err}",
+            output.path.display()
+        ))
+    })
+}
+
+async fn write_subtitle_file(path: &Path, contents: Vec<u8>) -> Result<(), DecoderError> {
+    tokio::fs::write(path, contents).await.map_err(|err| {
+        DecoderError::configuration(format!(
+            "failed to write subtitle file {}: {err}",
+            path.display()
+        ))
+    })
+}
+
+fn metadata_path(output_path: &Path) -> PathBuf {
+    let mut path = output_path.to_path_buf();
+    path.set_extension("meta.json");
+    path
+}
+
+fn frame_timings_path(output_path: &Path) -> PathBuf {
+    let mut path = output_path.to_path_buf();
+    path.set_extension("frames.tsv");
+    path
+}
+
+/// `name.srt` becomes `name.track<index>.srt`.
+fn track_path(output_path: &Path, index: usize) -> PathBuf {
+    let mut path = output_path.to_path_buf();
+    match output_path.extension() {
+        Some(extension) => {
+            path.set_extension(format!("track{index}.{}", extension.to_string_lossy()))
+        }
+        None => path.set_extension(format!("track{index}")),
+    };
+    path
+}
+
+fn gap_markers_path(output_path: &Path) -> PathBuf {
+    let mut path = output_path.to_path_buf();
+    path.set_extension("gaps.srt");
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use subtitle_fast_types::RoiConfig;
+
+    #[test]
+    fn pause_signals_drive_the_watch_flag() {
+        let (pause, mut updates) = watch::channel(false);
+
+        apply_pause_signal(&pause, PauseSignal::Toggle);
+        assert!(*updates.borrow_and_update());
+        apply_pause_signal(&pause, PauseSignal::Toggle);
+        assert!(!*updates.borrow_and_update());
+
+        // Resuming a running pipeline is a no-op and does not wake subscribers.
+        apply_pause_signal(&pause, PauseSignal::Resume);
+        assert!(!updates.has_changed().expect("sender alive"));
+
+        apply_pause_signal(&pause, PauseSignal::Toggle);
+        apply_pause_signal(&pause, PauseSignal::Resume);
+        assert!(updates.has_changed().expect("sender alive"));
+        assert!(!*updates.borrow_and_update());
+    }
+
+    #[tokio::test]
+    async fn only_transient_init_failures_are_retried_on_the_same_backend() {
+        let config = Configuration::default();
+        let attempts = std::sync::Mutex::new(Vec::new());
+        let fails_once = |config: &Configuration| {
+            let mut attempts = attempts.lock().unwrap();
+            attempts.push(config.backend);
+            if attempts.len() == 1 {
+                Err(DecoderError::backend_failure("mock", "device busy"))
+            } else {
+                Ok(config.backend)
+            }
+        };
+
+        let backend = create_with_retry(&config, 2, Duration::from_millis(1), &fails_once)
+            .await
+            .expect("second attempt succeeds");
+        assert_eq!(backend, config.backend);
+        assert_eq!(*attempts.lock().unwrap(), [config.backend, config.backend]);
+
+        attempts.lock().unwrap().clear();
+        let without_retries = create_with_retry(&config, 0, Duration::ZERO, &fails_once).await;
+        assert!(without_retries.is_err());
+        assert_eq!(attempts.lock().unwrap().len(), 1);
+
+        let permanent_attempts = std::cell::Cell::new(0);
+        let unsupported = create_with_retry(&config, 2, Duration::from_secs(60), |_| {
+            permanent_attempts.set(permanent_attempts.get() + 1);
+            Err::<(), _>(DecoderError::unsupported("mock"))
+        })
+        .await;
+        assert!(unsupported.is_err());
+        assert_eq!(permanent_attempts.get(), 1);
+    }
+
+    #[tokio::test]
+    async fn zero_cue_run_still_writes_an_empty_srt() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let output = stage::OutputPipelineConfig {
+            path: dir.path().join("nested").join("empty.srt"),
+            format: Default::default(),
+            gap_markers_ms: None,
+            layout: Default::default(),
+            tracks: Vec::new(),
+            emit_metadata: false,
+            timing_frames: false,
+            encoding: Default::default(),
+        };
+
+        sort_and_write(&output, &[], Duration::ZERO)
+            .await
+            .expect("write");
+
+        let written = std::fs::read(&output.path).expect("output file exists");
+        assert!(written.is_empty());
+    }
+
+    #[tokio::test]
+    async fn split_tracks_write_each_region_to_its_own_file() {
+        let top = RoiConfig {
+            x: 0.1,
+            y: 0.05,
+            width: 0.8,
+            height: 0.1,
+        };
+        let bottom = RoiConfig { y: 0.85, ..top };
+        let cue = |id: u64, text: &str, region: RoiConfig| stage::MergedSubtitle {
+            id,
+            start_time: Duration::from_secs(1),
+            end_time: Duration::from_secs(3),
+            start_frame: 30,
+            end_frame: 90,
+            lines: vec![stage::SubtitleLine {
+                center: 0.5,
+                text: text.to_string(),
+                rtl: false,
+                confidence: None,
+                region: Some(region),
+            }],
+        };
+        let dir = tempfile::tempdir().expect("tempdir");
+        let output = stage::OutputPipelineConfig {
+            path: dir.path().join("show.srt"),
+            format: Default::default(),
+            gap_markers_ms: None,
+            layout: Default::default(),
+            tracks: vec![top, bottom],
+            emit_metadata: false,
+            timing_frames: false,
+            encoding: Default::default(),
+        };
+
+        let cues = [
+            cue(0, "Sign: Exit", top),
+            cue(1, "Where are we?", bottom),
+            cue(2, "Sign: Stairs", top),
+        ];
+        sort_and_write(&output, &cues, Duration::ZERO)
+            .await
+            .expect("write");
+
+        assert!(!output.path.exists());
+        let track0 = std::fs::read_to_string(dir.path().join("show.track0.srt")).expect("track 0");
+        let track1 = std::fs::read_to_string(dir.path().join("show.track1.srt")).expect("track 1");
+        assert!(track0.contains("Sign: Exit") && track0.contains("Sign: Stairs"));
+        assert!(!track0.contains("Where are we?"));
+        assert!(track1.contains("Where are we?"));
+        assert!(!track1.contains("Sign:"));
+    }
+
+    fn committed(id: u64, text: &str) -> stage::PipelineResult {
+        Ok(stage::PipelineUpdate {
+            progress: stage::PipelineProgress {
+                samples_seen: id + 1,
+                ..Default::default()
+            },
+            updates: vec![stage::SubtitleUpdate {
+                kind: stage::SubtitleUpdateKind::New,
+                subtitle: stage::MergedSubtitle {
+                    id,
+                    start_time: Duration::from_secs(id * 2),
+                    end_time: Duration::from_secs(id * 2 + 1),
+                    start_frame: id * 60,
+                    end_frame: id * 60 + 30,
+                    lines: vec![stage::SubtitleLine {
+                        center: 0.9,
+                        text: text.to_string(),
+                        rtl: false,
+                        confidence: None,
+                        region: None,
+                    }],
+                },
+            }],
+        })
+    }
+
+    #[tokio::test]
+    async fn interrupted_run_writes_cues_committed_before_the_signal() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let output = stage::OutputPipelineConfig {
+            path: dir.path().join("partial.srt"),
+            format: Default::default(),
+            gap_markers_ms: None,
+            layout: Default::default(),
+            tracks: Vec::new(),
+            emit_metadata: false,
+            timing_frames: false,
+            encoding: Default::default(),
+        };
+        let (cancel_tx, mut cancel) = watch::channel(false);
+        // Two cues arrive, then the run is interrupted while waiting on a frame that never comes.
+        let interrupt = futures_util::stream::once(async move {
+            let _ = cancel_tx.send(true);
+            std::future::pending::<stage::PipelineResult>().await
+        });
+        let stream = futures_util::stream::iter([committed(0, "first"), committed(1, "second")])
+            .chain(interrupt);
+        let (_pause, pause_updates) = watch::channel(false);
+
+        let drained = drain_pipeline(
+            Box::pin(stream),
+            None,
+            pause_updates,
+            &output,
+            false,
+            &mut cancel,
+        )
         .await
-        .map_err(|err| {
-            DecoderError::configuration(format!(
-                "failed to write subtitle file {}: {err}",
-                output_path.display()
-            ))
+        .expect("drain");
+
+        assert_eq!(drained.end, RunEnd::Interrupted);
+        let written = std::fs::read_to_string(&output.path).expect("output file exists");
+        assert_eq!(
+            written,
+            "1\n00:00:00,000 --> 00:00:01,000\nfirst\n\n2\n00:00:02,000 --> 00:00:03,000\nsecond\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn bench_run_reports_a_summary_and_writes_no_srt() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let output = stage::OutputPipelineConfig {
+            path: dir.path().join("bench.srt"),
+            format: Default::default(),
+            gap_markers_ms: Some(1000),
+            layout: Default::default(),
+            tracks: Vec::new(),
+            emit_metadata: true,
+            timing_frames: false,
+            encoding: Default::default(),
+        };
+        let (_cancel_tx, mut cancel) = watch::channel(false);
+        let (_pause, pause_updates) = watch::channel(false);
+        let stream = futures_util::stream::iter([committed(0, "first"), committed(1, "second")])
+            .map(|result| {
+                result.map(|mut update| {
+                    update.progress.cues = update.progress.samples_seen;
+                    update.progress.fps = 250.0;
+                    update.progress.ocr_ms = 12.5;
+                    update
+                })
+            });
+
+        let drained = drain_pipeline(
+            Box::pin(stream),
+            None,
+            pause_updates,
+            &output,
+            true,
+            &mut cancel,
+        )
+        .await
+        .expect("drain");
+
+        assert_eq!(drained.end, RunEnd::Completed);
+        let summary = bench_summary(&drained.progress);
+        assert!(summary.starts_with("benchmark summary\n"), "{summary}");
+        assert!(summary.contains("samples     2\n"), "{summary}");
+        assert!(summary.contains("decode fps  250.0\n"), "{summary}");
+        assert!(
+            summary.contains("ocr         12.5 ms / region\n"),
+            "{summary}"
+        );
+        assert!(summary.contains("cues        2 (0 merged"), "{summary}");
+        let written: Vec<_> = std::fs::read_dir(dir.path()).expect("read dir").collect();
+        assert!(written.is_empty(), "bench run wrote {written:?}");
+    }
+
+    #[tokio::test]
+    async fn completed_run_summary_matches_the_written_output() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let output = stage::OutputPipelineConfig {
+            path: dir.path().join("run.srt"),
+            format: Default::default(),
+            gap_markers_ms: None,
+            layout: Default::default(),
+            tracks: Vec::new(),
+            emit_metadata: false,
+            timing_frames: false,
+            encoding: Default::default(),
+        };
+        let (_cancel_tx, mut cancel) = watch::channel(false);
+        let (_pause, pause_updates) = watch::channel(false);
+        let stream =
+            futures_util::stream::iter([committed(0, "first"), committed(1, "second wave")]).map(
+                |result| {
+                    result.map(|mut update| {
+                        update.progress.fps = 120.0;
+                        update
+                    })
+                },
+            );
+
+        let drained = drain_pipeline(
+            Box::pin(stream),
+            None,
+            pause_updates,
+            &output,
+            false,
+            &mut cancel,
+        )
+        .await
+        .expect("drain");
+        let summary = run_summary(
+            &drained,
+            Duration::from_millis(1500),
+            "mock",
+            "noop",
+            &output.path,
+        );
+
+        assert!(summary.starts_with("run summary\n"), "{summary}");
+        for row in [
+            "cues        2\n".to_string(),
+            "characters  15\n".to_string(),
+            "duration    1.50s\n".to_string(),
+            "avg fps     120.0\n".to_string(),
+            "backend     mock\n".to_string(),
+            "ocr         noop\n".to_string(),
+            format!("output      {}\n", output.path.display()),
+        ] {
+            assert!(summary.contains(&row), "missing '{row}' in:\n{summary}");
+        }
+        let written = std::fs::read_to_string(&output.path).expect("output file exists");
+        assert_eq!(written.matches(" --> ").count(), drained.cues);
+    }
+    #[tokio::test]
+    async fn cancelled_batch_starts_no_further_inputs() {
+        use crate::cli::{CliArgs, CliSources};
+        use crate::settings::resolve_settings;
+        use clap::Parser;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config = dir.path().join("batch.toml");
+        std::fs::write(&config, "").expect("write config");
+        let flag = config.to_str().expect("utf-8 path");
+        let cli = CliArgs::try_parse_from(["subtitle-fast", "--config", flag, "a.mp4", "b.mkv"])
+            .expect("cli args");
+        let settings = resolve_settings(&cli, &CliSources::default())
+            .expect("settings")
+            .settings;
+        let plans = stage::PipelineConfig::for_batch(&settings, &cli.inputs, |_| {
+            std::sync::Arc::new(subtitle_fast_ocr::NoopOcrEngine)
+        })
+        .expect("batch")
+        .into_iter()
+        .zip(&cli.inputs)
+        .map(|(pipeline, input)| ExecutionPlan {
+            config: Configuration {
+                input: Some(input.clone()),
+                ..Configuration::default()
+            },
+            backend_locked: false,
+            pipeline,
+            bench: false,
+            heatmap: None,
         })
+        .collect();
+
+        let (cancel_tx, cancel) = watch::channel(false);
+        cancel_tx.send(true).expect("batch is listening");
+        let outcome = run_batch_until(plans, 2, cancel).await;
+        assert!(outcome.finished.is_empty());
+        let err = outcome.result.expect_err("interrupted batch");
+        assert!(
+            err.to_string().contains("2 of 2 inputs were not started"),
+            "{err}"
+        );
+    }
 }
@@ -0,0 +1,241 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use subtitle_fast_decoder::{Backend, Configuration};
+use subtitle_fast_validator::subtitle_detection::preflight_detection_with_priority;
+
+use crate::backend::parse_backend;
+use crate::cli::{CliArgs, CliSources};
+use crate::model;
+use crate::settings::{EffectiveSettings, resolve_settings};
+use crate::stage::PipelineConfig;
+
+/// Outcome of a single `--check` step.
+#[derive(Debug, Clone)]
+pub struct CheckItem {
+    pub name: &'static str,
+    pub outcome: Result<String, String>,
+}
+
+/// Collected results of a configuration dry run.
+#[derive(Debug, Clone, Default)]
+pub struct CheckReport {
+    items: Vec<CheckItem>,
+}
+
+impl CheckReport {
+    fn pass(&mut self, name: &'static str, message: impl Into<String>) {
+        self.items.push(CheckItem {
+            name,
+            outcome: Ok(message.into()),
+        });
+    }
+
+    fn fail(&mut self, name: &'static str, message: impl Into<String>) {
+        self.items.push(CheckItem {
+            name,
+            outcome: Err(message.into()),
+        });
+    }
+
+    pub fn items(&self) -> &[CheckItem] {
+        &self.items
+    }
+
+    pub fn passed(&self) -> bool {
+        self.items.iter().all(|item| item.outcome.is_ok())
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = &CheckItem> {
+        self.items.iter().filter(|item| item.outcome.is_err())
+    }
+}
+
+impl fmt::Display for CheckReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for item in &self.items {
+            match &item.outcome {
+                Ok(message) => writeln!(f, "[pass] {}: {}", item.name, message)?,
+                Err(message) => writeln!(f, "[fail] {}: {}", item.name, message)?,
+            }
+        }
+        let failed = self.failures().count();
+        if failed == 0 {
+            writeln!(f, "check passed")
+        } else {
+            writeln!(f, "check failed ({failed} problem(s))")
+        }
+    }
+}
+
+/// Validate settings, backends and model assets without opening a decoder.
+pub fn run_check(cli: &CliArgs, sources: &CliSources) -> CheckReport {
+    let mut report = CheckReport::default();
+
+    let resolved = match resolve_settings(cli, sources) {
+        Ok(resolved) => {
+            let origin = resolved
+                .config_path
+                .as_ref()
+                .map(|path| format!("loaded from {}", path.display()))
+                .unwrap_or_else(|| "using defaults (no config file)".to_string());
+            report.pass("settings", origin);
+            Some(resolved)
+        }
+        Err(err) => {
+            report.fail("settings", err.to_string());
+            None
+        }
+    };
+
+    check_decoder_backends(&mut report, resolved.as_ref().map(|r| &r.settings));
+
+    let Some(resolved) = resolved else {
+        return report;
+    };
+    let settings = &resolved.settings;
+
+    for input in &cli.inputs {
+        if input.exists() {
+            report.pass("input", input.display().to_string());
+        } else {
+            report.fail(
+                "input",
+                format!("input file '{}' does not exist", input.display()),
+            );
+        }
+    }
+
+    let input = cli
+        .inputs
+        .first()
+        .cloned()
+        .unwrap_or_else(|| PathBuf::from("input"));
+    match PipelineConfig::from_settings(settings, &input) {
+        Ok(pipeline) => report.pass(
+            "pipeline",
+            format!(
+                "ocr engine '{}', output {}",
+                pipeline.ocr.engine.name(),
+                pipeline.output.path.display()
+            ),
+        ),
+        Err(err) => report.fail("pipeline", err.to_string()),
+    }
+
+    let detector = settings.detection.detector;
+    match preflight_detection_with_priority(detector, &settings.detection.detector_priority) {
+        Ok(()) => report.pass("detector", detector.as_str()),
+        Err(err) => report.fail("detector", format!("{}: {err}", detector.as_str())),
+    }
+
+    check_ort_models(&mut report, settings, resolved.config_path.as_deref());
+
+    report
+}
+
+fn check_decoder_backends(report: &mut CheckReport, settings: Option<&EffectiveSettings>) {
+    let available = Configuration::available_backends();
+    if available.is_empty() {
+        report.fail("decoder", "no decoding backends compiled into this build");
+        return;
+    }
+    let names: Vec<&'static str> = available.iter().map(Backend::as_str).collect();
+
+    let requested = settings.and_then(|settings| settings.decoder.backend.as_deref());
+    let Some(requested) = requested else {
+        report.pass("decoder", format!("available: {}", names.join(", ")));
+        return;
+    };
+    match parse_backend(requested) {
+        Ok(backend) if available.contains(&backend) => report.pass(
+            "decoder",
+            format!("backend '{}' available", backend.as_str()),
+        ),
+        Ok(backend) => report.fail(
+            "decoder",
+            format!(
+                "backend '{}' is not available (available: {})",
+                backend.as_str(),
+                names.join(", ")
+            ),
+        ),
+        Err(err) => report.fail("decoder", err.to_string()),
+    }
+}
+
+fn check_ort_models(
+    report: &mut CheckReport,
+    settings: &EffectiveSettings,
+    config_path: Option<&Path>,
+) {
+    if !model::should_prepare_ort(Some(settings)) {
+        report.pass("models", "not required for the selected OCR backend");
+        return;
+    }
+    match model::resolve_ort_model_paths(config_path) {
+        Ok(paths) if model::ort_models_present(&paths) => report.pass(
+            "models",
+            format!("found in {}", paths.model_path().display()),
+        ),
+        Ok(paths) => report.fail(
+            "models",
+            format!(
+                "ORT model assets missing at {}",
+                paths.model_path().display()
+            ),
+        ),
+        Err(err) => report.fail("models", err.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use std::fs;
+
+    fn check_with_config(contents: &str) -> CheckReport {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config_path = dir.path().join("config.toml");
+        fs::write(&config_path, contents).expect("write config");
+        let cli = CliArgs::try_parse_from([
+            "subtitle-fast",
+            "--check",
+            "--ocr-backend",
+            "noop",
+            "--config",
+            config_path.to_str().expect("utf-8 path"),
+        ])
+        .expect("cli args");
+        run_check(&cli, &CliSources::default())
+    }
+
+    #[test]
+    fn check_passes_on_valid_config() {
+        let report = check_with_config(
+            "[detection]\nsamples_per_second = 5\nroi = { x = 0.0, y = 0.75, width = 1.0, height = 0.25 }\n",
+        );
+        // Decoder availability depends on the features and runtime of the test build.
+        assert!(
+            report.failures().all(|item| item.name == "decoder"),
+            "unexpected failures:\n{report}"
+        );
+        assert!(report.items().iter().any(|item| item.name == "settings"));
+    }
+
+    #[test]
+    fn check_reports_invalid_roi() {
+        let report = check_with_config(
+            "[detection]\nroi = { x = -0.2, y = 0.75, width = 1.0, height = 0.25 }\n",
+        );
+        assert!(!report.passed());
+        let failure = report.failures().next().expect("settings failure");
+        assert_eq!(failure.name, "settings");
+        let message = failure.outcome.as_ref().unwrap_err();
+        assert!(
+            message.contains("detection_roi") && message.contains("-0.2"),
+            "unclear message: {message}"
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use std::path::PathBuf;
 
 use clap::parser::ValueSource;
-use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser};
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand};
 use subtitle_fast_types::RoiConfig;
 
 #[derive(Debug, Default)]
@@ -53,11 +53,15 @@ pub fn parse_cli() -> (CliArgs, CliSources) {
     disable_help_subcommand = true
 )]
 pub struct CliArgs {
+    #[command(subcommand)]
+    pub command: Option<CliCommand>,
+
     /// Lock decoding to a specific backend implementation
     #[arg(short = 'b', long = "backend")]
     pub backend: Option<String>,
 
-    /// Override the configuration file path
+    /// Load this configuration file instead of SUBFAST_CONFIG or the discovered config; it must
+    /// exist
     #[arg(long = "config")]
     pub config: Option<PathBuf>,
 
@@ -65,6 +69,28 @@ pub struct CliArgs {
     #[arg(long = "list-backends")]
     pub list_backends: bool,
 
+    /// Validate configuration, backends and models without decoding
+    #[arg(long = "check")]
+    pub check: bool,
+
+    /// Run the full pipeline and print stage timings instead of writing subtitle files
+    #[arg(long = "bench")]
+    pub bench: bool,
+
+    /// Detect over the whole frame, ignoring the ROI, and write a PNG heatmap of where
+    /// subtitles appear instead of subtitle files
+    #[arg(long = "heatmap", value_name = "PNG")]
+    pub heatmap: Option<PathBuf>,
+
+    /// Only report errors on stderr
+    #[arg(short = 'q', long = "quiet", conflicts_with = "verbose")]
+    pub quiet: bool,
+
+    /// Also report debug detail on stderr, including the detector's per-region trace
+    /// (same as REGION_DEBUG=1)
+    #[arg(short = 'v', long = "verbose")]
+    pub verbose: bool,
+
     /// Subtitle detection samples per second
     #[arg(
         long = "detection-samples-per-second",
@@ -82,6 +108,10 @@ pub struct CliArgs {
     )]
     pub decoder_channel_capacity: Option<usize>,
 
+    /// Consecutive decode errors to skip before giving up on a backend
+    #[arg(long = "decoder-error-tolerance", value_name = "N")]
+    pub decoder_error_tolerance: Option<usize>,
+
     /// Override the detector target value (0-255)
     #[arg(long = "detector-target", value_parser = parse_u8_byte)]
     pub detector_target: Option<u8>,
@@ -90,24 +120,110 @@ pub struct CliArgs {
     #[arg(long = "detector-delta", value_parser = parse_u8_byte)]
     pub detector_delta: Option<u8>,
 
-    /// Subtitle comparator to use (bitset-cover, sparse-chamfer)
+    /// Subtitle comparator to use (bitset-cover, sparse-chamfer, perceptual-hash)
     #[arg(long = "comparator")]
     pub comparator: Option<String>,
 
-    /// Normalized detection ROI as x,y,width,height (omit or zero size uses full frame)
+    /// Detection ROI as x,y,width,height (omit or zero size uses full frame)
     #[arg(long = "roi", value_name = "X,Y,W,H", value_parser = parse_roi)]
     pub roi: Option<RoiConfig>,
 
+    /// Units for the detection ROI (fraction, pixels)
+    #[arg(long = "roi-units", value_name = "UNITS")]
+    pub roi_units: Option<String>,
+
     /// Output subtitle file path
     #[arg(short = 'o', long = "output")]
     pub output: Option<PathBuf>,
 
+    /// Subtitle file format (srt, vtt, ass, jsonl); defaults to the output path's extension
+    #[arg(long = "format", value_name = "FORMAT")]
+    pub format: Option<String>,
+
+    /// Also write a .meta.json sidecar with per-cue timing, confidence and region
+    #[arg(long = "emit-metadata")]
+    pub emit_metadata: bool,
+
+    /// Also write a .frames.tsv sidecar with the first and last frame index of each cue
+    #[arg(long = "timing-frames")]
+    pub timing_frames: bool,
+
+    /// Also write a .gaps.srt file marking subtitle-free intervals at least this many ms long
+    #[arg(long = "gap-markers", value_name = "MS")]
+    pub gap_markers_ms: Option<u64>,
+
+    /// Wrap cue lines longer than this many characters, for players that do not wrap them
+    #[arg(long = "wrap-width", value_name = "CHARS")]
+    pub wrap_width: Option<usize>,
+
+    /// With several --fixed-region areas, write one subtitle file per area (NAME.track0.srt,
+    /// NAME.track1.srt, ...) instead of interleaving their cues in one file
+    #[arg(long = "split-tracks")]
+    pub split_tracks: bool,
+
+    /// Encoding for the written subtitle file (utf-8, gbk, big5, shift_jis, ...)
+    #[arg(long = "output-encoding", value_name = "NAME")]
+    pub output_encoding: Option<String>,
+
+    /// Replace characters the output encoding cannot represent with '?' instead of failing
+    #[arg(long = "output-encoding-replace")]
+    pub output_encoding_replace: bool,
+
     /// OCR backend to use (auto, vision, ort, noop)
     #[arg(long = "ocr-backend", id = "ocr_backend")]
     pub ocr_backend: Option<String>,
 
-    /// Input video path
-    pub input: Option<PathBuf>,
+    /// When no OCR engine is available, still write cues with placeholder text and real timings
+    #[arg(long = "ocr-placeholder")]
+    pub ocr_placeholder: bool,
+
+    /// Number of subtitle regions recognized concurrently (default 1)
+    #[arg(long = "ocr-concurrency", value_name = "N")]
+    pub ocr_concurrency: Option<usize>,
+
+    /// ORT recognition model input height in pixels, for custom models (default 48)
+    #[arg(long = "ocr-input-height", value_name = "PX")]
+    pub ocr_input_height: Option<usize>,
+
+    /// ORT recognition model input width in pixels, for custom models (default 320)
+    #[arg(long = "ocr-input-width", value_name = "PX")]
+    pub ocr_input_width: Option<usize>,
+
+    /// OCR this x,y,width,height frame-fraction area on every sample instead of detecting
+    /// subtitles; repeat for several areas
+    #[arg(long = "fixed-region", value_name = "X,Y,W,H", value_parser = parse_roi)]
+    pub fixed_regions: Vec<RoiConfig>,
+
+    /// Input files processed at once when several inputs are given; they share one OCR engine
+    #[arg(short = 'j', long = "jobs", value_name = "N", default_value_t = 2, value_parser = parse_positive_u32)]
+    pub jobs: u32,
+
+    /// Keep polling this directory and write subtitles beside every video that appears in it,
+    /// once the file stops growing; videos that already have a subtitle file are skipped
+    #[arg(
+        long = "watch",
+        value_name = "DIR",
+        conflicts_with_all = ["inputs", "output", "heatmap"]
+    )]
+    pub watch: Option<PathBuf>,
+
+    /// Write the resolved detection settings to this TOML config so later runs detect the same
+    /// way; other sections of an existing file are kept. Needs no input video
+    #[arg(long = "save-effective-config", value_name = "PATH")]
+    pub save_effective_config: Option<PathBuf>,
+
+    /// Input video path; several paths are processed as a batch
+    pub inputs: Vec<PathBuf>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum CliCommand {
+    /// Print resolution, frame rate, duration and the expected number of sampled frames
+    /// without running detection or OCR
+    Probe {
+        /// Video file to inspect
+        input: PathBuf,
+    },
 }
 
 fn parse_u8_byte(value: &str) -> Result<u8, String> {
@@ -0,0 +1,172 @@
+//! In-memory trail of manual cue edits made in the GUI, exported for QA review.
+//!
+//! The export is a JSON object with a `version` field and an `edits` array in the order the
+//! edits were made. Version 1 entries carry:
+//!
+//! - `id`: pipeline-assigned subtitle id
+//! - `edited_at_ms`: wall-clock time of the edit in milliseconds since the Unix epoch
+//! - `before` / `after`: the cue's `start_ms`, `end_ms` and `lines` around the edit; `after`
+//!   is `null` when the edit deleted the cue
+//!
+//! A cue's first entry has the auto-generated values as `before`. Fields are only ever added
+//! within a version; renames or removals bump `version`.
+
+use std::collections::HashSet;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+use crate::subtitle::MergedSubtitle;
+
+pub const EDIT_AUDIT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CueSnapshot {
+    pub start_ms: f64,
+    pub end_ms: f64,
+    pub lines: Vec<String>,
+}
+
+impl CueSnapshot {
+    fn of(subtitle: &MergedSubtitle) -> Self {
+        let timed = subtitle.as_timed();
+        Self {
+            start_ms: timed.start_ms,
+            end_ms: timed.end_ms,
+            lines: timed.lines,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EditAuditEntry {
+    pub id: u64,
+    pub edited_at_ms: u64,
+    pub before: CueSnapshot,
+    /// `None` when the cue was deleted.
+    pub after: Option<CueSnapshot>,
+}
+
+#[derive(Debug, Serialize)]
+struct EditAuditDocument<'a> {
+    version: u32,
+    edits: &'a [EditAuditEntry],
+}
+
+#[derive(Debug, Default)]
+pub struct EditAudit {
+    entries: Vec<EditAuditEntry>,
+}
+
+impl EditAudit {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records one edit of a cue, `before` and `after` being the cue around the change.
+    pub fn record(&mut self, before: &MergedSubtitle, after: &MergedSubtitle) {
+        self.push(after.id, before, Some(CueSnapshot::of(after)));
+    }
+
+    /// Records the deletion of `removed`.
+    pub fn record_removal(&mut self, removed: &MergedSubtitle) {
+        self.push(removed.id, removed, None);
+    }
+
+    fn push(&mut self, id: u64, before: &MergedSubtitle, after: Option<CueSnapshot>) {
+        let edited_at_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as u64)
+            .unwrap_or(0);
+        self.entries.push(EditAuditEntry {
+            id,
+            edited_at_ms,
+            before: CueSnapshot::of(before),
+            after,
+        });
+    }
+
+    pub fn entries(&self) -> &[EditAuditEntry] {
+        &self.entries
+    }
+
+    /// Ids of every cue edited at least once.
+    pub fn edited_ids(&self) -> HashSet<u64> {
+        self.entries.iter().map(|entry| entry.id).collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn render_json(&self) -> String {
+        let document = EditAuditDocument {
+            version: EDIT_AUDIT_VERSION,
+            edits: &self.entries,
+        };
+        let mut json =
+            serde_json::to_string_pretty(&document).expect("edit audit is always serializable");
+        json.push('\n');
+        json
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::subtitle::SubtitleLine;
+    use std::time::Duration;
+
+    fn cue(text: &str, start_ms: u64, end_ms: u64) -> MergedSubtitle {
+        MergedSubtitle {
+            id: 7,
+            start_time: Duration::from_millis(start_ms),
+            end_time: Duration::from_millis(end_ms),
+            start_frame: 0,
+            end_frame: 0,
+            lines: vec![SubtitleLine {
+                center: 0.9,
+                text: text.to_string(),
+                rtl: false,
+                confidence: Some(0.8),
+                region: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn editing_a_cue_records_before_and_after_values() {
+        let mut audit = EditAudit::new();
+        audit.record(&cue("Helo", 1000, 2000), &cue("Hello", 1100, 2500));
+
+        assert_eq!(audit.entries().len(), 1);
+        let entry = &audit.entries()[0];
+        assert_eq!(entry.id, 7);
+        assert!(entry.edited_at_ms > 0);
+        assert_eq!(
+            entry.before,
+            CueSnapshot {
+                start_ms: 1000.0,
+                end_ms: 2000.0,
+                lines: vec!["Helo".to_string()],
+            }
+        );
+        assert_eq!(
+            entry.after,
+            Some(CueSnapshot {
+                start_ms: 1100.0,
+                end_ms: 2500.0,
+                lines: vec!["Hello".to_string()],
+            })
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&audit.render_json()).expect("json");
+        assert_eq!(json["version"], EDIT_AUDIT_VERSION);
+        assert_eq!(json["edits"][0]["before"]["lines"][0], "Helo");
+        assert_eq!(json["edits"][0]["after"]["end_ms"], 2500.0);
+    }
+}
@@ -0,0 +1,141 @@
+use std::fmt;
+
+use encoding_rs::{EncoderResult, Encoding, UTF_8};
+
+/// Character written in place of text the target encoding cannot represent.
+const REPLACEMENT: &str = "?";
+
+/// Text encoding applied to rendered subtitle files just before they are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputEncoding {
+    encoding: &'static Encoding,
+    replace_unmappable: bool,
+}
+
+impl Default for OutputEncoding {
+    fn default() -> Self {
+        Self {
+            encoding: UTF_8,
+            replace_unmappable: false,
+        }
+    }
+}
+
+impl OutputEncoding {
+    /// Looks up a WHATWG encoding label such as `utf-8`, `gbk` or `shift_jis`.
+    ///
+    /// Encodings that cannot be produced (UTF-16 and `replacement`) are rejected.
+    pub fn from_label(label: &str) -> Option<Self> {
+        let encoding = Encoding::for_label(label.trim().as_bytes())?;
+        if encoding.output_encoding() != encoding {
+            return None;
+        }
+        Some(Self {
+            encoding,
+            replace_unmappable: false,
+        })
+    }
+
+    /// Substitute `?` for unrepresentable characters instead of failing.
+    pub fn with_replacement(mut self, replace_unmappable: bool) -> Self {
+        self.replace_unmappable = replace_unmappable;
+        self
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.encoding.name()
+    }
+
+    pub fn is_utf8(&self) -> bool {
+        self.encoding == UTF_8
+    }
+
+    pub fn encode(&self, text: &str) -> Result<Vec<u8>, UnmappableCharacter> {
+        if self.is_utf8() {
+            return Ok(text.as_bytes().to_vec());
+        }
+
+        let mut encoder = self.encoding.new_encoder();
+        let mut output = Vec::with_capacity(text.len());
+        let mut remaining = text;
+        loop {
+            let capacity = encoder
+                .max_buffer_length_from_utf8_without_replacement(remaining.len())
+                .unwrap_or(remaining.len() * 4 + 16);
+            let start = output.len();
+            output.resize(start + capacity, 0);
+            let (result, read, written) =
+                encoder.encode_from_utf8_without_replacement(remaining, &mut output[start..], true);
+            output.truncate(start + written);
+            remaining = &remaining[read..];
+            match result {
+                EncoderResult::InputEmpty => return Ok(output),
+                EncoderResult::OutputFull => continue,
+                EncoderResult::Unmappable(character) => {
+                    if !self.replace_unmappable {
+                        return Err(UnmappableCharacter {
+                            character,
+                            encoding: self.name(),
+                        });
+                    }
+                    output.extend_from_slice(REPLACEMENT.as_bytes());
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnmappableCharacter {
+    pub character: char,
+    pub encoding: &'static str,
+}
+
+impl fmt::Display for UnmappableCharacter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "'{}' (U+{:04X}) cannot be represented in {}; use --output-encoding-replace to substitute '{REPLACEMENT}'",
+            self.character, self.character as u32, self.encoding
+        )
+    }
+}
+
+impl std::error::Error for UnmappableCharacter {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cjk_round_trips_through_gbk() {
+        let text = "1\n00:00:01,000 --> 00:00:02,000\n你好，世界\n";
+        let encoding = OutputEncoding::from_label("GBK").expect("gbk label");
+        let bytes = encoding.encode(text).expect("encodable");
+        assert_ne!(bytes, text.as_bytes());
+
+        let (decoded, _, had_errors) = encoding_rs::GBK.decode(&bytes);
+        assert!(!had_errors);
+        assert_eq!(decoded, text);
+    }
+
+    #[test]
+    fn unmappable_characters_fail_unless_replaced() {
+        let encoding = OutputEncoding::from_label("gbk").expect("gbk label");
+        let err = encoding.encode("hi 🙂").unwrap_err();
+        assert_eq!(err.character, '🙂');
+
+        let bytes = encoding
+            .with_replacement(true)
+            .encode("hi 🙂!")
+            .expect("replaced");
+        assert_eq!(bytes, b"hi ?!");
+    }
+
+    #[test]
+    fn utf16_labels_are_rejected() {
+        assert!(OutputEncoding::from_label("utf-16le").is_none());
+        assert!(OutputEncoding::from_label("not-an-encoding").is_none());
+        assert!(OutputEncoding::from_label("utf8").unwrap().is_utf8());
+    }
+}
@@ -0,0 +1,183 @@
+//! Frame-index sidecar written next to the subtitle output with `--timing-frames`.
+//!
+//! A debugging aid for timing drift: every cue is listed with the decoded frame indices the
+//! lifecycle stage opened and closed it on, beside the timestamps the subtitle file carries.
+//! The file is tab-separated with a header row; columns are the 1-based cue `index` matching
+//! the SRT cue number, `start_ms` / `end_ms`, `start_frame` / `end_frame`, and the cue `text`
+//! with line breaks written as `\n`.
+
+use std::fmt::Write;
+
+use crate::subtitle::MergedSubtitle;
+
+const HEADER: &str = "index\tstart_ms\tend_ms\tstart_frame\tend_frame\ttext";
+
+/// Render the frame table for cues already sorted in output order.
+pub fn render_frame_timings(subtitles: &[MergedSubtitle]) -> String {
+    let mut output = String::from(HEADER);
+    output.push('\n');
+    for (idx, subtitle) in subtitles.iter().enumerate() {
+        let timed = subtitle.as_timed();
+        let text = timed
+            .lines
+            .iter()
+            .map(|line| line.replace('\t', " ").replace('\n', "\\n"))
+            .collect::<Vec<_>>()
+            .join("\\n");
+        let _ = writeln!(
+            &mut output,
+            "{}\t{}\t{}\t{}\t{}\t{text}",
+            idx + 1,
+            subtitle.start_time.as_millis(),
+            subtitle.end_time.as_millis(),
+            subtitle.start_frame,
+            subtitle.end_frame,
+        );
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use futures_util::StreamExt;
+
+    use crate::settings::{DetectionSettings, RoiUnits};
+    use crate::stage::StreamBundle;
+    use crate::stage::fixed_regions::FixedRegions;
+    use crate::stage::lifecycle::RegionLifecycleTracker;
+    use crate::stage::merge::Merge;
+    use crate::stage::ocr::SubtitleOcr;
+    use crate::stage::sampler::FrameSampler;
+    use crate::subtitle::sort_subtitles;
+    use subtitle_fast_ocr::{OcrEngine, OcrError, OcrRequest};
+    use subtitle_fast_types::{DecoderResult, OcrResponse, OcrText, RoiConfig, VideoFrame};
+    use subtitle_fast_validator::subtitle_detection::{
+        DEFAULT_AUTO_DETECTOR_PRIORITY, DEFAULT_DELTA, DEFAULT_LINE_VALLEY_DEPTH, DEFAULT_TARGET,
+        FusionPolicy, RegionSizeConfig, RoiRounding, SubtitleDetectorKind,
+    };
+
+    const WIDTH: u32 = 200;
+    const HEIGHT: u32 = 100;
+    const FPS: u64 = 4;
+
+    /// Reads the region's brightness back as the text it stands for.
+    struct BrightnessEngine;
+
+    impl OcrEngine for BrightnessEngine {
+        fn name(&self) -> &'static str {
+            "brightness"
+        }
+
+        fn recognize(&self, request: &OcrRequest<'_>) -> Result<OcrResponse, OcrError> {
+            let plane = request.plane();
+            Ok(OcrResponse::new(
+                request
+                    .regions()
+                    .iter()
+                    .map(|region| {
+                        let (x, y) = (region.x as usize, region.y as usize);
+                        let luma = plane.data()[y * plane.stride() + x];
+                        let text = if luma < 128 { "first" } else { "second" };
+                        OcrText::new(*region, text.to_string())
+                    })
+                    .collect(),
+            ))
+        }
+    }
+
+    /// Frame `index` of a 4 fps clip whose subtitle band turns bright from frame `switch_at`.
+    fn frame(index: u64, switch_at: u64) -> DecoderResult<VideoFrame> {
+        let luma = if index < switch_at { 40 } else { 220 };
+        VideoFrame::from_nv12_owned(
+            WIDTH,
+            HEIGHT,
+            WIDTH as usize,
+            WIDTH as usize,
+            Some(Duration::from_millis(index * 1000 / FPS)),
+            None,
+            vec![luma; (WIDTH * HEIGHT) as usize],
+            vec![128; (WIDTH * HEIGHT / 2) as usize],
+        )
+        .map(|frame| frame.with_index(Some(index)))
+    }
+
+    fn detection_settings(fixed_regions: Vec<RoiConfig>) -> DetectionSettings {
+        DetectionSettings {
+            samples_per_second: 2,
+            target: DEFAULT_TARGET,
+            delta: DEFAULT_DELTA,
+            detector: SubtitleDetectorKind::ProjectionBand,
+            detector_priority: DEFAULT_AUTO_DETECTOR_PRIORITY.to_vec(),
+            comparator: None,
+            comparator_time_decay: 0.0,
+            roi: None,
+            roi_units: RoiUnits::Fraction,
+            max_in_flight: None,
+            bridge_gap_ms: 0,
+            black_gap_bridge_ms: 0,
+            roi_clip_margin: 0.0,
+            roi_rounding: RoiRounding::Outward,
+            roi_half_pixel: false,
+            min_detection_confidence: 0.0,
+            fusion_policy: FusionPolicy::Intersection,
+            shear_compensation: None,
+            split_lines: false,
+            line_valley_depth: DEFAULT_LINE_VALLEY_DEPTH,
+            min_region: RegionSizeConfig::default(),
+            max_region_size_change: None,
+            region_smoothing: 0.0,
+            max_regions_per_frame: None,
+            full_width_guard: None,
+            fixed_regions,
+            exclusion_zones: Vec::new(),
+            exclusion_overlap: crate::stage::exclusion::DEFAULT_EXCLUSION_OVERLAP,
+            frame_rotation_deg: 0.0,
+            band_softness: 0,
+            skip_intro: Duration::ZERO,
+            warmup_frames: 0,
+            flush_at_end: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn frame_columns_follow_the_sampled_frames_of_a_mock_run() {
+        let band = RoiConfig {
+            x: 0.1,
+            y: 0.7,
+            width: 0.8,
+            height: 0.2,
+        };
+        let settings = detection_settings(vec![band]);
+        let frames = (0..12).map(|index| frame(index, 6));
+        let input = StreamBundle::new(Box::pin(futures_util::stream::iter(frames)), None);
+        let sampled = FrameSampler::new(settings.samples_per_second).attach(input);
+        let determined = FixedRegions::new(settings.fixed_regions.clone()).attach(sampled);
+        let tracked = RegionLifecycleTracker::new(&settings)
+            .with_split_on_change(true)
+            .attach(determined);
+        let ocred = SubtitleOcr::new(Arc::new(BrightnessEngine)).attach(tracked);
+        let mut merged = Merge::with_default_window().attach(ocred).stream;
+
+        let mut subtitles: Vec<MergedSubtitle> = Vec::new();
+        while let Some(output) = merged.next().await {
+            for update in output.expect("merge output").updates {
+                subtitles.retain(|subtitle| subtitle.id != update.subtitle.id);
+                subtitles.push(update.subtitle);
+            }
+        }
+        sort_subtitles(&mut subtitles);
+
+        // Frames 0, 2, 4, ... are sampled; the first cue ends on frame 5, the last one before
+        // the band changes, and the second runs to the final frame.
+        assert_eq!(
+            render_frame_timings(&subtitles),
+            "index\tstart_ms\tend_ms\tstart_frame\tend_frame\ttext\n\
+             1\t0\t1250\t0\t5\tfirst\n\
+             2\t1500\t2750\t6\t11\tsecond\n"
+        );
+    }
+}
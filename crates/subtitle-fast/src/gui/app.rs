@@ -5,7 +5,7 @@ use gpui::*;
 use rust_embed::RustEmbed;
 use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use subtitle_fast_validator::subtitle_detection::SubtitleDetectorKind;
@@ -15,20 +15,22 @@ use crate::gui::components::{
     CollapseDirection, ColorPicker, ConfigWindow, ConfirmDialog, ConfirmDialogButton,
     ConfirmDialogButtonStyle, ConfirmDialogConfig, ConfirmDialogTitle, DetectedSubtitlesList,
     DetectionControls, DetectionHandle, DetectionMetrics, DetectionRunState, DetectionSidebar,
-    DetectionSidebarHost, DragRange, DraggableEdge, HelpWindow, Sidebar, SidebarConfig,
-    SidebarHandle, SubtitleEditorWindow, TaskSidebar, TaskSidebarCallbacks, Titlebar,
-    TitlebarActions, TitlebarActionsCallbacks, VideoControls, VideoLumaControls, VideoLumaHandle,
-    VideoPlayer, VideoPlayerControlHandle, VideoPlayerInfoHandle, VideoRoiHandle, VideoRoiOverlay,
-    VideoToolbar,
+    DetectionSidebarHost, DragRange, DraggableEdge, HelpWindow, OcrPreviewPanel, Sidebar,
+    SidebarConfig, SidebarHandle, SubtitleEditorWindow, TaskSidebar, TaskSidebarCallbacks,
+    Titlebar, TitlebarActions, TitlebarActionsCallbacks, VideoControls, VideoLumaControls,
+    VideoLumaHandle, VideoPlayer, VideoPlayerControlHandle, VideoPlayerInfoHandle, VideoRoiHandle,
+    VideoRoiOverlay, VideoToolbar,
 };
 use crate::gui::icons::{Icon, icon_md, icon_sm};
 use crate::gui::menus::{self, OpenSubtitleEditor};
 use crate::gui::runtime;
 use crate::gui::session::{SessionHandle, SessionId, VideoSession};
+use crate::watch::{SUPPORTED_VIDEO_EXTENSIONS, is_supported_video_path};
 
 #[derive(RustEmbed)]
 #[folder = "assets"]
 #[include = "icons/**/*.svg"]
+#[include = "fonts/**/*.ttf"]
 struct EmbeddedAssets;
 
 pub struct AppAssets;
@@ -293,9 +295,6 @@ fn resolve_overlay_detector_kind() -> Option<SubtitleDetectorKind> {
     Some(settings.detection.detector)
 }
 
-const SUPPORTED_VIDEO_EXTENSIONS: &[&str] = &[
-    "mp4", "mov", "mkv", "webm", "avi", "m4v", "mpg", "mpeg", "ts",
-];
 const VIDEO_AREA_HEIGHT_RATIO: f32 = 0.6;
 const SIDEBAR_DRAG_HIT_THICKNESS: f32 = 6.0;
 const SIDEBAR_BORDER_WIDTH: f32 = 1.1;
@@ -1068,6 +1067,7 @@ impl MainWindow {
     ) -> Entity<DetectionSidebar> {
         let detection_controls_view = cx.new(|_| DetectionControls::new(handle.clone()));
         let detection_metrics_view = cx.new(|_| DetectionMetrics::new(handle.clone()));
+        let ocr_preview_view = cx.new(|_| OcrPreviewPanel::new(handle.clone()));
         let detection_subtitles_view =
             cx.new(|_| DetectedSubtitlesList::new(handle.clone(), self.controls.clone()));
         cx.new(|_| {
@@ -1075,6 +1075,7 @@ impl MainWindow {
                 handle,
                 detection_metrics_view.clone(),
                 detection_controls_view.clone(),
+                ocr_preview_view,
                 detection_subtitles_view.clone(),
             )
         })
@@ -1379,15 +1380,6 @@ impl Render for MainWindow {
     }
 }
 
-fn is_supported_video_path(path: &Path) -> bool {
-    let Some(ext) = path.extension().and_then(|ext| ext.to_str()) else {
-        return false;
-    };
-    SUPPORTED_VIDEO_EXTENSIONS
-        .iter()
-        .any(|allowed| allowed.eq_ignore_ascii_case(ext))
-}
-
 fn supported_video_extensions_detail() -> String {
     let list = SUPPORTED_VIDEO_EXTENSIONS
         .iter()
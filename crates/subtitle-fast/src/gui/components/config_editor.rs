@@ -14,10 +14,10 @@ use crate::gui::components::Titlebar;
 use crate::gui::components::inputs::{InputKind, SelectInput, SelectOption, TextInput};
 use crate::gui::menus;
 use crate::settings::{
-    self, DecoderFileConfig, DetectionFileConfig, FileConfig, OcrFileConfig, OutputFileConfig,
+    self, DecoderFileConfig, DetectionFileConfig, DetectionSensitivity, FileConfig, OcrFileConfig,
     RoiFileConfig,
 };
-use subtitle_fast_comparator::Configuration as ComparatorConfiguration;
+use subtitle_fast_comparator::ComparatorKind;
 use subtitle_fast_decoder::Configuration as DecoderConfiguration;
 use subtitle_fast_ocr::Configuration as OcrConfiguration;
 use subtitle_fast_validator::subtitle_detection::Configuration as DetectorConfiguration;
@@ -30,7 +30,7 @@ pub struct ConfigWindow {
     fields: ConfigFields,
     scroll_handle: ScrollHandle,
     config_path: Option<PathBuf>,
-    output_path: Option<PathBuf>,
+    loaded_config: FileConfig,
     status: Option<StatusMessage>,
     field_errors: FieldErrors,
     autosave_enabled: bool,
@@ -82,7 +82,7 @@ impl ConfigWindow {
             fields,
             scroll_handle: ScrollHandle::new(),
             config_path: settings::resolve_gui_config_path(),
-            output_path: None,
+            loaded_config: FileConfig::default(),
             status: None,
             field_errors: FieldErrors::default(),
             autosave_enabled: false,
@@ -136,12 +136,12 @@ impl ConfigWindow {
         };
 
         self.config_path = Some(path.clone());
-        self.output_path = None;
+        self.loaded_config = FileConfig::default();
 
         let values = if path.exists() {
             match settings::load_file_config(&path) {
                 Ok(file) => {
-                    self.output_path = file.output.as_ref().and_then(|output| output.path.clone());
+                    self.loaded_config = file.clone();
                     ConfigValues::from_file(file)
                 }
                 Err(err) => {
@@ -169,6 +169,7 @@ impl ConfigWindow {
 
     fn build_config_from_values(values: &ConfigValues) -> Result<FileConfig, SharedString> {
         let detection_sps = parse_optional_u32("detection.samples_per_second", &values.sps)?;
+        let sensitivity = normalize_optional(&values.sensitivity);
         let detection_target = parse_optional_u8("detection.target", &values.target)?;
         let detection_delta = parse_optional_u8("detection.delta", &values.delta)?;
 
@@ -188,6 +189,7 @@ impl ConfigWindow {
         let ocr_backend = normalize_optional(&values.ocr_backend);
 
         let detection = if detection_sps.is_some()
+            || sensitivity.is_some()
             || detection_target.is_some()
             || detection_delta.is_some()
             || detector.is_some()
@@ -196,11 +198,13 @@ impl ConfigWindow {
         {
             Some(DetectionFileConfig {
                 samples_per_second: detection_sps,
+                sensitivity,
                 target: detection_target,
                 delta: detection_delta,
                 detector,
                 comparator,
                 roi,
+                ..DetectionFileConfig::default()
             })
         } else {
             None
@@ -210,6 +214,7 @@ impl ConfigWindow {
             Some(DecoderFileConfig {
                 backend: decoder_backend,
                 channel_capacity: decoder_channel_capacity,
+                ..DecoderFileConfig::default()
             })
         } else {
             None
@@ -218,6 +223,7 @@ impl ConfigWindow {
         let ocr = if ocr_backend.is_some() {
             Some(OcrFileConfig {
                 backend: ocr_backend,
+                ..OcrFileConfig::default()
             })
         } else {
             None
@@ -249,10 +255,14 @@ impl ConfigWindow {
 
         let values = self.fields.read_values(cx);
         let detector_changed = values.detector_backend != self.last_saved_values.detector_backend;
-        let mut config = Self::build_config_from_values(&values)?;
-        config.output = self.output_path.as_ref().map(|path| OutputFileConfig {
-            path: Some(path.clone()),
-        });
+        let config = Self::build_config_from_values(&values)?;
+        let config = retain_unedited_keys(&config, &self.loaded_config).map_err(|err| {
+            let message: SharedString = format!("Failed to serialize config: {err}").into();
+            if show_status {
+                self.set_status(message.clone(), true, cx);
+            }
+            message
+        })?;
 
         let Some(parent) = path.parent() else {
             let message: SharedString = "Config path has no parent directory".into();
@@ -351,6 +361,9 @@ impl ConfigWindow {
                 this.handle_autosave(cx);
             }));
         }
+        subscriptions.push(cx.observe(&self.fields.sensitivity, |this, _input, cx| {
+            this.handle_autosave(cx);
+        }));
         subscriptions.push(cx.observe(&self.fields.comparator, |this, _input, cx| {
             this.handle_autosave(cx);
         }));
@@ -445,6 +458,7 @@ impl ConfigWindow {
 
     fn close_open_selects(&mut self, cx: &mut Context<Self>) {
         let selects = [
+            self.fields.sensitivity.clone(),
             self.fields.comparator.clone(),
             self.fields.detector_backend.clone(),
             self.fields.decoder_backend.clone(),
@@ -460,6 +474,7 @@ impl ConfigWindow {
 
     fn has_open_select(&self, cx: &Context<Self>) -> bool {
         let selects = [
+            self.fields.sensitivity.clone(),
             self.fields.comparator.clone(),
             self.fields.detector_backend.clone(),
             self.fields.decoder_backend.clone(),
@@ -471,6 +486,7 @@ impl ConfigWindow {
     fn select_popup(&mut self, window: &Window, cx: &mut Context<Self>) -> Option<AnyElement> {
         self.select_popup_bounds = None;
         let selects = [
+            self.fields.sensitivity.clone(),
             self.fields.comparator.clone(),
             self.fields.detector_backend.clone(),
             self.fields.decoder_backend.clone(),
@@ -722,6 +738,9 @@ impl ConfigWindow {
                 self.fields.sps.clone(),
                 self.field_errors.sps.clone(),
             ))
+            .child(
+                self.render_select_field("Detection sensitivity", self.fields.sensitivity.clone()),
+            )
             .child(self.render_field(
                 "Detection target",
                 self.fields.target.clone(),
@@ -1001,6 +1020,7 @@ impl Render for ConfigWindow {
                     }
                     if !inside {
                         let selects = [
+                            this.fields.sensitivity.clone(),
                             this.fields.comparator.clone(),
                             this.fields.detector_backend.clone(),
                             this.fields.decoder_backend.clone(),
@@ -1062,6 +1082,7 @@ impl Render for ConfigWindow {
 #[derive(Clone, PartialEq)]
 struct ConfigValues {
     sps: SharedString,
+    sensitivity: SharedString,
     target: SharedString,
     delta: SharedString,
     detector_backend: SharedString,
@@ -1079,8 +1100,9 @@ impl ConfigValues {
     fn default_example() -> Self {
         Self {
             sps: "7".into(),
-            target: "230".into(),
-            delta: "12".into(),
+            sensitivity: "".into(),
+            target: "".into(),
+            delta: "".into(),
             detector_backend: "auto".into(),
             comparator: "".into(),
             roi_x: "0.15".into(),
@@ -1099,6 +1121,9 @@ impl ConfigValues {
             if let Some(sps) = det.samples_per_second {
                 values.sps = sps.to_string().into();
             }
+            if let Some(sensitivity) = det.sensitivity {
+                values.sensitivity = sensitivity.into();
+            }
             if let Some(target) = det.target {
                 values.target = target.to_string().into();
             }
@@ -1148,6 +1173,7 @@ impl ConfigValues {
 
 struct ConfigFields {
     sps: Entity<TextInput>,
+    sensitivity: Entity<SelectInput>,
     target: Entity<TextInput>,
     delta: Entity<TextInput>,
     detector_backend: Entity<SelectInput>,
@@ -1163,6 +1189,7 @@ struct ConfigFields {
 
 impl ConfigFields {
     fn new(cx: &mut Context<ConfigWindow>) -> Self {
+        let sensitivity_options = sensitivity_options();
         let comparator_options = comparator_options();
         let detector_backend_options = detector_backend_options();
         let decoder_backend_options = decoder_backend_options();
@@ -1170,6 +1197,7 @@ impl ConfigFields {
 
         Self {
             sps: cx.new(|cx| TextInput::new(cx, "7", InputKind::Integer)),
+            sensitivity: cx.new(|_| SelectInput::new(sensitivity_options, "")),
             target: cx.new(|cx| TextInput::new(cx, "230", InputKind::Integer)),
             delta: cx.new(|cx| TextInput::new(cx, "12", InputKind::Integer)),
             detector_backend: cx.new(|_| SelectInput::new(detector_backend_options, "auto")),
@@ -1197,6 +1225,7 @@ impl ConfigFields {
             };
 
         update(&self.sps, values.sps, cx);
+        update_select(&self.sensitivity, values.sensitivity, cx);
         update(&self.target, values.target, cx);
         update(&self.delta, values.delta, cx);
         update_select(&self.detector_backend, values.detector_backend, cx);
@@ -1219,6 +1248,7 @@ impl ConfigFields {
         let read_select = |input: &Entity<SelectInput>, cx: &App| input.read(cx).value();
         ConfigValues {
             sps: read(&self.sps, cx),
+            sensitivity: read_select(&self.sensitivity, cx),
             target: read(&self.target, cx),
             delta: read(&self.delta, cx),
             detector_backend: read_select(&self.detector_backend, cx),
@@ -1234,6 +1264,54 @@ impl ConfigFields {
     }
 }
 
+/// Keys the editor has a field for, by config section; every other key is carried over.
+const EDITOR_KEYS: &[(&str, &[&str])] = &[
+    (
+        "detection",
+        &[
+            "samples_per_second",
+            "sensitivity",
+            "target",
+            "delta",
+            "detector",
+            "comparator",
+            "roi",
+        ],
+    ),
+    ("decoder", &["backend", "channel_capacity"]),
+    ("ocr", &["backend"]),
+];
+
+/// Carry over keys the editor has no field for so saving does not drop them.
+fn retain_unedited_keys(
+    config: &FileConfig,
+    loaded: &FileConfig,
+) -> Result<toml::Table, toml::ser::Error> {
+    let mut merged = config_table(loaded)?;
+    for (section, keys) in EDITOR_KEYS {
+        if let Some(toml::Value::Table(table)) = merged.get_mut(*section) {
+            table.retain(|key, _| !keys.contains(&key));
+        }
+    }
+    for (section, value) in config_table(config)? {
+        match (merged.get_mut(&section), value) {
+            (Some(toml::Value::Table(table)), toml::Value::Table(edited)) => table.extend(edited),
+            (_, value) => {
+                merged.insert(section, value);
+            }
+        }
+    }
+    merged.retain(|_, value| !matches!(value, toml::Value::Table(table) if table.is_empty()));
+    Ok(merged)
+}
+
+fn config_table(config: &FileConfig) -> Result<toml::Table, toml::ser::Error> {
+    match toml::Value::try_from(config)? {
+        toml::Value::Table(table) => Ok(table),
+        _ => Ok(toml::Table::new()),
+    }
+}
+
 fn decoder_backend_options() -> Vec<SelectOption> {
     let mut options = vec![SelectOption::new("auto", "")];
     let available = DecoderConfiguration::available_backends();
@@ -1250,7 +1328,7 @@ fn decoder_backend_options() -> Vec<SelectOption> {
 fn detector_backend_options() -> Vec<SelectOption> {
     let mut options = vec![SelectOption::new("auto", "auto")];
     let mut available = DetectorConfiguration::available_backends();
-    let order = ["projection-band", "integral-band", "macos-vision"];
+    let order = ["projection-band", "integral-band", "macos-vision", "fusion"];
     available.sort_by_key(|backend| {
         order
             .iter()
@@ -1281,11 +1359,23 @@ fn ocr_backend_options() -> Vec<SelectOption> {
     options
 }
 
+fn sensitivity_options() -> Vec<SelectOption> {
+    let mut options = vec![SelectOption::new("default", "")];
+    for sensitivity in [
+        DetectionSensitivity::Low,
+        DetectionSensitivity::Medium,
+        DetectionSensitivity::High,
+    ] {
+        let name = sensitivity.as_str();
+        options.push(SelectOption::new(name, name));
+    }
+    options
+}
+
 fn comparator_options() -> Vec<SelectOption> {
     let mut options = vec![SelectOption::new("auto", "")];
-    let available = ComparatorConfiguration::available_backends();
-    for backend in available {
-        let name = backend.as_str();
+    for kind in ComparatorKind::all() {
+        let name = kind.as_str();
         options.push(SelectOption::new(name, name));
     }
     options
@@ -1434,3 +1524,44 @@ enum ButtonStyle {
     Primary,
     Secondary,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saving_keeps_keys_the_editor_has_no_field_for() {
+        let loaded: FileConfig = toml::from_str(
+            r#"
+            [detection]
+            target = 200
+            warmup_frames = 3
+            roi = { x = 0.1, y = 0.8, width = 0.8, height = 0.2 }
+
+            [output]
+            min_cue_gap_ms = 40
+            "#,
+        )
+        .expect("config");
+        let edited = FileConfig {
+            detection: Some(DetectionFileConfig {
+                delta: Some(12),
+                ..DetectionFileConfig::default()
+            }),
+            ..FileConfig::default()
+        };
+
+        let merged = retain_unedited_keys(&edited, &loaded).expect("merge");
+        let detection = merged["detection"].as_table().expect("detection table");
+        assert_eq!(detection.get("delta"), Some(&toml::Value::Integer(12)));
+        assert_eq!(
+            detection.get("warmup_frames"),
+            Some(&toml::Value::Integer(3))
+        );
+        // Cleared editor fields are dropped rather than restored from the loaded file.
+        assert!(detection.get("target").is_none());
+        assert!(detection.get("roi").is_none());
+        assert_eq!(merged["output"]["min_cue_gap_ms"], toml::Value::Integer(40));
+        assert!(!merged.contains_key("ocr"));
+    }
+}
@@ -3,6 +3,7 @@ use gpui::{Context, Entity, Render, Window};
 
 use super::{
     DetectedSubtitlesList, DetectionControls, DetectionHandle, DetectionMetrics, DetectionSidebar,
+    OcrPreviewPanel,
 };
 
 pub struct DetectionSidebarHost {
@@ -19,12 +20,14 @@ impl DetectionSidebarHost {
     fn build_sidebar(handle: DetectionHandle, cx: &mut Context<Self>) -> Entity<DetectionSidebar> {
         let detection_controls_view = cx.new(|_| DetectionControls::new(handle.clone()));
         let detection_metrics_view = cx.new(|_| DetectionMetrics::new(handle.clone()));
+        let ocr_preview_view = cx.new(|_| OcrPreviewPanel::new(handle.clone()));
         let detection_subtitles_view = cx.new(|_| DetectedSubtitlesList::new(handle.clone(), None));
         cx.new(|_| {
             DetectionSidebar::new(
                 handle,
                 detection_metrics_view,
                 detection_controls_view,
+                ocr_preview_view,
                 detection_subtitles_view,
             )
         })
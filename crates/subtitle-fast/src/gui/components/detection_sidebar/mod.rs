@@ -1,5 +1,7 @@
+use std::collections::HashSet;
 use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
@@ -7,35 +9,46 @@ use futures_channel::mpsc::{UnboundedReceiver, UnboundedSender, unbounded};
 use futures_util::StreamExt;
 use tokio::sync::{oneshot, watch};
 
+use crate::backend;
+use crate::edit_audit::EditAudit;
 use crate::gui::components::{VideoLumaHandle, VideoRoiHandle};
 use crate::gui::runtime;
+use crate::output_format::{OutputFormat, UnknownOutputFormat};
 use crate::settings::{
-    DecoderSettings, DetectionSettings, EffectiveSettings, OcrSettings, OutputSettings,
+    ColorRange, DecoderSettings, DetectionSettings, EffectiveSettings, OcrSettings, OutputSettings,
+    RoiUnits,
 };
+use crate::stage::ocr::{OcrPreview, OcrPreviewSender};
 use crate::stage::{
-    self, MergedSubtitle, PipelineConfig, PipelineHandle, PipelineProgress, SubtitleLine,
-    SubtitleUpdate, SubtitleUpdateKind, TimedSubtitle,
+    self, CueLayout, MergedSubtitle, PipelineConfig, PipelineHandle, PipelineProgress,
+    SubtitleLine, SubtitleUpdate, SubtitleUpdateKind, TimedSubtitle,
 };
+use crate::subtitle_buffer::SubtitleBuffer;
 use subtitle_fast_decoder::{Backend, Configuration};
 use subtitle_fast_types::{DecoderError, RoiConfig};
 use subtitle_fast_validator::subtitle_detection::{
-    DEFAULT_DELTA, DEFAULT_TARGET, SubtitleDetectorKind,
+    DEFAULT_AUTO_DETECTOR_PRIORITY, DEFAULT_DELTA, DEFAULT_LINE_VALLEY_DEPTH, DEFAULT_TARGET,
+    FusionPolicy, RoiRounding, SubtitleDetectorKind,
 };
 
 pub mod controls;
 pub mod host;
 pub mod metrics;
 pub mod panel;
+pub mod preview;
 pub mod subtitles;
 
 pub use controls::DetectionControls;
 pub use host::DetectionSidebarHost;
 pub use metrics::DetectionMetrics;
 pub use panel::DetectionSidebar;
+pub use preview::OcrPreviewPanel;
 pub use subtitles::DetectedSubtitlesList;
 
 const DEFAULT_SAMPLES_PER_SECOND: u32 = 7;
 
+static SPILL_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum DetectionRunState {
     Idle,
@@ -59,6 +72,8 @@ pub enum SubtitleMessage {
     New(TimedSubtitle),
     Updated(TimedSubtitle),
     Removed(u64),
+    /// Cues moved out of memory by the resident cue limit; they remain in snapshots and exports.
+    Flushed(Vec<u64>),
 }
 
 /// Editable subtitle fields for the editor window.
@@ -79,18 +94,23 @@ impl DetectionHandle {
     pub fn new() -> Self {
         let (state_tx, state_rx) = watch::channel(DetectionRunState::Idle);
         let (progress_tx, progress_rx) = watch::channel(PipelineProgress::default());
+        // Receivers come from `subscribe_ocr_preview` only, so the OCR stage copies no crops
+        // until a preview panel is switched on.
+        let (ocr_preview_tx, _) = watch::channel(None);
         let inner = Arc::new(DetectionPipelineInner {
             state_tx,
             state_rx,
             pause_handle: Mutex::new(None),
             progress_tx,
             progress_rx,
+            ocr_preview_tx,
             video_path: Mutex::new(None),
             luma_handle: Mutex::new(None),
             roi_handle: Mutex::new(None),
             cancel_tx: Mutex::new(None),
             subtitle_subscribers: Mutex::new(Vec::new()),
-            subtitles: Mutex::new(Vec::new()),
+            subtitles: Mutex::new(SubtitleBuffer::unbounded()),
+            edit_audit: Mutex::new(EditAudit::new()),
         });
         Self { inner }
     }
@@ -119,6 +139,12 @@ impl DetectionHandle {
         self.inner.progress_snapshot()
     }
 
+    /// Latest OCR crop and text of the running detection; the stage publishes them only
+    /// while a receiver returned here is alive.
+    pub fn subscribe_ocr_preview(&self) -> watch::Receiver<Option<OcrPreview>> {
+        self.inner.ocr_preview_tx.subscribe()
+    }
+
     pub fn run_state(&self) -> DetectionRunState {
         self.inner.run_state()
     }
@@ -159,12 +185,35 @@ impl DetectionHandle {
         self.inner.remove_subtitle(id)
     }
 
+    /// Ends the cue `id` at `at_ms` and inserts a cue with the same lines starting there,
+    /// returning the new cue's id.
+    pub(crate) fn split_subtitle(&self, id: u64, at_ms: f64) -> Result<u64, String> {
+        self.inner.split_subtitle(id, at_ms)
+    }
+
+    /// Folds the cue following `id` in presentation order into it and removes the follower,
+    /// returning the removed cue's id.
+    pub(crate) fn merge_with_next(&self, id: u64) -> Result<u64, String> {
+        self.inner.merge_with_next(id)
+    }
+
     pub fn export_dialog_seed(&self) -> (PathBuf, Option<String>) {
         self.inner.export_dialog_seed()
     }
 
-    pub fn export_subtitles_to(&self, path: PathBuf) {
-        self.inner.export_subtitles_to(path);
+    /// Starts writing every cue to `path`; fails without writing when there is nothing to
+    /// export or the extension names no known format.
+    pub fn export_subtitles_to(&self, path: PathBuf) -> Result<(), String> {
+        self.inner.export_subtitles_to(path)
+    }
+
+    /// Exports only cues edited this run, plus `drafted` cues with unapplied changes.
+    pub fn export_edited_subtitles_to(
+        &self,
+        path: PathBuf,
+        drafted: &HashSet<u64>,
+    ) -> Result<(), String> {
+        self.inner.export_edited_subtitles_to(path, drafted)
     }
 }
 
@@ -180,12 +229,15 @@ struct DetectionPipelineInner {
     pause_handle: Mutex<Option<PipelineHandle>>,
     progress_tx: watch::Sender<PipelineProgress>,
     progress_rx: watch::Receiver<PipelineProgress>,
+    ocr_preview_tx: OcrPreviewSender,
     video_path: Mutex<Option<PathBuf>>,
     luma_handle: Mutex<Option<VideoLumaHandle>>,
     roi_handle: Mutex<Option<VideoRoiHandle>>,
     cancel_tx: Mutex<Option<oneshot::Sender<()>>>,
     subtitle_subscribers: Mutex<Vec<UnboundedSender<SubtitleMessage>>>,
-    subtitles: Mutex<Vec<MergedSubtitle>>,
+    subtitles: Mutex<SubtitleBuffer>,
+    /// Manual edits of the current run's cues, exported next to the SRT.
+    edit_audit: Mutex<EditAudit>,
 }
 
 impl DetectionPipelineInner {
@@ -255,9 +307,43 @@ impl DetectionPipelineInner {
                     decoder: DecoderSettings {
                         backend: None,
                         channel_capacity: None,
+                        error_tolerance: 0,
+                        color_range: ColorRange::Auto,
+                        init_retries: 1,
+                        init_retry_backoff: Duration::from_millis(200),
+                    },
+                    ocr: OcrSettings {
+                        backend: None,
+                        text_direction: None,
+                        dynamic_input_width: false,
+                        input_height: None,
+                        input_width: None,
+                        placeholder: false,
+                        concurrency: 1,
+                        normalize_nfc: true,
+                        min_cue_chars: 0,
+                        confidence_decay: 0.0,
+                        empty_hold: 0,
+                        upscale_retry_below: None,
+                        input_padding: None,
+                        preserve_blank_tokens: false,
+                        blank_index: 0,
+                        decode: subtitle_fast_ocr::DecodeMode::Greedy,
+                        max_crop_pixels: stage::ocr::DEFAULT_MAX_CROP_PIXELS,
+                        max_concurrent_downloads: crate::model::DEFAULT_MAX_CONCURRENT_DOWNLOADS,
+                    },
+                    output: OutputSettings {
+                        path: None,
+                        format: OutputFormat::default(),
+                        gap_markers_ms: None,
+                        min_cue_gap_ms: None,
+                        wrap_width: None,
+                        split_tracks: false,
+                        resident_cue_limit: None,
+                        emit_metadata: false,
+                        timing_frames: false,
+                        encoding: Default::default(),
                     },
-                    ocr: OcrSettings { backend: None },
-                    output: OutputSettings { path: None },
                 }
             }
         };
@@ -265,13 +351,16 @@ impl DetectionPipelineInner {
         settings.detection.target = target;
         settings.detection.delta = delta;
         settings.detection.roi = Some(roi);
-        let plan = match build_detection_plan(&path, &settings) {
+        settings.detection.roi_units = RoiUnits::Fraction;
+        let mut plan = match build_detection_plan(&path, &settings) {
             Ok(plan) => plan,
             Err(err) => {
                 eprintln!("detection start failed: {err}");
                 return self.run_state();
             }
         };
+        self.ocr_preview_tx.send_replace(None);
+        plan.pipeline.ocr.preview = Some(self.ocr_preview_tx.clone());
 
         let (cancel_tx, cancel_rx) = oneshot::channel();
 
@@ -286,7 +375,7 @@ impl DetectionPipelineInner {
             *slot = Some(cancel_tx);
         }
 
-        self.reset_subtitles();
+        self.replace_subtitles(subtitle_buffer(settings.output.resident_cue_limit));
         self.update_progress(PipelineProgress::default());
         let _ = self.state_tx.send(DetectionRunState::Running);
         DetectionRunState::Running
@@ -375,19 +464,13 @@ impl DetectionPipelineInner {
 
         if let Ok(mut slot) = self.subtitles.lock() {
             for update in updates {
-                match update.kind {
-                    SubtitleUpdateKind::New => slot.push(update.subtitle.clone()),
-                    SubtitleUpdateKind::Updated => {
-                        if let Some(existing) = slot
-                            .iter_mut()
-                            .find(|subtitle| subtitle.id == update.subtitle.id)
-                        {
-                            *existing = update.subtitle.clone();
-                        } else {
-                            slot.push(update.subtitle.clone());
-                        }
+                let flushed = match slot.apply(update) {
+                    Ok(flushed) => flushed,
+                    Err(err) => {
+                        eprintln!("failed to spill subtitles to disk: {err}");
+                        Vec::new()
                     }
-                }
+                };
 
                 let timed = update.subtitle.as_timed();
                 let message = match update.kind {
@@ -395,22 +478,61 @@ impl DetectionPipelineInner {
                     SubtitleUpdateKind::Updated => SubtitleMessage::Updated(timed),
                 };
                 self.send_subtitle_message(message);
+                if !flushed.is_empty() {
+                    self.send_subtitle_message(SubtitleMessage::Flushed(flushed));
+                }
             }
         }
     }
 
     fn current_detection_settings(&self) -> DetectionSettings {
         let (target, delta, roi) = self.current_detection_overrides();
-        let detector = crate::settings::resolve_gui_settings()
+        let resolved = crate::settings::resolve_gui_settings().ok();
+        let detector = resolved
+            .as_ref()
             .map(|settings| settings.detection.detector)
             .unwrap_or(SubtitleDetectorKind::ProjectionBand);
+        let detector_priority = resolved
+            .as_ref()
+            .map(|settings| settings.detection.detector_priority.clone())
+            .unwrap_or_else(|| DEFAULT_AUTO_DETECTOR_PRIORITY.to_vec());
+        let min_region = resolved
+            .map(|settings| settings.detection.min_region)
+            .unwrap_or_default();
         DetectionSettings {
             samples_per_second: DEFAULT_SAMPLES_PER_SECOND,
             target,
             delta,
             detector,
+            detector_priority,
             comparator: None,
+            comparator_time_decay: 0.0,
             roi: Some(roi),
+            roi_units: RoiUnits::Fraction,
+            max_in_flight: None,
+            bridge_gap_ms: 0,
+            black_gap_bridge_ms: 0,
+            roi_clip_margin: 0.0,
+            roi_rounding: RoiRounding::Outward,
+            roi_half_pixel: false,
+            min_detection_confidence: 0.0,
+            fusion_policy: FusionPolicy::Intersection,
+            shear_compensation: None,
+            split_lines: false,
+            line_valley_depth: DEFAULT_LINE_VALLEY_DEPTH,
+            min_region,
+            max_region_size_change: None,
+            region_smoothing: 0.0,
+            max_regions_per_frame: None,
+            full_width_guard: None,
+            fixed_regions: Vec::new(),
+            exclusion_zones: Vec::new(),
+            exclusion_overlap: stage::exclusion::DEFAULT_EXCLUSION_OVERLAP,
+            frame_rotation_deg: 0.0,
+            band_softness: 0,
+            skip_intro: Duration::ZERO,
+            warmup_frames: 0,
+            flush_at_end: true,
         }
     }
 
@@ -471,14 +593,22 @@ impl DetectionPipelineInner {
         let lines = build_subtitle_lines(edit.lines);
 
         let timed = if let Ok(mut slot) = self.subtitles.lock() {
-            if let Some(existing) = slot.iter_mut().find(|subtitle| subtitle.id == edit.id) {
-                existing.start_time = start_time;
-                existing.end_time = end_time;
-                existing.lines = lines;
-                existing.as_timed()
-            } else {
+            let mut before = None;
+            let updated = slot
+                .update(edit.id, |existing| {
+                    before = Some(existing.clone());
+                    existing.start_time = start_time;
+                    existing.end_time = end_time;
+                    existing.lines = lines;
+                })
+                .map_err(|err| format!("Subtitle store unavailable: {err}"))?;
+            let (Some(before), Some(subtitle)) = (before, updated) else {
                 return Err("Subtitle not found.".to_string());
+            };
+            if let Ok(mut audit) = self.edit_audit.lock() {
+                audit.record(&before, &subtitle);
             }
+            subtitle.as_timed()
         } else {
             return Err("Subtitle store unavailable.".to_string());
         };
@@ -490,18 +620,17 @@ impl DetectionPipelineInner {
 
     fn remove_subtitle(&self, id: u64) -> Result<(), String> {
         let removed = if let Ok(mut slot) = self.subtitles.lock() {
-            if let Some(index) = slot.iter().position(|subtitle| subtitle.id == id) {
-                slot.remove(index);
-                true
-            } else {
-                false
-            }
+            slot.remove(id)
+                .map_err(|err| format!("Subtitle store unavailable: {err}"))?
         } else {
             return Err("Subtitle store unavailable.".to_string());
         };
 
-        if !removed {
+        let Some(removed) = removed else {
             return Err("Subtitle not found.".to_string());
+        };
+        if let Ok(mut audit) = self.edit_audit.lock() {
+            audit.record_removal(&removed);
         }
 
         self.send_subtitle_message(SubtitleMessage::Removed(id));
@@ -509,26 +638,136 @@ impl DetectionPipelineInner {
         Ok(())
     }
 
+    fn split_subtitle(&self, id: u64, at_ms: f64) -> Result<u64, String> {
+        if !at_ms.is_finite() || at_ms < 0.0 {
+            return Err("Split time must be finite and positive.".to_string());
+        }
+        if self.run_state() != DetectionRunState::Idle {
+            return Err("Wait for detection to finish before splitting subtitles.".to_string());
+        }
+
+        let at = Duration::from_secs_f64(at_ms / 1000.0);
+        let (before, first, second, flushed) = if let Ok(mut slot) = self.subtitles.lock() {
+            let before = slot
+                .get(id)
+                .map_err(|err| format!("Subtitle store unavailable: {err}"))?;
+            let Some(before) = before else {
+                return Err("Subtitle not found.".to_string());
+            };
+            let Some((first, mut second)) = stage::split_subtitle(&before, at) else {
+                return Err("Move the preview inside the subtitle to split it.".to_string());
+            };
+            second.id = slot.next_id();
+
+            // Store the second half before shortening the original, and take it back out if
+            // either step fails, so an I/O error never loses part of the cue.
+            let stored = slot.insert(second.clone()).and_then(|flushed| {
+                slot.update(id, |existing| *existing = first.clone())
+                    .map(|_| flushed)
+            });
+            let flushed = match stored {
+                Ok(flushed) => flushed,
+                Err(err) => {
+                    let _ = slot.remove(second.id);
+                    return Err(format!("Subtitle store unavailable: {err}"));
+                }
+            };
+            (before, first, second, flushed)
+        } else {
+            return Err("Subtitle store unavailable.".to_string());
+        };
+
+        self.send_subtitle_message(SubtitleMessage::Updated(first.as_timed()));
+        self.send_subtitle_message(SubtitleMessage::New(second.as_timed()));
+        if !flushed.is_empty() {
+            self.send_subtitle_message(SubtitleMessage::Flushed(flushed));
+        }
+        if let Ok(mut audit) = self.edit_audit.lock() {
+            audit.record(&before, &first);
+            audit.record(&before, &second);
+        }
+
+        Ok(second.id)
+    }
+
+    fn merge_with_next(&self, id: u64) -> Result<u64, String> {
+        if self.run_state() != DetectionRunState::Idle {
+            return Err("Wait for detection to finish before merging subtitles.".to_string());
+        }
+
+        let subtitles = self.all_subtitles();
+        let Some(index) = subtitles.iter().position(|subtitle| subtitle.id == id) else {
+            return Err("Subtitle not found.".to_string());
+        };
+        let Some(next) = subtitles.get(index + 1) else {
+            return Err("There is no following subtitle to merge with.".to_string());
+        };
+        let before = &subtitles[index];
+        let merged = stage::merge_subtitles(before, next);
+
+        let updated = if let Ok(mut slot) = self.subtitles.lock() {
+            slot.update(id, |existing| *existing = merged.clone())
+                .map_err(|err| format!("Subtitle store unavailable: {err}"))?
+        } else {
+            return Err("Subtitle store unavailable.".to_string());
+        };
+        let Some(merged) = updated else {
+            return Err("Subtitle not found.".to_string());
+        };
+        if let Ok(mut audit) = self.edit_audit.lock() {
+            audit.record(before, &merged);
+        }
+        self.send_subtitle_message(SubtitleMessage::Updated(merged.as_timed()));
+        self.remove_subtitle(next.id)?;
+
+        Ok(next.id)
+    }
+
     fn subtitles_snapshot(&self) -> Vec<TimedSubtitle> {
-        let mut snapshot = self
-            .subtitles
-            .lock()
-            .map(|slot| slot.clone())
-            .unwrap_or_default();
-        stage::sort_subtitles(&mut snapshot);
-        snapshot
+        self.all_subtitles()
             .into_iter()
             .map(|subtitle| subtitle.as_timed())
             .collect()
     }
 
+    /// Every detected cue in presentation order, reloading any spilled to disk.
+    fn all_subtitles(&self) -> Vec<MergedSubtitle> {
+        let Ok(slot) = self.subtitles.lock() else {
+            return Vec::new();
+        };
+        match slot.all() {
+            Ok(subtitles) => subtitles,
+            Err(err) => {
+                eprintln!("failed to reload spilled subtitles: {err}");
+                let mut resident = slot.resident().to_vec();
+                stage::sort_subtitles(&mut resident);
+                resident
+            }
+        }
+    }
+
     fn reset_subtitles(&self) {
         if let Ok(mut slot) = self.subtitles.lock() {
             slot.clear();
         }
+        self.clear_edit_audit();
         self.send_subtitle_message(SubtitleMessage::Reset);
     }
 
+    fn replace_subtitles(&self, buffer: SubtitleBuffer) {
+        if let Ok(mut slot) = self.subtitles.lock() {
+            *slot = buffer;
+        }
+        self.clear_edit_audit();
+        self.send_subtitle_message(SubtitleMessage::Reset);
+    }
+
+    fn clear_edit_audit(&self) {
+        if let Ok(mut audit) = self.edit_audit.lock() {
+            audit.clear();
+        }
+    }
+
     fn send_subtitle_message(&self, message: SubtitleMessage) {
         if let Ok(mut slots) = self.subtitle_subscribers.lock() {
             slots.retain(|sender| sender.unbounded_send(message.clone()).is_ok());
@@ -554,34 +793,133 @@ impl DetectionPipelineInner {
         (directory, Some("subtitles.srt".to_string()))
     }
 
-    fn export_subtitles_to(&self, path: PathBuf) {
-        let subtitles = self
-            .subtitles
-            .lock()
-            .map(|slot| slot.clone())
-            .unwrap_or_default();
-        if subtitles.is_empty() {
-            eprintln!("export ignored: no subtitles detected");
-            return;
+    fn export_subtitles_to(&self, path: PathBuf) -> Result<(), String> {
+        let (path, format) = export_target(path).map_err(|err| err.to_string())?;
+        let mut ordered = self.all_subtitles();
+        if ordered.is_empty() {
+            return Err("No subtitles detected.".to_string());
         }
 
-        let mut ordered = subtitles;
-        stage::sort_subtitles(&mut ordered);
-        let contents = stage::render_srt(&ordered);
+        export_layout().apply(&mut ordered);
+        let contents = format.render(&ordered);
+        let audit = self
+            .edit_audit
+            .lock()
+            .ok()
+            .filter(|audit| !audit.is_empty())
+            .map(|audit| audit.render_json());
         let task = runtime::spawn(async move {
             if let Err(err) = tokio::fs::write(&path, contents).await {
                 eprintln!("subtitle export failed: {err}");
-            } else {
-                eprintln!("exported subtitles to {}", path.display());
+                return;
+            }
+            eprintln!("exported subtitles to {}", path.display());
+            if let Some(audit) = audit {
+                let audit_path = edit_audit_path(&path);
+                match tokio::fs::write(&audit_path, audit).await {
+                    Ok(()) => eprintln!("exported edit history to {}", audit_path.display()),
+                    Err(err) => eprintln!("edit history export failed: {err}"),
+                }
+            }
+        });
+
+        if task.is_none() {
+            return Err("Subtitle export failed: tokio runtime not initialized.".to_string());
+        }
+        Ok(())
+    }
+
+    fn export_edited_subtitles_to(
+        &self,
+        path: PathBuf,
+        drafted: &HashSet<u64>,
+    ) -> Result<(), String> {
+        let (path, format) = export_target(path).map_err(|err| err.to_string())?;
+        let Some((contents, count)) =
+            self.render_edited_subtitles(format, export_layout(), drafted)
+        else {
+            return Err("No subtitles have been edited.".to_string());
+        };
+        let task = runtime::spawn(async move {
+            match tokio::fs::write(&path, contents).await {
+                Ok(()) => eprintln!("exported {count} edited subtitle(s) to {}", path.display()),
+                Err(err) => eprintln!("subtitle export failed: {err}"),
             }
         });
 
         if task.is_none() {
-            eprintln!("subtitle export failed: tokio runtime not initialized");
+            return Err("Subtitle export failed: tokio runtime not initialized.".to_string());
+        }
+        Ok(())
+    }
+
+    /// Cues edited this run plus `drafted` ones rendered as `format`, with how many were
+    /// edited; `None` when there are none. `layout` is applied to the whole track first, so
+    /// edited cues keep the gap to their unedited neighbours.
+    fn render_edited_subtitles(
+        &self,
+        format: OutputFormat,
+        layout: CueLayout,
+        drafted: &HashSet<u64>,
+    ) -> Option<(String, usize)> {
+        let mut ids = self
+            .edit_audit
+            .lock()
+            .map(|audit| audit.edited_ids())
+            .unwrap_or_default();
+        ids.extend(drafted);
+        if ids.is_empty() {
+            return None;
+        }
+        let mut subtitles = self.all_subtitles();
+        layout.apply(&mut subtitles);
+        let contents = format.render_only(&subtitles, &ids);
+        Some((contents, ids.len()))
+    }
+}
+
+/// Where and how to export for a path picked in the save dialog: the format its extension
+/// names. A path without an extension gains `.srt`; any other extension is an error.
+fn export_target(path: PathBuf) -> Result<(PathBuf, OutputFormat), UnknownOutputFormat> {
+    if path.extension().is_none() {
+        return Ok((
+            path.with_extension(OutputFormat::Srt.as_str()),
+            OutputFormat::Srt,
+        ));
+    }
+    let format = OutputFormat::from_path(&path)?;
+    Ok((path, format))
+}
+
+/// Cue layout from the `[output]` config, so an export matches what a CLI run writes.
+fn export_layout() -> CueLayout {
+    match crate::settings::resolve_gui_settings() {
+        Ok(settings) => settings.output.cue_layout(),
+        Err(err) => {
+            eprintln!("failed to load config settings: {err}");
+            CueLayout::default()
         }
     }
 }
 
+fn edit_audit_path(output_path: &Path) -> PathBuf {
+    let mut path = output_path.to_path_buf();
+    path.set_extension("edits.json");
+    path
+}
+
+fn subtitle_buffer(resident_cue_limit: Option<usize>) -> SubtitleBuffer {
+    let Some(limit) = resident_cue_limit.and_then(NonZeroUsize::new) else {
+        return SubtitleBuffer::unbounded();
+    };
+    let serial = SPILL_FILE_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let path = std::env::temp_dir().join(format!(
+        "subtitle-fast-{}-{serial}.cues.jsonl",
+        std::process::id()
+    ));
+    SubtitleBuffer::bounded(limit, path)
+}
+
 fn build_subtitle_lines(lines: Vec<String>) -> Vec<SubtitleLine> {
     let cleaned: Vec<String> = lines
         .into_iter()
@@ -599,6 +937,9 @@ fn build_subtitle_lines(lines: Vec<String>) -> Vec<SubtitleLine> {
         .map(|(idx, text)| SubtitleLine {
             center: (idx as f32 + 0.5) * step,
             text,
+            rtl: false,
+            confidence: None,
+            region: None,
         })
         .collect()
 }
@@ -638,7 +979,13 @@ async fn run_detection_task(
         }
 
         let provider_started = Instant::now();
-        let provider_result = attempt_config.create_provider();
+        let provider_result = backend::create_with_retry(
+            &attempt_config,
+            pipeline.decoder_init_retries,
+            pipeline.decoder_init_backoff,
+            Configuration::create_provider,
+        )
+        .await;
         let provider_elapsed = provider_started.elapsed();
 
         let provider = match provider_result {
@@ -762,7 +1109,7 @@ fn build_detection_plan(
         )));
     }
 
-    let pipeline = PipelineConfig::from_settings(settings, input)?;
+    let mut pipeline = PipelineConfig::from_settings(settings, input)?;
 
     let env_backend_present = std::env::var("SUBFAST_BACKEND").is_ok();
     let mut config = Configuration::from_env().unwrap_or_default();
@@ -772,9 +1119,10 @@ fn build_detection_plan(
     };
     let backend_locked = backend_override.is_some() || env_backend_present;
     if let Some(backend_value) = backend_override {
-        config.backend = backend_value;
+        config = config.with_backend(backend_value)?;
     }
     config.input = Some(input.to_path_buf());
+    pipeline.decoder_start_frame = config.start_frame;
     if let Some(capacity) = settings.decoder.channel_capacity
         && let Some(non_zero) = NonZeroUsize::new(capacity)
     {
@@ -814,3 +1162,123 @@ struct DetectionPlan {
     backend_locked: bool,
     pipeline: PipelineConfig,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export_target_adds_srt_only_without_an_extension() {
+        assert_eq!(
+            export_target(PathBuf::from("movie.vtt")).expect("vtt"),
+            (PathBuf::from("movie.vtt"), OutputFormat::Vtt)
+        );
+        assert_eq!(
+            export_target(PathBuf::from("movie")).expect("no extension"),
+            (PathBuf::from("movie.srt"), OutputFormat::Srt)
+        );
+        let err = export_target(PathBuf::from("movie.txt")).expect_err("txt");
+        assert_eq!(err.0, "txt");
+    }
+
+    fn stored_cue(handle: &DetectionHandle, text: &str, start_ms: u64) -> u64 {
+        let mut slot = handle.inner.subtitles.lock().expect("subtitles");
+        let cue = MergedSubtitle {
+            id: slot.next_id(),
+            start_time: Duration::from_millis(start_ms),
+            end_time: Duration::from_millis(start_ms + 1000),
+            start_frame: 0,
+            end_frame: 0,
+            lines: build_subtitle_lines(vec![text.to_string()]),
+        };
+        let id = cue.id;
+        slot.insert(cue).expect("insert");
+        id
+    }
+
+    #[test]
+    fn handle_edits_and_removals_are_audited() {
+        let handle = DetectionHandle::new();
+        let kept = stored_cue(&handle, "Helo", 1000);
+        let dropped = stored_cue(&handle, "stray glyph", 3000);
+
+        handle
+            .update_subtitle(SubtitleEdit {
+                id: kept,
+                start_ms: 1100.0,
+                end_ms: 2500.0,
+                lines: vec!["Hello".to_string()],
+            })
+            .expect("update");
+        handle.remove_subtitle(dropped).expect("remove");
+
+        let audit = handle.inner.edit_audit.lock().expect("audit");
+        let entries = audit.entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].id, kept);
+        assert_eq!(entries[0].before.lines, ["Helo"]);
+        let after = entries[0].after.as_ref().expect("edited cue");
+        assert_eq!((after.start_ms, after.end_ms), (1100.0, 2500.0));
+        assert_eq!(after.lines, ["Hello"]);
+        assert_eq!(entries[1].id, dropped);
+        assert_eq!(entries[1].before.lines, ["stray glyph"]);
+        assert!(entries[1].after.is_none());
+    }
+
+    #[test]
+    fn edited_export_holds_only_the_edited_cues() {
+        let handle = DetectionHandle::new();
+        let ids: Vec<u64> = (0..5)
+            .map(|index| stored_cue(&handle, &format!("cue {index}"), index * 2000))
+            .collect();
+        for id in [ids[1], ids[3]] {
+            handle
+                .update_subtitle(SubtitleEdit {
+                    id,
+                    start_ms: (id * 2000) as f64,
+                    end_ms: (id * 2000 + 1000) as f64,
+                    lines: vec![format!("fixed {id}")],
+                })
+                .expect("update");
+        }
+
+        let (srt, count) = handle
+            .inner
+            .render_edited_subtitles(OutputFormat::Srt, CueLayout::default(), &HashSet::new())
+            .expect("edited cues");
+        assert_eq!(count, 2);
+        assert!(srt.starts_with("2\n"), "{srt}");
+        assert!(srt.contains("fixed 1") && srt.contains("fixed 3"), "{srt}");
+        assert!(!srt.contains("cue "), "{srt}");
+
+        // The configured output layout reaches the export too.
+        let layout = CueLayout {
+            min_cue_gap: None,
+            wrap_width: Some(5),
+        };
+        let (wrapped, _) = handle
+            .inner
+            .render_edited_subtitles(OutputFormat::Srt, layout, &HashSet::new())
+            .expect("edited cues");
+        assert!(wrapped.contains("fixed\n1\n"), "{wrapped}");
+    }
+
+    #[test]
+    fn split_halves_get_an_id_no_removed_cue_held() {
+        let handle = DetectionHandle::new();
+        let first = stored_cue(&handle, "one", 0);
+        let newest = stored_cue(&handle, "two", 2000);
+        handle.remove_subtitle(newest).expect("remove");
+
+        let second = handle.split_subtitle(first, 400.0).expect("split");
+        assert_ne!(second, newest);
+
+        let subtitles = handle.inner.all_subtitles();
+        assert_eq!(subtitles.len(), 2);
+        assert_eq!(subtitles[0].id, first);
+        assert_eq!(subtitles[0].end_time, Duration::from_millis(400));
+        assert_eq!(subtitles[1].id, second);
+        assert_eq!(subtitles[1].start_time, Duration::from_millis(400));
+        assert_eq!(subtitles[1].lines[0].text, "one");
+    }
+}
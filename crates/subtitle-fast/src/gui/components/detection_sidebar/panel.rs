@@ -1,15 +1,21 @@
 use gpui::prelude::*;
-use gpui::{Action, Context, Entity, FontWeight, Render, Window, div, hsla, px, rgb};
+use gpui::{
+    Action, Context, Entity, FontWeight, PromptButton, PromptLevel, Render, Window, div, hsla, px,
+    rgb,
+};
 
 use crate::gui::icons::{Icon, icon_sm};
 use crate::gui::menus::OpenSubtitleEditor;
 
-use super::{DetectedSubtitlesList, DetectionControls, DetectionHandle, DetectionMetrics};
+use super::{
+    DetectedSubtitlesList, DetectionControls, DetectionHandle, DetectionMetrics, OcrPreviewPanel,
+};
 
 pub struct DetectionSidebar {
     handle: DetectionHandle,
     metrics_view: Entity<DetectionMetrics>,
     controls_view: Entity<DetectionControls>,
+    preview_view: Entity<OcrPreviewPanel>,
     subtitles_view: Entity<DetectedSubtitlesList>,
 }
 
@@ -18,12 +24,14 @@ impl DetectionSidebar {
         handle: DetectionHandle,
         metrics_view: Entity<DetectionMetrics>,
         controls_view: Entity<DetectionControls>,
+        preview_view: Entity<OcrPreviewPanel>,
         subtitles_view: Entity<DetectedSubtitlesList>,
     ) -> Self {
         Self {
             handle,
             metrics_view,
             controls_view,
+            preview_view,
             subtitles_view,
         }
     }
@@ -151,9 +159,14 @@ impl DetectionSidebar {
         let receiver = cx.prompt_for_new_path(&directory, suggested_name.as_deref());
         let handle = self.handle.clone();
 
-        let task = window.spawn(cx, async move |_| match receiver.await {
+        let task = window.spawn(cx, async move |cx| match receiver.await {
             Ok(Ok(Some(path))) => {
-                handle.export_subtitles_to(path);
+                if let Err(err) = handle.export_subtitles_to(path) {
+                    let buttons = [PromptButton::ok("OK")];
+                    let _ = cx
+                        .prompt(PromptLevel::Warning, "Export failed", Some(&err), &buttons)
+                        .await;
+                }
             }
             Ok(Ok(None)) => {}
             Ok(Err(err)) => {
@@ -188,7 +201,8 @@ impl Render for DetectionSidebar {
                 cx,
             ))
             .child(self.metrics_view.clone())
-            .child(self.controls_view.clone());
+            .child(self.controls_view.clone())
+            .child(self.preview_view.clone());
 
         let lower = div()
             .id(("detection-sidebar-lower", cx.entity_id()))
@@ -0,0 +1,199 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use gpui::prelude::*;
+use gpui::{
+    Context, FontWeight, Image, ImageFormat, ObjectFit, Render, Task, Window, div, hsla, img, px,
+    rgb,
+};
+use tokio::sync::watch;
+
+use crate::gui::fonts::subtitle_font;
+use crate::gui::icons::{Icon, icon_sm};
+use crate::stage::ocr::OcrPreview;
+
+use super::DetectionHandle;
+
+const PREVIEW_THROTTLE: Duration = Duration::from_millis(250);
+
+/// Shows the crop the OCR stage read last, next to the text it recognized.
+pub struct OcrPreviewPanel {
+    handle: DetectionHandle,
+    preview_rx: Option<watch::Receiver<Option<OcrPreview>>>,
+    preview_task: Option<Task<()>>,
+    preview: Option<OcrPreview>,
+    image: Option<Arc<Image>>,
+}
+
+impl OcrPreviewPanel {
+    pub fn new(handle: DetectionHandle) -> Self {
+        Self {
+            handle,
+            preview_rx: None,
+            preview_task: None,
+            preview: None,
+            image: None,
+        }
+    }
+
+    fn enabled(&self) -> bool {
+        self.preview_rx.is_some()
+    }
+
+    fn toggle(&mut self, cx: &mut Context<Self>) {
+        if self.enabled() {
+            // Dropping the last receiver stops the OCR stage from copying crops.
+            self.preview_rx = None;
+            self.preview_task = None;
+            self.preview = None;
+            self.image = None;
+        } else {
+            self.preview_rx = Some(self.handle.subscribe_ocr_preview());
+        }
+        cx.notify();
+    }
+
+    fn ensure_preview_listener(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.preview_task.is_some() {
+            return;
+        }
+        let Some(mut preview_rx) = self.preview_rx.clone() else {
+            return;
+        };
+        let entity_id = cx.entity_id();
+        let task = window.spawn(cx, async move |cx| {
+            loop {
+                if preview_rx.changed().await.is_err() {
+                    break;
+                }
+                if cx
+                    .update(|_window, cx| {
+                        cx.notify(entity_id);
+                    })
+                    .is_err()
+                {
+                    break;
+                }
+                cx.background_executor().timer(PREVIEW_THROTTLE).await;
+            }
+        });
+        self.preview_task = Some(task);
+    }
+
+    fn sync_preview(&mut self) {
+        let Some(preview_rx) = self.preview_rx.as_ref() else {
+            return;
+        };
+        let next = preview_rx.borrow().clone();
+        if self.preview == next {
+            return;
+        }
+        self.image = next
+            .as_ref()
+            .and_then(OcrPreview::to_png)
+            .map(|png| Arc::new(Image::from_bytes(ImageFormat::Png, png)));
+        self.preview = next;
+    }
+}
+
+impl Render for OcrPreviewPanel {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        self.ensure_preview_listener(window, cx);
+        self.sync_preview();
+
+        let enabled = self.enabled();
+        let label_color = hsla(0.0, 0.0, 1.0, 0.62);
+        let value_color = hsla(0.0, 0.0, 1.0, 0.9);
+        let hover_bg = rgb(0x2a2a2a);
+        let border = rgb(0x343434);
+        let toggle_icon = if enabled { Icon::Eye } else { Icon::EyeOff };
+
+        let toggle_button = div()
+            .id(("ocr-preview-toggle", cx.entity_id()))
+            .flex()
+            .items_center()
+            .justify_center()
+            .h(px(20.0))
+            .w(px(20.0))
+            .rounded(px(4.0))
+            .cursor_pointer()
+            .hover(move |style| style.bg(hover_bg))
+            .child(
+                icon_sm(toggle_icon, if enabled { value_color } else { label_color })
+                    .w(px(12.0))
+                    .h(px(12.0)),
+            )
+            .on_click(cx.listener(|this, _event, _window, cx| {
+                this.toggle(cx);
+            }));
+
+        let header = div()
+            .flex()
+            .items_center()
+            .justify_between()
+            .w_full()
+            .text_size(px(10.0))
+            .text_color(label_color)
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap(px(6.0))
+                    .child(icon_sm(Icon::Scan, label_color).w(px(12.0)).h(px(12.0)))
+                    .child("OCR preview"),
+            )
+            .child(toggle_button);
+
+        let mut container = div()
+            .id(("ocr-preview", cx.entity_id()))
+            .flex()
+            .flex_col()
+            .gap(px(6.0))
+            .w_full()
+            .min_w(px(0.0))
+            .child(header);
+
+        if !enabled {
+            return container;
+        }
+
+        let Some(preview) = self.preview.as_ref() else {
+            return container.child(
+                div()
+                    .text_size(px(10.0))
+                    .text_color(label_color)
+                    .child("Waiting for OCR…"),
+            );
+        };
+
+        if let Some(image) = self.image.clone() {
+            container = container.child(
+                div()
+                    .w_full()
+                    .h(px(48.0))
+                    .rounded(px(4.0))
+                    .border_1()
+                    .border_color(border)
+                    .bg(rgb(0x000000))
+                    .overflow_hidden()
+                    .child(img(image).w_full().h_full().object_fit(ObjectFit::Contain)),
+            );
+        }
+
+        let text = if preview.text.trim().is_empty() {
+            "(no text)".to_string()
+        } else {
+            preview.text.clone()
+        };
+        container.child(
+            div()
+                .w_full()
+                .min_w(px(0.0))
+                .text_size(px(12.0))
+                .font_weight(FontWeight::MEDIUM)
+                .text_color(value_color)
+                .font(subtitle_font())
+                .child(text),
+        )
+    }
+}
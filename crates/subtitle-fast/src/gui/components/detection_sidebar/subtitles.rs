@@ -11,6 +11,7 @@ use crate::stage::TimedSubtitle;
 
 use super::{DetectionHandle, SubtitleMessage};
 use crate::gui::components::VideoPlayerControlHandle;
+use crate::gui::fonts::subtitle_font;
 
 #[derive(Clone, Debug)]
 struct DetectedSubtitleEntry {
@@ -206,6 +207,11 @@ impl DetectedSubtitlesList {
             SubtitleMessage::New(subtitle) => self.push_subtitle(subtitle),
             SubtitleMessage::Updated(subtitle) => self.update_subtitle(subtitle),
             SubtitleMessage::Removed(id) => self.remove_subtitle(id),
+            SubtitleMessage::Flushed(ids) => {
+                for id in ids {
+                    self.remove_subtitle(id);
+                }
+            }
         }
     }
 
@@ -550,7 +556,12 @@ impl DetectedSubtitlesList {
                 );
         }
 
-        let mut line_stack = div().flex().flex_col().gap(px(2.0)).min_w(px(0.0));
+        let mut line_stack = div()
+            .flex()
+            .flex_col()
+            .gap(px(2.0))
+            .min_w(px(0.0))
+            .font(subtitle_font());
         if entry.lines.is_empty() {
             line_stack = line_stack.child(
                 div()
@@ -144,20 +144,26 @@ impl DownloadWindow {
                 self.phase = DownloadPhase::Downloading;
             }
             ModelDownloadEvent::Progress {
+                file_index,
                 downloaded_bytes,
                 total_bytes,
-            } => {
+            } if file_index == self.progress.file_index => {
                 self.progress.downloaded_bytes = downloaded_bytes;
                 if total_bytes.is_some() {
                     self.progress.total_bytes = total_bytes;
                 }
             }
-            ModelDownloadEvent::Finished { file_label } => {
+            ModelDownloadEvent::Progress { .. } => {}
+            ModelDownloadEvent::Finished {
+                file_label,
+                file_index,
+            } if file_index == self.progress.file_index => {
                 self.progress.file_label = file_label.into();
                 if let Some(total) = self.progress.total_bytes {
                     self.progress.downloaded_bytes = total;
                 }
             }
+            ModelDownloadEvent::Finished { .. } => {}
             ModelDownloadEvent::Completed => {
                 if let Some(on_continue) = self.on_continue.as_ref() {
                     on_continue(window, cx);
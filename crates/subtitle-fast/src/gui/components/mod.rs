@@ -26,7 +26,7 @@ pub use confirm_dialog::{
 };
 pub use detection_sidebar::{
     DetectedSubtitlesList, DetectionControls, DetectionHandle, DetectionMetrics, DetectionRunState,
-    DetectionSidebar, DetectionSidebarHost,
+    DetectionSidebar, DetectionSidebarHost, OcrPreviewPanel,
 };
 pub use download_window::DownloadWindow;
 pub use help_window::HelpWindow;
@@ -35,7 +35,7 @@ pub use menu_bar::MenuBar;
 pub use sidebar::{
     CollapseDirection, DragRange, DraggableEdge, Sidebar, SidebarConfig, SidebarHandle,
 };
-pub use subtitle_editor_window::SubtitleEditorWindow;
+pub use subtitle_editor_window::{SubtitleEditorWindow, bind_subtitle_editor_keys};
 pub use task_sidebar::{TaskSidebar, TaskSidebarCallbacks};
 pub use titlebar::Titlebar;
 pub use titlebar_actions::{TitlebarActions, TitlebarActionsCallbacks};
@@ -1,14 +1,15 @@
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
 use futures_util::StreamExt;
 use gpui::prelude::*;
 use gpui::{
-    App, Bounds, Context, DispatchPhase, Div, Entity, MouseButton, MouseDownEvent, MouseMoveEvent,
-    MouseUpEvent, Pixels, Point, Render, ScrollHandle, SharedString, Subscription, Task, Window,
-    WindowBounds, WindowDecorations, WindowOptions, div, hsla, point, px, rgb, size,
+    App, Bounds, Context, DispatchPhase, Div, Entity, FocusHandle, KeyBinding, MouseButton,
+    MouseDownEvent, MouseMoveEvent, MouseUpEvent, PathPromptOptions, Pixels, Point, Render,
+    ScrollHandle, SharedString, Subscription, Task, Window, WindowBounds, WindowDecorations,
+    WindowOptions, actions, div, hsla, point, px, rgb, size,
 };
 
 use crate::gui::components::detection_sidebar::{SubtitleEdit, SubtitleMessage};
@@ -18,9 +19,11 @@ use crate::gui::components::video_player::VideoPlayerInfoSnapshot;
 use crate::gui::components::{
     DetectionHandle, Titlebar, VideoPlayer, VideoPlayerControlHandle, VideoPlayerInfoHandle,
 };
+use crate::gui::fonts::subtitle_font;
 use crate::gui::icons::{Icon, icon_sm};
 use crate::gui::session::VideoSession;
-use crate::subtitle::TimedSubtitle;
+use crate::subtitle::{TimedSubtitle, parse_srt};
+use crate::subtitle_diff::{CueDiff, SubtitleDiff, diff_subtitles};
 
 const PREVIEW_SEEK_OFFSET_MS: f64 = 100.0;
 const LIST_MIN_WIDTH: f32 = 320.0;
@@ -44,6 +47,8 @@ const SCROLLBAR_SETTLE_EPS: f32 = 1.0;
 const SCROLLBAR_CANCEL_EPS: f32 = 1.0;
 const SCROLLBAR_FADE_DELAY_MS: u64 = 1000;
 const SCROLLBAR_FADE_MS: u64 = 200;
+const MINIMAP_WIDTH: f32 = 10.0;
+const MINIMAP_TICK_HEIGHT: f32 = 2.0;
 const LINE_PLACEHOLDER: &str = "Subtitle line";
 const TRAILING_ICON_BUTTON_WIDTH: f32 = 34.0;
 const TRAILING_ICON_BUTTON_HEIGHT: f32 = 28.0;
@@ -52,6 +57,22 @@ const TIME_INPUT_TRAILING_GAP: f32 = 3.0;
 const TRAILING_ICON_BUTTON_GAP: f32 = 4.0;
 const LINE_INPUT_TRAILING_WIDTH: f32 = TRAILING_ICON_BUTTON_WIDTH * 2.0 + TRAILING_ICON_BUTTON_GAP;
 const TIME_COMPARE_EPS: f64 = 1e-6;
+/// Unedited lines read with less OCR confidence than this are flagged for review.
+const LOW_CONFIDENCE_THRESHOLD: f32 = 0.6;
+const KEY_CONTEXT: &str = "SubtitleEditor";
+
+actions!(
+    subtitle_editor,
+    [SelectPreviousSubtitle, SelectNextSubtitle]
+);
+
+/// Bind the editor's cue navigation keys.
+pub fn bind_subtitle_editor_keys(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("up", SelectPreviousSubtitle, Some(KEY_CONTEXT)),
+        KeyBinding::new("down", SelectNextSubtitle, Some(KEY_CONTEXT)),
+    ]);
+}
 
 #[derive(Clone, Debug)]
 struct EditableSubtitle {
@@ -59,6 +80,8 @@ struct EditableSubtitle {
     start_ms: f64,
     end_ms: f64,
     lines: Vec<String>,
+    /// OCR confidence per entry in `lines`; empty once the cue is edited or when unknown.
+    confidences: Vec<Option<f32>>,
 }
 
 #[derive(Clone, Debug)]
@@ -102,6 +125,7 @@ impl EditableSubtitle {
             start_ms: subtitle.start_ms,
             end_ms: subtitle.end_ms,
             lines: normalize_lines(&subtitle.lines),
+            confidences: normalize_line_confidences(&subtitle.lines, &subtitle.confidences),
         }
     }
 
@@ -113,6 +137,14 @@ impl EditableSubtitle {
     }
 }
 
+/// A previously exported SRT the current cues are compared against.
+struct ReferenceTrack {
+    name: SharedString,
+    cues: Vec<TimedSubtitle>,
+    diff: SubtitleDiff,
+    stale: bool,
+}
+
 #[derive(Clone)]
 struct StatusMessage {
     text: SharedString,
@@ -159,18 +191,26 @@ pub struct SubtitleEditorWindow {
     selected_snapshot: Option<SelectedSnapshot>,
     selected_deleted: bool,
     drafts: HashMap<u64, SubtitleDraft>,
+    /// Cues with an applied edit since the last reset. With the cues still holding a draft they
+    /// make up what "Export Edited" delivers; a draft reverted unapplied drops out.
+    edited_ids: HashSet<u64>,
     dirty: bool,
     suppress_input_observers: bool,
     status: Option<StatusMessage>,
+    reference: Option<ReferenceTrack>,
     subscriptions: Vec<Subscription>,
     titlebar: Entity<Titlebar>,
     player: Entity<VideoPlayer>,
     player_control: VideoPlayerControlHandle,
     player_info: VideoPlayerInfoHandle,
     list_scroll_handle: ScrollHandle,
+    /// Focused by clicking the list; the arrow keys move the selection only while it is.
+    list_focus_handle: FocusHandle,
     list_row_heights: Vec<Pixels>,
     list_row_offsets: Vec<Pixels>,
     list_row_measured: Vec<bool>,
+    /// Subtitle id of each laid-out row, to tell an appended row from a reordered list.
+    list_row_ids: Vec<u64>,
     list_estimated_row_height: Pixels,
     list_scroll_refresh_pending: bool,
     list_scroll_drag: Option<ScrollbarDragState>,
@@ -262,18 +302,22 @@ impl SubtitleEditorWindow {
             selected_snapshot: None,
             selected_deleted: false,
             drafts: HashMap::new(),
+            edited_ids: HashSet::new(),
             dirty: false,
             suppress_input_observers: false,
             status: None,
+            reference: None,
             subscriptions: Vec::new(),
             titlebar,
             player,
             player_control: control,
             player_info: info,
             list_scroll_handle: ScrollHandle::new(),
+            list_focus_handle: cx.focus_handle(),
             list_row_heights,
             list_row_offsets,
             list_row_measured,
+            list_row_ids: Vec::new(),
             list_estimated_row_height: estimated_row_height,
             list_scroll_refresh_pending: true,
             list_scroll_drag: None,
@@ -380,6 +424,22 @@ impl SubtitleEditorWindow {
         self.load_selected(id, cx);
     }
 
+    /// Moves the selection to the neighbouring cue of the visible list, keeping the current
+    /// draft; stops at either end.
+    fn select_adjacent_subtitle(&mut self, forward: bool, cx: &mut Context<Self>) {
+        let ids: Vec<u64> = self
+            .filtered_subtitles()
+            .into_iter()
+            .map(|index| self.subtitles[index].id)
+            .collect();
+        let Some(row_index) = adjacent_row(&ids, self.selected_id, forward) else {
+            return;
+        };
+        self.select_subtitle(ids[row_index], cx);
+        self.scroll_list_to_row(row_index);
+        cx.notify();
+    }
+
     fn sync_current_draft_state(&mut self, is_dirty: bool, cx: &mut Context<Self>) {
         let Some(id) = self.selected_id else {
             return;
@@ -501,6 +561,17 @@ impl SubtitleEditorWindow {
         }
     }
 
+    fn line_is_low_confidence(&self, index: usize) -> bool {
+        let Some(id) = self.selected_id else {
+            return false;
+        };
+        self.subtitles
+            .iter()
+            .find(|entry| entry.id == id)
+            .and_then(|entry| entry.confidences.get(index).copied().flatten())
+            .is_some_and(|confidence| confidence < LOW_CONFIDENCE_THRESHOLD)
+    }
+
     fn line_is_new(&self, index: usize) -> bool {
         let Some(snapshot) = self.selected_snapshot.as_ref() else {
             return false;
@@ -702,12 +773,14 @@ impl SubtitleEditorWindow {
     }
 
     fn apply_message(&mut self, message: SubtitleMessage, cx: &mut Context<Self>) {
+        self.mark_reference_stale();
         match message {
             SubtitleMessage::Reset => {
                 self.subtitles.clear();
                 self.selected_id = None;
                 self.dirty = false;
                 self.drafts.clear();
+                self.edited_ids.clear();
                 self.status = None;
                 self.clear_inputs(cx);
             }
@@ -733,6 +806,14 @@ impl SubtitleEditorWindow {
             SubtitleMessage::Removed(id) => {
                 self.remove_local_subtitle(id, cx);
             }
+            SubtitleMessage::Flushed(ids) => {
+                // Keep cues the user is working on; the rest reload with the next snapshot.
+                self.subtitles.retain(|entry| {
+                    !ids.contains(&entry.id)
+                        || self.selected_id == Some(entry.id)
+                        || self.drafts.contains_key(&entry.id)
+                });
+            }
         }
     }
 
@@ -752,6 +833,7 @@ impl SubtitleEditorWindow {
         };
         let removed = self.subtitles.remove(index);
         self.drafts.remove(&id);
+        self.mark_reference_stale();
 
         if self.selected_id == Some(id) {
             self.selected_id = None;
@@ -949,6 +1031,7 @@ impl SubtitleEditorWindow {
                 self.set_selected_snapshot(id, start_ms, end_ms, lines);
                 self.dirty = false;
                 self.drafts.remove(&id);
+                self.edited_ids.insert(id);
                 self.set_status("Subtitle updated.", false, cx);
                 self.seek_preview(start_ms);
             }
@@ -970,6 +1053,66 @@ impl SubtitleEditorWindow {
         }
     }
 
+    fn split_selected_at_preview(&mut self, cx: &mut Context<Self>) {
+        let Some(id) = self.selected_id else {
+            self.set_status("Select a subtitle first.", true, cx);
+            return;
+        };
+        if self.dirty || self.selected_deleted {
+            self.set_status(
+                "Apply or restore this subtitle before splitting it.",
+                true,
+                cx,
+            );
+            return;
+        }
+        let Some(at) = self.player_info.snapshot().last_timestamp else {
+            self.set_status("The preview has no frame to split at yet.", true, cx);
+            return;
+        };
+
+        let at_ms = at.as_secs_f64() * 1000.0;
+        match self.detection.split_subtitle(id, at_ms) {
+            Ok(new_id) => {
+                self.edited_ids.insert(id);
+                self.edited_ids.insert(new_id);
+                self.set_status(
+                    format!("Subtitle split at {}.", format_timestamp(at_ms)),
+                    false,
+                    cx,
+                );
+            }
+            Err(err) => {
+                self.set_status(err, true, cx);
+            }
+        }
+    }
+
+    fn merge_selected_with_next(&mut self, cx: &mut Context<Self>) {
+        let Some(id) = self.selected_id else {
+            self.set_status("Select a subtitle first.", true, cx);
+            return;
+        };
+        if self.dirty || self.selected_deleted {
+            self.set_status(
+                "Apply or restore this subtitle before merging it.",
+                true,
+                cx,
+            );
+            return;
+        }
+
+        match self.detection.merge_with_next(id) {
+            Ok(_) => {
+                self.edited_ids.insert(id);
+                self.set_status("Subtitle merged with the next one.", false, cx);
+            }
+            Err(err) => {
+                self.set_status(err, true, cx);
+            }
+        }
+    }
+
     fn collect_lines_from_draft(&self, draft: &SubtitleDraft) -> Vec<String> {
         let mut raw_lines = Vec::new();
         for line in &draft.lines {
@@ -1083,6 +1226,7 @@ impl SubtitleEditorWindow {
                         selected_start_ms = Some(edit.start_ms);
                     }
                     self.drafts.remove(&edit.id);
+                    self.edited_ids.insert(edit.id);
                     applied += 1;
                 }
                 Err(err) => {
@@ -1115,7 +1259,131 @@ impl SubtitleEditorWindow {
             entry.start_ms = start_ms;
             entry.end_ms = end_ms;
             entry.lines = normalize_lines(&lines);
+            entry.confidences.clear();
         }
+        self.mark_reference_stale();
+    }
+
+    /// Cues "Export Edited" delivers: applied edits, pending drafts and the selected cue while
+    /// it has unsaved changes.
+    fn export_edited_ids(&self) -> HashSet<u64> {
+        let unsaved = self.selected_id.filter(|_| self.dirty);
+        edited_export_ids(&self.edited_ids, self.drafts.keys().copied(), unsaved)
+    }
+
+    fn prompt_for_edited_export(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let edited_ids = self.export_edited_ids();
+        if edited_ids.is_empty() {
+            self.set_status("No subtitles have been edited.", true, cx);
+            return;
+        }
+        let (directory, suggested_name) = self.detection.export_dialog_seed();
+        let suggested_name = suggested_name.map(|name| match name.rsplit_once('.') {
+            Some((stem, extension)) => format!("{stem}.edited.{extension}"),
+            None => format!("{name}.edited"),
+        });
+        let receiver = cx.prompt_for_new_path(&directory, suggested_name.as_deref());
+        let detection = self.detection.clone();
+        let handle = cx.entity();
+        let task = window.spawn(cx, async move |cx| match receiver.await {
+            Ok(Ok(Some(path))) => {
+                if let Err(err) = detection.export_edited_subtitles_to(path, &edited_ids) {
+                    let _ = cx.update(|_window, cx| {
+                        handle.update(cx, |this, cx| this.set_status(err, true, cx));
+                    });
+                }
+            }
+            Ok(Ok(None)) => {}
+            Ok(Err(err)) => eprintln!("export dialog failed: {err}"),
+            Err(err) => eprintln!("export dialog failed: {err}"),
+        });
+        task.detach();
+    }
+
+    fn prompt_for_reference(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let receiver = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+            prompt: Some("Compare with SRT".into()),
+            allowed_extensions: Some(vec![SharedString::new_static("srt")]),
+        });
+        let handle = cx.entity();
+        let task = window.spawn(cx, async move |cx| {
+            let path = match receiver.await {
+                Ok(Ok(Some(mut paths))) => paths.pop(),
+                Ok(Ok(None)) => None,
+                Ok(Err(err)) => {
+                    eprintln!("reference selection failed: {err}");
+                    None
+                }
+                Err(err) => {
+                    eprintln!("reference selection canceled: {err}");
+                    None
+                }
+            };
+            let Some(path) = path else {
+                return;
+            };
+            let _ = cx.update(|_window, cx| {
+                handle.update(cx, |this, cx| this.load_reference(path, cx));
+            });
+        });
+        task.detach();
+    }
+
+    fn load_reference(&mut self, path: PathBuf, cx: &mut Context<Self>) {
+        let cues = match std::fs::read(&path) {
+            Ok(bytes) => parse_srt(&String::from_utf8_lossy(&bytes)),
+            Err(err) => {
+                self.set_status(
+                    format!("Failed to read {}: {err}", path.display()),
+                    true,
+                    cx,
+                );
+                return;
+            }
+        };
+        if cues.is_empty() {
+            self.set_status("No subtitles found in the selected file.", true, cx);
+            return;
+        }
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+        self.reference = Some(ReferenceTrack {
+            name: name.into(),
+            cues,
+            diff: SubtitleDiff::default(),
+            stale: true,
+        });
+        self.refresh_reference_diff();
+        cx.notify();
+    }
+
+    fn clear_reference(&mut self, cx: &mut Context<Self>) {
+        self.reference = None;
+        cx.notify();
+    }
+
+    fn mark_reference_stale(&mut self) {
+        if let Some(reference) = self.reference.as_mut() {
+            reference.stale = true;
+        }
+    }
+
+    /// Re-diffs against the reference using every applied cue, including ones spilled to disk.
+    fn refresh_reference_diff(&mut self) {
+        let Some(reference) = self.reference.as_mut() else {
+            return;
+        };
+        if !reference.stale {
+            return;
+        }
+        let current = self.detection.subtitles_snapshot();
+        reference.diff = diff_subtitles(&current, &reference.cues);
+        reference.stale = false;
     }
 
     fn collect_lines(&self, cx: &mut Context<Self>) -> Vec<String> {
@@ -1237,10 +1505,85 @@ impl SubtitleEditorWindow {
             apply_all_button = apply_all_button.bg(disabled_bg).text_color(disabled_text);
         }
 
-        let actions = div()
+        let compare_button = div()
             .flex()
             .items_center()
+            .justify_center()
+            .h(px(26.0))
+            .px(px(10.0))
+            .rounded(px(6.0))
+            .text_size(px(11.0))
+            .bg(secondary_bg)
+            .text_color(secondary_text)
+            .cursor_pointer()
+            .hover(move |style| style.bg(secondary_hover));
+        let compare_button = if self.reference.is_some() {
+            compare_button.child("Clear").on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|this, _event, _window, cx| {
+                    this.clear_reference(cx);
+                }),
+            )
+        } else {
+            compare_button.child("Compare").on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|this, _event, window, cx| {
+                    this.prompt_for_reference(window, cx);
+                }),
+            )
+        };
+
+        let can_export_edited = !self.export_edited_ids().is_empty();
+        let export_edited_icon_color = if can_export_edited {
+            secondary_text
+        } else {
+            disabled_text
+        };
+        let mut export_edited_button = div()
+            .flex()
+            .items_center()
+            .justify_center()
             .gap(px(6.0))
+            .h(px(26.0))
+            .px(px(10.0))
+            .rounded(px(6.0))
+            .text_size(px(11.0))
+            .child(icon_sm(Icon::Upload, export_edited_icon_color))
+            .child("Export Edited");
+
+        if can_export_edited {
+            export_edited_button = export_edited_button
+                .bg(secondary_bg)
+                .text_color(secondary_text)
+                .cursor_pointer()
+                .hover(move |style| style.bg(secondary_hover))
+                .on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(|this, _event, window, cx| {
+                        this.prompt_for_edited_export(window, cx);
+                    }),
+                );
+        } else {
+            export_edited_button = export_edited_button
+                .bg(disabled_bg)
+                .text_color(disabled_text);
+        }
+
+        let mut actions = div().flex().items_center().gap(px(6.0));
+        if let Some(reference) = self.reference.as_ref() {
+            actions = actions.child(div().text_size(px(11.0)).text_color(count_color).child(
+                format!(
+                    "vs {}: {} new, {} changed, {} removed",
+                    reference.name,
+                    reference.diff.count(CueDiff::New),
+                    reference.diff.count(CueDiff::Changed),
+                    reference.diff.removed().len()
+                ),
+            ));
+        }
+        let actions = actions
+            .child(compare_button)
+            .child(export_edited_button)
             .child(restore_all_button)
             .child(apply_all_button);
 
@@ -1299,6 +1642,10 @@ impl SubtitleEditorWindow {
         let deleted_badge_bg = hsla(0.0, 0.7, 0.45, 0.25);
         let deleted_badge_text = hsla(0.0, 0.7, 0.7, 1.0);
         let deleted_accent = hsla(0.0, 0.7, 0.65, 1.0);
+        let new_badge_bg = hsla(0.38, 0.6, 0.4, 0.25);
+        let new_badge_text = hsla(0.38, 0.6, 0.7, 1.0);
+        let changed_badge_bg = hsla(0.6, 0.7, 0.45, 0.25);
+        let changed_badge_text = hsla(0.6, 0.7, 0.75, 1.0);
         let is_selected = self.selected_id == Some(entry.id);
         let is_deleted = self.is_marked_deleted(entry.id);
         let is_dirty = self.drafts.contains_key(&entry.id)
@@ -1322,7 +1669,8 @@ impl SubtitleEditorWindow {
             .flex()
             .flex_col()
             .gap(px(LIST_ROW_LINE_GAP))
-            .min_w(px(0.0));
+            .min_w(px(0.0))
+            .font(subtitle_font());
         if lines_snapshot.is_empty() {
             lines = lines.child(
                 div()
@@ -1386,6 +1734,32 @@ impl SubtitleEditorWindow {
             );
         }
 
+        let diff_badge = match self
+            .reference
+            .as_ref()
+            .and_then(|reference| reference.diff.status(entry.id))
+        {
+            Some(CueDiff::New) => Some(("New", new_badge_text, new_badge_bg)),
+            Some(CueDiff::Changed) => Some(("Changed", changed_badge_text, changed_badge_bg)),
+            Some(CueDiff::Unchanged) | None => None,
+        };
+        if let Some((label, text, bg)) = diff_badge {
+            time_row = time_row.child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_center()
+                    .px(px(6.0))
+                    .h(px(LIST_ROW_BADGE_HEIGHT))
+                    .line_height(px(LIST_ROW_BADGE_HEIGHT))
+                    .rounded(px(4.0))
+                    .text_size(px(8.0))
+                    .text_color(text)
+                    .bg(bg)
+                    .child(label),
+            );
+        }
+
         let mut row = div()
             .id(("subtitle-editor-row", entry.id))
             .relative()
@@ -1441,24 +1815,43 @@ impl SubtitleEditorWindow {
         row
     }
 
-    fn sync_list_layout(&mut self, row_count: usize) {
-        if row_count == self.list_row_heights.len() {
+    fn sync_list_layout(&mut self, filtered: &[usize]) {
+        let row_ids: Vec<u64> = filtered
+            .iter()
+            .map(|index| self.subtitles[*index].id)
+            .collect();
+        if row_ids == self.list_row_ids && row_ids.len() == self.list_row_heights.len() {
+            return;
+        }
+        let row_count = row_ids.len();
+        let appended = appends_one_row(&self.list_row_ids, &row_ids);
+        self.list_row_ids = row_ids;
+
+        // Streaming cues arrive one at a time; extend the layout instead of rebuilding it so
+        // long runs stay O(1) per cue and keep the heights already measured. Only when every
+        // laid-out row is still in place: a cue sorted into the middle, or a changed search
+        // filter, shifts rows and needs the full rebuild.
+        if appended
+            && row_count == self.list_row_heights.len() + 1
+            && self.list_row_offsets.len() == self.list_row_heights.len() + 1
+        {
+            append_list_row(
+                &mut self.list_row_heights,
+                &mut self.list_row_offsets,
+                self.list_estimated_row_height,
+            );
+            self.list_row_measured.push(false);
+            self.list_scroll_refresh_pending = true;
             return;
         }
 
         self.list_row_heights.clear();
         self.list_row_measured.clear();
-        self.list_row_offsets.clear();
-        self.list_row_offsets.push(Pixels::ZERO);
-
         for _ in 0..row_count {
             self.list_row_heights.push(self.list_estimated_row_height);
             self.list_row_measured.push(false);
-            if let Some(last) = self.list_row_offsets.last().copied() {
-                self.list_row_offsets
-                    .push(last + self.list_estimated_row_height);
-            }
         }
+        rebuild_list_row_offsets(&self.list_row_heights, &mut self.list_row_offsets);
 
         self.list_scroll_refresh_pending = true;
         self.list_scrollbar_animation = None;
@@ -1490,13 +1883,7 @@ impl SubtitleEditorWindow {
     }
 
     fn rebuild_list_row_offsets(&mut self) {
-        self.list_row_offsets.clear();
-        self.list_row_offsets.push(Pixels::ZERO);
-        for height in &self.list_row_heights {
-            if let Some(last) = self.list_row_offsets.last().copied() {
-                self.list_row_offsets.push(last + *height);
-            }
-        }
+        rebuild_list_row_offsets(&self.list_row_heights, &mut self.list_row_offsets);
     }
 
     fn set_list_row_height(&mut self, index: usize, height: Pixels) {
@@ -1890,7 +2277,7 @@ impl SubtitleEditorWindow {
     ) -> impl IntoElement + 'static {
         let empty_color = hsla(0.0, 0.0, 0.6, 1.0);
 
-        self.sync_list_layout(filtered.len());
+        self.sync_list_layout(filtered);
         self.refresh_list_estimated_row_height(window);
         self.schedule_list_scroll_refresh(window, cx);
 
@@ -1985,11 +2372,23 @@ impl SubtitleEditorWindow {
             }))
             .child(list_body);
 
+        // Scoped to the list so Up/Down keep moving the caret in the text inputs.
         let mut container = div()
             .id(("subtitle-editor-list", cx.entity_id()))
+            .key_context(KEY_CONTEXT)
+            .track_focus(&self.list_focus_handle)
+            .on_action(
+                cx.listener(|this, _: &SelectPreviousSubtitle, _window, cx| {
+                    this.select_adjacent_subtitle(false, cx);
+                }),
+            )
+            .on_action(cx.listener(|this, _: &SelectNextSubtitle, _window, cx| {
+                this.select_adjacent_subtitle(true, cx);
+            }))
             .flex()
             .flex_col()
             .flex_1()
+            .min_w(px(0.0))
             .min_h(px(0.0))
             .relative()
             .child(scroll_area);
@@ -2059,7 +2458,130 @@ impl SubtitleEditorWindow {
             });
         }
 
-        container
+        let minimap = self.minimap_strip(filtered, cx);
+
+        div()
+            .flex()
+            .flex_row()
+            .flex_1()
+            .min_h(px(0.0))
+            .gap(px(6.0))
+            .child(container)
+            .child(minimap)
+    }
+
+    fn minimap_duration_ms(&self) -> Option<f64> {
+        let duration_ms = self
+            .player_info
+            .snapshot()
+            .metadata
+            .duration()
+            .map(|duration| duration.as_secs_f64() * 1000.0)
+            .or_else(|| {
+                self.subtitles
+                    .iter()
+                    .map(|entry| entry.end_ms)
+                    .max_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+            })?;
+        (duration_ms > 0.0).then_some(duration_ms)
+    }
+
+    fn minimap_strip(&self, filtered: &[usize], cx: &mut Context<Self>) -> Div {
+        let track_color = hsla(0.0, 0.0, 1.0, 0.04);
+        let tick_color = hsla(0.0, 0.0, 1.0, 0.35);
+        let selected_color = hsla(0.0, 0.0, 1.0, 0.9);
+        let dirty_color = hsla(0.08, 0.85, 0.6, 1.0);
+
+        let mut strip = div()
+            .relative()
+            .flex_none()
+            .w(px(MINIMAP_WIDTH))
+            .rounded(px(3.0))
+            .bg(track_color)
+            .cursor_pointer()
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|this, event: &MouseDownEvent, _window, cx| {
+                    this.jump_to_minimap_position(event.position, cx);
+                }),
+            );
+
+        let strip_height = f32::from(self.list_scroll_handle.bounds().size.height);
+        let Some(duration_ms) = self.minimap_duration_ms() else {
+            return strip;
+        };
+
+        for entry in filtered
+            .iter()
+            .filter_map(|entry_index| self.subtitles.get(*entry_index))
+        {
+            let is_dirty = self.drafts.contains_key(&entry.id)
+                || (self.selected_id == Some(entry.id) && self.dirty);
+            let color = if is_dirty {
+                dirty_color
+            } else if self.selected_id == Some(entry.id) {
+                selected_color
+            } else {
+                tick_color
+            };
+            strip = strip.child(
+                div()
+                    .absolute()
+                    .left(px(1.0))
+                    .right(px(1.0))
+                    .top(px(minimap_tick_offset(
+                        entry.start_ms,
+                        duration_ms,
+                        strip_height,
+                    )))
+                    .h(px(MINIMAP_TICK_HEIGHT))
+                    .bg(color),
+            );
+        }
+
+        strip
+    }
+
+    fn jump_to_minimap_position(&mut self, position: Point<Pixels>, cx: &mut Context<Self>) {
+        let Some(duration_ms) = self.minimap_duration_ms() else {
+            return;
+        };
+        let bounds = self.list_scroll_handle.bounds();
+        let strip_height = f32::from(bounds.size.height);
+        let local_y = f32::from(position.y - bounds.origin.y);
+        let target_ms = minimap_time_for_offset(local_y, duration_ms, strip_height);
+
+        let filtered = self.filtered_subtitles();
+        let Some((row_index, entry_index)) =
+            filtered
+                .iter()
+                .copied()
+                .enumerate()
+                .min_by(|(_, a), (_, b)| {
+                    let left = (self.subtitles[*a].start_ms - target_ms).abs();
+                    let right = (self.subtitles[*b].start_ms - target_ms).abs();
+                    left.partial_cmp(&right).unwrap_or(Ordering::Equal)
+                })
+        else {
+            return;
+        };
+
+        let id = self.subtitles[entry_index].id;
+        self.select_subtitle(id, cx);
+        self.scroll_list_to_row(row_index);
+        cx.notify();
+    }
+
+    fn scroll_list_to_row(&mut self, row_index: usize) {
+        let Some(row_top) = self.list_row_offsets.get(row_index).copied() else {
+            return;
+        };
+        let max_offset = f32::from(self.list_scroll_handle.max_offset().height).max(0.0);
+        let scroll_top = f32::from(row_top).clamp(0.0, max_offset);
+        self.list_scroll_handle
+            .set_offset(point(px(0.0), px(-scroll_top)));
+        self.list_scroll_refresh_pending = true;
+        self.mark_list_scrollbar_interaction();
     }
 
     fn preview_panel(&self) -> impl IntoElement + 'static {
@@ -2143,7 +2665,7 @@ impl SubtitleEditorWindow {
                     .child("Text"),
             )
             .child({
-                let mut line_rows = div().flex().flex_col().gap(px(6.0));
+                let mut line_rows = div().flex().flex_col().gap(px(6.0)).font(subtitle_font());
                 for (index, input) in line_inputs.into_iter().enumerate() {
                     line_rows =
                         line_rows.child(self.line_input_row(index, delete_enabled, input, cx));
@@ -2321,6 +2843,8 @@ impl SubtitleEditorWindow {
         let disabled_icon = hsla(0.0, 0.0, 1.0, 0.35);
         let new_accent = hsla(0.55, 0.55, 0.65, 1.0);
         let new_bg = hsla(0.55, 0.55, 0.45, 0.12);
+        let low_confidence_accent = hsla(0.0, 0.75, 0.6, 1.0);
+        let low_confidence_bg = hsla(0.0, 0.75, 0.5, 0.1);
         let button_bg = hsla(0.0, 0.0, 1.0, 0.06);
         let button_border = rgb(0x2f2f2f);
         let button_hover_border = rgb(0x3a3a3a);
@@ -2334,6 +2858,7 @@ impl SubtitleEditorWindow {
         let delete_hover = deleted_badge_bg;
         let is_new_line = self.line_is_new(index);
         let is_modified = self.line_is_modified(index, &input_state, cx);
+        let is_low_confidence = self.line_is_low_confidence(index);
         let toggle_icon = Icon::Trash;
         let input = input_state.input.clone();
         let rollback_enabled = is_modified;
@@ -2456,6 +2981,17 @@ impl SubtitleEditorWindow {
                         .rounded(px(2.0))
                         .bg(modified_accent),
                 );
+            } else if is_low_confidence {
+                wrapper = wrapper.rounded(px(6.0)).bg(low_confidence_bg).child(
+                    div()
+                        .absolute()
+                        .left(px(3.0))
+                        .top(px(4.0))
+                        .bottom(px(4.0))
+                        .w(px(2.0))
+                        .rounded(px(2.0))
+                        .bg(low_confidence_accent),
+                );
             }
             wrapper
         };
@@ -2496,7 +3032,15 @@ impl SubtitleEditorWindow {
         let can_apply = self.selected_id.is_some() && self.dirty;
         let can_restore = self.selected_id.is_some() && self.dirty;
         let can_remove = self.selected_id.is_some();
+        let can_edit_cue = self.selected_id.is_some() && !self.dirty && !self.selected_deleted;
         let remove_active = self.selected_deleted;
+        let ids: Vec<u64> = self
+            .filtered_subtitles()
+            .into_iter()
+            .map(|index| self.subtitles[index].id)
+            .collect();
+        let can_prev = adjacent_row(&ids, self.selected_id, false).is_some();
+        let can_next = adjacent_row(&ids, self.selected_id, true).is_some();
         let apply_icon_color = if can_apply {
             primary_text
         } else {
@@ -2615,6 +3159,107 @@ impl SubtitleEditorWindow {
             remove_button = remove_button.bg(disabled_bg).text_color(disabled_text);
         }
 
+        let cue_action_icon_color = if can_edit_cue {
+            secondary_text
+        } else {
+            disabled_text
+        };
+        let mut split_button = div()
+            .flex()
+            .items_center()
+            .justify_center()
+            .gap(px(6.0))
+            .h(px(30.0))
+            .px(px(14.0))
+            .rounded(px(6.0))
+            .text_size(px(12.0))
+            .child(icon_sm(Icon::Scissors, cue_action_icon_color))
+            .child("Split at Preview");
+
+        if can_edit_cue {
+            split_button = split_button
+                .bg(secondary_bg)
+                .text_color(secondary_text)
+                .cursor_pointer()
+                .hover(move |style| style.bg(secondary_hover))
+                .on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(|this, _event, _window, cx| {
+                        this.split_selected_at_preview(cx);
+                    }),
+                );
+        } else {
+            split_button = split_button.bg(disabled_bg).text_color(disabled_text);
+        }
+
+        let mut merge_button = div()
+            .flex()
+            .items_center()
+            .justify_center()
+            .gap(px(6.0))
+            .h(px(30.0))
+            .px(px(14.0))
+            .rounded(px(6.0))
+            .text_size(px(12.0))
+            .child(icon_sm(Icon::Merge, cue_action_icon_color))
+            .child("Merge with Next");
+
+        if can_edit_cue {
+            merge_button = merge_button
+                .bg(secondary_bg)
+                .text_color(secondary_text)
+                .cursor_pointer()
+                .hover(move |style| style.bg(secondary_hover))
+                .on_mouse_down(
+                    MouseButton::Left,
+                    cx.listener(|this, _event, _window, cx| {
+                        this.merge_selected_with_next(cx);
+                    }),
+                );
+        } else {
+            merge_button = merge_button.bg(disabled_bg).text_color(disabled_text);
+        }
+
+        let nav_button = |icon: Icon, label: &'static str, enabled: bool, forward: bool| {
+            let icon_color = if enabled {
+                secondary_text
+            } else {
+                disabled_text
+            };
+            let button = div()
+                .flex()
+                .items_center()
+                .justify_center()
+                .gap(px(4.0))
+                .h(px(30.0))
+                .px(px(10.0))
+                .rounded(px(6.0))
+                .text_size(px(12.0))
+                .child(icon_sm(icon, icon_color))
+                .child(label);
+            if enabled {
+                button
+                    .bg(secondary_bg)
+                    .text_color(secondary_text)
+                    .cursor_pointer()
+                    .hover(move |style| style.bg(secondary_hover))
+                    .on_mouse_down(
+                        MouseButton::Left,
+                        cx.listener(move |this, _event, _window, cx| {
+                            this.select_adjacent_subtitle(forward, cx);
+                        }),
+                    )
+            } else {
+                button.bg(disabled_bg).text_color(disabled_text)
+            }
+        };
+        let nav_actions = div()
+            .flex()
+            .items_center()
+            .gap(px(4.0))
+            .child(nav_button(Icon::ChevronLeft, "Prev", can_prev, false))
+            .child(nav_button(Icon::ChevronRight, "Next", can_next, true));
+
         let right_actions = div()
             .flex()
             .items_center()
@@ -2628,17 +3273,19 @@ impl SubtitleEditorWindow {
                 .items_center()
                 .gap(px(8.0))
                 .child(remove_button)
+                .child(split_button)
+                .child(merge_button)
+                .child(nav_actions)
                 .child(div().flex_1())
                 .child(right_actions),
         );
 
         if can_remove {
-            container = container.child(
-                div()
-                    .text_size(px(10.0))
-                    .text_color(hint_color)
-                    .child("Remove marks the subtitle for deletion. Apply to delete."),
-            );
+            container = container.child(div().text_size(px(10.0)).text_color(hint_color).child(
+                "Remove marks the subtitle for deletion. Apply to delete. \
+                 Split at Preview ends it at the preview frame and starts a copy there; \
+                 Merge with Next folds the following subtitle into it.",
+            ));
         }
 
         container
@@ -2648,6 +3295,7 @@ impl SubtitleEditorWindow {
 impl Render for SubtitleEditorWindow {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         self.ensure_subtitle_listener(window, cx);
+        self.refresh_reference_diff();
 
         let filtered = self.filtered_subtitles();
         let titlebar = self.titlebar.clone();
@@ -2818,6 +3466,21 @@ fn normalize_lines(lines: &[String]) -> Vec<String> {
     normalized
 }
 
+/// Spreads each line's confidence over the lines `normalize_lines` splits it into.
+fn normalize_line_confidences(lines: &[String], confidences: &[Option<f32>]) -> Vec<Option<f32>> {
+    if confidences.len() != lines.len() {
+        return Vec::new();
+    }
+    lines
+        .iter()
+        .zip(confidences)
+        .flat_map(|(line, confidence)| {
+            let parts = normalize_lines(std::slice::from_ref(line)).len();
+            std::iter::repeat_n(*confidence, parts)
+        })
+        .collect()
+}
+
 fn format_timestamp(ms: f64) -> String {
     if !ms.is_finite() || ms <= 0.0 {
         return "0:00.000".to_string();
@@ -2867,3 +3530,163 @@ fn lerp(start: f32, end: f32, t: f32) -> f32 {
 fn ease_out(t: f32) -> f32 {
     1.0 - (1.0 - t).powi(3)
 }
+
+fn minimap_tick_offset(start_ms: f64, duration_ms: f64, strip_height: f32) -> f32 {
+    if duration_ms <= 0.0 || strip_height <= 0.0 {
+        return 0.0;
+    }
+    let ratio = (start_ms / duration_ms).clamp(0.0, 1.0) as f32;
+    ratio * (strip_height - MINIMAP_TICK_HEIGHT).max(0.0)
+}
+
+fn minimap_time_for_offset(offset: f32, duration_ms: f64, strip_height: f32) -> f64 {
+    let usable = (strip_height - MINIMAP_TICK_HEIGHT).max(0.0);
+    if usable <= 0.0 {
+        return 0.0;
+    }
+    let ratio = (offset / usable).clamp(0.0, 1.0) as f64;
+    ratio * duration_ms.max(0.0)
+}
+
+/// Row of the cue before or after `selected` in the visible, sorted `ids`. Without a visible
+/// selection it starts from the first or last row; at either end there is nothing to move to.
+fn adjacent_row(ids: &[u64], selected: Option<u64>, forward: bool) -> Option<usize> {
+    let current = selected.and_then(|id| ids.iter().position(|candidate| *candidate == id));
+    match (current, forward) {
+        (Some(row), true) => (row + 1 < ids.len()).then_some(row + 1),
+        (Some(row), false) => row.checked_sub(1),
+        (None, true) => (!ids.is_empty()).then_some(0),
+        (None, false) => ids.len().checked_sub(1),
+    }
+}
+
+/// Recomputes every row's top offset from `heights`; `offsets` ends with the total height.
+fn rebuild_list_row_offsets(heights: &[Pixels], offsets: &mut Vec<Pixels>) {
+    offsets.clear();
+    offsets.push(Pixels::ZERO);
+    let mut total = Pixels::ZERO;
+    for height in heights {
+        total += *height;
+        offsets.push(total);
+    }
+}
+
+fn edited_export_ids(
+    applied: &HashSet<u64>,
+    drafted: impl IntoIterator<Item = u64>,
+    unsaved: Option<u64>,
+) -> HashSet<u64> {
+    let mut ids = applied.clone();
+    ids.extend(drafted);
+    ids.extend(unsaved);
+    ids
+}
+
+/// `current` is `previous` with one more row at the end.
+fn appends_one_row(previous: &[u64], current: &[u64]) -> bool {
+    current.len() == previous.len() + 1 && current.starts_with(previous)
+}
+
+/// Adds one row of `height` after the last, leaving earlier offsets untouched.
+fn append_list_row(heights: &mut Vec<Pixels>, offsets: &mut Vec<Pixels>, height: Pixels) {
+    let total = offsets.last().copied().unwrap_or(Pixels::ZERO);
+    if offsets.is_empty() {
+        offsets.push(Pixels::ZERO);
+    }
+    heights.push(height);
+    offsets.push(total + height);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adjacent_row_steps_through_the_filtered_order_and_stops_at_the_ends() {
+        // Ids of a filtered list already sorted by start time.
+        let ids = [7, 3, 12];
+        assert_eq!(adjacent_row(&ids, Some(3), true), Some(2));
+        assert_eq!(adjacent_row(&ids, Some(3), false), Some(0));
+        assert_eq!(adjacent_row(&ids, Some(12), true), None);
+        assert_eq!(adjacent_row(&ids, Some(7), false), None);
+        // A selection hidden by the filter restarts from the matching end.
+        assert_eq!(adjacent_row(&ids, Some(5), true), Some(0));
+        assert_eq!(adjacent_row(&ids, None, false), Some(2));
+        assert_eq!(adjacent_row(&[], None, true), None);
+    }
+
+    #[test]
+    fn reverted_drafts_leave_the_edited_export() {
+        let applied = HashSet::from([1]);
+        let mut drafts: HashMap<u64, SubtitleDraft> = HashMap::new();
+        let draft = || SubtitleDraft {
+            start_text: String::new(),
+            end_text: String::new(),
+            lines: Vec::new(),
+            deleted: false,
+        };
+        drafts.insert(2, draft());
+        drafts.insert(3, draft());
+        // Reverting cue 3 before applying it drops its draft.
+        drafts.remove(&3);
+
+        let ids = edited_export_ids(&applied, drafts.keys().copied(), Some(4));
+        assert_eq!(ids, HashSet::from([1, 2, 4]));
+        assert_eq!(
+            edited_export_ids(&applied, drafts.keys().copied(), None),
+            HashSet::from([1, 2])
+        );
+    }
+
+    #[test]
+    fn appending_rows_matches_a_full_offset_rebuild() {
+        let mut heights = vec![px(40.0), px(52.5)];
+        let mut offsets = Vec::new();
+        rebuild_list_row_offsets(&heights, &mut offsets);
+
+        for height in [px(40.0), px(61.0), px(40.0)] {
+            append_list_row(&mut heights, &mut offsets, height);
+        }
+
+        let mut rebuilt = Vec::new();
+        rebuild_list_row_offsets(&heights, &mut rebuilt);
+        assert_eq!(heights.len(), 5);
+        assert_eq!(offsets, rebuilt);
+        assert_eq!(offsets.last().copied(), Some(px(233.5)));
+
+        let mut fresh_heights = Vec::new();
+        let mut fresh_offsets = Vec::new();
+        append_list_row(&mut fresh_heights, &mut fresh_offsets, px(40.0));
+        assert_eq!(fresh_offsets, vec![Pixels::ZERO, px(40.0)]);
+    }
+
+    #[test]
+    fn only_a_row_added_at_the_end_is_an_append() {
+        assert!(appends_one_row(&[], &[7]));
+        assert!(appends_one_row(&[1, 2], &[1, 2, 3]));
+        // A cue sorted before the last row shifts it.
+        assert!(!appends_one_row(&[1, 2], &[1, 3, 2]));
+        // A search filter swapping rows while adding one.
+        assert!(!appends_one_row(&[1, 2], &[2, 3, 4]));
+        assert!(!appends_one_row(&[1, 2], &[1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn minimap_ticks_scale_with_duration() {
+        let height = 202.0;
+        assert_eq!(minimap_tick_offset(0.0, 60_000.0, height), 0.0);
+        assert!((minimap_tick_offset(30_000.0, 60_000.0, height) - 100.0).abs() < 1e-4);
+        assert!((minimap_tick_offset(60_000.0, 60_000.0, height) - 200.0).abs() < 1e-4);
+        // Cues past the reported duration stay on the strip.
+        assert!((minimap_tick_offset(90_000.0, 60_000.0, height) - 200.0).abs() < 1e-4);
+        assert_eq!(minimap_tick_offset(1_000.0, 0.0, height), 0.0);
+    }
+
+    #[test]
+    fn minimap_click_maps_back_to_time() {
+        let height = 202.0;
+        let offset = minimap_tick_offset(45_000.0, 60_000.0, height);
+        assert!((minimap_time_for_offset(offset, 60_000.0, height) - 45_000.0).abs() < 1.0);
+        assert_eq!(minimap_time_for_offset(-10.0, 60_000.0, height), 0.0);
+    }
+}
@@ -57,6 +57,7 @@ pub struct VideoRoiOverlay {
     roi: RoiConfig,
     reset_roi: RoiConfig,
     dragging: Option<DragState>,
+    aspect_lock: Option<f32>,
     visible: bool,
     sender: watch::Sender<RoiConfig>,
     color: Rgba,
@@ -77,6 +78,7 @@ impl VideoRoiOverlay {
                 roi,
                 reset_roi: roi,
                 dragging: None,
+                aspect_lock: None,
                 visible: true,
                 sender,
                 color,
@@ -129,6 +131,24 @@ impl VideoRoiOverlay {
         cx.notify();
     }
 
+    /// Lock resizing to the current ROI's width/height ratio, or release the lock.
+    pub fn set_aspect_locked(&mut self, locked: bool, cx: &mut Context<Self>) {
+        let next = if locked {
+            roi_aspect_ratio(self.roi)
+        } else {
+            None
+        };
+        if self.aspect_lock == next {
+            return;
+        }
+        self.aspect_lock = next;
+        cx.notify();
+    }
+
+    pub fn aspect_locked(&self) -> bool {
+        self.aspect_lock.is_some()
+    }
+
     pub fn set_color_picker(
         &mut self,
         picker: Option<Entity<ColorPicker>>,
@@ -244,42 +264,13 @@ impl VideoRoiOverlay {
         let dx = (position.x - drag.origin.x) / picture.size.width;
         let dy = (position.y - drag.origin.y) / picture.size.height;
 
-        let (mut left, mut top, mut right, mut bottom) = roi_edges(drag.roi);
         let min_height = min_roi_height(picture);
         let min_width = min_roi_width(picture);
 
-        match drag.corner {
-            DragCorner::TopLeft => {
-                let max_left = (right - min_width).max(0.0);
-                left = (left + dx).clamp(0.0, max_left);
-                let max_top = (bottom - min_height).max(0.0);
-                top = (top + dy).clamp(0.0, max_top);
-            }
-            DragCorner::TopRight => {
-                let min_right = (left + min_width).min(1.0);
-                right = (right + dx).clamp(min_right, 1.0);
-                let max_top = (bottom - min_height).max(0.0);
-                top = (top + dy).clamp(0.0, max_top);
-            }
-            DragCorner::BottomLeft => {
-                let max_left = (right - min_width).max(0.0);
-                left = (left + dx).clamp(0.0, max_left);
-                let min_bottom = (top + min_height).min(1.0);
-                bottom = (bottom + dy).clamp(min_bottom, 1.0);
-            }
-            DragCorner::BottomRight => {
-                let min_right = (left + min_width).min(1.0);
-                right = (right + dx).clamp(min_right, 1.0);
-                let min_bottom = (top + min_height).min(1.0);
-                bottom = (bottom + dy).clamp(min_bottom, 1.0);
-            }
-        }
-
-        let next = RoiConfig {
-            x: left,
-            y: top,
-            width: (right - left).max(0.0),
-            height: (bottom - top).max(0.0),
+        let next = if let Some(ratio) = self.aspect_lock {
+            constrained_resize(drag.roi, drag.corner, dx, dy, ratio, min_width, min_height)
+        } else {
+            free_resize(drag.roi, drag.corner, dx, dy, min_width, min_height)
         };
 
         if next != self.roi {
@@ -527,6 +518,100 @@ fn roi_edges(roi: RoiConfig) -> (f32, f32, f32, f32) {
     (left, top, right, bottom)
 }
 
+fn free_resize(
+    roi: RoiConfig,
+    corner: DragCorner,
+    dx: f32,
+    dy: f32,
+    min_width: f32,
+    min_height: f32,
+) -> RoiConfig {
+    let (mut left, mut top, mut right, mut bottom) = roi_edges(roi);
+
+    match corner {
+        DragCorner::TopLeft => {
+            let max_left = (right - min_width).max(0.0);
+            left = (left + dx).clamp(0.0, max_left);
+            let max_top = (bottom - min_height).max(0.0);
+            top = (top + dy).clamp(0.0, max_top);
+        }
+        DragCorner::TopRight => {
+            let min_right = (left + min_width).min(1.0);
+            right = (right + dx).clamp(min_right, 1.0);
+            let max_top = (bottom - min_height).max(0.0);
+            top = (top + dy).clamp(0.0, max_top);
+        }
+        DragCorner::BottomLeft => {
+            let max_left = (right - min_width).max(0.0);
+            left = (left + dx).clamp(0.0, max_left);
+            let min_bottom = (top + min_height).min(1.0);
+            bottom = (bottom + dy).clamp(min_bottom, 1.0);
+        }
+        DragCorner::BottomRight => {
+            let min_right = (left + min_width).min(1.0);
+            right = (right + dx).clamp(min_right, 1.0);
+            let min_bottom = (top + min_height).min(1.0);
+            bottom = (bottom + dy).clamp(min_bottom, 1.0);
+        }
+    }
+
+    RoiConfig {
+        x: left,
+        y: top,
+        width: (right - left).max(0.0),
+        height: (bottom - top).max(0.0),
+    }
+}
+
+/// Resize from `corner` while keeping the opposite corner fixed and `width / height == ratio`.
+///
+/// The drag axis that moves further wins; the other dimension follows. The result is clamped to
+/// the picture without breaking the ratio.
+fn constrained_resize(
+    roi: RoiConfig,
+    corner: DragCorner,
+    dx: f32,
+    dy: f32,
+    ratio: f32,
+    min_width: f32,
+    min_height: f32,
+) -> RoiConfig {
+    let (left, top, right, bottom) = roi_edges(roi);
+    let width = right - left;
+    let height = bottom - top;
+    let moves_left = matches!(corner, DragCorner::TopLeft | DragCorner::BottomLeft);
+    let moves_top = matches!(corner, DragCorner::TopLeft | DragCorner::TopRight);
+
+    let grow_x = if moves_left { -dx } else { dx };
+    let grow_y = if moves_top { -dy } else { dy };
+    let width_from_x = width + grow_x;
+    let width_from_y = (height + grow_y) * ratio;
+    let target = if (width_from_x - width).abs() >= (width_from_y - width).abs() {
+        width_from_x
+    } else {
+        width_from_y
+    };
+
+    let room_x = if moves_left { right } else { 1.0 - left };
+    let room_y = if moves_top { bottom } else { 1.0 - top };
+    let max_width = room_x.min(room_y * ratio).max(0.0);
+    let min_width = min_width.max(min_height * ratio).min(max_width);
+    let width = target.clamp(min_width, max_width);
+    let height = width / ratio;
+
+    RoiConfig {
+        x: if moves_left { right - width } else { left },
+        y: if moves_top { bottom - height } else { top },
+        width,
+        height,
+    }
+}
+
+fn roi_aspect_ratio(roi: RoiConfig) -> Option<f32> {
+    let ratio = roi.width / roi.height;
+    (roi.width > 0.0 && roi.height > 0.0 && ratio.is_finite()).then_some(ratio)
+}
+
 fn min_roi_height(picture: Bounds<Pixels>) -> f32 {
     let height_px: f32 = picture.size.height.into();
     if height_px <= 0.0 {
@@ -557,3 +642,70 @@ fn cursor_for_corner(corner: DragCorner) -> CursorStyle {
         DragCorner::TopRight | DragCorner::BottomLeft => CursorStyle::ResizeUpRightDownLeft,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_roi_close(actual: RoiConfig, expected: RoiConfig) {
+        for (a, e) in [
+            (actual.x, expected.x),
+            (actual.y, expected.y),
+            (actual.width, expected.width),
+            (actual.height, expected.height),
+        ] {
+            assert!(
+                (a - e).abs() < 1e-5,
+                "expected {expected:?}, got {actual:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn locked_resize_keeps_ratio_and_stays_in_frame() {
+        let roi = RoiConfig {
+            x: 0.2,
+            y: 0.5,
+            width: 0.4,
+            height: 0.2,
+        };
+        let ratio = roi_aspect_ratio(roi).expect("ratio");
+
+        let grown = constrained_resize(roi, DragCorner::BottomRight, 0.1, 0.0, ratio, 0.05, 0.05);
+        assert_roi_close(
+            grown,
+            RoiConfig {
+                x: 0.2,
+                y: 0.5,
+                width: 0.5,
+                height: 0.25,
+            },
+        );
+
+        // A vertical drag past the bottom edge is limited by the remaining height.
+        let clamped = constrained_resize(roi, DragCorner::BottomRight, 0.0, 0.6, ratio, 0.05, 0.05);
+        assert_roi_close(
+            clamped,
+            RoiConfig {
+                x: 0.2,
+                y: 0.5,
+                width: 0.8,
+                height: 0.4,
+            },
+        );
+        assert!(clamped.y + clamped.height <= 1.0 + 1e-6);
+        assert!(clamped.x + clamped.width <= 1.0 + 1e-6);
+
+        // Dragging the top-left corner keeps the bottom-right corner anchored.
+        let shrunk = constrained_resize(roi, DragCorner::TopLeft, 0.2, 0.0, ratio, 0.05, 0.05);
+        assert_roi_close(
+            shrunk,
+            RoiConfig {
+                x: 0.4,
+                y: 0.6,
+                width: 0.2,
+                height: 0.1,
+            },
+        );
+    }
+}
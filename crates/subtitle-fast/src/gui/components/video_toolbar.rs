@@ -249,6 +249,16 @@ impl VideoToolbar {
         self.set_roi_visible(visible, cx);
     }
 
+    fn toggle_roi_aspect_lock(&mut self, cx: &mut Context<Self>) {
+        let Some(roi_overlay) = self.roi_overlay.clone() else {
+            return;
+        };
+        roi_overlay.update(cx, |overlay, cx| {
+            let locked = !overlay.aspect_locked();
+            overlay.set_aspect_locked(locked, cx);
+        });
+    }
+
     fn set_highlight_visible(&mut self, visible: bool, cx: &mut Context<Self>) {
         if self.highlight_visible == visible {
             return;
@@ -397,6 +407,54 @@ impl Render for VideoToolbar {
             view
         };
 
+        let aspect_locked = self
+            .roi_overlay
+            .as_ref()
+            .is_some_and(|overlay| overlay.read(cx).aspect_locked());
+        let aspect_icon_color = if enabled {
+            if aspect_locked {
+                text_active_y.into()
+            } else {
+                text_hover.into()
+            }
+        } else {
+            text_inactive.into()
+        };
+        let aspect_icon = if aspect_locked {
+            Icon::Lock
+        } else {
+            Icon::LockOpen
+        };
+        let aspect_lock_button = {
+            let mut view = div()
+                .id(("video-view-lock-roi-aspect", cx.entity_id()))
+                .flex()
+                .items_center()
+                .justify_center()
+                .h(px(26.0))
+                .w(px(26.0))
+                .rounded(px(6.0))
+                .bg(container_bg)
+                .border_1()
+                .border_color(container_border)
+                .child(
+                    icon_sm(aspect_icon, aspect_icon_color)
+                        .w(px(12.0))
+                        .h(px(12.0)),
+                );
+
+            if enabled && self.roi_overlay.is_some() {
+                view = view
+                    .cursor_pointer()
+                    .hover(|style| style.bg(hover_bg))
+                    .on_click(cx.listener(|this, _event, _window, cx| {
+                        this.toggle_roi_aspect_lock(cx);
+                    }));
+            }
+
+            view
+        };
+
         let highlight_visible = self.highlight_visible;
         let highlight_icon_color = if enabled {
             if highlight_visible {
@@ -629,6 +687,7 @@ impl Render for VideoToolbar {
             .gap(px(6.0))
             .child(roi_toggle_button)
             .child(reset_button)
+            .child(aspect_lock_button)
             .child(divider("video-toolbar-divider-roi"))
             .child(highlight_toggle_button)
             .child(validator_toggle_button);
@@ -736,6 +795,7 @@ impl ValidatorOverlayState {
             config.luma_band = LumaBandConfig {
                 target: luma.target,
                 delta: luma.delta,
+                softness: 0,
             };
             match build_detector(detector_kind, config) {
                 Ok(detector) => {
@@ -0,0 +1,88 @@
+use gpui::*;
+
+use crate::gui::app::AppAssets;
+
+/// Family the subtitle text starts from; the platform UI font.
+pub const SUBTITLE_FONT_FAMILY: &str = ".SystemUIFont";
+
+/// A font file shipped in the app's assets and registered at startup.
+pub struct BundledFont {
+    pub family: &'static str,
+    pub path: &'static str,
+}
+
+/// Fonts embedded with the app so Arabic text has glyphs whatever the platform has installed.
+/// CJK fonts are too large to ship; CJK text relies on the platform fonts in
+/// [`SUBTITLE_FONT_FALLBACKS`].
+pub const BUNDLED_FONTS: &[BundledFont] = &[BundledFont {
+    family: "Noto Sans Arabic",
+    path: "fonts/NotoSansArabic.ttf",
+}];
+
+/// Families tried, in order, for glyphs the UI font lacks: the bundled Arabic font first, then
+/// the CJK fonts macOS and Windows ship and most Linux desktops install.
+pub const SUBTITLE_FONT_FALLBACKS: &[&str] = &[
+    "Noto Sans Arabic",
+    "PingFang SC",
+    "Microsoft YaHei",
+    "Noto Sans CJK SC",
+];
+
+/// Adds the bundled fonts to the text system so `subtitle_font` can fall back to them.
+pub fn register_bundled_fonts(cx: &mut App) -> Result<()> {
+    let fonts = BUNDLED_FONTS
+        .iter()
+        .filter_map(|bundled| AppAssets.load(bundled.path).ok().flatten())
+        .collect();
+    cx.text_system().add_fonts(fonts)
+}
+
+/// Font for recognized subtitle text, so Arabic cues render instead of tofu boxes, as do CJK
+/// cues wherever a platform CJK font is installed.
+pub fn subtitle_font() -> Font {
+    Font {
+        fallbacks: Some(FontFallbacks::from_fonts(
+            SUBTITLE_FONT_FALLBACKS
+                .iter()
+                .map(|family| family.to_string())
+                .collect(),
+        )),
+        ..font(SUBTITLE_FONT_FAMILY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BUNDLED_FONTS, SUBTITLE_FONT_FALLBACKS};
+    use crate::gui::app::AppAssets;
+    use gpui::AssetSource;
+
+    #[test]
+    fn bundled_fonts_are_embedded_and_part_of_the_fallback_stack() {
+        assert!(!BUNDLED_FONTS.is_empty());
+        for bundled in BUNDLED_FONTS {
+            let data = AppAssets
+                .load(bundled.path)
+                .expect("asset lookup")
+                .unwrap_or_else(|| panic!("{} is not embedded", bundled.path));
+            // TrueType or CFF-flavoured OpenType.
+            assert!(
+                data.starts_with(&[0, 1, 0, 0]) || data.starts_with(b"OTTO"),
+                "{} is not a font file",
+                bundled.path
+            );
+            assert!(
+                SUBTITLE_FONT_FALLBACKS.contains(&bundled.family),
+                "{} is bundled but never used",
+                bundled.family
+            );
+        }
+        assert!(
+            AppAssets
+                .list("fonts/")
+                .expect("asset list")
+                .iter()
+                .any(|path| path.as_ref() == BUNDLED_FONTS[0].path)
+        );
+    }
+}
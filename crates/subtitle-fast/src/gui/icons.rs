@@ -20,6 +20,8 @@ pub enum Icon {
     Info,
     Inbox,
     LifeBuoy,
+    Lock,
+    LockOpen,
     Logo,
     Merge,
     MessageSquare,
@@ -35,6 +37,7 @@ pub enum Icon {
     Search,
     SlidersHorizontal,
     RotateCcw,
+    Scissors,
     Sparkles,
     Stop,
     Sun,
@@ -62,6 +65,8 @@ impl Icon {
             Self::Info => "icons/info.svg",
             Self::Inbox => "icons/inbox.svg",
             Self::LifeBuoy => "icons/life-buoy.svg",
+            Self::Lock => "icons/lock.svg",
+            Self::LockOpen => "icons/lock-open.svg",
             Self::Logo => "icons/logo.svg",
             Self::Merge => "icons/merge.svg",
             Self::MessageSquare => "icons/message-square.svg",
@@ -77,6 +82,7 @@ impl Icon {
             Self::Search => "icons/search.svg",
             Self::SlidersHorizontal => "icons/sliders-horizontal.svg",
             Self::RotateCcw => "icons/rotate-ccw.svg",
+            Self::Scissors => "icons/scissors.svg",
             Self::Sparkles => "icons/sparkles.svg",
             Self::Stop => "icons/square.svg",
             Self::Sun => "icons/sun.svg",
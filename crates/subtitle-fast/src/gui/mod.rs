@@ -1,5 +1,6 @@
 pub mod app;
 pub mod components;
+pub mod fonts;
 pub mod icons;
 pub mod menus;
 pub mod runtime;
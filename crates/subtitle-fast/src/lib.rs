@@ -1,10 +1,34 @@
 pub mod backend;
+/// Configuration dry run used by `--check`.
+pub mod check;
 pub mod cli;
+/// Trail of manual cue edits made in the GUI, exported for QA review.
+pub mod edit_audit;
+/// Legacy text encodings for written subtitle files.
+pub mod encoding;
+/// Per-cue frame indices written with `--timing-frames`.
+pub mod frame_timing;
+/// Level-filtered stderr diagnostics for the CLI.
+pub mod log;
+/// Per-cue JSON sidecar written with `--emit-metadata`.
+pub mod metadata;
 /// Model asset helpers for ORT OCR.
 pub mod model;
+/// Subtitle file formats shared by the CLI `--format` flag and the GUI export.
+pub mod output_format;
+/// Metadata-only inspection of an input used by `probe`.
+pub mod probe;
+/// Per-script share of the recognized text, printed after a CLI run.
+pub mod script_summary;
 pub mod settings;
 pub mod stage;
 pub mod subtitle;
+/// Subtitle store that spills finalized cues to disk past a memory cap.
+pub mod subtitle_buffer;
+/// Comparison of a subtitle track against a previously exported reference.
+pub mod subtitle_diff;
+/// Directory polling for `--watch`.
+pub mod watch;
 
 #[cfg(feature = "gui")]
 pub mod gui;
@@ -0,0 +1,143 @@
+//! Stderr diagnostics filtered by the CLI's `-q/--quiet` and `-v/--verbose` flags.
+//!
+//! Use [`log_error!`](crate::log_error), [`log_warn!`](crate::log_warn),
+//! [`log_info!`](crate::log_info) and [`log_debug!`](crate::log_debug) instead of `eprintln!`.
+//! Progress bars and the run summary on stdout check [`enabled`] so `--quiet` hides them too.
+
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How much the CLI reports, from least to most.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    /// Failures only (`--quiet`).
+    Error,
+    /// Failures plus problems the run works around, such as a skipped region.
+    Warn,
+    /// Warnings plus routine progress such as the chosen backend.
+    #[default]
+    Info,
+    /// Everything, including the detector's per-region `REGION_DEBUG` trace (`--verbose`).
+    Debug,
+}
+
+impl LogLevel {
+    pub fn from_flags(quiet: bool, verbose: bool) -> Self {
+        if quiet {
+            LogLevel::Error
+        } else if verbose {
+            LogLevel::Debug
+        } else {
+            LogLevel::Info
+        }
+    }
+}
+
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(LogLevel::Info as u8);
+
+/// Sets the process-wide level; `Debug` also turns on the detector's region trace.
+pub fn init(level: LogLevel) {
+    MAX_LEVEL.store(level as u8, Ordering::Relaxed);
+    if level >= LogLevel::Debug {
+        subtitle_fast_validator::subtitle_detection::set_region_debug(true);
+    }
+}
+
+pub fn max_level() -> LogLevel {
+    match MAX_LEVEL.load(Ordering::Relaxed) {
+        0 => LogLevel::Error,
+        1 => LogLevel::Warn,
+        2 => LogLevel::Info,
+        _ => LogLevel::Debug,
+    }
+}
+
+pub fn enabled(level: LogLevel) -> bool {
+    level <= max_level()
+}
+
+#[doc(hidden)]
+pub fn emit(level: LogLevel, args: fmt::Arguments<'_>) {
+    emit_to(&mut io::stderr().lock(), max_level(), level, args);
+}
+
+fn emit_to(out: &mut impl Write, max: LogLevel, level: LogLevel, args: fmt::Arguments<'_>) {
+    if level <= max {
+        let _ = writeln!(out, "{args}");
+    }
+}
+
+/// Reports a failure; printed at every level.
+#[macro_export]
+macro_rules! log_error {
+    ($($arg:tt)*) => {
+        $crate::log::emit($crate::log::LogLevel::Error, format_args!($($arg)*))
+    };
+}
+
+/// Reports a problem the run works around; hidden by `--quiet`.
+#[macro_export]
+macro_rules! log_warn {
+    ($($arg:tt)*) => {
+        $crate::log::emit($crate::log::LogLevel::Warn, format_args!($($arg)*))
+    };
+}
+
+/// Reports routine progress; hidden by `--quiet`.
+#[macro_export]
+macro_rules! log_info {
+    ($($arg:tt)*) => {
+        $crate::log::emit($crate::log::LogLevel::Info, format_args!($($arg)*))
+    };
+}
+
+/// Reports detail only wanted with `--verbose`.
+#[macro_export]
+macro_rules! log_debug {
+    ($($arg:tt)*) => {
+        $crate::log::emit($crate::log::LogLevel::Debug, format_args!($($arg)*))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(max: LogLevel) -> String {
+        let mut out = Vec::new();
+        emit_to(
+            &mut out,
+            max,
+            LogLevel::Error,
+            format_args!("decode failed"),
+        );
+        emit_to(
+            &mut out,
+            max,
+            LogLevel::Warn,
+            format_args!("region skipped"),
+        );
+        emit_to(
+            &mut out,
+            max,
+            LogLevel::Info,
+            format_args!("initialized backend"),
+        );
+        emit_to(&mut out, max, LogLevel::Debug, format_args!("region trace"));
+        String::from_utf8(out).expect("utf-8")
+    }
+
+    #[test]
+    fn quiet_keeps_errors_and_drops_routine_messages() {
+        assert_eq!(render(LogLevel::from_flags(true, false)), "decode failed\n");
+        assert_eq!(
+            render(LogLevel::from_flags(false, false)),
+            "decode failed\nregion skipped\ninitialized backend\n"
+        );
+        assert_eq!(
+            render(LogLevel::from_flags(false, true)),
+            "decode failed\nregion skipped\ninitialized backend\nregion trace\n"
+        );
+    }
+}
@@ -6,16 +6,26 @@
 use std::env;
 use std::io::{self, IsTerminal, Write};
 use std::num::NonZeroUsize;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use clap::CommandFactory;
 use subtitle_fast::backend::{self, ExecutionPlan};
-use subtitle_fast::cli::{CliArgs, CliSources, parse_cli};
+use subtitle_fast::check;
+use subtitle_fast::cli::{CliArgs, CliCommand, CliSources, parse_cli};
 #[cfg(feature = "gui")]
 use subtitle_fast::gui::SubtitleFastApp;
+use subtitle_fast::log::{self, LogLevel};
 use subtitle_fast::model;
-use subtitle_fast::settings::{ConfigError, resolve_settings};
-use subtitle_fast::stage::PipelineConfig;
+use subtitle_fast::probe;
+use subtitle_fast::settings::{
+    ConfigError, EffectiveSettings, resolve_settings, save_detection_settings,
+};
+use subtitle_fast::stage::{self, PipelineConfig};
+use subtitle_fast::watch::WatchedDir;
+use subtitle_fast::{log_debug, log_error, log_info};
+use subtitle_fast_ocr::OcrEngine;
 use subtitle_fast_types::DecoderError;
 
 #[tokio::main(flavor = "multi_thread")]
@@ -38,8 +48,8 @@ fn run_gui() -> Result<(), DecoderError> {
     use futures_channel::mpsc::unbounded;
     use gpui::*;
     use subtitle_fast::gui::components::DownloadWindow;
-    use subtitle_fast::gui::components::bind_text_input_keys;
-    use subtitle_fast::gui::{AppAssets, menus, runtime};
+    use subtitle_fast::gui::components::{bind_subtitle_editor_keys, bind_text_input_keys};
+    use subtitle_fast::gui::{AppAssets, fonts, menus, runtime};
 
     let app = Application::new().with_assets(AppAssets);
     #[cfg(target_os = "macos")]
@@ -54,7 +64,11 @@ fn run_gui() -> Result<(), DecoderError> {
     }
     app.run(|cx: &mut App| {
         runtime::init(tokio::runtime::Handle::current());
+        if let Err(err) = fonts::register_bundled_fonts(cx) {
+            log_error!("failed to register bundled fonts: {err}");
+        }
         bind_text_input_keys(cx);
+        bind_subtitle_editor_keys(cx);
         menus::register_actions(cx);
         #[cfg(target_os = "macos")]
         cx.bind_keys([gpui::KeyBinding::new(
@@ -68,7 +82,8 @@ fn run_gui() -> Result<(), DecoderError> {
             menus::set_app_menus(cx, &[], false);
         }
         let settings = subtitle_fast::settings::resolve_gui_settings().ok();
-        let model_paths = match model::init_ort_model_paths(None) {
+        let config_path = subtitle_fast::settings::resolve_gui_config_path();
+        let model_paths = match model::init_ort_model_paths(config_path.as_deref()) {
             Ok(paths) => Some(paths),
             Err(err) => {
                 eprintln!("ort model path resolution failed: {err}");
@@ -76,7 +91,7 @@ fn run_gui() -> Result<(), DecoderError> {
             }
         };
 
-        if should_prepare_ort(settings.as_ref()) {
+        if model::should_prepare_ort(settings.as_ref()) {
             match model_paths {
                 Some(paths) if !model::ort_models_present(&paths) => {
                     let Some(handle) = DownloadWindow::open(cx) else {
@@ -109,11 +124,19 @@ fn run_gui() -> Result<(), DecoderError> {
                         let _ = progress_tx_events.unbounded_send(event);
                     });
                     let download_paths = paths.clone();
+                    let max_concurrent_downloads = settings
+                        .as_ref()
+                        .map_or(model::DEFAULT_MAX_CONCURRENT_DOWNLOADS, |settings| {
+                            settings.ocr.max_concurrent_downloads
+                        });
 
                     if runtime::spawn(async move {
-                        let result =
-                            model::download_ort_models(&download_paths, Some(progress_callback))
-                                .await;
+                        let result = model::download_ort_models(
+                            &download_paths,
+                            max_concurrent_downloads,
+                            Some(progress_callback),
+                        )
+                        .await;
                         let final_event = match result {
                             Ok(()) => model::ModelDownloadEvent::Completed,
                             Err(err) => model::ModelDownloadEvent::Failed {
@@ -167,50 +190,137 @@ fn run_gui() -> Result<(), DecoderError> {
     Ok(())
 }
 
+/// What the command line asked to run.
+enum CliRun {
+    /// One plan per input, with how many of them may run at once.
+    Inputs(Vec<ExecutionPlan>, usize),
+    /// Videos showing up in a directory, planned as they become complete.
+    Watch(WatchedDir, Box<PlanTemplate>, usize),
+}
+
+/// Everything an [`ExecutionPlan`] needs besides its input, so plans can be built for videos
+/// found after startup. The OCR engine is built once and shared by every plan.
+struct PlanTemplate {
+    settings: EffectiveSettings,
+    engine: Arc<dyn OcrEngine>,
+    config: subtitle_fast_decoder::Configuration,
+    backend_locked: bool,
+    bench: bool,
+    heatmap: Option<PathBuf>,
+}
+
+impl PlanTemplate {
+    fn plans(&self, inputs: &[PathBuf]) -> Result<Vec<ExecutionPlan>, DecoderError> {
+        let pipelines =
+            PipelineConfig::for_batch(&self.settings, inputs, |_| Arc::clone(&self.engine))?;
+        Ok(inputs
+            .iter()
+            .zip(pipelines)
+            .map(|(input, pipeline)| ExecutionPlan {
+                config: subtitle_fast_decoder::Configuration {
+                    input: Some(input.clone()),
+                    ..self.config.clone()
+                },
+                backend_locked: self.backend_locked,
+                pipeline: PipelineConfig {
+                    decoder_start_frame: self.config.start_frame,
+                    ..pipeline
+                },
+                bench: self.bench,
+                heatmap: self.heatmap.clone(),
+            })
+            .collect())
+    }
+}
+
 async fn run_cli() -> Result<(), DecoderError> {
-    match prepare_execution_plan().await? {
-        Some(plan) => backend::run(plan).await,
+    match prepare_cli_run().await? {
+        Some(CliRun::Inputs(mut plans, _)) if plans.len() == 1 => {
+            backend::run(plans.remove(0)).await
+        }
+        Some(CliRun::Inputs(plans, jobs)) => backend::run_batch(plans, jobs).await,
+        Some(CliRun::Watch(watched, template, jobs)) => {
+            backend::run_watch(watched, jobs, |inputs| template.plans(inputs)).await
+        }
         None => Ok(()),
     }
 }
 
-async fn prepare_execution_plan() -> Result<Option<ExecutionPlan>, DecoderError> {
+async fn prepare_cli_run() -> Result<Option<CliRun>, DecoderError> {
     let (cli_args, cli_sources): (CliArgs, CliSources) = parse_cli();
+    log::init(LogLevel::from_flags(cli_args.quiet, cli_args.verbose));
 
     if cli_args.list_backends {
         backend::display_available_backends();
         return Ok(None);
     }
 
-    let input = match cli_args.input.clone() {
-        Some(path) => path,
-        None => {
-            usage();
+    if let Some(CliCommand::Probe { input }) = cli_args.command.as_ref() {
+        let report = probe::run_probe(&cli_args, &cli_sources, input)?;
+        print!("{report}");
+        return Ok(None);
+    }
+
+    if cli_args.check {
+        let report = check::run_check(&cli_args, &cli_sources);
+        print!("{report}");
+        if report.passed() {
             return Ok(None);
         }
-    };
+        return Err(DecoderError::configuration("configuration check failed"));
+    }
 
-    if !input.exists() {
+    let inputs = cli_args.inputs.clone();
+    if let Some(dir) = cli_args.watch.as_ref().filter(|dir| !dir.is_dir()) {
+        return Err(DecoderError::configuration(format!(
+            "watch directory '{}' does not exist",
+            dir.display()
+        )));
+    }
+    if inputs.is_empty() && cli_args.watch.is_none() && cli_args.save_effective_config.is_none() {
+        usage();
+        return Ok(None);
+    }
+    if let Some(input) = inputs.iter().find(|input| !input.exists()) {
         return Err(DecoderError::configuration(format!(
             "input file '{}' does not exist",
             input.display()
         )));
     }
+    if inputs.len() > 1 && cli_args.heatmap.is_some() {
+        return Err(DecoderError::configuration(
+            "--heatmap takes a single input",
+        ));
+    }
 
     let resolved = resolve_settings(&cli_args, &cli_sources).map_err(map_config_error)?;
     let settings = resolved.settings;
+    if cli_args.watch.is_some() && settings.output.path.is_some() {
+        return Err(DecoderError::configuration(
+            "--watch writes subtitles next to each video; remove the configured output path",
+        ));
+    }
+    if let Some(path) = resolved.config_path.as_ref() {
+        log_debug!("using config {}", path.display());
+    }
+    if let Some(path) = cli_args.save_effective_config.as_ref() {
+        save_detection_settings(&settings.detection, path).map_err(map_config_error)?;
+        log_info!("saved detection settings to {}", path.display());
+        if inputs.is_empty() && cli_args.watch.is_none() {
+            return Ok(None);
+        }
+    }
     let model_paths = model::init_ort_model_paths(resolved.config_path.as_deref())
         .map_err(|err| DecoderError::configuration(err.to_string()))?;
 
-    if should_prepare_ort(Some(&settings)) && !model::ort_models_present(&model_paths) {
-        let proceed = ensure_ort_models_cli(&model_paths).await?;
+    if model::should_prepare_ort(Some(&settings)) && !model::ort_models_present(&model_paths) {
+        let proceed =
+            ensure_ort_models_cli(&model_paths, settings.ocr.max_concurrent_downloads).await?;
         if !proceed {
             return Ok(None);
         }
     }
 
-    let pipeline = PipelineConfig::from_settings(&settings, &input)?;
-
     let env_backend_present = std::env::var("SUBFAST_BACKEND").is_ok();
     let mut config = subtitle_fast_decoder::Configuration::from_env().unwrap_or_default();
     let backend_override = match settings.decoder.backend.as_ref() {
@@ -219,20 +329,28 @@ async fn prepare_execution_plan() -> Result<Option<ExecutionPlan>, DecoderError>
     };
     let backend_locked = backend_override.is_some() || env_backend_present;
     if let Some(backend_value) = backend_override {
-        config.backend = backend_value;
+        config = config.with_backend(backend_value)?;
     }
-    config.input = Some(input);
     if let Some(capacity) = settings.decoder.channel_capacity
         && let Some(non_zero) = NonZeroUsize::new(capacity)
     {
         config.channel_capacity = Some(non_zero);
     }
 
-    Ok(Some(ExecutionPlan {
+    let jobs = cli_args.jobs as usize;
+    let template = PlanTemplate {
+        engine: stage::build_ocr_engine(&settings),
+        settings,
         config,
         backend_locked,
-        pipeline,
-    }))
+        bench: cli_args.bench,
+        heatmap: cli_args.heatmap.clone(),
+    };
+    if let Some(dir) = cli_args.watch {
+        let watched = WatchedDir::new(dir, template.settings.output.format);
+        return Ok(Some(CliRun::Watch(watched, Box::new(template), jobs)));
+    }
+    Ok(Some(CliRun::Inputs(template.plans(&inputs)?, jobs)))
 }
 
 fn usage() {
@@ -253,29 +371,16 @@ fn open_main_window(cx: &mut gpui::App) {
     cx.activate(true);
 }
 
-fn should_prepare_ort(settings: Option<&subtitle_fast::settings::EffectiveSettings>) -> bool {
-    if !cfg!(feature = "ocr-ort") {
-        return false;
-    }
-
-    let backend = settings
-        .and_then(|settings| settings.ocr.backend.as_ref())
-        .map(|value| value.trim().to_ascii_lowercase());
-    let selected = backend.as_deref().unwrap_or("auto");
-    if selected == "ort" {
-        return true;
-    }
-    if selected == "vision" || selected == "noop" {
-        return false;
-    }
-    !cfg!(all(feature = "ocr-vision", target_os = "macos"))
-}
-
-async fn ensure_ort_models_cli(paths: &model::OrtModelPaths) -> Result<bool, DecoderError> {
+async fn ensure_ort_models_cli(
+    paths: &model::OrtModelPaths,
+    max_concurrent_downloads: usize,
+) -> Result<bool, DecoderError> {
     let progress = indicatif::ProgressBar::new(0);
     let progress_for_events = progress.clone();
     progress.set_style(download_spinner_style());
 
+    // The bar follows the most recently started file.
+    let current_file = Arc::new(AtomicUsize::new(0));
     let progress_handler = Arc::new(move |event: model::ModelDownloadEvent| match event {
         model::ModelDownloadEvent::Started {
             file_label,
@@ -283,6 +388,7 @@ async fn ensure_ort_models_cli(paths: &model::OrtModelPaths) -> Result<bool, Dec
             file_count,
             total_bytes,
         } => {
+            current_file.store(file_index, Ordering::Relaxed);
             progress_for_events.set_message(format!(
                 "downloading {file_label} ({file_index}/{file_count})"
             ));
@@ -296,9 +402,10 @@ async fn ensure_ort_models_cli(paths: &model::OrtModelPaths) -> Result<bool, Dec
             }
         }
         model::ModelDownloadEvent::Progress {
+            file_index,
             downloaded_bytes,
             total_bytes,
-        } => {
+        } if file_index == current_file.load(Ordering::Relaxed) => {
             if let Some(total) = total_bytes {
                 progress_for_events.set_length(total);
                 progress_for_events.set_position(downloaded_bytes);
@@ -306,13 +413,14 @@ async fn ensure_ort_models_cli(paths: &model::OrtModelPaths) -> Result<bool, Dec
                 progress_for_events.tick();
             }
         }
-        model::ModelDownloadEvent::Finished { file_label } => {
+        model::ModelDownloadEvent::Finished { file_label, .. } => {
             progress_for_events.set_message(format!("downloaded {file_label}"));
         }
         _ => {}
     });
 
-    let result = model::download_ort_models(paths, Some(progress_handler)).await;
+    let result =
+        model::download_ort_models(paths, max_concurrent_downloads, Some(progress_handler)).await;
     match result {
         Ok(()) => {
             progress.finish_with_message("model download complete");
@@ -328,9 +436,9 @@ async fn ensure_ort_models_cli(paths: &model::OrtModelPaths) -> Result<bool, Dec
 fn prompt_continue_after_download_failure(
     err: &model::ModelDownloadError,
 ) -> Result<bool, DecoderError> {
-    eprintln!("ort model download failed: {err}");
+    log_error!("ort model download failed: {err}");
     if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
-        eprintln!("continuing without the ORT model in non-interactive mode");
+        log_info!("continuing without the ORT model in non-interactive mode");
         return Ok(true);
     }
 
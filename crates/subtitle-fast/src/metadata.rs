@@ -0,0 +1,185 @@
+//! Sidecar metadata written next to the SRT output with `--emit-metadata`.
+//!
+//! The file is a JSON object with a `version` field and a `cues` array. Version 1 cues carry:
+//!
+//! - `index`: 1-based position in the sorted output, matching the SRT cue number
+//! - `id`: pipeline-assigned subtitle id
+//! - `start_ms` / `end_ms`: cue timing in milliseconds
+//! - `lines`: cue text lines in display order
+//! - `confidence`: mean OCR confidence across lines, or `null` when the engine reports none
+//! - `region`: `{x, y, width, height}` bounding box of the detected regions, normalized to the
+//!   frame, or `null` when unknown
+//!
+//! Fields are only ever added within a version; renames or removals bump `version`.
+
+use serde::Serialize;
+
+use crate::subtitle::MergedSubtitle;
+
+pub const METADATA_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize)]
+struct SubtitleMetadata {
+    version: u32,
+    cues: Vec<CueMetadata>,
+}
+
+#[derive(Debug, Serialize)]
+struct CueMetadata {
+    index: usize,
+    id: u64,
+    start_ms: u64,
+    end_ms: u64,
+    lines: Vec<String>,
+    confidence: Option<f32>,
+    region: Option<RegionMetadata>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+struct RegionMetadata {
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+}
+
+/// Render sidecar metadata for cues already sorted in output order.
+pub fn render_metadata(subtitles: &[MergedSubtitle]) -> String {
+    let cues = subtitles
+        .iter()
+        .enumerate()
+        .map(|(idx, subtitle)| cue_metadata(idx + 1, subtitle))
+        .collect();
+    let metadata = SubtitleMetadata {
+        version: METADATA_VERSION,
+        cues,
+    };
+    let mut json =
+        serde_json::to_string_pretty(&metadata).expect("subtitle metadata is always serializable");
+    json.push('\n');
+    json
+}
+
+fn cue_metadata(index: usize, subtitle: &MergedSubtitle) -> CueMetadata {
+    let timed = subtitle.as_timed();
+    let confidences: Vec<f32> = subtitle
+        .lines
+        .iter()
+        .filter_map(|line| line.confidence)
+        .collect();
+    let confidence = (!confidences.is_empty())
+        .then(|| confidences.iter().sum::<f32>() / confidences.len() as f32);
+
+    let region = subtitle
+        .lines
+        .iter()
+        .filter_map(|line| line.region)
+        .map(|roi| RegionMetadata {
+            x: roi.x,
+            y: roi.y,
+            width: roi.width,
+            height: roi.height,
+        })
+        .reduce(union_region);
+
+    CueMetadata {
+        index,
+        id: subtitle.id,
+        start_ms: subtitle.start_time.as_millis() as u64,
+        end_ms: subtitle.end_time.as_millis() as u64,
+        lines: timed.lines,
+        confidence,
+        region,
+    }
+}
+
+fn union_region(a: RegionMetadata, b: RegionMetadata) -> RegionMetadata {
+    let left = a.x.min(b.x);
+    let top = a.y.min(b.y);
+    let right = (a.x + a.width).max(b.x + b.width);
+    let bottom = (a.y + a.height).max(b.y + b.height);
+    RegionMetadata {
+        x: left,
+        y: top,
+        width: right - left,
+        height: bottom - top,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::subtitle::SubtitleLine;
+    use std::time::Duration;
+    use subtitle_fast_types::RoiConfig;
+
+    fn line(text: &str, confidence: Option<f32>, roi: RoiConfig) -> SubtitleLine {
+        SubtitleLine {
+            center: roi.y + roi.height * 0.5,
+            text: text.to_string(),
+            rtl: false,
+            confidence,
+            region: Some(roi),
+        }
+    }
+
+    #[test]
+    fn sidecar_lists_each_cue_with_geometry() {
+        let bottom = RoiConfig {
+            x: 0.25,
+            y: 0.8,
+            width: 0.5,
+            height: 0.1,
+        };
+        let top = RoiConfig {
+            x: 0.2,
+            y: 0.7,
+            width: 0.4,
+            height: 0.1,
+        };
+        let subtitles = [
+            MergedSubtitle {
+                id: 0,
+                start_time: Duration::from_millis(1_000),
+                end_time: Duration::from_millis(2_500),
+                start_frame: 30,
+                end_frame: 75,
+                lines: vec![line("hello", Some(0.9), bottom)],
+            },
+            MergedSubtitle {
+                id: 1,
+                start_time: Duration::from_millis(3_000),
+                end_time: Duration::from_millis(4_000),
+                start_frame: 90,
+                end_frame: 120,
+                lines: vec![line("second", Some(0.6), top), line("cue", None, bottom)],
+            },
+        ];
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render_metadata(&subtitles)).expect("valid json");
+        assert_eq!(json["version"], METADATA_VERSION);
+        let cues = json["cues"].as_array().expect("cues array");
+        assert_eq!(cues.len(), 2);
+
+        let first = &cues[0];
+        assert_eq!(first["index"], 1);
+        assert_eq!(first["id"], 0);
+        assert_eq!(first["start_ms"], 1_000);
+        assert_eq!(first["end_ms"], 2_500);
+        assert_eq!(first["lines"], serde_json::json!(["hello"]));
+        assert!((first["confidence"].as_f64().unwrap() - 0.9).abs() < 1e-6);
+        assert!((first["region"]["x"].as_f64().unwrap() - 0.25).abs() < 1e-6);
+        assert!((first["region"]["width"].as_f64().unwrap() - 0.5).abs() < 1e-6);
+
+        let second = &cues[1];
+        assert_eq!(second["index"], 2);
+        assert_eq!(second["lines"], serde_json::json!(["second", "cue"]));
+        assert!((second["confidence"].as_f64().unwrap() - 0.6).abs() < 1e-6);
+        let region = &second["region"];
+        assert!((region["x"].as_f64().unwrap() - 0.2).abs() < 1e-6);
+        assert!((region["y"].as_f64().unwrap() - 0.7).abs() < 1e-6);
+        assert!((region["width"].as_f64().unwrap() - 0.55).abs() < 1e-6);
+        assert!((region["height"].as_f64().unwrap() - 0.2).abs() < 1e-6);
+    }
+}
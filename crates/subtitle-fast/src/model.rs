@@ -6,7 +6,7 @@ use std::path::{Path, PathBuf};
 use std::sync::{Arc, OnceLock};
 
 #[cfg(feature = "ocr-ort")]
-use futures_util::StreamExt;
+use futures_util::{StreamExt, TryStreamExt, stream};
 #[cfg(feature = "ocr-ort")]
 use tokio::fs;
 #[cfg(feature = "ocr-ort")]
@@ -23,6 +23,10 @@ const ORT_MODEL_URL: &str =
 const ORT_DICT_URL: &str =
     "https://raw.githubusercontent.com/weidix/subtitle-fast/main/models/ch_PP-OCRv5_rec_infer.txt";
 
+/// Asset files fetched at once unless `ocr.max_concurrent_downloads` asks for more; one at a
+/// time is the safe choice on flaky links.
+pub const DEFAULT_MAX_CONCURRENT_DOWNLOADS: usize = 1;
+
 static ORT_MODEL_PATHS: OnceLock<OrtModelPaths> = OnceLock::new();
 
 /// Resolved file paths for ORT OCR assets.
@@ -92,12 +96,16 @@ pub enum ModelDownloadEvent {
         file_count: usize,
         total_bytes: Option<u64>,
     },
+    /// Bytes received so far for the file announced by the `Started` event with the same
+    /// `file_index`; files downloading concurrently interleave their events.
     Progress {
+        file_index: usize,
         downloaded_bytes: u64,
         total_bytes: Option<u64>,
     },
     Finished {
         file_label: String,
+        file_index: usize,
     },
     Completed,
     Failed {
@@ -187,10 +195,32 @@ pub fn ort_models_present(paths: &OrtModelPaths) -> bool {
     file_ready(&paths.model_path) && file_ready(&paths.dictionary_path)
 }
 
+/// Returns true when the resolved OCR backend may need the ORT model assets.
+pub fn should_prepare_ort(settings: Option<&settings::EffectiveSettings>) -> bool {
+    if !cfg!(feature = "ocr-ort") {
+        return false;
+    }
+
+    let backend = settings
+        .and_then(|settings| settings.ocr.backend.as_ref())
+        .map(|value| value.trim().to_ascii_lowercase());
+    let selected = backend.as_deref().unwrap_or("auto");
+    if selected == "ort" {
+        return true;
+    }
+    if selected == "vision" || selected == "noop" {
+        return false;
+    }
+    !cfg!(all(feature = "ocr-vision", target_os = "macos"))
+}
+
 #[cfg(feature = "ocr-ort")]
 /// Download missing ORT model assets, emitting progress events as data arrives.
+///
+/// Up to `max_concurrent_downloads` files are fetched at once; 0 is treated as 1.
 pub async fn download_ort_models(
     paths: &OrtModelPaths,
+    max_concurrent_downloads: usize,
     on_event: Option<Arc<dyn Fn(ModelDownloadEvent) + Send + Sync>>,
 ) -> Result<(), ModelDownloadError> {
     let assets = missing_assets(paths);
@@ -213,18 +243,20 @@ pub async fn download_ort_models(
         })?;
 
     let client = reqwest::Client::new();
-    let total_files = assets.len();
-    for (index, asset) in assets.iter().enumerate() {
-        download_asset(&client, asset, index + 1, total_files, on_event.as_ref()).await?;
-    }
-
-    Ok(())
+    download_assets(
+        &client,
+        &assets,
+        max_concurrent_downloads,
+        on_event.as_ref(),
+    )
+    .await
 }
 
 #[cfg(not(feature = "ocr-ort"))]
 /// Downloading ORT model assets requires the `ocr-ort` feature.
 pub async fn download_ort_models(
     _paths: &OrtModelPaths,
+    _max_concurrent_downloads: usize,
     _on_event: Option<Arc<dyn Fn(ModelDownloadEvent) + Send + Sync>>,
 ) -> Result<(), ModelDownloadError> {
     Err(ModelDownloadError::Unsupported {
@@ -232,7 +264,10 @@ pub async fn download_ort_models(
     })
 }
 
-fn resolve_ort_model_paths(config_path: Option<&Path>) -> Result<OrtModelPaths, ModelPathError> {
+/// Resolve ORT model paths for a config file location without caching them.
+pub fn resolve_ort_model_paths(
+    config_path: Option<&Path>,
+) -> Result<OrtModelPaths, ModelPathError> {
     let config_dir = resolve_config_dir(config_path)?;
     let model_dir = config_dir.join("models");
     Ok(OrtModelPaths {
@@ -295,17 +330,37 @@ fn file_ready(path: &Path) -> bool {
 #[derive(Debug, Clone)]
 struct ModelAsset {
     label: &'static str,
-    url: &'static str,
+    url: String,
     path: PathBuf,
 }
 
 #[cfg(feature = "ocr-ort")]
 impl ModelAsset {
-    fn new(label: &'static str, url: &'static str, path: PathBuf) -> Self {
-        Self { label, url, path }
+    fn new(label: &'static str, url: impl Into<String>, path: PathBuf) -> Self {
+        Self {
+            label,
+            url: url.into(),
+            path,
+        }
     }
 }
 
+/// Downloads `assets` with at most `max_concurrent` in flight, stopping at the first failure.
+#[cfg(feature = "ocr-ort")]
+async fn download_assets(
+    client: &reqwest::Client,
+    assets: &[ModelAsset],
+    max_concurrent: usize,
+    on_event: Option<&Arc<dyn Fn(ModelDownloadEvent) + Send + Sync>>,
+) -> Result<(), ModelDownloadError> {
+    let file_count = assets.len();
+    stream::iter(assets.iter().enumerate())
+        .map(|(index, asset)| download_asset(client, asset, index + 1, file_count, on_event))
+        .buffer_unordered(max_concurrent.max(1))
+        .try_collect()
+        .await
+}
+
 #[cfg(feature = "ocr-ort")]
 async fn download_asset(
     client: &reqwest::Client,
@@ -316,17 +371,17 @@ async fn download_asset(
 ) -> Result<(), ModelDownloadError> {
     let response =
         client
-            .get(asset.url)
+            .get(&asset.url)
             .send()
             .await
             .map_err(|err| ModelDownloadError::RequestFailed {
-                url: asset.url.to_string(),
+                url: asset.url.clone(),
                 message: err.to_string(),
             })?;
 
     if !response.status().is_success() {
         return Err(ModelDownloadError::HttpStatus {
-            url: asset.url.to_string(),
+            url: asset.url.clone(),
             status: response.status().as_u16(),
         });
     }
@@ -342,7 +397,11 @@ async fn download_asset(
         },
     );
 
-    let tmp_path = asset.path.with_extension("part");
+    // The model and dictionary share a stem, so keep the extension to give each its own
+    // partial file when both download at once.
+    let mut tmp_path = asset.path.clone().into_os_string();
+    tmp_path.push(".part");
+    let tmp_path = PathBuf::from(tmp_path);
     if let Some(parent) = tmp_path.parent() {
         fs::create_dir_all(parent)
             .await
@@ -372,7 +431,7 @@ async fn download_asset(
     let mut stream = response.bytes_stream();
     while let Some(chunk) = stream.next().await {
         let chunk = chunk.map_err(|err| ModelDownloadError::RequestFailed {
-            url: asset.url.to_string(),
+            url: asset.url.clone(),
             message: err.to_string(),
         })?;
 
@@ -391,6 +450,7 @@ async fn download_asset(
         emit_event(
             on_event,
             ModelDownloadEvent::Progress {
+                file_index,
                 downloaded_bytes: downloaded,
                 total_bytes,
             },
@@ -413,6 +473,7 @@ async fn download_asset(
         on_event,
         ModelDownloadEvent::Finished {
             file_label: asset.label.to_string(),
+            file_index,
         },
     );
 
@@ -466,4 +527,130 @@ mod tests {
         std::fs::write(&model_path, [1u8]).unwrap();
         assert!(ort_models_present(&paths));
     }
+
+    /// Serves each path's body from its own thread, holding the first half of every response
+    /// until all `bodies` have been requested or a few seconds have passed. Returns the base
+    /// URL and whether every request found the others in flight.
+    #[cfg(feature = "ocr-ort")]
+    fn serve_overlapping(
+        bodies: Vec<(&'static str, Vec<u8>)>,
+    ) -> (String, std::thread::JoinHandle<bool>) {
+        use std::io::{BufRead, BufReader, Write};
+        use std::net::TcpListener;
+        use std::sync::{Condvar, Mutex};
+        use std::time::Duration;
+
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+        let base = format!("http://{}", listener.local_addr().expect("local addr"));
+        let server = std::thread::spawn(move || {
+            let arrived = Arc::new((Mutex::new(0usize), Condvar::new()));
+            let expected = bodies.len();
+            let handlers: Vec<_> = (0..expected)
+                .map(|_| {
+                    let (stream, _) = listener.accept().expect("accept");
+                    let bodies = bodies.clone();
+                    let arrived = Arc::clone(&arrived);
+                    std::thread::spawn(move || {
+                        let mut reader = BufReader::new(stream);
+                        let mut request_line = String::new();
+                        reader.read_line(&mut request_line).expect("request line");
+                        let mut header = String::new();
+                        while reader.read_line(&mut header).expect("header") > 2 {
+                            header.clear();
+                        }
+                        let path = request_line.split_whitespace().nth(1).expect("path");
+                        let body = &bodies
+                            .iter()
+                            .find(|(name, _)| *name == path)
+                            .expect("body")
+                            .1;
+                        let mut stream = reader.into_inner();
+                        let (head, tail) = body.split_at(body.len() / 2);
+                        write!(
+                            stream,
+                            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                            body.len()
+                        )
+                        .and_then(|_| stream.write_all(head))
+                        .and_then(|_| stream.flush())
+                        .expect("write head");
+
+                        let (count, all_here) = &*arrived;
+                        let mut count = count.lock().unwrap();
+                        *count += 1;
+                        all_here.notify_all();
+                        let (count, _) = all_here
+                            .wait_timeout_while(count, Duration::from_secs(5), |count| {
+                                *count < expected
+                            })
+                            .unwrap();
+                        let overlapped = *count == expected;
+                        drop(count);
+                        stream.write_all(tail).expect("write tail");
+                        overlapped
+                    })
+                })
+                .collect();
+            handlers
+                .into_iter()
+                .all(|handler| handler.join().expect("handler"))
+        });
+        (base, server)
+    }
+
+    #[cfg(feature = "ocr-ort")]
+    #[tokio::test(flavor = "current_thread")]
+    async fn assets_download_concurrently_with_progress_per_file() {
+        use std::sync::Mutex;
+
+        let model_body: Vec<u8> = (0..70_000u32).map(|i| i as u8).collect();
+        let dict_body = b"a\nb\nc\n".repeat(500);
+        let (base, server) = serve_overlapping(vec![
+            ("/model.onnx", model_body.clone()),
+            ("/dict.txt", dict_body.clone()),
+        ]);
+
+        let dir = tempdir().unwrap();
+        let assets = [
+            ModelAsset::new(
+                "OCR model",
+                format!("{base}/model.onnx"),
+                dir.path().join("rec.onnx"),
+            ),
+            ModelAsset::new(
+                "OCR dictionary",
+                format!("{base}/dict.txt"),
+                dir.path().join("rec.txt"),
+            ),
+        ];
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&events);
+        let on_event: Arc<dyn Fn(ModelDownloadEvent) + Send + Sync> =
+            Arc::new(move |event| sink.lock().unwrap().push(event));
+        let client = reqwest::Client::builder().no_proxy().build().unwrap();
+
+        download_assets(&client, &assets, 2, Some(&on_event))
+            .await
+            .expect("downloads succeed");
+        assert!(
+            server.join().unwrap(),
+            "both requests were in flight together"
+        );
+
+        let sizes = [model_body.len() as u64, dict_body.len() as u64];
+        assert_eq!(std::fs::read(&assets[0].path).unwrap(), model_body);
+        assert_eq!(std::fs::read(&assets[1].path).unwrap(), dict_body);
+        let events = events.lock().unwrap();
+        for (file_index, size) in [1, 2].into_iter().zip(sizes) {
+            let last_progress = events.iter().rev().find_map(|event| match event {
+                ModelDownloadEvent::Progress {
+                    file_index: index,
+                    downloaded_bytes,
+                    total_bytes,
+                } if *index == file_index => Some((*downloaded_bytes, *total_bytes)),
+                _ => None,
+            });
+            assert_eq!(last_progress, Some((size, Some(size))), "file {file_index}");
+        }
+    }
 }
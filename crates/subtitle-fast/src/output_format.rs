@@ -0,0 +1,295 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::subtitle::{
+    MergedSubtitle, render_ass, render_jsonl, render_jsonl_where, render_srt, render_srt_where,
+    render_vtt,
+};
+
+/// Subtitle file format written by the CLI and the GUI export.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum OutputFormat {
+    #[default]
+    Srt,
+    Vtt,
+    Ass,
+    Jsonl,
+}
+
+impl OutputFormat {
+    /// Every format, in the order a picker should list them.
+    pub fn all() -> &'static [OutputFormat] {
+        &[
+            OutputFormat::Srt,
+            OutputFormat::Vtt,
+            OutputFormat::Ass,
+            OutputFormat::Jsonl,
+        ]
+    }
+
+    /// Name accepted by `--format` and `output.format`, which is also the file extension.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OutputFormat::Srt => "srt",
+            OutputFormat::Vtt => "vtt",
+            OutputFormat::Ass => "ass",
+            OutputFormat::Jsonl => "jsonl",
+        }
+    }
+
+    /// Maps a file extension, without the dot and in any case, to its format.
+    pub fn from_extension(extension: &str) -> Result<Self, UnknownOutputFormat> {
+        extension.parse()
+    }
+
+    /// Format implied by `path`'s extension; a missing extension is an error too.
+    pub fn from_path(path: &Path) -> Result<Self, UnknownOutputFormat> {
+        let extension = path
+            .extension()
+            .map(|ext| ext.to_string_lossy())
+            .unwrap_or_default();
+        Self::from_extension(&extension)
+    }
+
+    /// Renders cues already sorted in output order.
+    pub fn render(self, subtitles: &[MergedSubtitle]) -> String {
+        match self {
+            OutputFormat::Srt => render_srt(subtitles),
+            OutputFormat::Vtt => render_vtt(subtitles),
+            OutputFormat::Ass => render_ass(subtitles),
+            OutputFormat::Jsonl => render_jsonl(subtitles),
+        }
+    }
+
+    /// Renders only the cues whose id is in `ids`, for delivering corrections as a patch. SRT
+    /// numbers and JSONL `index` values match the full track's.
+    pub fn render_only(self, subtitles: &[MergedSubtitle], ids: &HashSet<u64>) -> String {
+        let keep = |cue: &MergedSubtitle| ids.contains(&cue.id);
+        match self {
+            OutputFormat::Srt => render_srt_where(subtitles, keep),
+            OutputFormat::Jsonl => render_jsonl_where(subtitles, keep),
+            OutputFormat::Vtt | OutputFormat::Ass => {
+                let kept: Vec<MergedSubtitle> =
+                    subtitles.iter().filter(|cue| keep(cue)).cloned().collect();
+                self.render(&kept)
+            }
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug)]
+pub struct UnknownOutputFormat(pub String);
+
+impl fmt::Display for UnknownOutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown output format '{}' (expected one of: {})",
+            self.0,
+            OutputFormat::all()
+                .iter()
+                .map(|format| format.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+impl std::error::Error for UnknownOutputFormat {}
+
+impl FromStr for OutputFormat {
+    type Err = UnknownOutputFormat;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_ascii_lowercase();
+        OutputFormat::all()
+            .iter()
+            .copied()
+            .find(|format| format.as_str() == lower)
+            .ok_or(UnknownOutputFormat(lower))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edit_audit::EditAudit;
+    use crate::subtitle::SubtitleLine;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    #[test]
+    fn extensions_map_to_formats_and_unknown_ones_are_rejected() {
+        assert_eq!(
+            OutputFormat::from_extension("srt").unwrap(),
+            OutputFormat::Srt
+        );
+        assert_eq!(
+            OutputFormat::from_extension("VTT").unwrap(),
+            OutputFormat::Vtt
+        );
+        assert_eq!(
+            OutputFormat::from_extension("ass").unwrap(),
+            OutputFormat::Ass
+        );
+        assert_eq!(
+            OutputFormat::from_extension("jsonl").unwrap(),
+            OutputFormat::Jsonl
+        );
+        assert_eq!(
+            OutputFormat::from_path(&PathBuf::from("out/movie.en.vtt")).unwrap(),
+            OutputFormat::Vtt
+        );
+
+        let err = OutputFormat::from_extension("txt").unwrap_err();
+        assert_eq!(err.0, "txt");
+        assert!(err.to_string().contains("srt, vtt, ass, jsonl"), "{err}");
+        assert!(OutputFormat::from_path(&PathBuf::from("subtitles")).is_err());
+    }
+
+    #[test]
+    fn all_lists_every_format_once_with_round_tripping_names() {
+        let names: Vec<&str> = OutputFormat::all().iter().map(|f| f.as_str()).collect();
+        assert_eq!(names, ["srt", "vtt", "ass", "jsonl"]);
+        for format in OutputFormat::all() {
+            assert_eq!(
+                OutputFormat::from_extension(format.as_str()).unwrap(),
+                *format
+            );
+        }
+    }
+
+    #[test]
+    fn each_format_renders_the_cue() {
+        let cue = MergedSubtitle {
+            id: 4,
+            start_time: Duration::from_millis(1_500),
+            end_time: Duration::from_millis(3_250),
+            start_frame: 0,
+            end_frame: 0,
+            lines: vec![SubtitleLine {
+                center: 0.9,
+                text: "Hello".to_string(),
+                rtl: false,
+                confidence: None,
+                region: None,
+            }],
+        };
+        let cues = [cue];
+
+        assert!(
+            OutputFormat::Srt
+                .render(&cues)
+                .contains("00:00:01,500 --> 00:00:03,250\nHello\n")
+        );
+        let vtt = OutputFormat::Vtt.render(&cues);
+        assert!(
+            vtt.starts_with("WEBVTT\n\n00:00:01.500 --> 00:00:03.250\nHello\n"),
+            "{vtt}"
+        );
+        let ass = OutputFormat::Ass.render(&cues);
+        assert!(ass.starts_with("[Script Info]"), "{ass}");
+        assert!(
+            ass.ends_with("Dialogue: 0,0:00:01.50,0:00:03.25,Default,,0,0,0,,Hello\n"),
+            "{ass}"
+        );
+        let jsonl = OutputFormat::Jsonl.render(&cues);
+        let record: serde_json::Value = serde_json::from_str(jsonl.trim_end()).expect("json");
+        assert_eq!(record["id"], 4);
+        assert_eq!(record["start_ms"], 1_500);
+        assert_eq!(record["lines"][0], "Hello");
+    }
+
+    #[test]
+    fn edited_only_export_keeps_the_edited_cues_and_their_numbers() {
+        let cue = |id: u64, text: &str| MergedSubtitle {
+            id,
+            start_time: Duration::from_secs(id * 2),
+            end_time: Duration::from_secs(id * 2 + 1),
+            start_frame: id,
+            end_frame: id,
+            lines: vec![SubtitleLine {
+                center: 0.9,
+                text: text.to_string(),
+                rtl: false,
+                confidence: None,
+                region: None,
+            }],
+        };
+        let mut cues: Vec<MergedSubtitle> =
+            (10..15).map(|id| cue(id, &format!("cue {id}"))).collect();
+        let mut audit = EditAudit::new();
+        for index in [1, 3] {
+            let before = cues[index].clone();
+            cues[index].lines[0].text = format!("fixed {}", before.id);
+            audit.record(&before, &cues[index]);
+        }
+        let edited = audit.edited_ids();
+
+        let srt = OutputFormat::Srt.render_only(&cues, &edited);
+        assert_eq!(
+            srt,
+            "2\n00:00:22,000 --> 00:00:23,000\nfixed 11\n\n\
+             4\n00:00:26,000 --> 00:00:27,000\nfixed 13\n"
+        );
+        let jsonl = OutputFormat::Jsonl.render_only(&cues, &edited);
+        let records: Vec<serde_json::Value> = jsonl
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("json"))
+            .collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(
+            (records[0]["index"].clone(), records[0]["id"].clone()),
+            (2.into(), 11.into())
+        );
+        assert_eq!(
+            (records[1]["index"].clone(), records[1]["id"].clone()),
+            (4.into(), 13.into())
+        );
+        assert_eq!(
+            OutputFormat::Vtt
+                .render_only(&cues, &edited)
+                .matches(" --> ")
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn jsonl_index_matches_srt_number_across_an_emptied_cue() {
+        let cue = |id: u64, text: &str| MergedSubtitle {
+            id,
+            start_time: Duration::from_secs(id * 2),
+            end_time: Duration::from_secs(id * 2 + 1),
+            start_frame: id,
+            end_frame: id,
+            lines: vec![SubtitleLine {
+                center: 0.9,
+                text: text.to_string(),
+                rtl: false,
+                confidence: None,
+                region: None,
+            }],
+        };
+        let cues = [cue(0, "first"), cue(1, "  "), cue(2, "third")];
+
+        let srt = OutputFormat::Srt.render(&cues);
+        assert!(srt.starts_with("1\n") && srt.contains("\n\n3\n"), "{srt}");
+        let indices: Vec<serde_json::Value> = OutputFormat::Jsonl
+            .render(&cues)
+            .lines()
+            .map(|line| {
+                serde_json::from_str::<serde_json::Value>(line).expect("json")["index"].clone()
+            })
+            .collect();
+        assert_eq!(indices, [1, 3]);
+    }
+}
@@ -0,0 +1,242 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use subtitle_fast_decoder::{Configuration, DecoderProvider, VideoMetadata};
+use subtitle_fast_types::{DecoderError, RoiConfig};
+
+use crate::backend::parse_backend;
+use crate::cli::{CliArgs, CliSources};
+use crate::settings::{DetectionSettings, resolve_settings};
+
+/// Facts about an input gathered by `probe` from the decoder's metadata alone.
+#[derive(Debug, Clone)]
+pub struct ProbeReport {
+    pub input: PathBuf,
+    pub backend: &'static str,
+    pub metadata: VideoMetadata,
+    pub samples_per_second: u32,
+    /// Sampled frames a run would feed to detection; `None` without a duration.
+    pub estimated_samples: Option<u64>,
+    /// ROI as frame fractions, `None` when detection covers the whole frame.
+    pub roi: Option<RoiConfig>,
+    pub warnings: Vec<String>,
+}
+
+impl fmt::Display for ProbeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unknown = || "unknown".to_string();
+        let metadata = &self.metadata;
+        writeln!(f, "input: {}", self.input.display())?;
+        writeln!(f, "backend: {}", self.backend)?;
+        let resolution = metadata
+            .width
+            .zip(metadata.height)
+            .map(|(width, height)| format!("{width}x{height}"));
+        writeln!(f, "resolution: {}", resolution.unwrap_or_else(unknown))?;
+        let fps = metadata.fps.map(|fps| format!("{fps:.3}"));
+        writeln!(f, "fps: {}", fps.unwrap_or_else(unknown))?;
+        let duration = metadata
+            .duration
+            .map(|duration| format!("{:.3}s", duration.as_secs_f64()));
+        writeln!(f, "duration: {}", duration.unwrap_or_else(unknown))?;
+        let frames = metadata.total_frames.map(|frames| frames.to_string());
+        writeln!(f, "frames: {}", frames.unwrap_or_else(unknown))?;
+        let samples = self.estimated_samples.map(|samples| format!("~{samples}"));
+        writeln!(
+            f,
+            "samples: {} at {}/s",
+            samples.unwrap_or_else(unknown),
+            self.samples_per_second
+        )?;
+        match self.roi {
+            Some(roi) => {
+                write!(
+                    f,
+                    "roi: x={:.3} y={:.3} width={:.3} height={:.3}",
+                    roi.x, roi.y, roi.width, roi.height
+                )?;
+                match roi_pixels(&roi, metadata) {
+                    Some((width, height)) => writeln!(f, " ({width}x{height} px)")?,
+                    None => writeln!(f)?,
+                }
+            }
+            None => writeln!(f, "roi: not set (full frame)")?,
+        }
+        for warning in &self.warnings {
+            writeln!(f, "warning: {warning}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Opens `input` with the configured decoder backend and reports its metadata without
+/// decoding frames.
+pub fn run_probe(
+    cli: &CliArgs,
+    sources: &CliSources,
+    input: &Path,
+) -> Result<ProbeReport, DecoderError> {
+    if !input.exists() {
+        return Err(DecoderError::configuration(format!(
+            "input file '{}' does not exist",
+            input.display()
+        )));
+    }
+    let settings = resolve_settings(cli, sources)
+        .map_err(|err| DecoderError::configuration(err.to_string()))?
+        .settings;
+    let mut config = Configuration::from_env().unwrap_or_default();
+    if let Some(name) = settings.decoder.backend.as_deref() {
+        config = config.with_backend(parse_backend(name)?)?;
+    }
+    config.input = Some(input.to_path_buf());
+    let provider = config.create_provider()?;
+    Ok(probe_provider(
+        input,
+        config.backend.as_str(),
+        provider.as_ref(),
+        &settings.detection,
+    ))
+}
+
+/// Builds the report for an already created `provider`.
+pub fn probe_provider(
+    input: &Path,
+    backend: &'static str,
+    provider: &dyn DecoderProvider,
+    detection: &DetectionSettings,
+) -> ProbeReport {
+    let metadata = provider.metadata();
+    let mut warnings = Vec::new();
+
+    let roi = match detection.fractional_roi(metadata.width, metadata.height) {
+        Ok(roi) => roi.filter(|roi| !is_full_frame(roi)),
+        Err(err) => {
+            warnings.push(format!("roi cannot be resolved: {err}"));
+            None
+        }
+    };
+    if let Some(roi) = roi.as_ref()
+        && let Some((width, height)) = roi_pixels(roi, &metadata)
+    {
+        let min = &detection.min_region;
+        if width < min.min_width_px || height < min.min_height_px {
+            warnings.push(format!(
+                "roi is only {width}x{height} px, smaller than the {}x{} px minimum region; \
+                 nothing will be detected",
+                min.min_width_px, min.min_height_px
+            ));
+        }
+    }
+
+    let estimated_samples = estimate_samples(&metadata, detection);
+    if estimated_samples.is_none() {
+        warnings.push("duration unknown; cannot estimate sampled frames".to_string());
+    }
+    if metadata.width.is_none() || metadata.height.is_none() {
+        warnings.push("frame size unknown until decoding starts".to_string());
+    }
+
+    ProbeReport {
+        input: input.to_path_buf(),
+        backend,
+        metadata,
+        samples_per_second: detection.samples_per_second,
+        estimated_samples,
+        roi,
+        warnings,
+    }
+}
+
+/// The sampler takes up to `samples_per_second` frames from every second after the skipped
+/// intro, and never more frames than the video has.
+fn estimate_samples(metadata: &VideoMetadata, detection: &DetectionSettings) -> Option<u64> {
+    let duration = metadata.duration?;
+    let sampled = duration.saturating_sub(detection.skip_intro);
+    let rate = match metadata.fps {
+        Some(fps) if fps > 0.0 => fps.min(detection.samples_per_second as f64),
+        _ => detection.samples_per_second as f64,
+    };
+    if sampled == Duration::ZERO {
+        return Some(0);
+    }
+    Some((sampled.as_secs_f64() * rate).ceil() as u64)
+}
+
+fn roi_pixels(roi: &RoiConfig, metadata: &VideoMetadata) -> Option<(usize, usize)> {
+    let (width, height) = metadata.width.zip(metadata.height)?;
+    Some((
+        (roi.width * width as f32).round() as usize,
+        (roi.height * height as f32).round() as usize,
+    ))
+}
+
+fn is_full_frame(roi: &RoiConfig) -> bool {
+    roi.x <= 0.0 && roi.y <= 0.0 && roi.width >= 1.0 && roi.height >= 1.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use std::fs;
+    use subtitle_fast_decoder::backends::mock::MockProvider;
+
+    fn detection_with_config(contents: &str) -> DetectionSettings {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config_path = dir.path().join("config.toml");
+        fs::write(&config_path, contents).expect("write config");
+        let cli = CliArgs::try_parse_from([
+            "subtitle-fast",
+            "--config",
+            config_path.to_str().expect("utf-8 path"),
+        ])
+        .expect("cli args");
+        resolve_settings(&cli, &CliSources::default())
+            .expect("valid settings")
+            .settings
+            .detection
+    }
+
+    #[test]
+    fn probe_reports_mock_metadata_and_sample_estimate() {
+        let provider = MockProvider::new(&Configuration::default()).expect("mock provider");
+        let input = Path::new("clip.mp4");
+
+        let report = probe_provider(
+            input,
+            "mock",
+            &provider,
+            &detection_with_config("[detection]\nsamples_per_second = 7\n"),
+        );
+        let printed = report.to_string();
+        for line in [
+            "input: clip.mp4",
+            "backend: mock",
+            "resolution: 640x360",
+            "fps: 60.000",
+            "duration: 2.000s",
+            "frames: 120",
+            "samples: ~14 at 7/s",
+            "roi: not set (full frame)",
+        ] {
+            assert!(printed.contains(line), "missing '{line}' in:\n{printed}");
+        }
+        assert!(report.warnings.is_empty(), "{printed}");
+
+        // A 2 px tall band cannot hold the smallest region the detector keeps.
+        let thin = detection_with_config(
+            "[detection]\nroi = { x = 0.1, y = 0.9, width = 0.8, height = 0.005 }\n",
+        );
+        let printed = probe_provider(input, "mock", &provider, &thin).to_string();
+        assert!(
+            printed.contains("roi: x=0.100 y=0.900 width=0.800 height=0.005 (512x2 px)"),
+            "{printed}"
+        );
+        assert!(
+            printed.contains("warning: roi is only 512x2 px"),
+            "{printed}"
+        );
+    }
+}
@@ -0,0 +1,150 @@
+//! Share of each writing system in a run's recognized text, printed after a CLI run so a
+//! model/dictionary pairing that reads the wrong language stands out.
+//!
+//! Merged cues do not keep the engine's script tags, and most engines report none, so the
+//! script of every letter is taken from its Unicode block instead. Digits, punctuation and
+//! whitespace are not counted.
+
+use crate::subtitle::MergedSubtitle;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Script {
+    Cjk,
+    Kana,
+    Hangul,
+    Latin,
+    Cyrillic,
+    Greek,
+    Arabic,
+    Hebrew,
+    Thai,
+    Devanagari,
+    Other,
+}
+
+impl Script {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Script::Cjk => "CJK",
+            Script::Kana => "Kana",
+            Script::Hangul => "Hangul",
+            Script::Latin => "Latin",
+            Script::Cyrillic => "Cyrillic",
+            Script::Greek => "Greek",
+            Script::Arabic => "Arabic",
+            Script::Hebrew => "Hebrew",
+            Script::Thai => "Thai",
+            Script::Devanagari => "Devanagari",
+            Script::Other => "other",
+        }
+    }
+
+    /// Script of a letter; `None` for digits, punctuation, symbols and whitespace.
+    pub fn of(ch: char) -> Option<Self> {
+        if !ch.is_alphabetic() {
+            return None;
+        }
+        let script = match ch as u32 {
+            0x0041..=0x024F | 0x1E00..=0x1EFF | 0xFF21..=0xFF5A => Script::Latin,
+            0x0370..=0x03FF | 0x1F00..=0x1FFF => Script::Greek,
+            0x0400..=0x052F => Script::Cyrillic,
+            0x0590..=0x05FF => Script::Hebrew,
+            0x0600..=0x06FF | 0x0750..=0x077F | 0xFB50..=0xFDFF | 0xFE70..=0xFEFF => Script::Arabic,
+            0x0900..=0x097F => Script::Devanagari,
+            0x0E00..=0x0E7F => Script::Thai,
+            0x1100..=0x11FF | 0x3130..=0x318F | 0xAC00..=0xD7AF => Script::Hangul,
+            0x3040..=0x30FF | 0x31F0..=0x31FF | 0xFF66..=0xFF9F => Script::Kana,
+            0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF | 0x20000..=0x3134F => Script::Cjk,
+            _ => Script::Other,
+        };
+        Some(script)
+    }
+}
+
+/// Letters per script across every cue, most frequent first.
+pub fn script_counts(subtitles: &[MergedSubtitle]) -> Vec<(Script, usize)> {
+    let mut counts: Vec<(Script, usize)> = Vec::new();
+    let letters = subtitles
+        .iter()
+        .flat_map(|subtitle| &subtitle.lines)
+        .flat_map(|line| line.text.chars())
+        .filter_map(Script::of);
+    for script in letters {
+        match counts.iter_mut().find(|(seen, _)| *seen == script) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((script, 1)),
+        }
+    }
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    counts
+}
+
+/// One-line share of each script, e.g. `92% CJK, 8% Latin`; `None` when the cues hold no
+/// letters.
+pub fn summarize_scripts(subtitles: &[MergedSubtitle]) -> Option<String> {
+    let counts = script_counts(subtitles);
+    let total: usize = counts.iter().map(|(_, count)| count).sum();
+    if total == 0 {
+        return None;
+    }
+    let parts: Vec<String> = counts
+        .into_iter()
+        .map(|(script, count)| {
+            let percent = (count * 100 + total / 2) / total;
+            if percent == 0 {
+                format!("<1% {}", script.as_str())
+            } else {
+                format!("{percent}% {}", script.as_str())
+            }
+        })
+        .collect();
+    Some(parts.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::subtitle::SubtitleLine;
+    use std::time::Duration;
+
+    fn cue(id: u64, lines: &[&str]) -> MergedSubtitle {
+        MergedSubtitle {
+            id,
+            start_time: Duration::from_secs(id),
+            end_time: Duration::from_secs(id + 1),
+            start_frame: 0,
+            end_frame: 0,
+            lines: lines
+                .iter()
+                .map(|text| SubtitleLine {
+                    center: 0.9,
+                    text: text.to_string(),
+                    rtl: false,
+                    confidence: None,
+                    region: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn mixed_script_cues_are_summarized_by_letter_share() {
+        // 19 Han characters and 2 Latin letters; digits and punctuation are not counted.
+        let cues = [
+            cue(0, &["我们明天见。", "OK"]),
+            cue(1, &["今天天气很好，1999年"]),
+            cue(2, &["你说什么？真的吗"]),
+        ];
+        assert_eq!(
+            script_counts(&cues),
+            [(Script::Cjk, 19), (Script::Latin, 2)]
+        );
+        assert_eq!(
+            summarize_scripts(&cues).as_deref(),
+            Some("90% CJK, 10% Latin")
+        );
+
+        assert_eq!(summarize_scripts(&[]), None);
+        assert_eq!(summarize_scripts(&[cue(0, &["123 !?"])]), None);
+    }
+}
@@ -1,19 +1,35 @@
 use std::env;
+use std::ffi::OsString;
 use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::time::Duration;
 
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
 use subtitle_fast_comparator::Backend;
-use subtitle_fast_types::RoiConfig;
-use subtitle_fast_validator::subtitle_detection::SubtitleDetectorKind;
-use subtitle_fast_validator::subtitle_detection::{DEFAULT_DELTA, DEFAULT_TARGET};
+use subtitle_fast_ocr::{DEFAULT_BEAM_WIDTH, DecodeMode, InputPadding};
+use subtitle_fast_types::{RoiConfig, TextDirection};
+use subtitle_fast_validator::subtitle_detection::{
+    DEFAULT_AUTO_DETECTOR_PRIORITY, DEFAULT_DELTA, DEFAULT_LINE_VALLEY_DEPTH, DEFAULT_TARGET,
+    FusionPolicy, RegionSizeConfig, RoiRounding, ShearCompensation, SubtitleDetectorKind,
+};
 
 use crate::cli::{CliArgs, CliSources};
+use crate::encoding::OutputEncoding;
+use crate::log_warn;
+use crate::model::DEFAULT_MAX_CONCURRENT_DOWNLOADS;
+use crate::output_format::OutputFormat;
+use crate::stage::determiner::FullWidthGuard;
+use crate::stage::exclusion::DEFAULT_EXCLUSION_OVERLAP;
+use crate::stage::ocr::DEFAULT_MAX_CROP_PIXELS;
+use crate::subtitle::CueLayout;
+
+/// Environment variable naming a config file, used when `--config` is not given.
+const CONFIG_PATH_ENV: &str = "SUBFAST_CONFIG";
 
-#[derive(Debug, Default, Deserialize, Serialize)]
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
 #[serde(default)]
 pub(crate) struct FileConfig {
     pub(crate) detection: Option<DetectionFileConfig>,
@@ -27,17 +43,66 @@ pub(crate) struct FileConfig {
 pub(crate) struct DecoderFileConfig {
     pub(crate) backend: Option<String>,
     pub(crate) channel_capacity: Option<usize>,
+    pub(crate) error_tolerance: Option<usize>,
+    pub(crate) color_range: Option<String>,
+    pub(crate) init_retries: Option<u32>,
+    pub(crate) init_retry_backoff_ms: Option<u64>,
 }
 
 #[derive(Debug, Default, Deserialize, Serialize, Clone)]
 #[serde(default)]
 pub(crate) struct DetectionFileConfig {
     pub(crate) samples_per_second: Option<u32>,
+    #[serde(alias = "detection_sensitivity")]
+    pub(crate) sensitivity: Option<String>,
     pub(crate) target: Option<u8>,
     pub(crate) delta: Option<u8>,
     pub(crate) detector: Option<String>,
+    pub(crate) detector_priority: Option<Vec<String>>,
     pub(crate) comparator: Option<String>,
+    pub(crate) comparator_time_decay: Option<f32>,
     pub(crate) roi: Option<RoiFileConfig>,
+    pub(crate) roi_units: Option<String>,
+    pub(crate) max_in_flight: Option<usize>,
+    pub(crate) bridge_gap_ms: Option<u64>,
+    pub(crate) black_gap_bridge_ms: Option<u64>,
+    pub(crate) roi_clip_margin: Option<f32>,
+    pub(crate) roi_rounding: Option<String>,
+    pub(crate) roi_half_pixel: Option<bool>,
+    pub(crate) min_detection_confidence: Option<f32>,
+    pub(crate) fusion_policy: Option<String>,
+    pub(crate) shear_compensation: Option<ShearFileConfig>,
+    pub(crate) split_lines: Option<bool>,
+    pub(crate) line_valley_depth: Option<f32>,
+    pub(crate) min_region_width: Option<usize>,
+    pub(crate) min_region_height: Option<usize>,
+    pub(crate) max_region_size_change: Option<f32>,
+    pub(crate) region_smoothing: Option<f32>,
+    pub(crate) max_regions_per_frame: Option<usize>,
+    pub(crate) full_width_guard: Option<FullWidthGuardFileConfig>,
+    pub(crate) fixed_regions: Option<Vec<RoiFileConfig>>,
+    pub(crate) exclusion_zones: Option<Vec<RoiFileConfig>>,
+    pub(crate) exclusion_overlap: Option<f32>,
+    pub(crate) frame_rotation_deg: Option<f32>,
+    pub(crate) band_softness: Option<u8>,
+    pub(crate) skip_intro_seconds: Option<f64>,
+    pub(crate) warmup_frames: Option<u64>,
+    pub(crate) flush_at_end: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub(crate) struct ShearFileConfig {
+    pub(crate) max_angle: Option<f32>,
+    pub(crate) step: Option<f32>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub(crate) struct FullWidthGuardFileConfig {
+    pub(crate) min_width: Option<f32>,
+    pub(crate) min_height: Option<f32>,
+    pub(crate) min_edge_density: Option<f32>,
 }
 
 #[derive(Debug, Default, Deserialize, Serialize, Clone)]
@@ -53,12 +118,39 @@ pub(crate) struct RoiFileConfig {
 #[serde(default)]
 pub(crate) struct OcrFileConfig {
     pub(crate) backend: Option<String>,
+    pub(crate) text_direction: Option<String>,
+    pub(crate) dynamic_input_width: Option<bool>,
+    pub(crate) input_height: Option<usize>,
+    pub(crate) input_width: Option<usize>,
+    pub(crate) placeholder: Option<bool>,
+    pub(crate) concurrency: Option<usize>,
+    pub(crate) normalize_nfc: Option<bool>,
+    pub(crate) min_cue_chars: Option<usize>,
+    pub(crate) confidence_decay: Option<f32>,
+    pub(crate) empty_hold: Option<usize>,
+    pub(crate) upscale_retry_below: Option<f32>,
+    pub(crate) input_padding: Option<String>,
+    pub(crate) preserve_blank_tokens: Option<bool>,
+    pub(crate) blank_index: Option<usize>,
+    pub(crate) decode: Option<String>,
+    pub(crate) beam_width: Option<usize>,
+    pub(crate) max_crop_pixels: Option<usize>,
+    pub(crate) max_concurrent_downloads: Option<usize>,
 }
 
 #[derive(Debug, Default, Deserialize, Serialize, Clone)]
 #[serde(default)]
 pub(crate) struct OutputFileConfig {
     pub(crate) path: Option<PathBuf>,
+    pub(crate) format: Option<String>,
+    pub(crate) gap_markers_ms: Option<u64>,
+    pub(crate) min_cue_gap_ms: Option<u64>,
+    pub(crate) wrap_width: Option<usize>,
+    pub(crate) split_tracks: Option<bool>,
+    pub(crate) emit_metadata: Option<bool>,
+    pub(crate) encoding: Option<String>,
+    pub(crate) replace_unencodable: Option<bool>,
+    pub(crate) resident_cue_limit: Option<usize>,
 }
 
 #[derive(Debug)]
@@ -81,21 +173,46 @@ pub struct ResolvedSettings {
 #[cfg(feature = "gui")]
 pub fn resolve_gui_settings() -> Result<EffectiveSettings, ConfigError> {
     let cli = CliArgs {
+        command: None,
         backend: None,
         config: None,
         list_backends: false,
+        check: false,
+        bench: false,
+        heatmap: None,
+        quiet: false,
+        verbose: false,
         detection_samples_per_second: 7,
         decoder_channel_capacity: None,
+        decoder_error_tolerance: None,
         detector_target: None,
         detector_delta: None,
         comparator: None,
         roi: None,
+        roi_units: None,
         output: None,
+        format: None,
+        emit_metadata: false,
+        timing_frames: false,
+        gap_markers_ms: None,
+        wrap_width: None,
+        split_tracks: false,
+        output_encoding: None,
+        output_encoding_replace: false,
         ocr_backend: None,
-        input: None,
+        ocr_placeholder: false,
+        ocr_concurrency: None,
+        ocr_input_height: None,
+        ocr_input_width: None,
+        fixed_regions: Vec::new(),
+        jobs: 2,
+        watch: None,
+        save_effective_config: None,
+        inputs: Vec::new(),
     };
     let sources = CliSources::default();
-    let (file, config_path) = load_config(None)?;
+    let explicit = explicit_config_path(None, env::var_os(CONFIG_PATH_ENV));
+    let (file, config_path) = load_config(explicit.as_deref())?;
     let roi_specified = file
         .detection
         .as_ref()
@@ -105,8 +222,17 @@ pub fn resolve_gui_settings() -> Result<EffectiveSettings, ConfigError> {
         });
     let resolved = merge(&cli, &sources, file, config_path)?;
     let mut settings = resolved.settings;
+    // The GUI edits fractional ROIs and has no frame size to convert a pixel ROI with.
+    if roi_specified && settings.detection.roi_units == RoiUnits::Pixels {
+        return Err(ConfigError::InvalidValue {
+            path: resolved.config_path,
+            field: "detection.roi_units",
+            value: "pixels (the GUI only accepts fractional ROIs)".into(),
+        });
+    }
     if !roi_specified {
         settings.detection.roi = Some(default_gui_roi());
+        settings.detection.roi_units = RoiUnits::Fraction;
     }
     Ok(settings)
 }
@@ -117,24 +243,381 @@ pub struct DetectionSettings {
     pub target: u8,
     pub delta: u8,
     pub detector: SubtitleDetectorKind,
+    /// Backends the `auto` detector tries, in order.
+    pub detector_priority: Vec<SubtitleDetectorKind>,
     pub comparator: Option<Backend>,
+    /// Per-second decay of comparator similarity with the time between compared frames, so
+    /// identical text far apart in time is less readily joined into one cue; 0.0 disables it.
+    pub comparator_time_decay: f32,
     pub roi: Option<RoiConfig>,
+    /// Units the `roi` coordinates are expressed in.
+    pub roi_units: RoiUnits,
+    /// Maximum number of sampled frames allowed between the sampler and OCR output.
+    pub max_in_flight: Option<usize>,
+    /// Detection dropouts shorter than this keep a region alive instead of splitting it.
+    pub bridge_gap_ms: u64,
+    /// Identical cues separated only by black frames for at most this long are joined into
+    /// one; 0 leaves them split.
+    pub black_gap_bridge_ms: u64,
+    /// Frame fraction a detected region may extend past the ROI before being clipped.
+    pub roi_clip_margin: f32,
+    /// How the Vision detector snaps the ROI's fractional edges to whole pixels.
+    pub roi_rounding: RoiRounding,
+    /// Snap the Vision ROI's edges against pixel centres instead of pixel corners.
+    pub roi_half_pixel: bool,
+    /// Vision text observations below this confidence are dropped; 0.0 keeps them all.
+    pub min_detection_confidence: f32,
+    /// How the fusion detector combines Vision's boxes with the luma-band regions.
+    pub fusion_policy: FusionPolicy,
+    /// Shear angles the projection-band detector tries for slanted text; `None` disables it.
+    pub shear_compensation: Option<ShearCompensation>,
+    /// Split projection bands holding several stacked lines into one region per line.
+    pub split_lines: bool,
+    /// Fraction below the band's peak row mass a row must drop to separate two lines.
+    pub line_valley_depth: f32,
+    /// Smallest region, in pixels, the band detectors keep.
+    pub min_region: RegionSizeConfig,
+    /// Largest per-frame change of a tracked region's width or height, as a fraction of its
+    /// previous size; `None` leaves region geometry unsmoothed.
+    pub max_region_size_change: Option<f32>,
+    /// Weight, in [0, 1), a tracked region's previous geometry keeps against each new
+    /// detection when smoothing its center and size; 0.0 follows detections as they are.
+    pub region_smoothing: f32,
+    /// Highest-scoring regions kept per sampled frame before OCR; `None` keeps all of them.
+    pub max_regions_per_frame: Option<usize>,
+    /// Regions covering at least this much of the ROI are kept only when their luma has
+    /// text-like edges; `None` accepts them as detected.
+    pub full_width_guard: Option<FullWidthGuard>,
+    /// Frame-fraction areas OCR'd on every sampled frame in place of detection; empty runs the
+    /// detector as usual.
+    pub fixed_regions: Vec<RoiConfig>,
+    /// Frame-fraction areas of UI furniture; merged cues lying mostly inside one are dropped.
+    pub exclusion_zones: Vec<RoiConfig>,
+    /// Share of a cue's region an exclusion zone may cover before the cue is dropped.
+    pub exclusion_overlap: f32,
+    /// Counter-clockwise rotation, in degrees, applied to every frame's luma before sampling
+    /// to straighten tilted sources; 0.0 disables it.
+    pub frame_rotation_deg: f32,
+    /// Luma levels past the band edges over which the detectors feather membership; 0 keeps
+    /// the hard threshold.
+    pub band_softness: u8,
+    /// Leading stretch of every video, such as an intro or recap, that is never sampled.
+    pub skip_intro: Duration,
+    /// Leading sampled frames of the run whose detections are ignored, so start-up
+    /// artifacts don't open a cue; 0 tracks from the first sample.
+    pub warmup_frames: u64,
+    /// Emit regions still on screen when the video ends, ending at their last sighting,
+    /// instead of dropping them.
+    pub flush_at_end: bool,
+}
+
+impl DetectionSettings {
+    /// `[detection]` section resolving back to these settings. Every key is spelled out, so
+    /// neither a sensitivity preset nor a changed default can shift the result.
+    pub(crate) fn to_file_config(&self) -> DetectionFileConfig {
+        let file_roi = |roi: &RoiConfig| RoiFileConfig {
+            x: Some(roi.x),
+            y: Some(roi.y),
+            width: Some(roi.width),
+            height: Some(roi.height),
+        };
+        DetectionFileConfig {
+            samples_per_second: Some(self.samples_per_second),
+            sensitivity: None,
+            target: Some(self.target),
+            delta: Some(self.delta),
+            detector: Some(self.detector.as_str().to_string()),
+            detector_priority: Some(
+                self.detector_priority
+                    .iter()
+                    .map(|kind| kind.as_str().to_string())
+                    .collect(),
+            ),
+            comparator: self.comparator.map(|kind| kind.as_str().to_string()),
+            comparator_time_decay: Some(self.comparator_time_decay),
+            roi: self.roi.as_ref().map(file_roi),
+            roi_units: Some(self.roi_units.as_str().to_string()),
+            max_in_flight: self.max_in_flight,
+            bridge_gap_ms: Some(self.bridge_gap_ms),
+            black_gap_bridge_ms: Some(self.black_gap_bridge_ms),
+            roi_clip_margin: Some(self.roi_clip_margin),
+            roi_rounding: Some(self.roi_rounding.as_str().to_string()),
+            roi_half_pixel: Some(self.roi_half_pixel),
+            min_detection_confidence: Some(self.min_detection_confidence),
+            fusion_policy: Some(self.fusion_policy.as_str().to_string()),
+            shear_compensation: self.shear_compensation.map(|shear| ShearFileConfig {
+                max_angle: Some(shear.max_angle_deg),
+                step: Some(shear.step_deg),
+            }),
+            split_lines: Some(self.split_lines),
+            line_valley_depth: Some(self.line_valley_depth),
+            min_region_width: Some(self.min_region.min_width_px),
+            min_region_height: Some(self.min_region.min_height_px),
+            max_region_size_change: self.max_region_size_change,
+            region_smoothing: Some(self.region_smoothing),
+            max_regions_per_frame: self.max_regions_per_frame,
+            full_width_guard: self.full_width_guard.map(|guard| FullWidthGuardFileConfig {
+                min_width: Some(guard.min_width),
+                min_height: Some(guard.min_height),
+                min_edge_density: Some(guard.min_edge_density),
+            }),
+            fixed_regions: Some(self.fixed_regions.iter().map(file_roi).collect()),
+            exclusion_zones: Some(self.exclusion_zones.iter().map(file_roi).collect()),
+            exclusion_overlap: Some(self.exclusion_overlap),
+            frame_rotation_deg: Some(self.frame_rotation_deg),
+            band_softness: Some(self.band_softness),
+            skip_intro_seconds: Some(self.skip_intro.as_secs_f64()),
+            warmup_frames: Some(self.warmup_frames),
+            flush_at_end: Some(self.flush_at_end),
+        }
+    }
+
+    /// Returns the ROI as frame fractions, converting pixel coordinates using the frame size.
+    pub fn fractional_roi(
+        &self,
+        frame_width: Option<u32>,
+        frame_height: Option<u32>,
+    ) -> Result<Option<RoiConfig>, ConfigError> {
+        let Some(roi) = self.roi else {
+            return Ok(None);
+        };
+        match self.roi_units {
+            RoiUnits::Fraction => Ok(Some(roi)),
+            RoiUnits::Pixels => match (frame_width, frame_height) {
+                (Some(width), Some(height)) if width > 0 && height > 0 => {
+                    pixel_roi_to_fraction(roi, width, height)
+                        .map(Some)
+                        .ok_or_else(|| ConfigError::InvalidValue {
+                            path: None,
+                            field: "detection.roi",
+                            value: format!(
+                                "{},{},{},{} (outside the {width}x{height} frame)",
+                                roi.x, roi.y, roi.width, roi.height
+                            ),
+                        })
+                }
+                _ => Err(ConfigError::InvalidValue {
+                    path: None,
+                    field: "detection.roi_units",
+                    value: "pixels (frame dimensions unavailable)".into(),
+                }),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RoiUnits {
+    /// Coordinates are fractions of the frame size in `0.0..=1.0`.
+    #[default]
+    Fraction,
+    /// Coordinates are absolute pixel offsets and sizes.
+    Pixels,
+}
+
+impl RoiUnits {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RoiUnits::Fraction => "fraction",
+            RoiUnits::Pixels => "pixels",
+        }
+    }
+}
+
+impl FromStr for RoiUnits {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "fraction" | "fractions" | "normalized" => Ok(RoiUnits::Fraction),
+            "pixel" | "pixels" | "px" => Ok(RoiUnits::Pixels),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Luma range of the decoded frames, used to bring limited-range sources to full range.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorRange {
+    /// Use frames as the decoder delivers them.
+    #[default]
+    Auto,
+    /// Luma spans 16..=235 and is stretched to 0..=255 before detection and OCR.
+    Limited,
+    /// Luma already spans 0..=255.
+    Full,
+}
+
+impl ColorRange {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ColorRange::Auto => "auto",
+            ColorRange::Limited => "limited",
+            ColorRange::Full => "full",
+        }
+    }
+}
+
+impl FromStr for ColorRange {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "auto" => Ok(ColorRange::Auto),
+            "limited" | "tv" | "mpeg" => Ok(ColorRange::Limited),
+            "full" | "pc" | "jpeg" => Ok(ColorRange::Full),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Curated detector tuning; explicit `target`, `delta` and region-size keys override it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DetectionSensitivity {
+    /// Narrower luma band and larger minimum regions; fewer false positives.
+    Low,
+    #[default]
+    Medium,
+    /// Wider luma band and smaller minimum regions; catches thin or dim text.
+    High,
+}
+
+/// Concrete detector values a [`DetectionSensitivity`] stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SensitivityPreset {
+    pub target: u8,
+    pub delta: u8,
+    pub min_region: RegionSizeConfig,
+}
+
+impl DetectionSensitivity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DetectionSensitivity::Low => "low",
+            DetectionSensitivity::Medium => "medium",
+            DetectionSensitivity::High => "high",
+        }
+    }
+
+    pub fn preset(self) -> SensitivityPreset {
+        let (target, delta, min_side) = match self {
+            DetectionSensitivity::Low => (235, 8, 32),
+            DetectionSensitivity::Medium => (DEFAULT_TARGET, DEFAULT_DELTA, 24),
+            DetectionSensitivity::High => (225, 20, 16),
+        };
+        SensitivityPreset {
+            target,
+            delta,
+            min_region: RegionSizeConfig {
+                min_width_px: min_side,
+                min_height_px: min_side,
+            },
+        }
+    }
+}
+
+impl FromStr for DetectionSensitivity {
+    type Err = ();
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "low" => Ok(DetectionSensitivity::Low),
+            "medium" => Ok(DetectionSensitivity::Medium),
+            "high" => Ok(DetectionSensitivity::High),
+            _ => Err(()),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct DecoderSettings {
     pub backend: Option<String>,
     pub channel_capacity: Option<usize>,
+    /// Consecutive decode errors skipped before a run fails.
+    pub error_tolerance: usize,
+    pub color_range: ColorRange,
+    /// Extra attempts at initializing a backend before falling back to another one.
+    pub init_retries: u32,
+    /// Wait before the first retry; it doubles for each one after.
+    pub init_retry_backoff: Duration,
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct OcrSettings {
     pub backend: Option<String>,
+    /// Forced reading direction for assembled lines; `None` infers it from the OCR output.
+    pub text_direction: Option<TextDirection>,
+    /// ORT only: size the model input to each region's aspect ratio. Requires a model with a
+    /// dynamic width axis.
+    pub dynamic_input_width: bool,
+    /// ORT only: model input height in pixels; `None` keeps the engine default.
+    pub input_height: Option<usize>,
+    /// ORT only: fixed model input width in pixels; `None` keeps the engine default.
+    pub input_width: Option<usize>,
+    /// Write timing-only cues with placeholder text when only the noop engine is available.
+    pub placeholder: bool,
+    /// Lifecycle events recognized at once; always at least 1.
+    pub concurrency: usize,
+    /// Compose recognized text to Unicode NFC before it is merged into cues.
+    pub normalize_nfc: bool,
+    /// Cues with fewer non-whitespace characters than this are dropped as noise; 0 keeps all.
+    pub min_cue_chars: usize,
+    /// Share of confidence a held line loses each time a merged cue reads it differently, so a
+    /// persistent new reading can replace it; 0 keeps both readings.
+    pub confidence_decay: f32,
+    /// Empty recognitions in a row that extend the cue they continue with its last text
+    /// instead of being dropped; 0 drops every empty recognition.
+    pub empty_hold: usize,
+    /// Regions recognized below this mean confidence get one retry on a 2x upscaled crop;
+    /// `None` never retries.
+    pub upscale_retry_below: Option<f32>,
+    /// ORT only: fill for the model input right of a scaled crop; `None` pads at the
+    /// normalization mean.
+    pub input_padding: Option<InputPadding>,
+    /// ORT only: keep empty dictionary lines as space tokens instead of skipping them.
+    pub preserve_blank_tokens: bool,
+    /// ORT only: model class used for the CTC blank.
+    pub blank_index: usize,
+    /// ORT only: greedy best-path decoding, or a slower prefix-beam search.
+    pub decode: DecodeMode,
+    /// Regions larger than this many frame pixels are skipped instead of recognized.
+    pub max_crop_pixels: usize,
+    /// ORT only: model files fetched at once when they are missing; always at least 1.
+    pub max_concurrent_downloads: usize,
 }
 
 #[derive(Debug, Clone, Default)]
 pub struct OutputSettings {
     pub path: Option<PathBuf>,
+    /// Format of the subtitle file; without `--format` it follows `path`'s extension.
+    pub format: OutputFormat,
+    /// Write subtitle-free intervals at least this long to a `.gaps.srt` file; `None` disables it.
+    pub gap_markers_ms: Option<u64>,
+    /// End each cue at least this long before the next one starts when they touch; `None`
+    /// leaves cue times as merged.
+    pub min_cue_gap_ms: Option<u64>,
+    /// Wrap cue lines longer than this many characters; `None` writes lines as recognized.
+    pub wrap_width: Option<usize>,
+    /// Write each fixed region's cues to its own `.trackN` file instead of one merged file.
+    pub split_tracks: bool,
+    /// Write a `.meta.json` sidecar describing each cue next to the subtitle file.
+    pub emit_metadata: bool,
+    /// Write a `.frames.tsv` sidecar with each cue's frame indices; `--timing-frames` only.
+    pub timing_frames: bool,
+    /// Encoding applied to subtitle files when they are written.
+    pub encoding: OutputEncoding,
+    /// GUI only: cues kept in memory before older ones spill to disk; `None` keeps everything.
+    pub resident_cue_limit: Option<usize>,
+}
+
+impl OutputSettings {
+    /// Cue timing and wrapping every written or exported track gets.
+    pub fn cue_layout(&self) -> CueLayout {
+        CueLayout {
+            min_cue_gap: self.min_cue_gap_ms.map(Duration::from_millis),
+            wrap_width: self.wrap_width,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -155,6 +638,10 @@ pub enum ConfigError {
     NotFound {
         path: PathBuf,
     },
+    Write {
+        path: PathBuf,
+        source: std::io::Error,
+    },
 }
 
 impl fmt::Display for ConfigError {
@@ -192,6 +679,14 @@ impl fmt::Display for ConfigError {
             ConfigError::NotFound { path } => {
                 write!(f, "config file {} does not exist", path.display())
             }
+            ConfigError::Write { path, source } => {
+                write!(
+                    f,
+                    "failed to write config file {}: {}",
+                    path.display(),
+                    source
+                )
+            }
         }
     }
 }
@@ -203,6 +698,7 @@ impl std::error::Error for ConfigError {
             ConfigError::Parse { source, .. } => Some(source),
             ConfigError::InvalidValue { .. } => None,
             ConfigError::NotFound { .. } => None,
+            ConfigError::Write { source, .. } => Some(source),
         }
     }
 }
@@ -211,10 +707,20 @@ pub fn resolve_settings(
     cli: &CliArgs,
     sources: &CliSources,
 ) -> Result<ResolvedSettings, ConfigError> {
-    let (file, config_path) = load_config(cli.config.as_deref())?;
+    let explicit = explicit_config_path(cli.config.as_deref(), env::var_os(CONFIG_PATH_ENV));
+    let (file, config_path) = load_config(explicit.as_deref())?;
     merge(cli, sources, file, config_path)
 }
 
+/// Config file named by `--config`, else by `SUBFAST_CONFIG`; `None` falls back to discovery.
+fn explicit_config_path(flag: Option<&Path>, env_value: Option<OsString>) -> Option<PathBuf> {
+    flag.map(Path::to_path_buf).or_else(|| {
+        env_value
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+    })
+}
+
 fn load_config(path_override: Option<&Path>) -> Result<(FileConfig, Option<PathBuf>), ConfigError> {
     if let Some(path) = path_override {
         let path = path.to_path_buf();
@@ -288,21 +794,32 @@ fn merge(
         config_path.as_ref(),
     )?;
 
+    let preset =
+        resolve_detection_sensitivity(detection_cfg.sensitivity.clone(), config_path.as_ref())?
+            .preset();
+
     let detector_target = resolve_detector_u8(
         cli.detector_target,
         detection_cfg.target,
         !sources.detector_target_from_cli,
-        DEFAULT_TARGET,
+        preset.target,
     )?;
     let detector_delta = resolve_detector_u8(
         cli.detector_delta,
         detection_cfg.delta,
         !sources.detector_delta_from_cli,
-        DEFAULT_DELTA,
+        preset.delta,
+    )?;
+    let min_region = resolve_min_region(
+        detection_cfg.min_region_width,
+        detection_cfg.min_region_height,
+        preset.min_region,
+        config_path.as_ref(),
     )?;
 
     let detector_kind =
         resolve_detector_kind(detection_cfg.detector.clone(), config_path.as_ref())?;
+    let detector_priority = resolve_detector_priority(detection_cfg.detector_priority.clone());
 
     let comparator_kind = resolve_comparator_kind(
         cli.comparator.clone(),
@@ -311,10 +828,80 @@ fn merge(
         config_path.as_ref(),
     )?;
 
-    let detection_roi = resolve_detection_roi(
-        cli.roi,
-        detection_cfg.roi,
-        !sources.detector_roi_from_cli,
+    let roi_units = resolve_roi_units(
+        cli.roi_units.clone(),
+        detection_cfg.roi_units.clone(),
+        config_path.as_ref(),
+    )?;
+
+    let (detection_roi, roi_units) = match roi_units {
+        RoiUnits::Fraction => (
+            resolve_detection_roi(
+                cli.roi,
+                detection_cfg.roi,
+                !sources.detector_roi_from_cli,
+                config_path.as_ref(),
+            )?,
+            RoiUnits::Fraction,
+        ),
+        RoiUnits::Pixels => match resolve_pixel_roi(
+            cli.roi,
+            detection_cfg.roi,
+            !sources.detector_roi_from_cli,
+            config_path.as_ref(),
+        )? {
+            Some(roi) => (roi, RoiUnits::Pixels),
+            None => (full_frame_roi(), RoiUnits::Fraction),
+        },
+    };
+
+    let roi_clip_margin =
+        resolve_roi_clip_margin(detection_cfg.roi_clip_margin, config_path.as_ref())?;
+    let roi_rounding =
+        resolve_roi_rounding(detection_cfg.roi_rounding.clone(), config_path.as_ref())?;
+    let min_detection_confidence = resolve_min_detection_confidence(
+        detection_cfg.min_detection_confidence,
+        config_path.as_ref(),
+    )?;
+    let fusion_policy =
+        resolve_fusion_policy(detection_cfg.fusion_policy.clone(), config_path.as_ref())?;
+    let max_region_size_change =
+        resolve_max_region_size_change(detection_cfg.max_region_size_change, config_path.as_ref())?;
+    let region_smoothing =
+        resolve_region_smoothing(detection_cfg.region_smoothing, config_path.as_ref())?;
+    let max_regions_per_frame = reject_zero(
+        detection_cfg.max_regions_per_frame,
+        "detection.max_regions_per_frame",
+        config_path.as_ref(),
+    )?;
+    let frame_rotation_deg =
+        resolve_frame_rotation(detection_cfg.frame_rotation_deg, config_path.as_ref())?;
+    let skip_intro = resolve_skip_intro(detection_cfg.skip_intro_seconds, config_path.as_ref())?;
+    let comparator_time_decay =
+        resolve_comparator_time_decay(detection_cfg.comparator_time_decay, config_path.as_ref())?;
+    let fixed_regions = resolve_fixed_regions(
+        &cli.fixed_regions,
+        detection_cfg.fixed_regions.clone(),
+        config_path.as_ref(),
+    )?;
+    let exclusion_zones = resolve_regions(
+        file_regions(detection_cfg.exclusion_zones.clone()),
+        "detection.exclusion_zones",
+        config_path.as_ref(),
+    )?;
+    let exclusion_overlap =
+        resolve_exclusion_overlap(detection_cfg.exclusion_overlap, config_path.as_ref())?;
+
+    let shear_compensation =
+        resolve_shear_compensation(detection_cfg.shear_compensation, config_path.as_ref())?;
+    let full_width_guard =
+        resolve_full_width_guard(detection_cfg.full_width_guard.clone(), config_path.as_ref())?;
+    let line_valley_depth =
+        resolve_line_valley_depth(detection_cfg.line_valley_depth, config_path.as_ref())?;
+
+    let max_in_flight = reject_zero(
+        detection_cfg.max_in_flight,
+        "detection.max_in_flight",
         config_path.as_ref(),
     )?;
 
@@ -328,18 +915,144 @@ fn merge(
     let decoder_backend = normalize_string(cli.backend.clone())
         .or_else(|| normalize_string(decoder_cfg.backend.clone()));
 
+    let color_range = resolve_color_range(decoder_cfg.color_range.clone(), config_path.as_ref())?;
+
     let decoder_settings = DecoderSettings {
         backend: decoder_backend,
         channel_capacity: decoder_channel_capacity,
+        error_tolerance: cli
+            .decoder_error_tolerance
+            .or(decoder_cfg.error_tolerance)
+            .unwrap_or(0),
+        color_range,
+        init_retries: decoder_cfg.init_retries.unwrap_or(1),
+        init_retry_backoff: Duration::from_millis(decoder_cfg.init_retry_backoff_ms.unwrap_or(200)),
     };
 
+    let text_direction = resolve_text_direction(ocr_cfg.text_direction, config_path.as_ref())?;
+
+    let ocr_concurrency = reject_zero(
+        cli.ocr_concurrency.or(ocr_cfg.concurrency),
+        "ocr.concurrency",
+        config_path.as_ref(),
+    )?
+    .unwrap_or(1);
+
+    let confidence_decay =
+        resolve_confidence_decay(ocr_cfg.confidence_decay, config_path.as_ref())?;
+    let upscale_retry_below =
+        resolve_upscale_retry_below(ocr_cfg.upscale_retry_below, config_path.as_ref())?;
+
+    let input_height = reject_zero(
+        cli.ocr_input_height.or(ocr_cfg.input_height),
+        "ocr.input_height",
+        config_path.as_ref(),
+    )?;
+    let input_width = reject_zero(
+        cli.ocr_input_width.or(ocr_cfg.input_width),
+        "ocr.input_width",
+        config_path.as_ref(),
+    )?;
+
+    let input_padding = resolve_input_padding(ocr_cfg.input_padding.clone(), config_path.as_ref())?;
+    let max_crop_pixels = reject_zero(
+        ocr_cfg.max_crop_pixels,
+        "ocr.max_crop_pixels",
+        config_path.as_ref(),
+    )?
+    .unwrap_or(DEFAULT_MAX_CROP_PIXELS);
+    let max_concurrent_downloads = reject_zero(
+        ocr_cfg.max_concurrent_downloads,
+        "ocr.max_concurrent_downloads",
+        config_path.as_ref(),
+    )?
+    .unwrap_or(DEFAULT_MAX_CONCURRENT_DOWNLOADS);
+    let decode = resolve_decode_mode(
+        ocr_cfg.decode.clone(),
+        ocr_cfg.beam_width,
+        config_path.as_ref(),
+    )?;
+
     let ocr_settings = OcrSettings {
         backend: normalize_string(cli.ocr_backend.clone())
             .or_else(|| normalize_string(ocr_cfg.backend)),
+        text_direction,
+        dynamic_input_width: ocr_cfg.dynamic_input_width.unwrap_or(false),
+        input_height,
+        input_width,
+        placeholder: cli.ocr_placeholder || ocr_cfg.placeholder.unwrap_or(false),
+        concurrency: ocr_concurrency,
+        normalize_nfc: ocr_cfg.normalize_nfc.unwrap_or(true),
+        min_cue_chars: ocr_cfg.min_cue_chars.unwrap_or(0),
+        confidence_decay,
+        empty_hold: ocr_cfg.empty_hold.unwrap_or(0),
+        upscale_retry_below,
+        input_padding,
+        preserve_blank_tokens: ocr_cfg.preserve_blank_tokens.unwrap_or(false),
+        blank_index: ocr_cfg.blank_index.unwrap_or(0),
+        decode,
+        max_crop_pixels,
+        max_concurrent_downloads,
+    };
+
+    let gap_markers_ms = reject_zero(
+        cli.gap_markers_ms.or(output_cfg.gap_markers_ms),
+        "output.gap_markers_ms",
+        config_path.as_ref(),
+    )?;
+    let min_cue_gap_ms = reject_zero(
+        output_cfg.min_cue_gap_ms,
+        "output.min_cue_gap_ms",
+        config_path.as_ref(),
+    )?;
+    let wrap_width = reject_zero(
+        cli.wrap_width.or(output_cfg.wrap_width),
+        "output.wrap_width",
+        config_path.as_ref(),
+    )?;
+    let split_tracks = cli.split_tracks || output_cfg.split_tracks.unwrap_or(false);
+    if split_tracks && fixed_regions.is_empty() {
+        return Err(ConfigError::InvalidValue {
+            path: config_path.clone(),
+            field: "output.split_tracks",
+            value: "true (tracks are the detection.fixed_regions areas, and none are set)".into(),
+        });
+    }
+    let resident_cue_limit = reject_zero(
+        output_cfg.resident_cue_limit,
+        "output.resident_cue_limit",
+        config_path.as_ref(),
+    )?;
+    let encoding = resolve_output_encoding(
+        cli.output_encoding.clone(),
+        output_cfg.encoding,
+        config_path.as_ref(),
+    )?
+    .with_replacement(cli.output_encoding_replace || output_cfg.replace_unencodable == Some(true));
+
+    let (output_path, output_path_source) = match cli.output.clone() {
+        Some(path) => (Some(path), None),
+        None => (output_cfg.path, config_path.as_ref()),
     };
+    let format = resolve_output_format(
+        cli.format.clone(),
+        output_cfg.format,
+        config_path.as_ref(),
+        output_path.as_deref(),
+        output_path_source,
+    )?;
 
     let output_settings = OutputSettings {
-        path: cli.output.clone().or(output_cfg.path),
+        path: output_path,
+        format,
+        gap_markers_ms,
+        min_cue_gap_ms,
+        wrap_width,
+        split_tracks,
+        resident_cue_limit,
+        emit_metadata: cli.emit_metadata || output_cfg.emit_metadata.unwrap_or(false),
+        timing_frames: cli.timing_frames,
+        encoding,
     };
 
     let settings = EffectiveSettings {
@@ -348,8 +1061,35 @@ fn merge(
             target: detector_target,
             delta: detector_delta,
             detector: detector_kind,
+            detector_priority,
             comparator: comparator_kind,
+            comparator_time_decay,
             roi: Some(detection_roi),
+            roi_units,
+            max_in_flight,
+            bridge_gap_ms: detection_cfg.bridge_gap_ms.unwrap_or(0),
+            black_gap_bridge_ms: detection_cfg.black_gap_bridge_ms.unwrap_or(0),
+            roi_clip_margin,
+            roi_rounding,
+            roi_half_pixel: detection_cfg.roi_half_pixel.unwrap_or(false),
+            min_detection_confidence,
+            fusion_policy,
+            shear_compensation,
+            split_lines: detection_cfg.split_lines.unwrap_or(false),
+            line_valley_depth,
+            min_region,
+            max_region_size_change,
+            region_smoothing,
+            max_regions_per_frame,
+            full_width_guard,
+            fixed_regions,
+            exclusion_zones,
+            exclusion_overlap,
+            frame_rotation_deg,
+            band_softness: detection_cfg.band_softness.unwrap_or(0),
+            skip_intro,
+            warmup_frames: detection_cfg.warmup_frames.unwrap_or(0),
+            flush_at_end: detection_cfg.flush_at_end.unwrap_or(true),
         },
         decoder: decoder_settings,
         ocr: ocr_settings,
@@ -367,8 +1107,12 @@ pub(crate) fn default_config_path() -> Option<PathBuf> {
         .map(|dirs| dirs.config_dir().join("config.toml"))
 }
 
+/// Config file the GUI reads and edits: `SUBFAST_CONFIG`, else the discovered config.
 #[cfg(feature = "gui")]
-pub(crate) fn resolve_gui_config_path() -> Option<PathBuf> {
+pub fn resolve_gui_config_path() -> Option<PathBuf> {
+    if let Some(path) = explicit_config_path(None, env::var_os(CONFIG_PATH_ENV)) {
+        return Some(path);
+    }
     if let Some(path) = project_config_path()
         && path.exists()
     {
@@ -377,7 +1121,6 @@ pub(crate) fn resolve_gui_config_path() -> Option<PathBuf> {
     default_config_path()
 }
 
-#[cfg(feature = "gui")]
 pub(crate) fn load_file_config(path: &Path) -> Result<FileConfig, ConfigError> {
     let contents = fs::read_to_string(path).map_err(|source| ConfigError::Io {
         path: path.to_path_buf(),
@@ -390,6 +1133,33 @@ pub(crate) fn load_file_config(path: &Path) -> Result<FileConfig, ConfigError> {
     Ok(config)
 }
 
+/// Writes `detection` as the `[detection]` section of the config at `path`, creating the file
+/// if needed and keeping its other sections, so a run using that config detects the same way.
+pub fn save_detection_settings(
+    detection: &DetectionSettings,
+    path: &Path,
+) -> Result<(), ConfigError> {
+    let mut config = if path.exists() {
+        load_file_config(path)?
+    } else {
+        FileConfig::default()
+    };
+    config.detection = Some(detection.to_file_config());
+    let write_err = |source| ConfigError::Write {
+        path: path.to_path_buf(),
+        source,
+    };
+    let toml =
+        toml::to_string_pretty(&config).map_err(|err| write_err(std::io::Error::other(err)))?;
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent).map_err(write_err)?;
+    }
+    fs::write(path, toml).map_err(write_err)
+}
+
 fn project_config_path() -> Option<PathBuf> {
     env::current_dir().ok().map(|dir| dir.join("config.toml"))
 }
@@ -458,13 +1228,12 @@ fn default_gui_roi() -> RoiConfig {
     }
 }
 
-fn resolve_detection_roi(
+fn raw_detection_roi(
     cli_value: Option<RoiConfig>,
     file_value: Option<RoiFileConfig>,
     use_file: bool,
-    config_path: Option<&PathBuf>,
-) -> Result<RoiConfig, ConfigError> {
-    let raw = if let Some(roi) = cli_value {
+) -> Option<RoiConfig> {
+    if let Some(roi) = cli_value {
         Some(roi)
     } else if use_file {
         file_value.map(|roi| RoiConfig {
@@ -475,7 +1244,16 @@ fn resolve_detection_roi(
         })
     } else {
         None
-    };
+    }
+}
+
+fn resolve_detection_roi(
+    cli_value: Option<RoiConfig>,
+    file_value: Option<RoiFileConfig>,
+    use_file: bool,
+    config_path: Option<&PathBuf>,
+) -> Result<RoiConfig, ConfigError> {
+    let raw = raw_detection_roi(cli_value, file_value, use_file);
 
     let normalized = match raw {
         Some(roi) => normalize_roi(roi, config_path)?,
@@ -520,6 +1298,106 @@ fn normalize_roi(
     }))
 }
 
+fn resolve_pixel_roi(
+    cli_value: Option<RoiConfig>,
+    file_value: Option<RoiFileConfig>,
+    use_file: bool,
+    config_path: Option<&PathBuf>,
+) -> Result<Option<RoiConfig>, ConfigError> {
+    let Some(roi) = raw_detection_roi(cli_value, file_value, use_file) else {
+        return Ok(None);
+    };
+    let values = [roi.x, roi.y, roi.width, roi.height];
+    if values
+        .iter()
+        .any(|value| !value.is_finite() || *value < 0.0)
+    {
+        return Err(ConfigError::InvalidValue {
+            path: config_path.cloned(),
+            field: "detection_roi",
+            value: format!("{},{},{},{}", roi.x, roi.y, roi.width, roi.height),
+        });
+    }
+    if roi.width < 1.0 || roi.height < 1.0 {
+        return Ok(None);
+    }
+    Ok(Some(RoiConfig {
+        x: roi.x.round(),
+        y: roi.y.round(),
+        width: roi.width.round(),
+        height: roi.height.round(),
+    }))
+}
+
+fn pixel_roi_to_fraction(roi: RoiConfig, frame_width: u32, frame_height: u32) -> Option<RoiConfig> {
+    let frame_width = frame_width as f32;
+    let frame_height = frame_height as f32;
+    let fractional = RoiConfig {
+        x: roi.x / frame_width,
+        y: roi.y / frame_height,
+        width: roi.width / frame_width,
+        height: roi.height / frame_height,
+    };
+    normalize_roi(fractional, None).ok().flatten()
+}
+
+fn resolve_detection_sensitivity(
+    value: Option<String>,
+    config_path: Option<&PathBuf>,
+) -> Result<DetectionSensitivity, ConfigError> {
+    let Some(value) = normalize_string(value) else {
+        return Ok(DetectionSensitivity::default());
+    };
+    DetectionSensitivity::from_str(&value).map_err(|_| ConfigError::InvalidValue {
+        path: config_path.cloned(),
+        field: "detection.sensitivity",
+        value,
+    })
+}
+
+fn resolve_min_region(
+    width: Option<usize>,
+    height: Option<usize>,
+    preset: RegionSizeConfig,
+    config_path: Option<&PathBuf>,
+) -> Result<RegionSizeConfig, ConfigError> {
+    for (field, value) in [
+        ("detection.min_region_width", width),
+        ("detection.min_region_height", height),
+    ] {
+        if value == Some(0) {
+            return Err(ConfigError::InvalidValue {
+                path: config_path.cloned(),
+                field,
+                value: "0".to_string(),
+            });
+        }
+    }
+    Ok(RegionSizeConfig {
+        min_width_px: width.unwrap_or(preset.min_width_px),
+        min_height_px: height.unwrap_or(preset.min_height_px),
+    })
+}
+
+fn resolve_roi_units(
+    cli_value: Option<String>,
+    file_value: Option<String>,
+    config_path: Option<&PathBuf>,
+) -> Result<RoiUnits, ConfigError> {
+    let (value, path) = match normalize_string(cli_value) {
+        Some(value) => (value, None),
+        None => match normalize_string(file_value) {
+            Some(value) => (value, config_path.cloned()),
+            None => return Ok(RoiUnits::Fraction),
+        },
+    };
+    RoiUnits::from_str(&value).map_err(|_| ConfigError::InvalidValue {
+        path,
+        field: "detection.roi_units",
+        value,
+    })
+}
+
 fn round_roi(value: f32) -> f32 {
     const SCALE: f32 = 1_000_000.0;
     (value * SCALE).round() / SCALE
@@ -573,6 +1451,29 @@ fn resolve_detector_kind(
     }
 }
 
+/// Parses the auto-detector order, skipping unknown names with a warning; falls back to the
+/// default order when nothing usable is listed.
+fn resolve_detector_priority(file_value: Option<Vec<String>>) -> Vec<SubtitleDetectorKind> {
+    let mut priority = Vec::new();
+    for name in file_value.unwrap_or_default() {
+        match SubtitleDetectorKind::from_str(&name) {
+            Ok(kind) if kind != SubtitleDetectorKind::Auto => {
+                if !priority.contains(&kind) {
+                    priority.push(kind);
+                }
+            }
+            _ => log_warn!(
+                "warning: ignoring unknown detector '{name}' in detection.detector_priority"
+            ),
+        }
+    }
+    if priority.is_empty() {
+        DEFAULT_AUTO_DETECTOR_PRIORITY.to_vec()
+    } else {
+        priority
+    }
+}
+
 fn resolve_decoder_capacity(
     cli_value: Option<usize>,
     file_value: Option<usize>,
@@ -600,32 +1501,507 @@ fn resolve_decoder_capacity(
     Ok(capacity)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+fn resolve_color_range(
+    file_value: Option<String>,
+    config_path: Option<&PathBuf>,
+) -> Result<ColorRange, ConfigError> {
+    let Some(value) = normalize_string(file_value) else {
+        return Ok(ColorRange::Auto);
+    };
+    ColorRange::from_str(&value).map_err(|_| ConfigError::InvalidValue {
+        path: config_path.cloned(),
+        field: "decoder.color_range",
+        value,
+    })
+}
 
-    #[test]
-    fn roi_defaults_to_full_when_missing() {
-        let roi = resolve_detection_roi(None, None, true, None).unwrap();
-        assert_eq!(roi, full_frame_roi());
+fn resolve_text_direction(
+    file_value: Option<String>,
+    config_path: Option<&PathBuf>,
+) -> Result<Option<TextDirection>, ConfigError> {
+    let Some(value) = normalize_string(file_value) else {
+        return Ok(None);
+    };
+    match value.to_ascii_lowercase().as_str() {
+        "auto" => Ok(None),
+        "ltr" | "left-to-right" => Ok(Some(TextDirection::LeftToRight)),
+        "rtl" | "right-to-left" => Ok(Some(TextDirection::RightToLeft)),
+        _ => Err(ConfigError::InvalidValue {
+            path: config_path.cloned(),
+            field: "ocr.text_direction",
+            value,
+        }),
     }
+}
 
-    #[test]
-    fn zero_sized_roi_falls_back_to_full_frame() {
-        let roi = resolve_detection_roi(
-            Some(RoiConfig {
-                x: 0.5,
-                y: 0.5,
-                width: 0.0,
-                height: 0.0,
-            }),
-            None,
-            false,
-            None,
-        )
-        .unwrap();
-        assert_eq!(roi, full_frame_roi());
-    }
+fn resolve_output_encoding(
+    cli_value: Option<String>,
+    file_value: Option<String>,
+    config_path: Option<&PathBuf>,
+) -> Result<OutputEncoding, ConfigError> {
+    let (value, path) = match normalize_string(cli_value) {
+        Some(value) => (value, None),
+        None => match normalize_string(file_value) {
+            Some(value) => (value, config_path.cloned()),
+            None => return Ok(OutputEncoding::default()),
+        },
+    };
+    OutputEncoding::from_label(&value).ok_or(ConfigError::InvalidValue {
+        path,
+        field: "output.encoding",
+        value,
+    })
+}
+
+/// An explicit `--format`/`output.format` wins; otherwise the output path's extension decides,
+/// and an unrecognized one is an error rather than a silent SRT.
+fn resolve_output_format(
+    cli_value: Option<String>,
+    file_value: Option<String>,
+    config_path: Option<&PathBuf>,
+    output_path: Option<&Path>,
+    output_path_source: Option<&PathBuf>,
+) -> Result<OutputFormat, ConfigError> {
+    let explicit = match normalize_string(cli_value) {
+        Some(value) => Some((value, None)),
+        None => normalize_string(file_value).map(|value| (value, config_path.cloned())),
+    };
+    if let Some((value, path)) = explicit {
+        return value.parse().map_err(|_| ConfigError::InvalidValue {
+            path,
+            field: "output.format",
+            value,
+        });
+    }
+    let Some(output_path) = output_path else {
+        return Ok(OutputFormat::default());
+    };
+    OutputFormat::from_path(output_path).map_err(|_| ConfigError::InvalidValue {
+        path: output_path_source.cloned(),
+        field: "output.path",
+        value: output_path.display().to_string(),
+    })
+}
+
+/// Passes `value` through, rejecting an explicit zero for settings where it has no meaning.
+fn reject_zero<T: Copy + Default + PartialEq + fmt::Display>(
+    value: Option<T>,
+    field: &'static str,
+    config_path: Option<&PathBuf>,
+) -> Result<Option<T>, ConfigError> {
+    match value {
+        Some(value) if value == T::default() => Err(ConfigError::InvalidValue {
+            path: config_path.cloned(),
+            field,
+            value: value.to_string(),
+        }),
+        other => Ok(other),
+    }
+}
+
+fn resolve_confidence_decay(
+    file_value: Option<f32>,
+    config_path: Option<&PathBuf>,
+) -> Result<f32, ConfigError> {
+    let Some(value) = file_value else {
+        return Ok(0.0);
+    };
+    if !value.is_finite() || !(0.0..1.0).contains(&value) {
+        return Err(ConfigError::InvalidValue {
+            path: config_path.cloned(),
+            field: "ocr.confidence_decay",
+            value: value.to_string(),
+        });
+    }
+    Ok(value)
+}
+
+fn resolve_upscale_retry_below(
+    file_value: Option<f32>,
+    config_path: Option<&PathBuf>,
+) -> Result<Option<f32>, ConfigError> {
+    match file_value {
+        Some(value) if !value.is_finite() || !(0.0..=1.0).contains(&value) => {
+            Err(ConfigError::InvalidValue {
+                path: config_path.cloned(),
+                field: "ocr.upscale_retry_below",
+                value: value.to_string(),
+            })
+        }
+        other => Ok(other),
+    }
+}
+
+fn resolve_input_padding(
+    file_value: Option<String>,
+    config_path: Option<&PathBuf>,
+) -> Result<Option<InputPadding>, ConfigError> {
+    let Some(value) = normalize_string(file_value) else {
+        return Ok(None);
+    };
+    match value.to_ascii_lowercase().as_str() {
+        "mean" => Ok(None),
+        "black" => Ok(Some(InputPadding::Value(0.0))),
+        "white" => Ok(Some(InputPadding::Value(1.0))),
+        "border" => Ok(Some(InputPadding::BorderMedian)),
+        _ => Err(ConfigError::InvalidValue {
+            path: config_path.cloned(),
+            field: "ocr.input_padding",
+            value,
+        }),
+    }
+}
+
+fn resolve_decode_mode(
+    file_value: Option<String>,
+    beam_width: Option<usize>,
+    config_path: Option<&PathBuf>,
+) -> Result<DecodeMode, ConfigError> {
+    if beam_width == Some(0) {
+        return Err(ConfigError::InvalidValue {
+            path: config_path.cloned(),
+            field: "ocr.beam_width",
+            value: "0".to_string(),
+        });
+    }
+    let Some(value) = normalize_string(file_value) else {
+        return Ok(DecodeMode::Greedy);
+    };
+    match value.to_ascii_lowercase().as_str() {
+        "greedy" => Ok(DecodeMode::Greedy),
+        "beam" | "prefix-beam" => Ok(DecodeMode::PrefixBeam {
+            width: beam_width.unwrap_or(DEFAULT_BEAM_WIDTH),
+        }),
+        _ => Err(ConfigError::InvalidValue {
+            path: config_path.cloned(),
+            field: "ocr.decode",
+            value,
+        }),
+    }
+}
+
+fn resolve_roi_clip_margin(
+    file_value: Option<f32>,
+    config_path: Option<&PathBuf>,
+) -> Result<f32, ConfigError> {
+    let Some(value) = file_value else {
+        return Ok(0.0);
+    };
+    if !value.is_finite() || !(0.0..=1.0).contains(&value) {
+        return Err(ConfigError::InvalidValue {
+            path: config_path.cloned(),
+            field: "detection.roi_clip_margin",
+            value: value.to_string(),
+        });
+    }
+    Ok(value)
+}
+
+fn resolve_roi_rounding(
+    file_value: Option<String>,
+    config_path: Option<&PathBuf>,
+) -> Result<RoiRounding, ConfigError> {
+    let Some(value) = normalize_string(file_value) else {
+        return Ok(RoiRounding::Outward);
+    };
+    RoiRounding::from_str(&value).map_err(|_| ConfigError::InvalidValue {
+        path: config_path.cloned(),
+        field: "detection.roi_rounding",
+        value,
+    })
+}
+
+fn resolve_fusion_policy(
+    file_value: Option<String>,
+    config_path: Option<&PathBuf>,
+) -> Result<FusionPolicy, ConfigError> {
+    let Some(value) = normalize_string(file_value) else {
+        return Ok(FusionPolicy::Intersection);
+    };
+    FusionPolicy::from_str(&value).map_err(|_| ConfigError::InvalidValue {
+        path: config_path.cloned(),
+        field: "detection.fusion_policy",
+        value,
+    })
+}
+
+fn resolve_line_valley_depth(
+    file_value: Option<f32>,
+    config_path: Option<&PathBuf>,
+) -> Result<f32, ConfigError> {
+    let Some(value) = file_value else {
+        return Ok(DEFAULT_LINE_VALLEY_DEPTH);
+    };
+    if !value.is_finite() || value <= 0.0 || value > 1.0 {
+        return Err(ConfigError::InvalidValue {
+            path: config_path.cloned(),
+            field: "detection.line_valley_depth",
+            value: value.to_string(),
+        });
+    }
+    Ok(value)
+}
+
+fn resolve_max_region_size_change(
+    file_value: Option<f32>,
+    config_path: Option<&PathBuf>,
+) -> Result<Option<f32>, ConfigError> {
+    match file_value {
+        Some(value) if !value.is_finite() || value <= 0.0 => Err(ConfigError::InvalidValue {
+            path: config_path.cloned(),
+            field: "detection.max_region_size_change",
+            value: value.to_string(),
+        }),
+        other => Ok(other),
+    }
+}
+
+fn resolve_min_detection_confidence(
+    file_value: Option<f32>,
+    config_path: Option<&PathBuf>,
+) -> Result<f32, ConfigError> {
+    let Some(value) = file_value else {
+        return Ok(0.0);
+    };
+    if !(0.0..=1.0).contains(&value) {
+        return Err(ConfigError::InvalidValue {
+            path: config_path.cloned(),
+            field: "detection.min_detection_confidence",
+            value: value.to_string(),
+        });
+    }
+    Ok(value)
+}
+
+fn resolve_region_smoothing(
+    file_value: Option<f32>,
+    config_path: Option<&PathBuf>,
+) -> Result<f32, ConfigError> {
+    let Some(value) = file_value else {
+        return Ok(0.0);
+    };
+    if !(0.0..1.0).contains(&value) {
+        return Err(ConfigError::InvalidValue {
+            path: config_path.cloned(),
+            field: "detection.region_smoothing",
+            value: value.to_string(),
+        });
+    }
+    Ok(value)
+}
+
+fn resolve_frame_rotation(
+    file_value: Option<f32>,
+    config_path: Option<&PathBuf>,
+) -> Result<f32, ConfigError> {
+    let Some(value) = file_value else {
+        return Ok(0.0);
+    };
+    if !value.is_finite() || value.abs() > 45.0 {
+        return Err(ConfigError::InvalidValue {
+            path: config_path.cloned(),
+            field: "detection.frame_rotation_deg",
+            value: value.to_string(),
+        });
+    }
+    Ok(value)
+}
+
+fn resolve_skip_intro(
+    file_value: Option<f64>,
+    config_path: Option<&PathBuf>,
+) -> Result<Duration, ConfigError> {
+    let Some(value) = file_value else {
+        return Ok(Duration::ZERO);
+    };
+    Duration::try_from_secs_f64(value).map_err(|_| ConfigError::InvalidValue {
+        path: config_path.cloned(),
+        field: "detection.skip_intro_seconds",
+        value: value.to_string(),
+    })
+}
+
+fn resolve_comparator_time_decay(
+    file_value: Option<f32>,
+    config_path: Option<&PathBuf>,
+) -> Result<f32, ConfigError> {
+    let Some(value) = file_value else {
+        return Ok(0.0);
+    };
+    if !value.is_finite() || value < 0.0 {
+        return Err(ConfigError::InvalidValue {
+            path: config_path.cloned(),
+            field: "detection.comparator_time_decay",
+            value: value.to_string(),
+        });
+    }
+    Ok(value)
+}
+
+fn resolve_fixed_regions(
+    cli_value: &[RoiConfig],
+    file_value: Option<Vec<RoiFileConfig>>,
+    config_path: Option<&PathBuf>,
+) -> Result<Vec<RoiConfig>, ConfigError> {
+    let raw: Vec<RoiConfig> = if cli_value.is_empty() {
+        file_regions(file_value)
+    } else {
+        cli_value.to_vec()
+    };
+    resolve_regions(raw, "detection.fixed_regions", config_path)
+}
+
+fn file_regions(file_value: Option<Vec<RoiFileConfig>>) -> Vec<RoiConfig> {
+    file_value
+        .unwrap_or_default()
+        .into_iter()
+        .map(|roi| RoiConfig {
+            x: roi.x.unwrap_or(0.0),
+            y: roi.y.unwrap_or(0.0),
+            width: roi.width.unwrap_or(0.0),
+            height: roi.height.unwrap_or(0.0),
+        })
+        .collect()
+}
+
+/// Validates frame-fraction areas, rejecting any that are not finite, negative or zero-sized.
+fn resolve_regions(
+    raw: Vec<RoiConfig>,
+    field: &'static str,
+    config_path: Option<&PathBuf>,
+) -> Result<Vec<RoiConfig>, ConfigError> {
+    raw.into_iter()
+        .map(|roi| {
+            let invalid = || ConfigError::InvalidValue {
+                path: config_path.cloned(),
+                field,
+                value: format!("{},{},{},{}", roi.x, roi.y, roi.width, roi.height),
+            };
+            if [roi.x, roi.y, roi.width, roi.height]
+                .iter()
+                .any(|value| !value.is_finite())
+            {
+                return Err(invalid());
+            }
+            normalize_roi(roi, config_path)
+                .map_err(|_| invalid())?
+                .ok_or_else(invalid)
+        })
+        .collect()
+}
+
+fn resolve_exclusion_overlap(
+    file_value: Option<f32>,
+    config_path: Option<&PathBuf>,
+) -> Result<f32, ConfigError> {
+    let Some(value) = file_value else {
+        return Ok(DEFAULT_EXCLUSION_OVERLAP);
+    };
+    if !value.is_finite() || value <= 0.0 || value > 1.0 {
+        return Err(ConfigError::InvalidValue {
+            path: config_path.cloned(),
+            field: "detection.exclusion_overlap",
+            value: value.to_string(),
+        });
+    }
+    Ok(value)
+}
+
+fn resolve_shear_compensation(
+    file_value: Option<ShearFileConfig>,
+    config_path: Option<&PathBuf>,
+) -> Result<Option<ShearCompensation>, ConfigError> {
+    let Some(file_value) = file_value else {
+        return Ok(None);
+    };
+    let defaults = ShearCompensation::default();
+    let max_angle_deg = file_value.max_angle.unwrap_or(defaults.max_angle_deg);
+    if !max_angle_deg.is_finite() || max_angle_deg <= 0.0 || max_angle_deg > 45.0 {
+        return Err(ConfigError::InvalidValue {
+            path: config_path.cloned(),
+            field: "detection.shear_compensation.max_angle",
+            value: max_angle_deg.to_string(),
+        });
+    }
+    let step_deg = file_value.step.unwrap_or(defaults.step_deg);
+    if !step_deg.is_finite() || step_deg <= 0.0 || step_deg > max_angle_deg {
+        return Err(ConfigError::InvalidValue {
+            path: config_path.cloned(),
+            field: "detection.shear_compensation.step",
+            value: step_deg.to_string(),
+        });
+    }
+    Ok(Some(ShearCompensation {
+        max_angle_deg,
+        step_deg,
+    }))
+}
+
+fn resolve_full_width_guard(
+    file_value: Option<FullWidthGuardFileConfig>,
+    config_path: Option<&PathBuf>,
+) -> Result<Option<FullWidthGuard>, ConfigError> {
+    let Some(file_value) = file_value else {
+        return Ok(None);
+    };
+    let defaults = FullWidthGuard::default();
+    let fraction = |value: Option<f32>, default: f32, field: &'static str| {
+        let value = value.unwrap_or(default);
+        if !value.is_finite() || value <= 0.0 || value > 1.0 {
+            return Err(ConfigError::InvalidValue {
+                path: config_path.cloned(),
+                field,
+                value: value.to_string(),
+            });
+        }
+        Ok(value)
+    };
+    Ok(Some(FullWidthGuard {
+        min_width: fraction(
+            file_value.min_width,
+            defaults.min_width,
+            "detection.full_width_guard.min_width",
+        )?,
+        min_height: fraction(
+            file_value.min_height,
+            defaults.min_height,
+            "detection.full_width_guard.min_height",
+        )?,
+        min_edge_density: fraction(
+            file_value.min_edge_density,
+            defaults.min_edge_density,
+            "detection.full_width_guard.min_edge_density",
+        )?,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[test]
+    fn roi_defaults_to_full_when_missing() {
+        let roi = resolve_detection_roi(None, None, true, None).unwrap();
+        assert_eq!(roi, full_frame_roi());
+    }
+
+    #[test]
+    fn zero_sized_roi_falls_back_to_full_frame() {
+        let roi = resolve_detection_roi(
+            Some(RoiConfig {
+                x: 0.5,
+                y: 0.5,
+                width: 0.0,
+                height: 0.0,
+            }),
+            None,
+            false,
+            None,
+        )
+        .unwrap();
+        assert_eq!(roi, full_frame_roi());
+    }
 
     #[test]
     fn roi_clamps_to_bounds() {
@@ -683,4 +2059,354 @@ mod tests {
         let roi = resolve_detection_roi(None, Some(file_roi), true, None).unwrap();
         assert_eq!(roi, full_frame_roi());
     }
+
+    #[test]
+    fn pixel_roi_converts_to_fractions() {
+        let file_roi = RoiFileConfig {
+            x: Some(192.0),
+            y: Some(864.0),
+            width: Some(1536.0),
+            height: Some(216.0),
+        };
+        let roi = resolve_pixel_roi(None, Some(file_roi), true, None)
+            .unwrap()
+            .unwrap();
+        let settings = DetectionSettings {
+            samples_per_second: 7,
+            target: DEFAULT_TARGET,
+            delta: DEFAULT_DELTA,
+            detector: SubtitleDetectorKind::ProjectionBand,
+            detector_priority: DEFAULT_AUTO_DETECTOR_PRIORITY.to_vec(),
+            comparator: None,
+            comparator_time_decay: 0.0,
+            roi: Some(roi),
+            roi_units: RoiUnits::Pixels,
+            max_in_flight: None,
+            bridge_gap_ms: 0,
+            black_gap_bridge_ms: 0,
+            roi_clip_margin: 0.0,
+            roi_rounding: RoiRounding::Outward,
+            roi_half_pixel: false,
+            min_detection_confidence: 0.0,
+            fusion_policy: FusionPolicy::Intersection,
+            shear_compensation: None,
+            split_lines: false,
+            line_valley_depth: DEFAULT_LINE_VALLEY_DEPTH,
+            min_region: RegionSizeConfig::default(),
+            max_region_size_change: None,
+            region_smoothing: 0.0,
+            max_regions_per_frame: None,
+            full_width_guard: None,
+            fixed_regions: Vec::new(),
+            exclusion_zones: Vec::new(),
+            exclusion_overlap: DEFAULT_EXCLUSION_OVERLAP,
+            frame_rotation_deg: 0.0,
+            band_softness: 0,
+            skip_intro: Duration::ZERO,
+            warmup_frames: 0,
+            flush_at_end: true,
+        };
+        let fractional = settings
+            .fractional_roi(Some(1920), Some(1080))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            fractional,
+            RoiConfig {
+                x: 0.1,
+                y: 0.8,
+                width: 0.8,
+                height: 0.2
+            }
+        );
+    }
+
+    #[test]
+    fn pixel_roi_requires_frame_dimensions() {
+        let settings = DetectionSettings {
+            samples_per_second: 7,
+            target: DEFAULT_TARGET,
+            delta: DEFAULT_DELTA,
+            detector: SubtitleDetectorKind::ProjectionBand,
+            detector_priority: DEFAULT_AUTO_DETECTOR_PRIORITY.to_vec(),
+            comparator: None,
+            comparator_time_decay: 0.0,
+            roi: Some(RoiConfig {
+                x: 10.0,
+                y: 10.0,
+                width: 100.0,
+                height: 50.0,
+            }),
+            roi_units: RoiUnits::Pixels,
+            max_in_flight: None,
+            bridge_gap_ms: 0,
+            black_gap_bridge_ms: 0,
+            roi_clip_margin: 0.0,
+            roi_rounding: RoiRounding::Outward,
+            roi_half_pixel: false,
+            min_detection_confidence: 0.0,
+            fusion_policy: FusionPolicy::Intersection,
+            shear_compensation: None,
+            split_lines: false,
+            line_valley_depth: DEFAULT_LINE_VALLEY_DEPTH,
+            min_region: RegionSizeConfig::default(),
+            max_region_size_change: None,
+            region_smoothing: 0.0,
+            max_regions_per_frame: None,
+            full_width_guard: None,
+            fixed_regions: Vec::new(),
+            exclusion_zones: Vec::new(),
+            exclusion_overlap: DEFAULT_EXCLUSION_OVERLAP,
+            frame_rotation_deg: 0.0,
+            band_softness: 0,
+            skip_intro: Duration::ZERO,
+            warmup_frames: 0,
+            flush_at_end: true,
+        };
+        assert!(settings.fractional_roi(None, Some(1080)).is_err());
+        // A pixel ROI that misses the frame entirely is an error, not the full frame.
+        assert!(settings.fractional_roi(Some(8), Some(8)).is_err());
+    }
+
+    #[test]
+    fn config_flag_file_is_loaded_over_discovered_configs() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("job.toml");
+        fs::write(&path, "[detection]\nsamples_per_second = 3\n").expect("write config");
+        let env_path = dir.path().join("env.toml");
+
+        let explicit = explicit_config_path(Some(&path), Some(env_path.clone().into()));
+        assert_eq!(explicit.as_deref(), Some(path.as_path()));
+        assert_eq!(
+            explicit_config_path(None, Some(env_path.clone().into())),
+            Some(env_path)
+        );
+        assert_eq!(explicit_config_path(None, Some(OsString::new())), None);
+
+        let flag = path.to_str().expect("utf-8 path");
+        let cli = CliArgs::try_parse_from(["subtitle-fast", "--config", flag]).expect("cli args");
+        let resolved = resolve_settings(&cli, &CliSources::default()).expect("settings");
+        assert_eq!(resolved.config_path.as_deref(), Some(path.as_path()));
+        assert_eq!(resolved.settings.detection.samples_per_second, 3);
+
+        let missing = dir.path().join("missing.toml");
+        let flag = missing.to_str().expect("utf-8 path");
+        let cli = CliArgs::try_parse_from(["subtitle-fast", "--config", flag]).expect("cli args");
+        assert!(matches!(
+            resolve_settings(&cli, &CliSources::default()),
+            Err(ConfigError::NotFound { path }) if path == missing
+        ));
+    }
+
+    fn resolve_detection(detection: DetectionFileConfig) -> DetectionSettings {
+        let cli = CliArgs::try_parse_from(["subtitle-fast"]).expect("cli args");
+        let file = FileConfig {
+            detection: Some(detection),
+            ..FileConfig::default()
+        };
+        merge(&cli, &CliSources::default(), file, None)
+            .expect("valid settings")
+            .settings
+            .detection
+    }
+
+    #[test]
+    fn sensitivity_presets_map_to_concrete_values() {
+        let cases = [
+            ("low", 235, 8, 32),
+            ("medium", 230, 12, 24),
+            ("high", 225, 20, 16),
+        ];
+        for (name, target, delta, min_side) in cases {
+            let detection = resolve_detection(DetectionFileConfig {
+                sensitivity: Some(name.to_string()),
+                ..DetectionFileConfig::default()
+            });
+            assert_eq!(detection.target, target, "{name} target");
+            assert_eq!(detection.delta, delta, "{name} delta");
+            assert_eq!(
+                detection.min_region,
+                RegionSizeConfig {
+                    min_width_px: min_side,
+                    min_height_px: min_side,
+                },
+                "{name} min region"
+            );
+        }
+
+        let unset = resolve_detection(DetectionFileConfig::default());
+        assert_eq!(unset.target, DEFAULT_TARGET);
+        assert_eq!(unset.delta, DEFAULT_DELTA);
+        assert_eq!(unset.min_region, RegionSizeConfig::default());
+    }
+
+    #[test]
+    fn explicit_detection_values_override_sensitivity_preset() {
+        let detection = resolve_detection(DetectionFileConfig {
+            sensitivity: Some("high".to_string()),
+            delta: Some(5),
+            min_region_height: Some(40),
+            ..DetectionFileConfig::default()
+        });
+        assert_eq!(detection.target, 225);
+        assert_eq!(detection.delta, 5);
+        assert_eq!(detection.min_region.min_width_px, 16);
+        assert_eq!(detection.min_region.min_height_px, 40);
+
+        let err = resolve_detection_sensitivity(Some("extreme".into()), None).unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigError::InvalidValue { field, .. } if field == "detection.sensitivity"
+        ));
+    }
+
+    #[test]
+    fn saved_detection_settings_resolve_to_the_same_values() {
+        let detection = resolve_detection(DetectionFileConfig {
+            sensitivity: Some("high".to_string()),
+            delta: Some(5),
+            roi: Some(RoiFileConfig {
+                x: Some(0.1),
+                y: Some(0.7),
+                width: Some(0.8),
+                height: Some(0.25),
+            }),
+            split_lines: Some(true),
+            shear_compensation: Some(ShearFileConfig {
+                max_angle: Some(6.0),
+                step: Some(1.5),
+            }),
+            full_width_guard: Some(FullWidthGuardFileConfig {
+                min_height: Some(0.4),
+                ..FullWidthGuardFileConfig::default()
+            }),
+            exclusion_zones: Some(vec![RoiFileConfig {
+                x: Some(0.0),
+                y: Some(0.9),
+                width: Some(1.0),
+                height: Some(0.1),
+            }]),
+            skip_intro_seconds: Some(2.5),
+            ..DetectionFileConfig::default()
+        });
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("effective.toml");
+        fs::write(&path, "[output]\nformat = \"vtt\"\n").expect("write config");
+        save_detection_settings(&detection, &path).expect("save");
+
+        let saved = load_file_config(&path).expect("load saved config");
+        assert_eq!(
+            saved.output.and_then(|output| output.format).as_deref(),
+            Some("vtt")
+        );
+        let restored = resolve_detection(saved.detection.expect("detection section"));
+        assert_eq!(format!("{restored:?}"), format!("{detection:?}"));
+    }
+
+    #[test]
+    fn zero_ocr_input_size_is_invalid() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("config.toml");
+        fs::write(&path, "[ocr]\ninput_width = 0\n").expect("write config");
+        let flag = path.to_str().expect("utf-8 path");
+        let cli = CliArgs::try_parse_from(["subtitle-fast", "--config", flag]).expect("cli args");
+        assert!(matches!(
+            resolve_settings(&cli, &CliSources::default()),
+            Err(ConfigError::InvalidValue { field, value, .. })
+                if field == "ocr.input_width" && value == "0"
+        ));
+
+        let cli = CliArgs::try_parse_from(["subtitle-fast", "--ocr-input-height", "0"])
+            .expect("cli args");
+        assert!(matches!(
+            merge(&cli, &CliSources::default(), FileConfig::default(), None),
+            Err(ConfigError::InvalidValue { field, .. }) if field == "ocr.input_height"
+        ));
+    }
+
+    #[test]
+    fn model_download_concurrency_comes_from_the_config() {
+        let cli = CliArgs::try_parse_from(["subtitle-fast"]).expect("cli args");
+        let resolved =
+            merge(&cli, &CliSources::default(), FileConfig::default(), None).expect("settings");
+        assert_eq!(
+            resolved.settings.ocr.max_concurrent_downloads,
+            DEFAULT_MAX_CONCURRENT_DOWNLOADS
+        );
+
+        let file = |value| FileConfig {
+            ocr: Some(OcrFileConfig {
+                max_concurrent_downloads: Some(value),
+                ..OcrFileConfig::default()
+            }),
+            ..FileConfig::default()
+        };
+        let resolved = merge(&cli, &CliSources::default(), file(3), None).expect("settings");
+        assert_eq!(resolved.settings.ocr.max_concurrent_downloads, 3);
+        assert!(matches!(
+            merge(&cli, &CliSources::default(), file(0), None),
+            Err(ConfigError::InvalidValue { field, .. }) if field == "ocr.max_concurrent_downloads"
+        ));
+    }
+
+    #[test]
+    fn gap_markers_flag_overrides_the_config() {
+        let file = FileConfig {
+            output: Some(OutputFileConfig {
+                gap_markers_ms: Some(5000),
+                ..OutputFileConfig::default()
+            }),
+            ..FileConfig::default()
+        };
+        let cli =
+            CliArgs::try_parse_from(["subtitle-fast", "--gap-markers", "1500"]).expect("cli args");
+        let resolved = merge(&cli, &CliSources::default(), file, None).expect("settings");
+        assert_eq!(resolved.settings.output.gap_markers_ms, Some(1500));
+
+        let cli =
+            CliArgs::try_parse_from(["subtitle-fast", "--gap-markers", "0"]).expect("cli args");
+        assert!(matches!(
+            merge(&cli, &CliSources::default(), FileConfig::default(), None),
+            Err(ConfigError::InvalidValue { field, .. }) if field == "output.gap_markers_ms"
+        ));
+    }
+
+    #[test]
+    fn unknown_roi_units_are_invalid() {
+        let err = resolve_roi_units(None, Some("inches".into()), None).unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigError::InvalidValue { field, .. } if field == "detection.roi_units"
+        ));
+    }
+
+    #[test]
+    fn output_format_follows_the_path_unless_forced() {
+        let vtt = PathBuf::from("movie.vtt");
+        let txt = PathBuf::from("movie.txt");
+        assert_eq!(
+            resolve_output_format(None, None, None, Some(&vtt), None).unwrap(),
+            OutputFormat::Vtt
+        );
+        assert_eq!(
+            resolve_output_format(None, None, None, None, None).unwrap(),
+            OutputFormat::Srt
+        );
+        assert_eq!(
+            resolve_output_format(Some("ass".into()), None, None, Some(&txt), None).unwrap(),
+            OutputFormat::Ass
+        );
+
+        let err = resolve_output_format(None, None, None, Some(&txt), None).unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigError::InvalidValue { field, .. } if field == "output.path"
+        ));
+        let err = resolve_output_format(None, Some("docx".into()), None, None, None).unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigError::InvalidValue { field, .. } if field == "output.format"
+        ));
+    }
 }
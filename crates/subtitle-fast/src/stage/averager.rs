@@ -1,10 +1,11 @@
 use std::time::{Duration, Instant};
 
 use futures_util::{StreamExt, stream::unfold};
-use tokio::sync::mpsc;
+use tokio::sync::{broadcast, mpsc};
 
 use super::StreamBundle;
-use super::merge::{MergeOutput, MergeResult};
+use super::lifecycle::sample_time;
+use super::merge::{MergeOutput, MergeResult, SubtitleUpdate};
 use super::ocr::OcrTimings;
 use super::{PipelineError, PipelineProgress, PipelineUpdate};
 
@@ -13,7 +14,9 @@ const EMA_ALPHA: f64 = 0.1;
 
 pub type AveragerResult = Result<PipelineUpdate, PipelineError>;
 
-pub struct Averager;
+pub struct Averager {
+    subtitle_updates: Option<broadcast::Sender<SubtitleUpdate>>,
+}
 
 impl Default for Averager {
     fn default() -> Self {
@@ -23,7 +26,15 @@ impl Default for Averager {
 
 impl Averager {
     pub fn new() -> Self {
-        Self
+        Self {
+            subtitle_updates: None,
+        }
+    }
+
+    /// Publish every subtitle update to `sender` as well as the result stream.
+    pub fn with_subtitle_updates(mut self, sender: broadcast::Sender<SubtitleUpdate>) -> Self {
+        self.subtitle_updates = Some(sender);
+        self
     }
 
     pub fn attach(self, input: StreamBundle<MergeResult>) -> StreamBundle<AveragerResult> {
@@ -33,6 +44,7 @@ impl Averager {
         } = input;
 
         let (tx, rx) = mpsc::channel::<AveragerResult>(AVERAGER_CHANNEL_CAPACITY);
+        let subtitle_updates = self.subtitle_updates;
 
         tokio::spawn(async move {
             let mut upstream = stream;
@@ -42,6 +54,12 @@ impl Averager {
                 match event {
                     Ok(output) => {
                         state.observe(&output);
+                        if let Some(sender) = subtitle_updates.as_ref() {
+                            for update in &output.updates {
+                                // Sending only fails when nobody is subscribed.
+                                let _ = sender.send(update.clone());
+                            }
+                        }
                         let snapshot = state.snapshot(false);
                         let update = PipelineUpdate {
                             progress: snapshot,
@@ -52,7 +70,7 @@ impl Averager {
                         }
                     }
                     Err(err) => {
-                        let _ = tx.send(Err(PipelineError::Ocr(err))).await;
+                        let _ = tx.send(Err(PipelineError::from(err))).await;
                         return;
                     }
                 }
@@ -78,6 +96,7 @@ struct AveragerState {
     total_frames: Option<u64>,
     samples_seen: u64,
     latest_frame_index: Option<u64>,
+    latest_time: Duration,
     started: Instant,
     avg_detection_ms: Option<f64>,
     region_frames: u64,
@@ -95,6 +114,7 @@ impl AveragerState {
             total_frames,
             samples_seen: 0,
             latest_frame_index: None,
+            latest_time: Duration::ZERO,
             started: Instant::now(),
             avg_detection_ms: None,
             region_frames: 0,
@@ -110,6 +130,7 @@ impl AveragerState {
     fn observe(&mut self, event: &MergeOutput) {
         if let Some(sample) = &event.sample {
             self.samples_seen = self.samples_seen.saturating_add(1);
+            self.latest_time = self.latest_time.max(sample_time(&sample.sample));
             if let Some(total) = self.total_frames {
                 let frame_index = sample.sample.frame_index();
                 self.latest_frame_index = Some(frame_index);
@@ -155,6 +176,7 @@ impl AveragerState {
         PipelineProgress {
             samples_seen: self.samples_seen,
             latest_frame_index: latest,
+            latest_time: self.latest_time,
             total_frames: self.total_frames,
             fps: if elapsed > 0.0 {
                 (latest as f64) / elapsed
@@ -187,3 +209,60 @@ fn average_ms(total: Duration, units: u64) -> f64 {
     }
     total.as_secs_f64() * 1000.0 / units as f64
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stage::merge::{SubtitleStats, SubtitleUpdateKind};
+    use crate::subtitle::{MergedSubtitle, SubtitleLine};
+
+    fn output(kind: SubtitleUpdateKind, text: &str, end_ms: u64) -> MergeResult {
+        Ok(MergeOutput {
+            sample: None,
+            region_timings: None,
+            ocr_timings: None,
+            updates: vec![SubtitleUpdate {
+                kind,
+                subtitle: MergedSubtitle {
+                    id: 1,
+                    start_time: Duration::ZERO,
+                    end_time: Duration::from_millis(end_ms),
+                    start_frame: 0,
+                    end_frame: 0,
+                    lines: vec![SubtitleLine {
+                        center: 0.5,
+                        text: text.to_string(),
+                        rtl: false,
+                        confidence: None,
+                        region: None,
+                    }],
+                },
+            }],
+            stats: SubtitleStats::default(),
+        })
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn subscribers_receive_subtitle_updates() {
+        let (tx, mut rx) = broadcast::channel(8);
+        let events = vec![
+            output(SubtitleUpdateKind::New, "hello", 500),
+            output(SubtitleUpdateKind::Updated, "hello", 900),
+        ];
+        let input = StreamBundle::new(Box::pin(futures_util::stream::iter(events)), None);
+
+        let mut stream = Averager::new()
+            .with_subtitle_updates(tx)
+            .attach(input)
+            .stream;
+        while stream.next().await.is_some() {}
+
+        let first = rx.recv().await.expect("new update");
+        assert_eq!(first.kind, SubtitleUpdateKind::New);
+        assert_eq!(first.subtitle.lines[0].text, "hello");
+        let second = rx.recv().await.expect("updated update");
+        assert_eq!(second.kind, SubtitleUpdateKind::Updated);
+        assert_eq!(second.subtitle.end_time, Duration::from_millis(900));
+        assert!(rx.recv().await.is_err(), "channel closes with the run");
+    }
+}
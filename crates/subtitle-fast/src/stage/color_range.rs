@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use super::sampler::FrameCorrection;
+use crate::settings::ColorRange;
+use subtitle_fast_types::{DecoderResult, FrameBuffer, VideoFrame};
+
+/// Lowest and highest luma code of limited ("TV") range video.
+const LIMITED_LUMA_MIN: f32 = 16.0;
+const LIMITED_LUMA_MAX: f32 = 235.0;
+
+/// Stretches limited-range luma to full range, so detection thresholds such as `target` mean the
+/// same brightness whatever range the source was encoded in.
+pub struct LumaRangeNormalizer {
+    range: ColorRange,
+}
+
+impl LumaRangeNormalizer {
+    pub fn new(range: ColorRange) -> Self {
+        Self { range }
+    }
+
+    /// The sampler correction applying the expansion, or `None` when frames are kept as decoded.
+    pub fn correction(self) -> Option<FrameCorrection> {
+        // Decoders don't report the source range, so `Auto` keeps their output as it is.
+        if self.range != ColorRange::Limited {
+            return None;
+        }
+        let table = limited_to_full_table();
+        Some(Arc::new(move |frame: &VideoFrame| {
+            expand_frame(frame, &table)
+        }))
+    }
+}
+
+/// Maps a limited-range luma code to full range; codes outside 16..=235 clamp to the ends.
+pub(crate) fn expand_limited_luma(value: u8) -> u8 {
+    let scaled =
+        (f32::from(value) - LIMITED_LUMA_MIN) * 255.0 / (LIMITED_LUMA_MAX - LIMITED_LUMA_MIN);
+    scaled.round().clamp(0.0, 255.0) as u8
+}
+
+fn limited_to_full_table() -> [u8; 256] {
+    let mut table = [0u8; 256];
+    for (value, entry) in table.iter_mut().enumerate() {
+        *entry = expand_limited_luma(value as u8);
+    }
+    table
+}
+
+/// Returns a copy of `frame` with its luma expanded; chroma is left as decoded. Native-handle
+/// frames pass through.
+fn expand_frame(frame: &VideoFrame, table: &[u8; 256]) -> DecoderResult<VideoFrame> {
+    if !matches!(frame.buffer(), FrameBuffer::Nv12(_)) {
+        return Ok(frame.clone());
+    }
+    let y_plane = frame
+        .y_plane()
+        .iter()
+        .map(|value| table[*value as usize])
+        .collect();
+    let expanded = VideoFrame::from_nv12_owned(
+        frame.width(),
+        frame.height(),
+        frame.y_stride(),
+        frame.uv_stride(),
+        frame.pts(),
+        frame.dts(),
+        y_plane,
+        frame.uv_plane().to_vec(),
+    )?;
+    Ok(expanded
+        .with_index(frame.index())
+        .with_serial(frame.serial()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limited_range_luma_expands_to_full_range() {
+        assert_eq!(expand_limited_luma(16), 0);
+        assert_eq!(expand_limited_luma(235), 255);
+        assert_eq!(expand_limited_luma(126), 128);
+        // Footroom and headroom codes clamp rather than wrap.
+        assert_eq!(expand_limited_luma(0), 0);
+        assert_eq!(expand_limited_luma(250), 255);
+
+        let table = limited_to_full_table();
+        assert!(table.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+}
@@ -0,0 +1,123 @@
+//! `REGION_DEBUG_IMAGES=<dir>` companion to the validator's textual `REGION_DEBUG` log: each
+//! sampled frame with detections is written as a grayscale PNG of its ROI with every
+//! detected region outlined.
+
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufWriter};
+use std::path::PathBuf;
+
+use subtitle_fast_types::{DetectionRegion, RoiConfig, SubtitleDetectionResult, VideoFrame};
+
+const REGION_DEBUG_IMAGES_ENV: &str = "REGION_DEBUG_IMAGES";
+
+pub(crate) struct RegionDebugImages {
+    dir: PathBuf,
+    /// Fractional ROI; `None` dumps the full frame.
+    roi: Option<RoiConfig>,
+}
+
+impl RegionDebugImages {
+    pub(crate) fn new(dir: PathBuf, roi: Option<RoiConfig>) -> Self {
+        Self { dir, roi }
+    }
+
+    pub(crate) fn from_env(roi: Option<RoiConfig>) -> Option<Self> {
+        let dir = env::var_os(REGION_DEBUG_IMAGES_ENV).filter(|value| !value.is_empty())?;
+        Some(Self::new(PathBuf::from(dir), roi))
+    }
+
+    /// Writes `frame-<index>.png` when `detection` found any regions.
+    pub(crate) fn write(
+        &self,
+        frame_index: u64,
+        frame: &VideoFrame,
+        detection: &SubtitleDetectionResult,
+    ) -> io::Result<Option<PathBuf>> {
+        if detection.regions.is_empty() {
+            return Ok(None);
+        }
+        let Some((left, top, width, height)) = roi_rect(frame, self.roi) else {
+            return Ok(None);
+        };
+
+        let stride = frame.stride();
+        let data = frame.data();
+        if data.len() < (top + height - 1) * stride + left + width {
+            return Ok(None);
+        }
+        let mut pixels = Vec::with_capacity(width * height);
+        for row in top..top + height {
+            pixels.extend_from_slice(&data[row * stride + left..row * stride + left + width]);
+        }
+        for region in &detection.regions {
+            outline(&mut pixels, width, height, left, top, region);
+        }
+
+        fs::create_dir_all(&self.dir)?;
+        let path = self.dir.join(format!("frame-{frame_index:08}.png"));
+        let mut encoder = png::Encoder::new(
+            BufWriter::new(File::create(&path)?),
+            width as u32,
+            height as u32,
+        );
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer.write_image_data(&pixels).map_err(io::Error::other)?;
+        writer.finish().map_err(io::Error::other)?;
+        Ok(Some(path))
+    }
+}
+
+fn roi_rect(frame: &VideoFrame, roi: Option<RoiConfig>) -> Option<(usize, usize, usize, usize)> {
+    let frame_width = frame.width() as usize;
+    let frame_height = frame.height() as usize;
+    let roi = roi.unwrap_or(RoiConfig {
+        x: 0.0,
+        y: 0.0,
+        width: 1.0,
+        height: 1.0,
+    });
+    let left = ((roi.x * frame_width as f32).floor().max(0.0) as usize).min(frame_width);
+    let top = ((roi.y * frame_height as f32).floor().max(0.0) as usize).min(frame_height);
+    let right = (((roi.x + roi.width) * frame_width as f32).ceil().max(0.0) as usize)
+        .clamp(left, frame_width);
+    let bottom = (((roi.y + roi.height) * frame_height as f32).ceil().max(0.0) as usize)
+        .clamp(top, frame_height);
+    (right > left && bottom > top).then_some((left, top, right - left, bottom - top))
+}
+
+/// Draws a two-pixel border (white outside, black inside) so it shows on any background.
+fn outline(
+    pixels: &mut [u8],
+    width: usize,
+    height: usize,
+    left: usize,
+    top: usize,
+    region: &DetectionRegion,
+) {
+    let x0 = region.x.floor() as isize - left as isize;
+    let y0 = region.y.floor() as isize - top as isize;
+    let x1 = (region.x + region.width).ceil() as isize - left as isize - 1;
+    let y1 = (region.y + region.height).ceil() as isize - top as isize - 1;
+    for (inset, value) in [(0, 255u8), (1, 0u8)] {
+        let (x0, y0, x1, y1) = (x0 + inset, y0 + inset, x1 - inset, y1 - inset);
+        if x1 < x0 || y1 < y0 {
+            return;
+        }
+        let mut set = |x: isize, y: isize| {
+            if (0..width as isize).contains(&x) && (0..height as isize).contains(&y) {
+                pixels[y as usize * width + x as usize] = value;
+            }
+        };
+        for x in x0..=x1 {
+            set(x, y0);
+            set(x, y1);
+        }
+        for y in y0..=y1 {
+            set(x0, y);
+            set(x1, y);
+        }
+    }
+}
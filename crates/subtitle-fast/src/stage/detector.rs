@@ -1,12 +1,15 @@
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
 use futures_util::{StreamExt, stream::unfold};
 use tokio::sync::mpsc;
 
 use super::StreamBundle;
+use super::debug_images::RegionDebugImages;
 use super::sampler::{SampledFrame, SamplerResult};
+use crate::log_error;
 use crate::settings::DetectionSettings;
-use subtitle_fast_types::{DecoderError, SubtitleDetectionResult};
+use subtitle_fast_types::{DecoderError, RoiConfig, SubtitleDetectionResult};
 use subtitle_fast_validator::subtitle_detection::SubtitleDetectionError;
 use subtitle_fast_validator::{FrameValidator, FrameValidatorConfig, SubtitleDetectionOptions};
 
@@ -17,6 +20,8 @@ pub struct DetectionSample {
     pub sample: SampledFrame,
     pub detection: SubtitleDetectionResult,
     pub elapsed: Duration,
+    /// The ROI was pixel-identical to the previous sample, so its detection was reused.
+    pub reused: bool,
 }
 
 #[derive(Debug)]
@@ -27,6 +32,8 @@ pub enum DetectorError {
 
 pub struct Detector {
     validator: FrameValidator,
+    roi: Option<RoiConfig>,
+    debug_images: Option<RegionDebugImages>,
 }
 
 impl Detector {
@@ -34,14 +41,36 @@ impl Detector {
         let mut detection_options = SubtitleDetectionOptions::default();
         detection_options.luma_band.target = settings.target;
         detection_options.luma_band.delta = settings.delta;
+        detection_options.luma_band.softness = settings.band_softness;
         detection_options.roi = settings.roi;
         detection_options.detector = settings.detector;
+        detection_options.auto_priority = settings.detector_priority.clone();
+        detection_options.roi_clip_margin = settings.roi_clip_margin;
+        detection_options.roi_rounding = settings.roi_rounding;
+        detection_options.roi_half_pixel = settings.roi_half_pixel;
+        detection_options.min_detection_confidence = settings.min_detection_confidence;
+        detection_options.fusion_policy = settings.fusion_policy;
+        detection_options.shear_compensation = settings.shear_compensation;
+        detection_options.split_lines = settings.split_lines;
+        detection_options.line_valley_depth = settings.line_valley_depth;
+        detection_options.min_region = settings.min_region;
+        detection_options.max_regions = settings.max_regions_per_frame;
 
         let config = FrameValidatorConfig {
             detection: detection_options,
         };
         let validator = FrameValidator::new(config)?;
-        Ok(Self { validator })
+        Ok(Self {
+            validator,
+            roi: settings.roi,
+            debug_images: RegionDebugImages::from_env(settings.roi),
+        })
+    }
+
+    /// Dump each sampled frame with detections to `dir`, as `REGION_DEBUG_IMAGES` does.
+    pub fn with_debug_images(mut self, dir: PathBuf) -> Self {
+        self.debug_images = Some(RegionDebugImages::new(dir, self.roi));
+        self
     }
 
     pub fn attach(self, input: StreamBundle<SamplerResult>) -> StreamBundle<DetectionSampleResult> {
@@ -52,9 +81,10 @@ impl Detector {
 
         let (tx, rx) = mpsc::channel::<DetectionSampleResult>(DETECTOR_CHANNEL_CAPACITY);
         let validator = self.validator;
+        let debug_images = self.debug_images;
 
         tokio::spawn(async move {
-            let worker = DetectorWorker::new(validator);
+            let mut worker = DetectorWorker::new(validator, debug_images);
             let mut upstream = stream;
 
             while let Some(sample_result) = upstream.next().await {
@@ -89,14 +119,36 @@ impl Detector {
 
 struct DetectorWorker {
     validator: FrameValidator,
+    last: Option<(u64, SubtitleDetectionResult)>,
+    debug_images: Option<RegionDebugImages>,
 }
 
 impl DetectorWorker {
-    fn new(validator: FrameValidator) -> Self {
-        Self { validator }
+    fn new(validator: FrameValidator, debug_images: Option<RegionDebugImages>) -> Self {
+        Self {
+            validator,
+            last: None,
+            debug_images,
+        }
     }
 
-    async fn handle_sample(&self, sample: SampledFrame) -> Result<DetectionSample, DetectorError> {
+    async fn handle_sample(
+        &mut self,
+        sample: SampledFrame,
+    ) -> Result<DetectionSample, DetectorError> {
+        let roi_hash = sample.roi_hash();
+        if let (Some(hash), Some((last_hash, last_detection))) = (roi_hash, self.last.as_ref())
+            && hash == *last_hash
+        {
+            self.dump_debug_image(&sample, last_detection);
+            return Ok(DetectionSample {
+                sample,
+                detection: last_detection.clone(),
+                elapsed: Duration::ZERO,
+                reused: true,
+            });
+        }
+
         let frame = sample.frame().clone();
         let started = Instant::now();
         let detection = self
@@ -105,15 +157,195 @@ impl DetectorWorker {
             .await
             .map_err(DetectorError::Detection)?;
         let elapsed = started.elapsed();
+        self.last = roi_hash.map(|hash| (hash, detection.clone()));
+        self.dump_debug_image(&sample, &detection);
 
         Ok(DetectionSample {
             sample,
             detection,
             elapsed,
+            reused: false,
         })
     }
 
+    fn dump_debug_image(&self, sample: &SampledFrame, detection: &SubtitleDetectionResult) {
+        let Some(debug_images) = self.debug_images.as_ref() else {
+            return;
+        };
+        if let Err(err) = debug_images.write(sample.frame_index(), sample.frame(), detection) {
+            log_error!(
+                "[region-debug] failed to write image for frame {}: {err}",
+                sample.frame_index()
+            );
+        }
+    }
+
     async fn finalize(&self) {
         self.validator.finalize().await;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::RoiUnits;
+    use crate::stage::determiner::RegionDeterminer;
+    use crate::stage::lifecycle::RegionLifecycleTracker;
+    use crate::stage::sampler::FrameSampler;
+    use subtitle_fast_types::{DecoderResult, VideoFrame};
+    use subtitle_fast_validator::subtitle_detection::{
+        DEFAULT_AUTO_DETECTOR_PRIORITY, DEFAULT_DELTA, DEFAULT_LINE_VALLEY_DEPTH, DEFAULT_TARGET,
+        FusionPolicy, RegionSizeConfig, RoiRounding, SubtitleDetectorKind,
+    };
+
+    const WIDTH: usize = 640;
+    const HEIGHT: usize = 240;
+
+    fn held_frame(second: u64) -> DecoderResult<VideoFrame> {
+        let mut y_plane = vec![0u8; WIDTH * HEIGHT];
+        for y in 160..190 {
+            y_plane[y * WIDTH + 40..y * WIDTH + WIDTH - 40].fill(230);
+        }
+        frame_with_luma(second, y_plane)
+    }
+
+    fn frame_with_luma(second: u64, y_plane: Vec<u8>) -> DecoderResult<VideoFrame> {
+        VideoFrame::from_nv12_owned(
+            WIDTH as u32,
+            HEIGHT as u32,
+            WIDTH,
+            WIDTH,
+            Some(Duration::from_secs(second)),
+            None,
+            y_plane,
+            vec![128u8; WIDTH * HEIGHT / 2],
+        )
+    }
+
+    fn band_settings() -> DetectionSettings {
+        DetectionSettings {
+            samples_per_second: 1,
+            target: DEFAULT_TARGET,
+            delta: DEFAULT_DELTA,
+            detector: SubtitleDetectorKind::ProjectionBand,
+            detector_priority: DEFAULT_AUTO_DETECTOR_PRIORITY.to_vec(),
+            comparator: None,
+            comparator_time_decay: 0.0,
+            roi: Some(RoiConfig {
+                x: 0.0,
+                y: 0.0,
+                width: 1.0,
+                height: 1.0,
+            }),
+            roi_units: RoiUnits::Fraction,
+            max_in_flight: None,
+            bridge_gap_ms: 0,
+            black_gap_bridge_ms: 0,
+            roi_clip_margin: 0.0,
+            roi_rounding: RoiRounding::Outward,
+            roi_half_pixel: false,
+            min_detection_confidence: 0.0,
+            fusion_policy: FusionPolicy::Intersection,
+            shear_compensation: None,
+            split_lines: false,
+            line_valley_depth: DEFAULT_LINE_VALLEY_DEPTH,
+            min_region: RegionSizeConfig::default(),
+            max_region_size_change: None,
+            region_smoothing: 0.0,
+            max_regions_per_frame: None,
+            full_width_guard: None,
+            fixed_regions: Vec::new(),
+            exclusion_zones: Vec::new(),
+            exclusion_overlap: crate::stage::exclusion::DEFAULT_EXCLUSION_OVERLAP,
+            frame_rotation_deg: 0.0,
+            band_softness: 0,
+            skip_intro: Duration::ZERO,
+            warmup_frames: 0,
+            flush_at_end: true,
+        }
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn static_hold_detects_once_and_extends_region() {
+        let settings = band_settings();
+        let frames: Vec<_> = (0..5).map(held_frame).collect();
+        let input = StreamBundle::new(Box::pin(futures_util::stream::iter(frames)), None);
+        let sampled = FrameSampler::new(1).with_roi(settings.roi).attach(input);
+        let detected = Detector::new(&settings).expect("detector").attach(sampled);
+        let determined = RegionDeterminer::new().attach(detected);
+        let mut tracked = RegionLifecycleTracker::new(&settings)
+            .attach(determined)
+            .stream;
+
+        let mut detections_run = 0;
+        let mut reused = 0;
+        let mut completed = Vec::new();
+        while let Some(event) = tracked.next().await {
+            let event = event.expect("lifecycle event");
+            if let Some(sample) = event.sample {
+                assert!(sample.detection.has_subtitle);
+                if sample.reused {
+                    reused += 1;
+                } else {
+                    detections_run += 1;
+                }
+            }
+            completed.extend(event.completed);
+        }
+
+        assert_eq!(detections_run, 1);
+        assert_eq!(reused, 4);
+        assert_eq!(completed.len(), 1);
+        assert_eq!(completed[0].start_time, Duration::ZERO);
+        assert_eq!(completed[0].end_time, Duration::from_secs(4));
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn debug_images_are_written_for_frames_with_detections() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let roi = RoiConfig {
+            x: 0.0,
+            y: 0.5,
+            width: 1.0,
+            height: 0.5,
+        };
+        let settings = DetectionSettings {
+            roi: Some(roi),
+            ..band_settings()
+        };
+        let frames = vec![
+            held_frame(0),
+            frame_with_luma(1, vec![0u8; WIDTH * HEIGHT]),
+            held_frame(2),
+        ];
+        let input = StreamBundle::new(Box::pin(futures_util::stream::iter(frames)), None);
+        let sampled = FrameSampler::new(1).with_roi(settings.roi).attach(input);
+        let mut detected = Detector::new(&settings)
+            .expect("detector")
+            .with_debug_images(dir.path().to_path_buf())
+            .attach(sampled)
+            .stream;
+
+        let mut with_regions = 0;
+        while let Some(sample) = detected.next().await {
+            let sample = sample.expect("detection sample");
+            if !sample.detection.regions.is_empty() {
+                with_regions += 1;
+            }
+        }
+
+        let mut written: Vec<_> = std::fs::read_dir(dir.path())
+            .expect("dump dir")
+            .map(|entry| entry.expect("entry").path())
+            .collect();
+        written.sort();
+        assert_eq!(with_regions, 2);
+        assert_eq!(written.len(), 2);
+        let decoder = png::Decoder::new(std::io::BufReader::new(
+            std::fs::File::open(&written[0]).expect("png file"),
+        ));
+        let reader = decoder.read_info().expect("png header");
+        assert_eq!(reader.info().width as usize, WIDTH);
+        assert_eq!(reader.info().height as usize, HEIGHT / 2);
+    }
+}
@@ -7,10 +7,13 @@ use tokio::sync::mpsc;
 
 use super::StreamBundle;
 use super::detector::{DetectionSample, DetectionSampleResult, DetectorError};
-use subtitle_fast_types::{DetectionRegion, RoiConfig, VideoFrame};
+use subtitle_fast_types::{DetectionRegion, RoiConfig, SubtitleDetectionResult, VideoFrame};
 
 const REGION_DETERMINER_CHANNEL_CAPACITY: usize = 4;
 const IOU_THRESHOLD: f32 = 0.05;
+/// Luma difference between horizontal neighbours that counts as an edge for the full-width
+/// guard; glyph strokes step far more than gradients in sky or snow.
+const EDGE_LUMA_STEP: u8 = 40;
 
 pub type RegionId = u64;
 
@@ -32,17 +35,53 @@ pub enum RegionDeterminerError {
     Detector(DetectorError),
 }
 
+/// Detected regions spanning most of the ROI are more often a bright background (sky, snow)
+/// that passed the luma band than a subtitle; they are kept only when their luma has the
+/// dense horizontal edges of text.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FullWidthGuard {
+    /// Share of the ROI width a region must span to be checked.
+    pub min_width: f32,
+    /// Share of the ROI height a region must span to be checked.
+    pub min_height: f32,
+    /// Share of a checked region's pixels that must sit on an edge for it to be kept.
+    pub min_edge_density: f32,
+}
+
+impl Default for FullWidthGuard {
+    fn default() -> Self {
+        Self {
+            min_width: 0.9,
+            min_height: 0.5,
+            min_edge_density: 0.04,
+        }
+    }
+}
+
 pub struct RegionDeterminer {
     persistent: Arc<Mutex<PersistentStore>>,
+    full_width_guard: Option<(FullWidthGuard, RoiConfig)>,
 }
 
 impl RegionDeterminer {
     pub fn new() -> Self {
         Self {
             persistent: Arc::new(Mutex::new(PersistentStore::new())),
+            full_width_guard: None,
         }
     }
 
+    /// Checks regions against `guard` relative to the frame-fraction `roi`, or the whole
+    /// frame when it is `None`; a `None` guard accepts every region.
+    pub fn with_full_width_guard(
+        mut self,
+        guard: Option<FullWidthGuard>,
+        roi: Option<RoiConfig>,
+    ) -> Self {
+        self.full_width_guard = guard.map(|guard| (guard, roi.unwrap_or(FULL_FRAME)));
+        self
+    }
+
     pub fn attach(
         self,
         input: StreamBundle<DetectionSampleResult>,
@@ -53,11 +92,12 @@ impl RegionDeterminer {
         } = input;
 
         let persistent = Arc::clone(&self.persistent);
+        let full_width_guard = self.full_width_guard;
         let (tx, rx) = mpsc::channel::<RegionDeterminerResult>(REGION_DETERMINER_CHANNEL_CAPACITY);
 
         tokio::spawn(async move {
             let mut upstream = stream;
-            let mut worker = RegionDeterminerWorker::new(persistent);
+            let mut worker = RegionDeterminerWorker::new(persistent, full_width_guard);
 
             while let Some(event) = upstream.next().await {
                 match event {
@@ -91,15 +131,25 @@ impl Default for RegionDeterminer {
 
 struct RegionDeterminerWorker {
     persistent: Arc<Mutex<PersistentStore>>,
+    full_width_guard: Option<(FullWidthGuard, RoiConfig)>,
 }
 
 impl RegionDeterminerWorker {
-    fn new(persistent: Arc<Mutex<PersistentStore>>) -> Self {
-        Self { persistent }
+    fn new(
+        persistent: Arc<Mutex<PersistentStore>>,
+        full_width_guard: Option<(FullWidthGuard, RoiConfig)>,
+    ) -> Self {
+        Self {
+            persistent,
+            full_width_guard,
+        }
     }
 
-    fn handle_sample(&mut self, sample: DetectionSample) -> RegionDeterminerEvent {
+    fn handle_sample(&mut self, mut sample: DetectionSample) -> RegionDeterminerEvent {
         let frame = sample.sample.frame_handle();
+        if let Some((guard, roi)) = self.full_width_guard {
+            reject_background_regions(&mut sample.detection, &guard, &roi, &frame);
+        }
         let mut used_ids = HashSet::new();
         let mut emitted: Vec<RegionUnit> = Vec::with_capacity(sample.detection.regions.len());
 
@@ -204,6 +254,63 @@ struct PersistentRegion {
     roi: RoiConfig,
 }
 
+const FULL_FRAME: RoiConfig = RoiConfig {
+    x: 0.0,
+    y: 0.0,
+    width: 1.0,
+    height: 1.0,
+};
+
+fn reject_background_regions(
+    detection: &mut SubtitleDetectionResult,
+    guard: &FullWidthGuard,
+    roi: &RoiConfig,
+    frame: &VideoFrame,
+) {
+    let before = detection.regions.len();
+    detection.regions.retain(|region| {
+        let spans_roi = region_to_roi(region, frame);
+        let covers_roi = spans_roi.width >= roi.width * guard.min_width
+            && spans_roi.height >= roi.height * guard.min_height;
+        !covers_roi || edge_density(region, frame) >= guard.min_edge_density
+    });
+    if detection.regions.len() != before {
+        detection.has_subtitle = !detection.regions.is_empty();
+        detection.max_score = detection
+            .regions
+            .iter()
+            .map(|region| region.score)
+            .fold(0.0, f32::max);
+    }
+}
+
+/// Share of the region's luma pixels that differ from their right neighbour by at least
+/// `EDGE_LUMA_STEP`.
+fn edge_density(region: &DetectionRegion, frame: &VideoFrame) -> f32 {
+    let width = frame.width() as usize;
+    let height = frame.height() as usize;
+    let stride = frame.y_stride();
+    let plane = frame.y_plane();
+    let x0 = (region.x.max(0.0) as usize).min(width);
+    let x1 = ((region.x + region.width).max(0.0) as usize).min(width);
+    let y0 = (region.y.max(0.0) as usize).min(height);
+    let y1 = ((region.y + region.height).max(0.0) as usize).min(height);
+    if x1 <= x0 + 1 || y1 <= y0 {
+        return 0.0;
+    }
+    let mut edges = 0usize;
+    for row in plane.chunks(stride).skip(y0).take(y1 - y0) {
+        let Some(row) = row.get(x0..x1) else {
+            break;
+        };
+        edges += row
+            .windows(2)
+            .filter(|pair| pair[0].abs_diff(pair[1]) >= EDGE_LUMA_STEP)
+            .count();
+    }
+    edges as f32 / ((x1 - x0 - 1) * (y1 - y0)) as f32
+}
+
 fn region_to_roi(region: &DetectionRegion, frame: &VideoFrame) -> RoiConfig {
     let fw = frame.width().max(1) as f32;
     let fh = frame.height().max(1) as f32;
@@ -301,3 +408,104 @@ fn clip_region(smaller: &RoiConfig, larger: &RoiConfig) -> Option<RoiConfig> {
                 .unwrap_or(std::cmp::Ordering::Equal)
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stage::sampler::{FrameHistory, SampledFrame, SamplerContext};
+    use std::time::Duration;
+
+    const WIDTH: usize = 200;
+    const HEIGHT: usize = 100;
+
+    fn region(x: f32, y: f32, width: f32, height: f32) -> DetectionRegion {
+        DetectionRegion {
+            x,
+            y,
+            width,
+            height,
+            score: 0.8,
+        }
+    }
+
+    /// A flat bright top half, like snow or an overcast sky, above a bottom half of
+    /// alternating dark and bright columns standing in for glyph strokes.
+    fn sample(regions: Vec<DetectionRegion>) -> DetectionSample {
+        let mut luma = vec![220; WIDTH * HEIGHT];
+        for (y, row) in luma.chunks_mut(WIDTH).enumerate() {
+            if y >= HEIGHT / 2 {
+                for (x, value) in row.iter_mut().enumerate() {
+                    *value = if (x / 3) % 2 == 0 { 235 } else { 16 };
+                }
+            }
+        }
+        let frame = VideoFrame::from_nv12_owned(
+            WIDTH as u32,
+            HEIGHT as u32,
+            WIDTH,
+            WIDTH,
+            None,
+            None,
+            luma,
+            vec![128; WIDTH * HEIGHT / 2],
+        )
+        .expect("frame");
+        DetectionSample {
+            sample: SampledFrame::new(
+                0,
+                Arc::new(frame),
+                FrameHistory::new(Vec::new()),
+                Arc::new(SamplerContext::initial(None)),
+            ),
+            detection: SubtitleDetectionResult {
+                has_subtitle: !regions.is_empty(),
+                max_score: 0.8,
+                regions,
+            },
+            elapsed: Duration::ZERO,
+            reused: false,
+        }
+    }
+
+    fn worker(guard: Option<FullWidthGuard>) -> RegionDeterminerWorker {
+        let roi = RoiConfig {
+            x: 0.0,
+            y: 0.0,
+            width: 1.0,
+            height: 1.0,
+        };
+        RegionDeterminerWorker::new(
+            Arc::new(Mutex::new(PersistentStore::new())),
+            guard.map(|guard| (guard, roi)),
+        )
+    }
+
+    #[test]
+    fn full_width_guard_rejects_flat_backgrounds_and_keeps_text_bands() {
+        let guard = FullWidthGuard {
+            min_width: 0.9,
+            min_height: 0.4,
+            min_edge_density: 0.1,
+        };
+        let background = region(0.0, 0.0, WIDTH as f32, 50.0);
+        let band = region(0.0, 50.0, WIDTH as f32, 50.0);
+
+        let event = worker(Some(guard)).handle_sample(sample(vec![background.clone()]));
+        assert!(event.regions.is_empty());
+        assert!(!event.sample.detection.has_subtitle);
+        assert_eq!(event.sample.detection.max_score, 0.0);
+
+        let event = worker(Some(guard)).handle_sample(sample(vec![band.clone()]));
+        assert_eq!(event.regions.len(), 1);
+        assert!(event.sample.detection.has_subtitle);
+
+        // A flat region narrower than the guard's threshold is not checked at all.
+        let narrow = region(20.0, 0.0, 100.0, 50.0);
+        let event = worker(Some(guard)).handle_sample(sample(vec![narrow]));
+        assert_eq!(event.regions.len(), 1);
+
+        // Without a guard every region passes through.
+        let event = worker(None).handle_sample(sample(vec![background, band]));
+        assert_eq!(event.regions.len(), 2);
+    }
+}
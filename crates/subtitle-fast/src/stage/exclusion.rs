@@ -0,0 +1,181 @@
+use std::collections::HashSet;
+
+use futures_util::StreamExt;
+
+use super::StreamBundle;
+use super::merge::{MergeResult, SubtitleUpdate};
+use crate::log_debug;
+use crate::subtitle::MergedSubtitle;
+use subtitle_fast_types::RoiConfig;
+
+/// Share of a cue's region an exclusion zone may cover before the cue is dropped.
+pub const DEFAULT_EXCLUSION_OVERLAP: f32 = 0.5;
+
+/// Drops merged cues that sit on known UI furniture such as a top bar or a news ticker.
+///
+/// Unlike the ROI, which limits where the detector looks, this acts on merged cues, using the
+/// bounding box of the lines' regions in the first update seen for each cue. A cue goes when
+/// more than `max_overlap` of that box falls inside any zone. The decision is not revisited: a
+/// cue passed downstream is never withdrawn, so its later updates go through even if the merged
+/// box drifts into a zone, and a dropped cue stays dropped.
+pub struct ExclusionZones {
+    zones: Vec<RoiConfig>,
+    max_overlap: f32,
+    published: HashSet<u64>,
+    dropped: HashSet<u64>,
+}
+
+impl ExclusionZones {
+    /// `zones` are frame fractions; `max_overlap` is the share of a cue's box, in (0, 1], a
+    /// zone may cover before the cue is dropped.
+    pub fn new(zones: Vec<RoiConfig>, max_overlap: f32) -> Self {
+        Self {
+            zones,
+            max_overlap,
+            published: HashSet::new(),
+            dropped: HashSet::new(),
+        }
+    }
+
+    pub fn attach(self, input: StreamBundle<MergeResult>) -> StreamBundle<MergeResult> {
+        if self.zones.is_empty() {
+            return input;
+        }
+        let StreamBundle {
+            stream,
+            total_frames,
+        } = input;
+        let mut filter = self;
+        let stream = stream.map(move |event| {
+            event.map(|mut output| {
+                filter.retain(&mut output.updates);
+                output
+            })
+        });
+        StreamBundle::new(Box::pin(stream), total_frames)
+    }
+
+    fn retain(&mut self, updates: &mut Vec<SubtitleUpdate>) {
+        updates.retain(|update| {
+            let id = update.subtitle.id;
+            if self.published.contains(&id) {
+                return true;
+            }
+            if self.dropped.contains(&id) {
+                return false;
+            }
+            let overlap = cue_region(&update.subtitle)
+                .map(|region| self.overlap(&region))
+                .unwrap_or(0.0);
+            if overlap > self.max_overlap {
+                log_debug!(
+                    "dropping cue {id}: {:.0}% inside an exclusion zone",
+                    overlap * 100.0
+                );
+                self.dropped.insert(id);
+                return false;
+            }
+            self.published.insert(id);
+            true
+        });
+    }
+
+    /// Largest share of `region` covered by a single zone.
+    fn overlap(&self, region: &RoiConfig) -> f32 {
+        let area = region.width * region.height;
+        if area <= 0.0 {
+            return 0.0;
+        }
+        self.zones
+            .iter()
+            .map(|zone| intersection_area(region, zone) / area)
+            .fold(0.0, f32::max)
+    }
+}
+
+/// Bounding box of the regions the cue's lines were read from.
+fn cue_region(subtitle: &MergedSubtitle) -> Option<RoiConfig> {
+    let mut regions = subtitle.lines.iter().filter_map(|line| line.region);
+    let first = regions.next()?;
+    let (mut left, mut top) = (first.x, first.y);
+    let (mut right, mut bottom) = (first.x + first.width, first.y + first.height);
+    for region in regions {
+        left = left.min(region.x);
+        top = top.min(region.y);
+        right = right.max(region.x + region.width);
+        bottom = bottom.max(region.y + region.height);
+    }
+    Some(RoiConfig {
+        x: left,
+        y: top,
+        width: right - left,
+        height: bottom - top,
+    })
+}
+
+fn intersection_area(a: &RoiConfig, b: &RoiConfig) -> f32 {
+    let width = (a.x + a.width).min(b.x + b.width) - a.x.max(b.x);
+    let height = (a.y + a.height).min(b.y + b.height) - a.y.max(b.y);
+    width.max(0.0) * height.max(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stage::merge::SubtitleUpdateKind;
+    use crate::subtitle::SubtitleLine;
+    use std::time::Duration;
+
+    fn update(id: u64, region: RoiConfig) -> SubtitleUpdate {
+        SubtitleUpdate {
+            kind: SubtitleUpdateKind::New,
+            subtitle: MergedSubtitle {
+                id,
+                start_time: Duration::from_secs(id),
+                end_time: Duration::from_secs(id + 1),
+                start_frame: 0,
+                end_frame: 0,
+                lines: vec![SubtitleLine {
+                    center: region.y + region.height / 2.0,
+                    text: format!("cue {id}"),
+                    rtl: false,
+                    confidence: None,
+                    region: Some(region),
+                }],
+            },
+        }
+    }
+
+    #[test]
+    fn cues_mostly_inside_an_exclusion_zone_are_dropped() {
+        // A ticker along the bottom tenth of the frame.
+        let ticker = RoiConfig {
+            x: 0.0,
+            y: 0.9,
+            width: 1.0,
+            height: 0.1,
+        };
+        let mut zones = ExclusionZones::new(vec![ticker], 0.5);
+        let band = |y: f32| RoiConfig {
+            x: 0.2,
+            y,
+            width: 0.6,
+            height: 0.05,
+        };
+        // 80% of the first cue's box lies in the ticker, 20% of the second's.
+        let mut updates = vec![update(1, band(0.89)), update(2, band(0.86))];
+        zones.retain(&mut updates);
+        let kept: Vec<u64> = updates.iter().map(|update| update.subtitle.id).collect();
+        assert_eq!(kept, [2]);
+
+        // A cue already published is not withdrawn when it grows into the zone.
+        let mut later = vec![update(2, band(0.89))];
+        zones.retain(&mut later);
+        assert_eq!(later.len(), 1);
+
+        // Nor does a dropped cue come back when it moves out of the zone.
+        let mut moved = vec![update(1, band(0.5))];
+        zones.retain(&mut moved);
+        assert!(moved.is_empty());
+    }
+}
@@ -0,0 +1,209 @@
+use std::time::Duration;
+
+use futures_util::StreamExt;
+
+use super::StreamBundle;
+use super::detector::{DetectionSample, DetectorError};
+use super::determiner::{
+    RegionDeterminerError, RegionDeterminerEvent, RegionDeterminerResult, RegionUnit,
+};
+use super::sampler::SamplerResult;
+use subtitle_fast_types::{DetectionRegion, RoiConfig, SubtitleDetectionResult};
+
+/// Stands in for `Detector` and `RegionDeterminer` when the subtitle areas are known up front:
+/// every sampled frame reports the same regions, whatever its content.
+pub struct FixedRegions {
+    regions: Vec<RoiConfig>,
+}
+
+impl FixedRegions {
+    /// `regions` are frame fractions; region `n` keeps id `n` for the whole run.
+    pub fn new(regions: Vec<RoiConfig>) -> Self {
+        Self { regions }
+    }
+
+    pub fn attach(
+        self,
+        input: StreamBundle<SamplerResult>,
+    ) -> StreamBundle<RegionDeterminerResult> {
+        let StreamBundle {
+            stream,
+            total_frames,
+        } = input;
+        let regions = self.regions;
+
+        let stream = stream.map(move |result| {
+            let sample = result
+                .map_err(|err| RegionDeterminerError::Detector(DetectorError::Sampler(err)))?;
+            let frame = sample.frame();
+            let (width, height) = (frame.width() as f32, frame.height() as f32);
+            let detection = SubtitleDetectionResult {
+                has_subtitle: !regions.is_empty(),
+                max_score: 1.0,
+                regions: regions
+                    .iter()
+                    .map(|roi| DetectionRegion {
+                        x: roi.x * width,
+                        y: roi.y * height,
+                        width: roi.width * width,
+                        height: roi.height * height,
+                        score: 1.0,
+                    })
+                    .collect(),
+            };
+            let units = regions
+                .iter()
+                .enumerate()
+                .map(|(id, roi)| RegionUnit {
+                    id: id as u64,
+                    label: format!("region-{id}"),
+                    roi: *roi,
+                })
+                .collect();
+            Ok(RegionDeterminerEvent {
+                sample: DetectionSample {
+                    sample,
+                    detection,
+                    elapsed: Duration::ZERO,
+                    reused: false,
+                },
+                regions: units,
+            })
+        });
+
+        StreamBundle::new(Box::pin(stream), total_frames)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    use crate::settings::{DetectionSettings, RoiUnits};
+    use crate::stage::lifecycle::RegionLifecycleTracker;
+    use crate::stage::ocr::SubtitleOcr;
+    use crate::stage::sampler::FrameSampler;
+    use subtitle_fast_ocr::{OcrEngine, OcrError, OcrRequest};
+    use subtitle_fast_types::{OcrRegion, OcrResponse, OcrText, VideoFrame};
+    use subtitle_fast_validator::subtitle_detection::{
+        DEFAULT_AUTO_DETECTOR_PRIORITY, DEFAULT_DELTA, DEFAULT_LINE_VALLEY_DEPTH, DEFAULT_TARGET,
+        FusionPolicy, RegionSizeConfig, RoiRounding, SubtitleDetectorKind,
+    };
+
+    /// Records every region it is asked to read.
+    #[derive(Default)]
+    struct RecordingEngine {
+        regions: Mutex<Vec<OcrRegion>>,
+    }
+
+    impl OcrEngine for RecordingEngine {
+        fn name(&self) -> &'static str {
+            "recording"
+        }
+
+        fn recognize(&self, request: &OcrRequest<'_>) -> Result<OcrResponse, OcrError> {
+            let mut recorded = self.regions.lock().unwrap();
+            recorded.extend_from_slice(request.regions());
+            Ok(OcrResponse::new(
+                request
+                    .regions()
+                    .iter()
+                    .map(|region| OcrText::new(*region, "text".to_string()))
+                    .collect(),
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn ocr_reads_exactly_the_fixed_regions_on_blank_frames() {
+        let (width, height) = (200u32, 100u32);
+        let frames = (0..4).map(move |second| {
+            VideoFrame::from_nv12_owned(
+                width,
+                height,
+                width as usize,
+                width as usize,
+                Some(Duration::from_secs(second)),
+                None,
+                vec![0u8; (width * height) as usize],
+                vec![128u8; (width * height / 2) as usize],
+            )
+        });
+        let regions = vec![
+            RoiConfig {
+                x: 0.1,
+                y: 0.7,
+                width: 0.5,
+                height: 0.2,
+            },
+            RoiConfig {
+                x: 0.6,
+                y: 0.1,
+                width: 0.3,
+                height: 0.2,
+            },
+        ];
+        let settings = DetectionSettings {
+            samples_per_second: 1,
+            target: DEFAULT_TARGET,
+            delta: DEFAULT_DELTA,
+            detector: SubtitleDetectorKind::ProjectionBand,
+            detector_priority: DEFAULT_AUTO_DETECTOR_PRIORITY.to_vec(),
+            comparator: None,
+            comparator_time_decay: 0.0,
+            roi: None,
+            roi_units: RoiUnits::Fraction,
+            max_in_flight: None,
+            bridge_gap_ms: 0,
+            black_gap_bridge_ms: 0,
+            roi_clip_margin: 0.0,
+            roi_rounding: RoiRounding::Outward,
+            roi_half_pixel: false,
+            min_detection_confidence: 0.0,
+            fusion_policy: FusionPolicy::Intersection,
+            shear_compensation: None,
+            split_lines: false,
+            line_valley_depth: DEFAULT_LINE_VALLEY_DEPTH,
+            min_region: RegionSizeConfig::default(),
+            max_region_size_change: None,
+            region_smoothing: 0.0,
+            max_regions_per_frame: None,
+            full_width_guard: None,
+            fixed_regions: regions.clone(),
+            exclusion_zones: Vec::new(),
+            exclusion_overlap: crate::stage::exclusion::DEFAULT_EXCLUSION_OVERLAP,
+            frame_rotation_deg: 0.0,
+            band_softness: 0,
+            skip_intro: Duration::ZERO,
+            warmup_frames: 0,
+            flush_at_end: true,
+        };
+
+        let engine = Arc::new(RecordingEngine::default());
+        let input = StreamBundle::new(Box::pin(futures_util::stream::iter(frames)), None);
+        let sampled = FrameSampler::new(1).attach(input);
+        let determined = FixedRegions::new(regions).attach(sampled);
+        let tracked = RegionLifecycleTracker::new(&settings)
+            .with_split_on_change(true)
+            .attach(determined);
+        let mut ocred = SubtitleOcr::new(engine.clone()).attach(tracked).stream;
+
+        let mut read = 0;
+        while let Some(event) = ocred.next().await {
+            read += event.expect("ocr event").regions.len();
+        }
+
+        assert_eq!(read, 2);
+        let mut recorded = engine.regions.lock().unwrap().clone();
+        recorded.sort_by(|a, b| a.x.total_cmp(&b.x));
+        let expected = [(20.0, 70.0, 100.0, 20.0), (120.0, 10.0, 60.0, 20.0)];
+        assert_eq!(recorded.len(), expected.len());
+        for (region, (x, y, w, h)) in recorded.iter().zip(expected) {
+            assert!((region.x - x).abs() < 1e-3, "{region:?}");
+            assert!((region.y - y).abs() < 1e-3, "{region:?}");
+            assert!((region.width - w).abs() < 1e-3, "{region:?}");
+            assert!((region.height - h).abs() < 1e-3, "{region:?}");
+        }
+    }
+}
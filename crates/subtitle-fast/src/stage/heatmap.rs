@@ -0,0 +1,220 @@
+//! `--heatmap` mode: detection over the whole frame, with every detected region accumulated
+//! into an occupancy grid that is written as a grayscale PNG for choosing an ROI.
+
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::Path;
+
+use subtitle_fast_types::DetectionRegion;
+
+/// Widest grid the heatmap accumulates into; larger frames are binned down to it.
+const HEATMAP_MAX_WIDTH: u32 = 320;
+
+/// Counts, per grid cell, how many sampled frames had a detected region covering it.
+pub struct RegionHeatmap {
+    frame_width: u32,
+    frame_height: u32,
+    width: usize,
+    height: usize,
+    cells: Vec<u32>,
+    samples: u64,
+}
+
+impl RegionHeatmap {
+    /// Grid for `frame_width`x`frame_height` frames, keeping their aspect ratio.
+    pub fn new(frame_width: u32, frame_height: u32) -> Self {
+        let frame_width = frame_width.max(1);
+        let frame_height = frame_height.max(1);
+        let bin = frame_width.div_ceil(HEATMAP_MAX_WIDTH).max(1);
+        let width = frame_width.div_ceil(bin) as usize;
+        let height = frame_height.div_ceil(bin) as usize;
+        Self {
+            frame_width,
+            frame_height,
+            width,
+            height,
+            cells: vec![0; width * height],
+            samples: 0,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Sampled frames added so far, with or without detections.
+    pub fn samples(&self) -> u64 {
+        self.samples
+    }
+
+    /// Adds one sampled frame; `regions` are in frame pixels. A cell counts once per frame
+    /// even when several regions overlap it.
+    pub fn add(&mut self, regions: &[DetectionRegion]) {
+        self.samples += 1;
+        let mut covered = vec![false; self.cells.len()];
+        let scale_x = self.width as f32 / self.frame_width as f32;
+        let scale_y = self.height as f32 / self.frame_height as f32;
+        for region in regions {
+            let left = (region.x * scale_x).floor().clamp(0.0, self.width as f32) as usize;
+            let right = ((region.x + region.width) * scale_x)
+                .ceil()
+                .clamp(0.0, self.width as f32) as usize;
+            let top = (region.y * scale_y).floor().clamp(0.0, self.height as f32) as usize;
+            let bottom = ((region.y + region.height) * scale_y)
+                .ceil()
+                .clamp(0.0, self.height as f32) as usize;
+            for row in top..bottom {
+                covered[row * self.width + left..row * self.width + right].fill(true);
+            }
+        }
+        for (cell, hit) in self.cells.iter_mut().zip(covered) {
+            *cell += u32::from(hit);
+        }
+    }
+
+    /// Row-major 8-bit intensities, the busiest cell at 255.
+    pub fn to_grayscale(&self) -> Vec<u8> {
+        let max = self.cells.iter().copied().max().unwrap_or(0);
+        if max == 0 {
+            return vec![0; self.cells.len()];
+        }
+        self.cells
+            .iter()
+            .map(|count| ((*count as f32 / max as f32) * 255.0).round() as u8)
+            .collect()
+    }
+
+    pub fn write_png(&self, path: &Path) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(
+            BufWriter::new(File::create(path)?),
+            self.width as u32,
+            self.height as u32,
+        );
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer
+            .write_image_data(&self.to_grayscale())
+            .map_err(io::Error::other)?;
+        writer.finish().map_err(io::Error::other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    use futures_util::StreamExt;
+
+    use crate::settings::{DetectionSettings, RoiUnits};
+    use crate::stage::StreamBundle;
+    use crate::stage::detector::Detector;
+    use crate::stage::sampler::FrameSampler;
+    use subtitle_fast_types::{DecoderResult, VideoFrame};
+    use subtitle_fast_validator::subtitle_detection::{
+        DEFAULT_AUTO_DETECTOR_PRIORITY, DEFAULT_DELTA, DEFAULT_LINE_VALLEY_DEPTH, DEFAULT_TARGET,
+        FusionPolicy, RegionSizeConfig, RoiRounding, SubtitleDetectorKind,
+    };
+
+    const WIDTH: usize = 640;
+    const HEIGHT: usize = 360;
+
+    /// A dark frame with a bright, glyph-like caption band whose top sits at `top`.
+    fn caption_frame(second: u64, top: usize) -> DecoderResult<VideoFrame> {
+        let mut y_plane = vec![0u8; WIDTH * HEIGHT];
+        for y in top..top + 24 {
+            for x in 120 + (second as usize % 3) * 20..500 {
+                if x % 12 < 9 {
+                    y_plane[y * WIDTH + x] = 230;
+                }
+            }
+        }
+        VideoFrame::from_nv12_owned(
+            WIDTH as u32,
+            HEIGHT as u32,
+            WIDTH,
+            WIDTH,
+            Some(Duration::from_secs(second)),
+            None,
+            y_plane,
+            vec![128u8; WIDTH * HEIGHT / 2],
+        )
+    }
+
+    #[tokio::test]
+    async fn lower_third_captions_concentrate_the_heatmap_at_the_bottom() {
+        let frames: Vec<_> = (0..6)
+            .map(|second| caption_frame(second, 280 + (second as usize % 2) * 20))
+            .collect();
+        let settings = DetectionSettings {
+            samples_per_second: 1,
+            target: DEFAULT_TARGET,
+            delta: DEFAULT_DELTA,
+            detector: SubtitleDetectorKind::ProjectionBand,
+            detector_priority: DEFAULT_AUTO_DETECTOR_PRIORITY.to_vec(),
+            comparator: None,
+            comparator_time_decay: 0.0,
+            roi: None,
+            roi_units: RoiUnits::Fraction,
+            max_in_flight: None,
+            bridge_gap_ms: 0,
+            black_gap_bridge_ms: 0,
+            roi_clip_margin: 0.0,
+            roi_rounding: RoiRounding::Outward,
+            roi_half_pixel: false,
+            min_detection_confidence: 0.0,
+            fusion_policy: FusionPolicy::Intersection,
+            shear_compensation: None,
+            split_lines: false,
+            line_valley_depth: DEFAULT_LINE_VALLEY_DEPTH,
+            min_region: RegionSizeConfig::default(),
+            max_region_size_change: None,
+            region_smoothing: 0.0,
+            max_regions_per_frame: None,
+            full_width_guard: None,
+            fixed_regions: Vec::new(),
+            exclusion_zones: Vec::new(),
+            exclusion_overlap: crate::stage::exclusion::DEFAULT_EXCLUSION_OVERLAP,
+            frame_rotation_deg: 0.0,
+            band_softness: 0,
+            skip_intro: Duration::ZERO,
+            warmup_frames: 0,
+            flush_at_end: true,
+        };
+
+        let input = StreamBundle::new(Box::pin(futures_util::stream::iter(frames)), None);
+        let sampled = FrameSampler::new(1).attach(input);
+        let mut detected = Detector::new(&settings)
+            .expect("detector")
+            .attach(sampled)
+            .stream;
+
+        let mut heatmap = RegionHeatmap::new(WIDTH as u32, HEIGHT as u32);
+        while let Some(sample) = detected.next().await {
+            heatmap.add(&sample.expect("detection").detection.regions);
+        }
+        assert_eq!(heatmap.samples(), 6);
+
+        let gray = heatmap.to_grayscale();
+        let rows = heatmap.height();
+        let mass = |range: std::ops::Range<usize>| -> u64 {
+            range
+                .flat_map(|row| &gray[row * heatmap.width()..(row + 1) * heatmap.width()])
+                .map(|value| u64::from(*value))
+                .sum()
+        };
+        let total = mass(0..rows);
+        let lower_third = mass(rows * 2 / 3..rows);
+        assert!(total > 0, "no regions accumulated");
+        assert!(
+            lower_third as f64 > total as f64 * 0.95,
+            "lower third holds {lower_third} of {total}"
+        );
+        assert_eq!(gray.iter().copied().max(), Some(255));
+    }
+}
@@ -58,6 +58,12 @@ pub struct RegionTimings {
 
 pub struct RegionLifecycleTracker {
     configuration: Configuration,
+    bridge_gap: Duration,
+    max_size_change: Option<f32>,
+    smoothing: f32,
+    split_on_change: bool,
+    flush_at_end: bool,
+    warmup_frames: u64,
 }
 
 impl RegionLifecycleTracker {
@@ -69,8 +75,24 @@ impl RegionLifecycleTracker {
                 target: settings.target,
                 delta: settings.delta,
             },
+            temporal_decay: settings.comparator_time_decay,
         };
-        Self { configuration }
+        Self {
+            configuration,
+            bridge_gap: Duration::from_millis(settings.bridge_gap_ms),
+            max_size_change: settings.max_region_size_change,
+            smoothing: settings.region_smoothing,
+            split_on_change: false,
+            flush_at_end: settings.flush_at_end,
+            warmup_frames: settings.warmup_frames,
+        }
+    }
+
+    /// End a region and start a new one when its content no longer matches, instead of
+    /// waiting for the region to disappear.
+    pub fn with_split_on_change(mut self, enabled: bool) -> Self {
+        self.split_on_change = enabled;
+        self
     }
 
     pub fn attach(
@@ -83,11 +105,21 @@ impl RegionLifecycleTracker {
         } = input;
 
         let configuration = self.configuration;
+        let bridge_gap = self.bridge_gap;
+        let max_size_change = self.max_size_change;
+        let smoothing = self.smoothing;
+        let split_on_change = self.split_on_change;
+        let flush_at_end = self.flush_at_end;
+        let warmup_frames = self.warmup_frames;
         let (tx, rx) = mpsc::channel::<LifecycleResult>(REGION_TRACKER_CHANNEL_CAPACITY);
 
         tokio::spawn(async move {
             let comparator = configuration.create_comparator();
-            let mut worker = RegionLifecycleWorker::new(comparator);
+            let mut worker = RegionLifecycleWorker::new(comparator, bridge_gap, max_size_change);
+            worker.smoothing = smoothing;
+            worker.split_on_change = split_on_change;
+            worker.flush_at_end = flush_at_end;
+            worker.warmup_frames = warmup_frames;
             let mut upstream = stream;
 
             while let Some(event) = upstream.next().await {
@@ -105,7 +137,7 @@ impl RegionLifecycleTracker {
                     }
                     Err(err) => {
                         let mut timings = RegionTimings::default();
-                        let flush = worker.flush_active(&mut timings);
+                        let flush = worker.finish(&mut timings);
                         if !flush.is_empty() {
                             let _ = tx
                                 .send(Ok(LifecycleEvent {
@@ -122,7 +154,7 @@ impl RegionLifecycleTracker {
             }
 
             let mut timings = RegionTimings::default();
-            let flush = worker.flush_active(&mut timings);
+            let flush = worker.finish(&mut timings);
             if !flush.is_empty() {
                 let _ = tx
                     .send(Ok(LifecycleEvent {
@@ -153,20 +185,43 @@ struct ActiveRegion {
     last_time: Duration,
     last_frame: u64,
     frame: Arc<VideoFrame>,
+    /// History captured when the region first went missing, used to refine its end.
+    missing_history: Option<FrameHistory>,
 }
 
 struct RegionLifecycleWorker {
     comparator: Arc<dyn SubtitleComparator>,
     active: HashMap<RegionId, ActiveRegion>,
     last_history: Option<FrameHistory>,
+    bridge_gap: Duration,
+    /// Largest per-frame width/height change, as a fraction of the previous size.
+    max_size_change: Option<f32>,
+    /// Weight the previous geometry keeps when smoothing a matched region; 0.0 disables it.
+    smoothing: f32,
+    split_on_change: bool,
+    flush_at_end: bool,
+    /// Leading sampled frames whose detections are ignored.
+    warmup_frames: u64,
+    samples_seen: u64,
 }
 
 impl RegionLifecycleWorker {
-    fn new(comparator: Arc<dyn SubtitleComparator>) -> Self {
+    fn new(
+        comparator: Arc<dyn SubtitleComparator>,
+        bridge_gap: Duration,
+        max_size_change: Option<f32>,
+    ) -> Self {
         Self {
             comparator,
             active: HashMap::new(),
             last_history: None,
+            bridge_gap,
+            max_size_change,
+            smoothing: 0.0,
+            split_on_change: false,
+            flush_at_end: true,
+            warmup_frames: 0,
+            samples_seen: 0,
         }
     }
 
@@ -177,6 +232,15 @@ impl RegionLifecycleWorker {
     ) -> LifecycleEvent {
         let frame_ctx = FrameContext::from_sample(&event.sample);
         self.last_history = Some(frame_ctx.history.clone());
+        self.samples_seen = self.samples_seen.saturating_add(1);
+        if self.samples_seen <= self.warmup_frames {
+            // Nothing is tracked yet, so there is nothing to close either.
+            return LifecycleEvent {
+                sample: Some(event.sample),
+                completed: Vec::new(),
+                region_timings: None,
+            };
+        }
 
         let mut roi_features: Vec<Option<FeatureBlob>> = Vec::with_capacity(event.regions.len());
         for region in &event.regions {
@@ -207,13 +271,29 @@ impl RegionLifecycleWorker {
                 );
                 if matched {
                     if let Some(active) = self.active.get_mut(&region.id) {
-                        active.roi = region.roi;
+                        let next = match self.max_size_change {
+                            Some(max_change) => {
+                                clamp_size_change(active.roi, region.roi, max_change)
+                            }
+                            None => region.roi,
+                        };
+                        active.roi = smooth_geometry(active.roi, next, self.smoothing);
                         active.frame = Arc::clone(&frame_ctx.frame);
                         active.last_time = frame_ctx.time;
                         active.last_frame = frame_ctx.frame_index;
                         active.template_features = features.clone();
                         active.anchor_features = Some(features);
+                        active.missing_history = None;
+                    }
+                    seen.insert(region.id);
+                } else if self.split_on_change {
+                    if let Some(done) =
+                        self.close_by_id(region.id, frame_ctx.history.clone(), timings)
+                    {
+                        completed.push(done);
                     }
+                    let active = self.start_region(region, frame_ctx.clone(), features, timings);
+                    self.active.insert(region.id, active);
                     seen.insert(region.id);
                 } else {
                     // Keep the existing active region; treat this as a non-updating observation.
@@ -233,6 +313,9 @@ impl RegionLifecycleWorker {
             .filter(|id| !seen.contains(id))
             .collect();
         for id in missing {
+            if self.bridge_dropout(id, &frame_ctx) {
+                continue;
+            }
             if let Some(done) = self.close_by_id(id, frame_ctx.history.clone(), timings) {
                 completed.push(done);
             }
@@ -271,16 +354,36 @@ impl RegionLifecycleWorker {
             last_time: frame.time,
             last_frame: frame.frame_index,
             frame: frame.frame,
+            missing_history: None,
         }
     }
 
+    /// Keeps a missing region alive while its dropout is shorter than the bridge gap.
+    fn bridge_dropout(&mut self, id: RegionId, frame: &FrameContext) -> bool {
+        if self.bridge_gap.is_zero() {
+            return false;
+        }
+        let Some(active) = self.active.get_mut(&id) else {
+            return false;
+        };
+        let gap = frame.time.saturating_sub(active.last_time);
+        if gap > self.bridge_gap {
+            return false;
+        }
+        if active.missing_history.is_none() {
+            active.missing_history = Some(frame.history.clone());
+        }
+        true
+    }
+
     fn close_by_id(
         &mut self,
         id: RegionId,
         history: FrameHistory,
         timings: &mut RegionTimings,
     ) -> Option<CompletedRegion> {
-        let active = self.active.remove(&id)?;
+        let mut active = self.active.remove(&id)?;
+        let history = active.missing_history.take().unwrap_or(history);
         let completed = self.close_active(active, &history, timings);
         is_valid_region(&completed).then_some(completed)
     }
@@ -306,6 +409,17 @@ impl RegionLifecycleWorker {
         }
     }
 
+    /// Closes out the regions still active when the stream ends, or drops them when
+    /// `flush_at_end` is off.
+    fn finish(&mut self, timings: &mut RegionTimings) -> Vec<CompletedRegion> {
+        if self.flush_at_end {
+            self.flush_active(timings)
+        } else {
+            self.active.clear();
+            Vec::new()
+        }
+    }
+
     fn flush_active(&mut self, timings: &mut RegionTimings) -> Vec<CompletedRegion> {
         let mut completed = Vec::new();
         let history = self
@@ -359,6 +473,7 @@ fn determine_start(
     let mut best_time = frame.time;
     let mut template_features = features.clone();
     let mut anchor_features = None;
+    let mut anchor_time = frame.time;
 
     for record in frame.history.records().iter().rev() {
         if record.frame_index >= frame.frame_index {
@@ -367,14 +482,22 @@ fn determine_start(
         let Some(candidate) = timed_extract(timings, comparator, record.frame(), roi) else {
             continue;
         };
+        let record_time = frame_time(record.frame(), record.frame_index, &frame.sampler_context)
+            .unwrap_or(best_time);
         let reference = comparison_anchor(&anchor_features, features);
-        let report = timed_compare(timings, comparator, reference, &candidate);
+        let report = timed_compare(
+            timings,
+            comparator,
+            reference,
+            &candidate,
+            anchor_time.saturating_sub(record_time),
+        );
         if report.same_segment {
             best_frame = record.frame_index;
-            best_time = frame_time(record.frame(), record.frame_index, &frame.sampler_context)
-                .unwrap_or(best_time);
+            best_time = record_time;
             if anchor_features.is_none() {
                 anchor_features = Some(candidate.clone());
+                anchor_time = record_time;
             }
             template_features = candidate;
         }
@@ -402,12 +525,19 @@ fn refine_end(
         else {
             continue;
         };
+        let record_time = record.frame().pts().unwrap_or(best_time);
         let reference = comparison_anchor(&anchor, &active.template_features);
-        let report = timed_compare(timings, comparator, reference, &candidate);
+        let report = timed_compare(
+            timings,
+            comparator,
+            reference,
+            &candidate,
+            record_time.saturating_sub(best_time),
+        );
         if report.same_segment {
             anchor = Some(candidate.clone());
             best_frame = record.frame_index;
-            best_time = record.frame().pts().unwrap_or(best_time);
+            best_time = record_time;
             best_frame_handle = record.frame_handle();
         }
     }
@@ -415,16 +545,63 @@ fn refine_end(
     (best_time, best_frame, best_frame_handle)
 }
 
+/// Limits how far `next` may grow or shrink relative to `previous`, keeping `next`'s center so
+/// the region still follows movement.
+fn clamp_size_change(previous: RoiConfig, next: RoiConfig, max_change: f32) -> RoiConfig {
+    let clamp = |previous: f32, next: f32| {
+        next.clamp(
+            (previous * (1.0 - max_change)).max(0.0),
+            previous * (1.0 + max_change),
+        )
+    };
+    let width = clamp(previous.width, next.width);
+    let height = clamp(previous.height, next.height);
+    RoiConfig {
+        x: next.x + (next.width - width) / 2.0,
+        y: next.y + (next.height - height) / 2.0,
+        width,
+        height,
+    }
+}
+
+/// Exponentially smooths `next` towards `previous`, filtering center and size separately so a
+/// steady region moving across the frame does not shrink or grow along the way.
+fn smooth_geometry(previous: RoiConfig, next: RoiConfig, smoothing: f32) -> RoiConfig {
+    if smoothing <= 0.0 {
+        return next;
+    }
+    let blend = |previous: f32, next: f32| previous * smoothing + next * (1.0 - smoothing);
+    let center_x = blend(previous.x + previous.width / 2.0, next.x + next.width / 2.0);
+    let center_y = blend(
+        previous.y + previous.height / 2.0,
+        next.y + next.height / 2.0,
+    );
+    let width = blend(previous.width, next.width);
+    let height = blend(previous.height, next.height);
+    RoiConfig {
+        x: center_x - width / 2.0,
+        y: center_y - height / 2.0,
+        width,
+        height,
+    }
+}
+
 fn match_active(
     comparator: &dyn SubtitleComparator,
     active: &ActiveRegion,
-    _frame: &FrameContext,
+    frame: &FrameContext,
     _roi: &RoiConfig,
     candidate: &FeatureBlob,
     timings: &mut RegionTimings,
 ) -> bool {
     let reference = comparison_anchor(&active.anchor_features, &active.template_features);
-    let report = timed_compare(timings, comparator, reference, candidate);
+    let report = timed_compare(
+        timings,
+        comparator,
+        reference,
+        candidate,
+        frame.time.saturating_sub(active.last_time),
+    );
     report.same_segment
 }
 
@@ -453,19 +630,20 @@ fn timed_compare(
     comparator: &dyn SubtitleComparator,
     reference: &FeatureBlob,
     candidate: &FeatureBlob,
+    time_delta: Duration,
 ) -> subtitle_fast_comparator::pipeline::ComparisonReport {
     let started = Instant::now();
-    let report = comparator.compare(reference, candidate);
+    let report = comparator.compare_at(reference, candidate, time_delta);
     timings.comparisons = timings.comparisons.saturating_add(1);
     timings.compare = timings.compare.saturating_add(started.elapsed());
     report
 }
 
-fn sample_time(sample: &SampledFrame) -> Duration {
+pub(super) fn sample_time(sample: &SampledFrame) -> Duration {
     if let Some(ts) = sample.frame().pts() {
         return ts;
     }
-    if let Some(fps) = sample.sampler_context().estimated_fps()
+    if let Some(fps) = sample.sampler_context().frame_rate()
         && fps > 0.0
     {
         let secs = sample.frame_index() as f64 / fps;
@@ -478,7 +656,7 @@ fn frame_time(frame: &VideoFrame, frame_index: u64, context: &SamplerContext) ->
     if let Some(ts) = frame.pts() {
         return Some(ts);
     }
-    let fps = context.estimated_fps()?;
+    let fps = context.frame_rate()?;
     if fps <= 0.0 {
         return None;
     }
@@ -503,3 +681,375 @@ fn is_valid_region(region: &CompletedRegion) -> bool {
         .unwrap_or_else(|| Duration::from_secs(0));
     duration >= MIN_REGION_DURATION
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stage::determiner::RegionUnit;
+    use crate::stage::sampler::{FrameType, HistoryRecord};
+    use subtitle_fast_comparator::pipeline::ComparisonReport;
+    use subtitle_fast_types::SubtitleDetectionResult;
+
+    struct SameSegmentComparator;
+
+    impl SubtitleComparator for SameSegmentComparator {
+        fn name(&self) -> &'static str {
+            "same-segment"
+        }
+
+        fn extract(&self, _frame: &VideoFrame, _roi: &RoiConfig) -> Option<FeatureBlob> {
+            Some(FeatureBlob::new("same-segment", ()))
+        }
+
+        fn compare(&self, _reference: &FeatureBlob, _candidate: &FeatureBlob) -> ComparisonReport {
+            ComparisonReport::new(1.0, true)
+        }
+    }
+
+    const REGION_ROI: RoiConfig = RoiConfig {
+        x: 0.1,
+        y: 0.5,
+        width: 0.8,
+        height: 0.4,
+    };
+
+    fn event(index: u64, present: bool) -> RegionDeterminerEvent {
+        event_with_roi(index, present.then_some(REGION_ROI))
+    }
+
+    fn event_with_roi(index: u64, roi: Option<RoiConfig>) -> RegionDeterminerEvent {
+        event_at(index, Some(Duration::from_millis(index * 100)), None, roi)
+    }
+
+    fn event_at(
+        index: u64,
+        pts: Option<Duration>,
+        container_fps: Option<f64>,
+        roi: Option<RoiConfig>,
+    ) -> RegionDeterminerEvent {
+        let frame = VideoFrame::from_nv12_owned(
+            64,
+            64,
+            64,
+            64,
+            pts,
+            None,
+            vec![0; 64 * 64],
+            vec![128; 64 * 32],
+        )
+        .expect("frame");
+        let sample = SampledFrame::new(
+            index,
+            Arc::new(frame),
+            FrameHistory::new(Vec::new()),
+            Arc::new(SamplerContext::initial(container_fps)),
+        );
+        let regions = roi
+            .map(|roi| RegionUnit {
+                id: 0,
+                label: "region-0".into(),
+                roi,
+            })
+            .into_iter()
+            .collect();
+        RegionDeterminerEvent {
+            sample: DetectionSample {
+                sample,
+                detection: SubtitleDetectionResult::empty(),
+                elapsed: Duration::ZERO,
+                reused: false,
+            },
+            regions,
+        }
+    }
+
+    fn run(bridge_gap: Duration) -> Vec<CompletedRegion> {
+        let mut worker =
+            RegionLifecycleWorker::new(Arc::new(SameSegmentComparator), bridge_gap, None);
+        let mut timings = RegionTimings::default();
+        let mut completed = Vec::new();
+        for index in 0..10 {
+            // Frame 4 drops the detection for a single sample.
+            let lifecycle = worker.handle_event(event(index, index != 4), &mut timings);
+            completed.extend(lifecycle.completed);
+        }
+        completed.extend(worker.flush_active(&mut timings));
+        completed
+    }
+
+    #[test]
+    fn single_frame_dropout_is_bridged() {
+        let completed = run(Duration::from_millis(150));
+        assert_eq!(completed.len(), 1);
+        assert_eq!(completed[0].start_time, Duration::ZERO);
+        assert_eq!(completed[0].end_time, Duration::from_millis(900));
+    }
+
+    #[test]
+    fn cue_times_follow_pts_on_variable_frame_rate_input() {
+        let run = |pts_ms: [Option<u64>; 6]| {
+            let mut worker =
+                RegionLifecycleWorker::new(Arc::new(SameSegmentComparator), Duration::ZERO, None);
+            let mut timings = RegionTimings::default();
+            let mut completed = Vec::new();
+            for (index, pts) in pts_ms.into_iter().enumerate() {
+                // The region shows on frames 2 through 4 of a nominal 10 fps stream.
+                let roi = (2..=4).contains(&index).then_some(REGION_ROI);
+                let pts = pts.map(Duration::from_millis);
+                let event = event_at(index as u64, pts, Some(10.0), roi);
+                completed.extend(worker.handle_event(event, &mut timings).completed);
+            }
+            completed.extend(worker.flush_active(&mut timings));
+            completed
+        };
+
+        let vfr = run([
+            Some(0),
+            Some(40),
+            Some(300),
+            Some(330),
+            Some(900),
+            Some(910),
+        ]);
+        assert_eq!(vfr.len(), 1);
+        assert_eq!(vfr[0].start_time, Duration::from_millis(300));
+        assert_eq!(vfr[0].end_time, Duration::from_millis(900));
+
+        // Without PTS the index/fps estimate is the only clock left.
+        let unstamped = run([None; 6]);
+        assert_eq!(unstamped.len(), 1);
+        assert_eq!(unstamped[0].start_time, Duration::from_millis(200));
+        assert_eq!(unstamped[0].end_time, Duration::from_millis(400));
+    }
+
+    #[test]
+    fn detections_during_warmup_produce_no_cues() {
+        let run = |warmup_frames: u64| {
+            let mut worker =
+                RegionLifecycleWorker::new(Arc::new(SameSegmentComparator), Duration::ZERO, None);
+            worker.warmup_frames = warmup_frames;
+            let mut timings = RegionTimings::default();
+            let mut completed = Vec::new();
+            for index in 0..10 {
+                // A transient detection right at the start, then a real line from frame 5.
+                let present = !(3..5).contains(&index);
+                completed.extend(
+                    worker
+                        .handle_event(event(index, present), &mut timings)
+                        .completed,
+                );
+            }
+            completed.extend(worker.flush_active(&mut timings));
+            completed
+        };
+
+        let starts = |completed: Vec<CompletedRegion>| -> Vec<Duration> {
+            completed.iter().map(|region| region.start_time).collect()
+        };
+        assert_eq!(starts(run(0)), [Duration::ZERO, Duration::from_millis(500)]);
+        assert_eq!(starts(run(3)), [Duration::from_millis(500)]);
+    }
+
+    #[test]
+    fn dropout_splits_without_bridge() {
+        let completed = run(Duration::ZERO);
+        assert_eq!(completed.len(), 2);
+    }
+
+    #[test]
+    fn size_spike_is_clamped_while_gradual_growth_passes() {
+        let mut worker =
+            RegionLifecycleWorker::new(Arc::new(SameSegmentComparator), Duration::ZERO, Some(0.3));
+        let mut timings = RegionTimings::default();
+        let roi = |width: f32| RoiConfig {
+            x: 0.5 - width / 2.0,
+            y: 0.8,
+            width,
+            height: 0.1,
+        };
+        let mut observed = Vec::new();
+        for (index, width) in [0.4, 0.44, 0.48, 0.9, 0.5].into_iter().enumerate() {
+            worker.handle_event(event_with_roi(index as u64, Some(roi(width))), &mut timings);
+            observed.push(worker.active[&0].roi);
+        }
+
+        let widths: Vec<f32> = observed.iter().map(|roi| roi.width).collect();
+        assert_eq!(widths[..3], [0.4, 0.44, 0.48]);
+        assert!(
+            (widths[3] - 0.48 * 1.3).abs() < 1e-6,
+            "spike clamped: {widths:?}"
+        );
+        assert!((widths[4] - 0.5).abs() < 1e-6, "recovers: {widths:?}");
+        let spike = observed[3];
+        assert!((spike.x + spike.width / 2.0 - 0.5).abs() < 1e-6);
+        assert_eq!(spike.height, 0.1);
+    }
+
+    #[test]
+    fn smoothing_steadies_a_jittery_region() {
+        // Detections wobbling a few pixels around a fixed line of text.
+        let jitter = [
+            0.0, 0.012, -0.01, 0.008, -0.014, 0.011, -0.006, 0.013, -0.012, 0.009,
+        ];
+        let track = |smoothing: f32| {
+            let mut worker =
+                RegionLifecycleWorker::new(Arc::new(SameSegmentComparator), Duration::ZERO, None);
+            worker.smoothing = smoothing;
+            let mut timings = RegionTimings::default();
+            let mut observed = Vec::new();
+            for (index, offset) in jitter.into_iter().enumerate() {
+                let roi = RoiConfig {
+                    x: 0.2 + offset,
+                    y: 0.8 - offset,
+                    width: 0.6 + offset * 2.0,
+                    height: 0.1 + offset,
+                };
+                worker.handle_event(event_with_roi(index as u64, Some(roi)), &mut timings);
+                observed.push(worker.active[&0].roi);
+            }
+            observed
+        };
+        let variance = |values: Vec<f32>| {
+            let mean = values.iter().sum::<f32>() / values.len() as f32;
+            values
+                .iter()
+                .map(|value| (value - mean).powi(2))
+                .sum::<f32>()
+                / values.len() as f32
+        };
+        let spread = |observed: &[RoiConfig]| {
+            [
+                variance(observed.iter().map(|roi| roi.x + roi.width / 2.0).collect()),
+                variance(
+                    observed
+                        .iter()
+                        .map(|roi| roi.y + roi.height / 2.0)
+                        .collect(),
+                ),
+                variance(observed.iter().map(|roi| roi.width).collect()),
+                variance(observed.iter().map(|roi| roi.height).collect()),
+            ]
+        };
+
+        let raw = track(0.0);
+        assert_eq!(raw[3].x, 0.2 + jitter[3], "disabled follows detections");
+        let smoothed = track(0.7);
+        for (smoothed, raw) in spread(&smoothed).into_iter().zip(spread(&raw)) {
+            assert!(smoothed < raw / 2.0, "smoothed {smoothed} vs raw {raw}");
+        }
+    }
+
+    #[test]
+    fn subtitle_on_screen_at_end_of_stream_is_flushed_with_its_last_sighting() {
+        let run = |flush_at_end: bool| {
+            let mut worker =
+                RegionLifecycleWorker::new(Arc::new(SameSegmentComparator), Duration::ZERO, None);
+            worker.flush_at_end = flush_at_end;
+            let mut timings = RegionTimings::default();
+            let mut completed = Vec::new();
+            // The stream ends at frame 7 while the subtitle from frame 3 is still showing.
+            for index in 0..8 {
+                let lifecycle = worker.handle_event(event(index, index >= 3), &mut timings);
+                completed.extend(lifecycle.completed);
+            }
+            completed.extend(worker.finish(&mut timings));
+            completed
+        };
+
+        let flushed = run(true);
+        assert_eq!(flushed.len(), 1);
+        assert_eq!(flushed[0].start_time, Duration::from_millis(300));
+        assert_eq!(flushed[0].end_time, Duration::from_millis(700));
+        assert_eq!(flushed[0].end_frame, 7);
+
+        assert!(run(false).is_empty());
+    }
+
+    /// Tells frames apart by whether their luma is lit, the way a subtitle appearing would.
+    struct LitComparator;
+
+    impl SubtitleComparator for LitComparator {
+        fn name(&self) -> &'static str {
+            "lit"
+        }
+
+        fn extract(&self, frame: &VideoFrame, _roi: &RoiConfig) -> Option<FeatureBlob> {
+            let tag = if frame.data()[0] > 128 { "lit" } else { "dark" };
+            Some(FeatureBlob::new(tag, ()))
+        }
+
+        fn compare(&self, reference: &FeatureBlob, candidate: &FeatureBlob) -> ComparisonReport {
+            let same = reference.tag() == "lit" && candidate.tag() == "lit";
+            ComparisonReport::new(if same { 1.0 } else { 0.0 }, same)
+        }
+    }
+
+    #[test]
+    fn boundaries_are_refined_to_the_frame_from_the_decoded_history() {
+        // A 30 fps stream sampled every 7th frame; the subtitle is lit on frames 10 to 38, so
+        // samples first see it at frame 14 and last at frame 35.
+        let lit = 10..=38u64;
+        let frame = |index: u64| {
+            let luma = if lit.contains(&index) { 230 } else { 0 };
+            Arc::new(
+                VideoFrame::from_nv12_owned(
+                    64,
+                    64,
+                    64,
+                    64,
+                    Some(Duration::from_secs_f64(index as f64 / 30.0)),
+                    None,
+                    vec![luma; 64 * 64],
+                    vec![128; 64 * 32],
+                )
+                .expect("frame"),
+            )
+        };
+        let mut worker = RegionLifecycleWorker::new(Arc::new(LitComparator), Duration::ZERO, None);
+        let mut timings = RegionTimings::default();
+        let mut completed = Vec::new();
+        for index in (0..=49u64).step_by(7) {
+            let history = (index.saturating_sub(29)..=index)
+                .map(|past| {
+                    let frame_type = if past % 7 == 0 {
+                        FrameType::Sampled
+                    } else {
+                        FrameType::Skipped
+                    };
+                    HistoryRecord::new(past, frame_type, frame(past))
+                })
+                .collect();
+            let sample = SampledFrame::new(
+                index,
+                frame(index),
+                FrameHistory::new(history),
+                Arc::new(SamplerContext::initial(Some(30.0))),
+            );
+            let regions = lit
+                .contains(&index)
+                .then(|| RegionUnit {
+                    id: 0,
+                    label: "region-0".into(),
+                    roi: REGION_ROI,
+                })
+                .into_iter()
+                .collect();
+            let event = RegionDeterminerEvent {
+                sample: DetectionSample {
+                    sample,
+                    detection: SubtitleDetectionResult::empty(),
+                    elapsed: Duration::ZERO,
+                    reused: false,
+                },
+                regions,
+            };
+            completed.extend(worker.handle_event(event, &mut timings).completed);
+        }
+
+        assert_eq!(completed.len(), 1);
+        let region = &completed[0];
+        assert_eq!((region.start_frame, region.end_frame), (10, 38));
+        assert_eq!(region.start_time, Duration::from_secs_f64(10.0 / 30.0));
+        assert_eq!(region.end_time, Duration::from_secs_f64(38.0 / 30.0));
+    }
+}
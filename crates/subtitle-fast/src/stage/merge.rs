@@ -5,30 +5,98 @@ use tokio::sync::mpsc;
 
 use super::StreamBundle;
 use super::detector::DetectionSample;
-use super::lifecycle::RegionTimings;
+use super::lifecycle::{CompletedRegion, RegionTimings, sample_time};
 use super::ocr::{OcrEvent, OcrStageError, OcrStageResult, OcrTimings};
 use crate::subtitle::{MergedSubtitle, SubtitleLine};
-use subtitle_fast_ocr::OcrResponse;
+use subtitle_fast_ocr::{OcrResponse, OcrText, TextDirection};
+use subtitle_fast_types::{RoiConfig, VideoFrame};
 
 const MERGE_CHANNEL_CAPACITY: usize = 4;
 const MERGE_GAP: Duration = Duration::from_millis(120);
 const SUBTITLE_CACHE_WINDOW: Duration = Duration::from_secs(2);
+const SAME_LINE_OVERLAP: f32 = 0.5;
+/// Frames whose mean luma is at most this are treated as black (limited-range black is 16).
+const BLACK_FRAME_MAX_LUMA: f32 = 24.0;
+/// Every n-th pixel of every n-th row is read for the mean; plenty for an all-black check.
+const BLACK_FRAME_SAMPLE_STEP: usize = 4;
 
 pub type MergeResult = Result<MergeOutput, OcrStageError>;
 
 pub struct Merge {
     cache_window: Duration,
+    text_direction: Option<TextDirection>,
+    min_cue_chars: usize,
+    confidence_decay: f32,
+    empty_hold: usize,
+    black_gap: Duration,
+    separate_regions: bool,
 }
 
 impl Merge {
     pub fn new(cache_window: Duration) -> Self {
-        Self { cache_window }
+        Self {
+            cache_window,
+            text_direction: None,
+            min_cue_chars: 0,
+            confidence_decay: 0.0,
+            empty_hold: 0,
+            black_gap: Duration::ZERO,
+            separate_regions: false,
+        }
     }
 
     pub fn with_default_window() -> Self {
         Self::new(SUBTITLE_CACHE_WINDOW)
     }
 
+    /// Forces the reading direction used when assembling same-line fragments.
+    pub fn with_text_direction(mut self, text_direction: Option<TextDirection>) -> Self {
+        self.text_direction = text_direction;
+        self
+    }
+
+    /// Drops merged cues whose text, across every region merged into them, has fewer than
+    /// `min_cue_chars` non-whitespace characters, counting them as OCR-empty. A cue is held back
+    /// until it reaches the threshold, so short regions of one subtitle still add up. Zero keeps
+    /// every cue.
+    pub fn with_min_cue_chars(mut self, min_cue_chars: usize) -> Self {
+        self.min_cue_chars = min_cue_chars;
+        self
+    }
+
+    /// Lets a recognition that keeps contradicting a held line replace it: each time a merged
+    /// cue reads the line differently, the held text's confidence counts for `1 - per_repeat`
+    /// less, and the new text wins once its own confidence reaches that. Zero (the default)
+    /// keeps both readings as separate lines.
+    pub fn with_confidence_decay(mut self, per_repeat: f32) -> Self {
+        self.confidence_decay = per_repeat.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Lets up to `empty_hold` consecutive empty recognitions that continue the last cue extend
+    /// it with its last text instead of being dropped, so a glare frame does not split the cue.
+    /// Zero (the default) drops every empty recognition.
+    pub fn with_empty_hold(mut self, empty_hold: usize) -> Self {
+        self.empty_hold = empty_hold;
+        self
+    }
+
+    /// Joins a cue to the identical one before it when every frame sampled between them was
+    /// black and the gap is at most `max_gap`, so an ad break or fade to black does not split
+    /// a subtitle that resumes after it. Zero (the default) leaves such cues split.
+    pub fn with_black_gap_bridge(mut self, max_gap: Duration) -> Self {
+        self.black_gap = max_gap;
+        self
+    }
+
+    /// Continues a cue only with the last one read from the same region, so cues of different
+    /// regions never share a subtitle, overlapping or not. Used when each fixed region is
+    /// written as its own track; off (the default) merges across regions.
+    pub fn with_separate_regions(mut self, separate: bool) -> Self {
+        self.separate_regions = separate;
+        self
+    }
+
     pub fn attach(self, input: StreamBundle<OcrStageResult>) -> StreamBundle<MergeResult> {
         let StreamBundle {
             stream,
@@ -37,10 +105,21 @@ impl Merge {
 
         let (tx, rx) = mpsc::channel::<MergeResult>(MERGE_CHANNEL_CAPACITY);
         let cache_window = self.cache_window;
+        let text_direction = self.text_direction;
+        let min_cue_chars = self.min_cue_chars;
+        let confidence_decay = self.confidence_decay;
+        let empty_hold = self.empty_hold;
+        let black_gap = self.black_gap;
+        let separate_regions = self.separate_regions;
 
         tokio::spawn(async move {
             let mut upstream = stream;
-            let mut worker = MergeWorker::new(cache_window);
+            let mut worker = MergeWorker::new(cache_window, text_direction)
+                .with_min_cue_chars(min_cue_chars)
+                .with_confidence_decay(confidence_decay)
+                .with_empty_hold(empty_hold)
+                .with_black_gap_bridge(black_gap)
+                .with_separate_regions(separate_regions);
 
             while let Some(event) = upstream.next().await {
                 match event {
@@ -97,43 +176,124 @@ struct SubtitleCue {
     start_time: Duration,
     end_time: Duration,
     start_frame: u64,
+    end_frame: u64,
+    /// Visual lines of the region, top to bottom, each with its own confidence.
+    lines: Vec<CueLine>,
+    rtl: bool,
+    region: RoiConfig,
+}
+
+struct CueLine {
     text: String,
     center: f32,
+    confidence: Option<f32>,
 }
 
 struct MergeWorker {
     cache_window: Duration,
+    text_direction: Option<TextDirection>,
+    min_cue_chars: usize,
+    confidence_decay: f32,
+    empty_hold: usize,
+    /// Empty recognitions in a row that extended the last cue.
+    held_empties: usize,
     subtitles: Vec<MergedSubtitle>,
+    /// Merged cues in a row that read each line of subtitle `contradicted` differently.
+    contradictions: Vec<u32>,
+    contradicted: u64,
+    /// Ids of cached subtitles still shorter than `min_cue_chars`, not yet sent downstream.
+    withheld: Vec<u64>,
+    separate_regions: bool,
+    black_gap: Duration,
+    /// Runs of consecutive samples sharing a black or lit frame, tracked while `black_gap` is
+    /// set: first and last sample time, and whether the run is black.
+    luma_runs: Vec<(Duration, Duration, bool)>,
     next_id: u64,
     stats: SubtitleStats,
 }
 
 impl MergeWorker {
-    fn new(cache_window: Duration) -> Self {
+    fn new(cache_window: Duration, text_direction: Option<TextDirection>) -> Self {
         Self {
             cache_window,
+            text_direction,
+            min_cue_chars: 0,
+            confidence_decay: 0.0,
+            empty_hold: 0,
+            held_empties: 0,
             subtitles: Vec::new(),
+            contradictions: Vec::new(),
+            contradicted: 0,
+            withheld: Vec::new(),
+            separate_regions: false,
+            black_gap: Duration::ZERO,
+            luma_runs: Vec::new(),
             next_id: 0,
             stats: SubtitleStats::default(),
         }
     }
 
+    fn with_min_cue_chars(mut self, min_cue_chars: usize) -> Self {
+        self.min_cue_chars = min_cue_chars;
+        self
+    }
+
+    fn with_confidence_decay(mut self, per_repeat: f32) -> Self {
+        self.confidence_decay = per_repeat;
+        self
+    }
+
+    fn with_empty_hold(mut self, empty_hold: usize) -> Self {
+        self.empty_hold = empty_hold;
+        self
+    }
+
+    fn with_black_gap_bridge(mut self, max_gap: Duration) -> Self {
+        self.black_gap = max_gap;
+        self
+    }
+
+    fn with_separate_regions(mut self, separate: bool) -> Self {
+        self.separate_regions = separate;
+        self
+    }
+
     fn handle_event(&mut self, event: OcrEvent) -> MergeOutput {
         let mut updates = Vec::new();
+        if let Some(sample) = event.sample.as_ref() {
+            self.observe_frame(sample_time(&sample.sample), sample.sample.frame());
+        }
 
         for subtitle in event.regions {
-            let text = normalize_text(&response_to_text(&subtitle.response));
-            if text.is_empty() {
-                self.stats.ocr_empty = self.stats.ocr_empty.saturating_add(1);
+            let assembled = response_to_text(&subtitle.response, self.text_direction);
+            let mut lines: Vec<CueLine> = assembled
+                .rows
+                .into_iter()
+                .filter_map(|row| {
+                    let text = normalize_text(&row.text);
+                    (!text.is_empty()).then_some(CueLine {
+                        text,
+                        center: row.center,
+                        confidence: row.confidence,
+                    })
+                })
+                .collect();
+            collapse_repeated_lines(&mut lines);
+            if lines.is_empty() {
+                match self.hold_last_text(&subtitle.lifecycle) {
+                    Some(update) => updates.push(update),
+                    None => self.stats.ocr_empty = self.stats.ocr_empty.saturating_add(1),
+                }
                 continue;
             }
-            let center = subtitle.region.y + subtitle.region.height * 0.5;
             let cue = SubtitleCue {
                 start_time: subtitle.lifecycle.start_time,
                 end_time: subtitle.lifecycle.end_time,
                 start_frame: subtitle.lifecycle.start_frame,
-                text,
-                center,
+                end_frame: subtitle.lifecycle.end_frame,
+                lines,
+                rtl: assembled.direction.is_rtl(),
+                region: subtitle.lifecycle.roi,
             };
             if let Some(update) = self.apply_cue(cue) {
                 updates.push(update);
@@ -149,26 +309,129 @@ impl MergeWorker {
         }
     }
 
+    /// Extends the last cue over an empty recognition that continues it, while fewer than
+    /// `empty_hold` empties in a row have done so.
+    fn hold_last_text(&mut self, region: &CompletedRegion) -> Option<SubtitleUpdate> {
+        if self.held_empties >= self.empty_hold {
+            return None;
+        }
+        let index = self.continued(region.roi)?;
+        let last = &mut self.subtitles[index];
+        if self.withheld.contains(&last.id) {
+            return None;
+        }
+        if region.start_time > last.end_time.saturating_add(MERGE_GAP) {
+            return None;
+        }
+        last.end_time = last.end_time.max(region.end_time);
+        last.end_frame = last.end_frame.max(region.end_frame);
+        self.held_empties += 1;
+        self.stats.merged = self.stats.merged.saturating_add(1);
+        Some(SubtitleUpdate {
+            kind: SubtitleUpdateKind::Updated,
+            subtitle: last.clone(),
+        })
+    }
+
+    /// Records whether the sampled frame at `time` is black, for `bridges_black_gap`.
+    fn observe_frame(&mut self, time: Duration, frame: &VideoFrame) {
+        if self.black_gap.is_zero() {
+            return;
+        }
+        let black = is_black_frame(frame);
+        match self.luma_runs.last_mut() {
+            Some((_, end, run_black)) if *run_black == black => *end = (*end).max(time),
+            _ => self.luma_runs.push((time, time, black)),
+        }
+    }
+
+    /// The gap between `current` and `incoming` is short enough, held only black samples, and
+    /// `incoming` repeats `current`'s text.
+    fn bridges_black_gap(&self, current: &MergedSubtitle, incoming: &SubtitleCue) -> bool {
+        let Some(gap) = incoming.start_time.checked_sub(current.end_time) else {
+            return false;
+        };
+        if self.black_gap.is_zero() || gap > self.black_gap || !repeats_text(current, incoming) {
+            return false;
+        }
+        let mut within = self
+            .luma_runs
+            .iter()
+            .filter(|(start, end, _)| *start < incoming.start_time && *end > current.end_time)
+            .peekable();
+        within.peek().is_some() && within.all(|(_, _, black)| *black)
+    }
+
+    /// Index of the cached subtitle a cue read from `region` may continue: the last one, or
+    /// with `separate_regions` the last one read from that region.
+    fn continued(&self, region: RoiConfig) -> Option<usize> {
+        if !self.separate_regions {
+            return self.subtitles.len().checked_sub(1);
+        }
+        self.subtitles.iter().rposition(|subtitle| {
+            subtitle
+                .lines
+                .iter()
+                .any(|line| line.region == Some(region))
+        })
+    }
+
     fn apply_cue(&mut self, cue: SubtitleCue) -> Option<SubtitleUpdate> {
         self.prune(cue.start_time);
+        self.held_empties = 0;
 
-        if let Some(last) = self.subtitles.last_mut()
-            && should_merge(last, &cue)
+        let mut continued = self.continued(cue.region);
+        let mut bridged =
+            continued.is_some_and(|index| self.bridges_black_gap(&self.subtitles[index], &cue));
+        // A withheld cue that this one does not continue can no longer grow; drop it so a stray
+        // glyph between two readings of a subtitle does not split it.
+        while let Some(index) = continued
+            && !(bridged || should_merge(&self.subtitles[index], &cue))
+            && self.withheld.contains(&self.subtitles[index].id)
         {
+            self.discard(index);
+            continued = self.continued(cue.region);
+            bridged =
+                continued.is_some_and(|index| self.bridges_black_gap(&self.subtitles[index], &cue));
+        }
+        if let Some(index) = continued
+            && (bridged || should_merge(&self.subtitles[index], &cue))
+        {
+            let last = &mut self.subtitles[index];
+            if self.contradicted != last.id {
+                self.contradicted = last.id;
+                self.contradictions = vec![0; last.lines.len()];
+            }
             last.start_time = last.start_time.min(cue.start_time);
             last.end_time = last.end_time.max(cue.end_time);
             last.start_frame = last.start_frame.min(cue.start_frame);
-            if !last.lines.iter().any(|line| line.text == cue.text) {
-                last.lines.push(SubtitleLine {
-                    center: cue.center,
-                    text: cue.text.clone(),
-                });
+            last.end_frame = last.end_frame.max(cue.end_frame);
+            if self.confidence_decay > 0.0 && cue.lines.len() == last.lines.len() {
+                for (index, line) in cue.lines.into_iter().enumerate() {
+                    let held = &mut last.lines[index];
+                    let repeats = &mut self.contradictions[index];
+                    if held.text == line.text {
+                        *repeats = 0;
+                        continue;
+                    }
+                    *repeats = repeats.saturating_add(1);
+                    let decay = (1.0 - self.confidence_decay).powi(*repeats as i32);
+                    let effective = held.confidence.unwrap_or(1.0) * decay;
+                    if line.confidence.unwrap_or(1.0) >= effective {
+                        *held = subtitle_line(line, cue.rtl, cue.region);
+                        *repeats = 0;
+                    }
+                }
+            } else {
+                for line in cue.lines {
+                    if !last.lines.iter().any(|existing| existing.text == line.text) {
+                        last.lines.push(subtitle_line(line, cue.rtl, cue.region));
+                    }
+                }
+                self.contradictions.resize(last.lines.len(), 0);
             }
             self.stats.merged = self.stats.merged.saturating_add(1);
-            return Some(SubtitleUpdate {
-                kind: SubtitleUpdateKind::Updated,
-                subtitle: last.clone(),
-            });
+            return self.announce(index);
         }
 
         let subtitle = MergedSubtitle {
@@ -176,31 +439,83 @@ impl MergeWorker {
             start_time: cue.start_time,
             end_time: cue.end_time,
             start_frame: cue.start_frame,
-            lines: vec![SubtitleLine {
-                center: cue.center,
-                text: cue.text,
-            }],
+            end_frame: cue.end_frame,
+            lines: cue
+                .lines
+                .into_iter()
+                .map(|line| subtitle_line(line, cue.rtl, cue.region))
+                .collect(),
         };
         self.next_id = self.next_id.saturating_add(1);
-        self.stats.cues = self.stats.cues.saturating_add(1);
-        self.subtitles.push(subtitle.clone());
+        self.contradictions = vec![0; subtitle.lines.len()];
+        self.contradicted = subtitle.id;
+        self.withheld.push(subtitle.id);
+        self.subtitles.push(subtitle);
+        self.announce(self.subtitles.len() - 1)
+    }
+
+    /// The update for subtitle `index` after it changed: `New` the first time its text reaches
+    /// `min_cue_chars`, `Updated` afterwards, and none while it is still too short.
+    fn announce(&mut self, index: usize) -> Option<SubtitleUpdate> {
+        let subtitle = &self.subtitles[index];
+        let kind = match self.withheld.iter().position(|id| *id == subtitle.id) {
+            Some(_) if visible_chars(subtitle) < self.min_cue_chars => return None,
+            Some(position) => {
+                self.withheld.swap_remove(position);
+                self.stats.cues = self.stats.cues.saturating_add(1);
+                SubtitleUpdateKind::New
+            }
+            None => SubtitleUpdateKind::Updated,
+        };
         Some(SubtitleUpdate {
-            kind: SubtitleUpdateKind::New,
-            subtitle,
+            kind,
+            subtitle: subtitle.clone(),
         })
     }
 
+    /// Forgets withheld subtitle `index`, counting it as OCR-empty.
+    fn discard(&mut self, index: usize) {
+        let subtitle = self.subtitles.remove(index);
+        self.withheld.retain(|id| *id != subtitle.id);
+        self.stats.ocr_empty = self.stats.ocr_empty.saturating_add(1);
+    }
+
     fn prune(&mut self, now: Duration) {
-        let Some(cutoff) = now.checked_sub(self.cache_window) else {
+        // A cue waiting out a black gap has to stay around long enough to be bridged.
+        let Some(cutoff) = now.checked_sub(self.cache_window.max(self.black_gap)) else {
             return;
         };
         while let Some(first) = self.subtitles.first() {
             if first.end_time < cutoff {
-                self.subtitles.remove(0);
+                if self.withheld.contains(&first.id) {
+                    self.discard(0);
+                } else {
+                    self.subtitles.remove(0);
+                }
             } else {
                 break;
             }
         }
+        self.luma_runs.retain(|(_, end, _)| *end >= cutoff);
+    }
+}
+
+/// Non-whitespace characters across every line of `subtitle`.
+fn visible_chars(subtitle: &MergedSubtitle) -> usize {
+    subtitle
+        .lines
+        .iter()
+        .map(|line| line.text.chars().filter(|ch| !ch.is_whitespace()).count())
+        .sum()
+}
+
+fn subtitle_line(line: CueLine, rtl: bool, region: RoiConfig) -> SubtitleLine {
+    SubtitleLine {
+        center: line.center,
+        text: line.text,
+        rtl,
+        confidence: line.confidence,
+        region: Some(region),
     }
 }
 
@@ -212,26 +527,169 @@ fn should_merge(current: &MergedSubtitle, incoming: &SubtitleCue) -> bool {
         .start_time
         .checked_sub(current.end_time)
         .unwrap_or(Duration::ZERO);
-    if gap <= MERGE_GAP {
-        current.lines.iter().any(|line| line.text == incoming.text)
-    } else {
-        false
+    gap <= MERGE_GAP && repeats_text(current, incoming)
+}
+
+/// Every line of `incoming` is already one of `current`'s.
+fn repeats_text(current: &MergedSubtitle, incoming: &SubtitleCue) -> bool {
+    incoming.lines.iter().all(|new| {
+        current
+            .lines
+            .iter()
+            .any(|existing| existing.text == new.text)
+    })
+}
+
+/// Mean luma over a sparse grid of the frame is at most [`BLACK_FRAME_MAX_LUMA`].
+fn is_black_frame(frame: &VideoFrame) -> bool {
+    let (width, height, stride) = (
+        frame.width() as usize,
+        frame.height() as usize,
+        frame.stride(),
+    );
+    let data = frame.data();
+    let (mut sum, mut count) = (0u64, 0u64);
+    for row in (0..height).step_by(BLACK_FRAME_SAMPLE_STEP) {
+        let Some(line) = data.get(row * stride..row * stride + width) else {
+            break;
+        };
+        for &luma in line.iter().step_by(BLACK_FRAME_SAMPLE_STEP) {
+            sum += u64::from(luma);
+            count += 1;
+        }
     }
+    count > 0 && sum as f32 / count as f32 <= BLACK_FRAME_MAX_LUMA
+}
+
+struct AssembledText {
+    rows: Vec<AssembledRow>,
+    direction: TextDirection,
 }
 
-fn response_to_text(response: &OcrResponse) -> String {
-    if response.texts.is_empty() {
-        return String::new();
+struct AssembledRow {
+    text: String,
+    center: f32,
+    /// Mean confidence of the row's fragments, so one misread line doesn't taint the others.
+    confidence: Option<f32>,
+}
+
+/// Groups OCR fragments into visual lines and joins each line in reading order.
+fn response_to_text(response: &OcrResponse, hint: Option<TextDirection>) -> AssembledText {
+    let mut fragments: Vec<&OcrText> = response
+        .texts
+        .iter()
+        .filter(|entry| !entry.text.trim().is_empty())
+        .collect();
+    let direction = hint.unwrap_or_else(|| infer_direction(&fragments));
+    if fragments.is_empty() {
+        return AssembledText {
+            rows: Vec::new(),
+            direction,
+        };
     }
-    let mut parts = Vec::new();
-    for entry in &response.texts {
-        let trimmed = entry.text.trim();
-        if trimmed.is_empty() {
-            continue;
+
+    fragments.sort_by(|a, b| a.region.reading_order(&b.region));
+    let mut rows: Vec<Vec<&OcrText>> = Vec::new();
+    for fragment in fragments {
+        match rows.last_mut() {
+            Some(row) if same_line(row[0], fragment) => row.push(fragment),
+            _ => rows.push(vec![fragment]),
         }
-        parts.push(trimmed.to_string());
     }
-    parts.join("\n")
+
+    let rows = rows
+        .into_iter()
+        .map(|mut row| {
+            row.sort_by(|a, b| {
+                let order = a.region.x.total_cmp(&b.region.x);
+                let order = if direction.is_rtl() {
+                    order.reverse()
+                } else {
+                    order
+                };
+                order.then_with(|| a.region.overlap_tiebreak(&b.region))
+            });
+            AssembledRow {
+                text: row
+                    .iter()
+                    .map(|entry| entry.text.trim())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                center: row.iter().map(|entry| vertical_center(entry)).sum::<f32>()
+                    / row.len() as f32,
+                confidence: average_confidence(&row),
+            }
+        })
+        .collect();
+
+    AssembledText { rows, direction }
+}
+
+/// Drops a line that repeats the one right above it, as OCR sometimes reads a row twice.
+/// Repeats further apart are left alone; the kept line takes the better confidence.
+fn collapse_repeated_lines(lines: &mut Vec<CueLine>) {
+    lines.dedup_by(|next, kept| {
+        if next.text != kept.text {
+            return false;
+        }
+        kept.confidence = match (kept.confidence, next.confidence) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        };
+        true
+    });
+}
+
+fn average_confidence(fragments: &[&OcrText]) -> Option<f32> {
+    let values: Vec<f32> = fragments
+        .iter()
+        .filter_map(|entry| entry.confidence)
+        .collect();
+    if values.is_empty() {
+        return None;
+    }
+    Some(values.iter().sum::<f32>() / values.len() as f32)
+}
+
+fn infer_direction(fragments: &[&OcrText]) -> TextDirection {
+    let rtl = fragments.iter().any(|entry| {
+        entry
+            .direction()
+            .unwrap_or_else(|| text_direction(&entry.text))
+            .is_rtl()
+    });
+    if rtl {
+        TextDirection::RightToLeft
+    } else {
+        TextDirection::LeftToRight
+    }
+}
+
+/// Direction of the first strongly directional character in `text`.
+fn text_direction(text: &str) -> TextDirection {
+    for ch in text.chars() {
+        let code = ch as u32;
+        let rtl = matches!(code, 0x0590..=0x08FF | 0xFB1D..=0xFDFF | 0xFE70..=0xFEFF);
+        if rtl {
+            return TextDirection::RightToLeft;
+        }
+        if ch.is_alphabetic() {
+            return TextDirection::LeftToRight;
+        }
+    }
+    TextDirection::LeftToRight
+}
+
+fn vertical_center(entry: &OcrText) -> f32 {
+    entry.region.y + entry.region.height * 0.5
+}
+
+fn same_line(a: &OcrText, b: &OcrText) -> bool {
+    let top = a.region.y.max(b.region.y);
+    let bottom = (a.region.y + a.region.height).min(b.region.y + b.region.height);
+    let overlap = (bottom - top).max(0.0);
+    let min_height = a.region.height.min(b.region.height).max(f32::EPSILON);
+    overlap / min_height >= SAME_LINE_OVERLAP
 }
 
 fn normalize_text(text: &str) -> String {
@@ -247,3 +705,392 @@ fn normalize_text(text: &str) -> String {
         .collect::<Vec<_>>()
         .join("\n")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stage::ocr::{OcredSubtitle, placeholder_text};
+    use crate::subtitle::render_srt;
+    use std::sync::Arc;
+    use subtitle_fast_ocr::{OcrRegion, order_regions};
+    use subtitle_fast_types::VideoFrame;
+
+    fn fragment(x: f32, y: f32, text: &str) -> OcrText {
+        OcrText::new(OcrRegion::new(x, y, 60.0, 20.0), text.to_string())
+    }
+
+    fn row_texts(assembled: &AssembledText) -> Vec<&str> {
+        assembled.rows.iter().map(|row| row.text.as_str()).collect()
+    }
+
+    #[test]
+    fn rtl_fragments_are_joined_right_to_left() {
+        // Visual order left to right is "C B A"; logical RTL order starts at the right.
+        let response = OcrResponse::new(vec![
+            fragment(10.0, 100.0, "ج").with_script("Arab"),
+            fragment(150.0, 102.0, "أ").with_script("Arab"),
+            fragment(80.0, 98.0, "ب").with_script("Arab"),
+            fragment(80.0, 140.0, "د").with_script("Arab"),
+        ]);
+
+        let assembled = response_to_text(&response, None);
+
+        assert_eq!(assembled.direction, TextDirection::RightToLeft);
+        assert_eq!(row_texts(&assembled), ["أ ب ج", "د"]);
+    }
+
+    #[test]
+    fn ltr_fragments_keep_left_to_right_order() {
+        let response = OcrResponse::new(vec![
+            fragment(150.0, 100.0, "world"),
+            fragment(10.0, 100.0, "hello"),
+        ]);
+
+        let assembled = response_to_text(&response, None);
+
+        assert_eq!(assembled.direction, TextDirection::LeftToRight);
+        assert_eq!(row_texts(&assembled), ["hello world"]);
+    }
+
+    #[test]
+    fn overlapping_fragments_assemble_the_same_in_any_input_order() {
+        // Nested boxes sharing a left edge and vertical center: the larger one reads first.
+        let outer = OcrText::new(OcrRegion::new(10.0, 90.0, 120.0, 30.0), "outer".into());
+        let inner = OcrText::new(OcrRegion::new(10.0, 95.0, 60.0, 20.0), "inner".into());
+        let right = fragment(200.0, 95.0, "right");
+
+        let forward = OcrResponse::new(vec![outer.clone(), inner.clone(), right.clone()]);
+        let backward = OcrResponse::new(vec![right, inner, outer]);
+
+        assert_eq!(
+            row_texts(&response_to_text(&forward, None)),
+            ["outer inner right"]
+        );
+        assert_eq!(
+            row_texts(&response_to_text(&backward, None)),
+            ["outer inner right"]
+        );
+
+        let mut regions = vec![
+            OcrRegion::new(10.0, 95.0, 60.0, 20.0),
+            OcrRegion::new(10.0, 90.0, 120.0, 30.0),
+        ];
+        order_regions(&mut regions);
+        assert_eq!(regions[0].width, 120.0);
+    }
+
+    /// One completed region at `start_secs` whose OCR response holds `texts`.
+    fn ocr_event(start_secs: u64, texts: Vec<OcrText>) -> OcrEvent {
+        let frame = Arc::new(VideoFrame::filled(200, 100, 0).expect("frame"));
+        OcrEvent {
+            sample: None,
+            regions: vec![OcredSubtitle {
+                lifecycle: CompletedRegion {
+                    id: start_secs,
+                    label: format!("region-{start_secs}"),
+                    start_time: Duration::from_secs(start_secs),
+                    end_time: Duration::from_secs(start_secs + 1),
+                    start_frame: start_secs * 30,
+                    end_frame: start_secs * 30 + 30,
+                    roi: RoiConfig {
+                        x: 0.0,
+                        y: 0.6,
+                        width: 1.0,
+                        height: 0.4,
+                    },
+                    frame,
+                },
+                region: OcrRegion::new(0.0, 60.0, 200.0, 40.0),
+                response: OcrResponse::new(texts),
+            }],
+            region_timings: None,
+            timings: None,
+        }
+    }
+
+    #[test]
+    fn two_line_cue_keeps_a_confidence_per_line() {
+        let event = ocr_event(
+            1,
+            vec![
+                fragment(10.0, 62.0, "Where were").with_confidence(0.95),
+                fragment(80.0, 63.0, "you?").with_confidence(0.85),
+                fragment(10.0, 82.0, "Wh4t").with_confidence(0.3),
+            ],
+        );
+
+        let mut worker = MergeWorker::new(SUBTITLE_CACHE_WINDOW, None);
+        let output = worker.handle_event(event);
+
+        assert_eq!(output.updates.len(), 1);
+        let timed = output.updates[0].subtitle.as_timed();
+        assert_eq!(timed.lines, ["Where were you?", "Wh4t"]);
+        assert_eq!(timed.confidences.len(), 2);
+        let top = timed.confidences[0].expect("top confidence");
+        let bottom = timed.confidences[1].expect("bottom confidence");
+        assert!((top - 0.9).abs() < 1e-6, "top line confidence {top}");
+        assert!(
+            (bottom - 0.3).abs() < 1e-6,
+            "bottom line confidence {bottom}"
+        );
+    }
+
+    #[test]
+    fn adjacent_duplicate_lines_collapse_within_a_cue() {
+        let event = ocr_event(
+            1,
+            vec![
+                fragment(10.0, 0.0, "See you").with_confidence(0.6),
+                fragment(10.0, 20.0, "See you").with_confidence(0.8),
+                fragment(10.0, 40.0, "tomorrow"),
+                fragment(10.0, 60.0, "See you"),
+            ],
+        );
+
+        let mut worker = MergeWorker::new(SUBTITLE_CACHE_WINDOW, None);
+        let output = worker.handle_event(event);
+
+        assert_eq!(output.updates.len(), 1);
+        let timed = output.updates[0].subtitle.as_timed();
+        assert_eq!(timed.lines, ["See you", "tomorrow", "See you"]);
+        assert_eq!(timed.confidences[0], Some(0.8));
+    }
+
+    #[test]
+    fn separate_regions_keep_overlapping_cues_apart() {
+        let mut top = ocr_event(1, vec![fragment(10.0, 2.0, "Sign: Exit")]);
+        top.regions[0].lifecycle.roi.y = 0.0;
+        let bottom = ocr_event(1, vec![fragment(10.0, 62.0, "Where are we?")]);
+        let mut top_again = ocr_event(2, vec![fragment(10.0, 2.0, "Sign: Exit")]);
+        top_again.regions[0].lifecycle.roi.y = 0.0;
+
+        let mut worker = MergeWorker::new(SUBTITLE_CACHE_WINDOW, None).with_separate_regions(true);
+        let top = worker.handle_event(top);
+        let bottom = worker.handle_event(bottom);
+        let top_again = worker.handle_event(top_again);
+
+        assert_eq!(bottom.updates[0].kind, SubtitleUpdateKind::New);
+        assert_ne!(top.updates[0].subtitle.id, bottom.updates[0].subtitle.id);
+        assert_eq!(
+            bottom.updates[0].subtitle.as_timed().lines,
+            ["Where are we?"]
+        );
+        // The top region's next cue continues its own subtitle, not the later bottom one.
+        assert_eq!(top_again.updates[0].kind, SubtitleUpdateKind::Updated);
+        assert_eq!(top_again.updates[0].subtitle.id, top.updates[0].subtitle.id);
+    }
+
+    #[test]
+    fn back_to_back_placeholder_cues_stay_separate() {
+        let first = ocr_event(1, vec![fragment(10.0, 62.0, &placeholder_text(1))]);
+        let mut second = ocr_event(2, vec![fragment(10.0, 62.0, &placeholder_text(2))]);
+        second.regions[0].lifecycle.start_time += Duration::from_millis(50);
+
+        let mut worker = MergeWorker::new(SUBTITLE_CACHE_WINDOW, None);
+        let first = worker.handle_event(first);
+        let second = worker.handle_event(second);
+
+        assert_eq!(first.updates.len(), 1);
+        assert_eq!(second.updates.len(), 1);
+        assert_eq!(second.updates[0].kind, SubtitleUpdateKind::New);
+        assert_ne!(first.updates[0].subtitle.id, second.updates[0].subtitle.id);
+    }
+
+    #[test]
+    fn cues_shorter_than_min_cue_chars_are_dropped() {
+        let mut worker = MergeWorker::new(SUBTITLE_CACHE_WINDOW, None).with_min_cue_chars(2);
+
+        let dropped = worker.handle_event(ocr_event(1, vec![fragment(10.0, 62.0, "I")]));
+        assert!(dropped.updates.is_empty());
+        assert_eq!(dropped.stats.cues, 0);
+
+        let kept = worker.handle_event(ocr_event(5, vec![fragment(10.0, 62.0, "OK")]));
+        assert_eq!(kept.updates.len(), 1);
+        assert_eq!(kept.updates[0].kind, SubtitleUpdateKind::New);
+        assert_eq!(kept.updates[0].subtitle.as_timed().lines, ["OK"]);
+        assert_eq!(kept.stats.cues, 1);
+        assert_eq!(kept.stats.ocr_empty, 1);
+    }
+
+    #[test]
+    fn min_cue_chars_counts_every_region_of_the_merged_cue() {
+        let mut event = ocr_event(1, vec![fragment(10.0, 2.0, "你")]);
+        event.regions[0].lifecycle.roi.y = 0.0;
+        let bottom = ocr_event(1, vec![fragment(10.0, 62.0, "好")]);
+        event.regions.extend(bottom.regions);
+
+        let mut worker = MergeWorker::new(SUBTITLE_CACHE_WINDOW, None).with_min_cue_chars(2);
+        let output = worker.handle_event(event);
+
+        assert_eq!(output.updates.len(), 1);
+        assert_eq!(output.updates[0].kind, SubtitleUpdateKind::New);
+        assert_eq!(output.updates[0].subtitle.as_timed().lines, ["你", "好"]);
+        assert_eq!(output.stats.cues, 1);
+        assert_eq!(output.stats.ocr_empty, 0);
+    }
+
+    #[test]
+    fn persistent_new_reading_replaces_decayed_held_line() {
+        let mut worker = MergeWorker::new(SUBTITLE_CACHE_WINDOW, None).with_confidence_decay(0.1);
+        let held = worker.handle_event(ocr_event(
+            1,
+            vec![fragment(10.0, 62.0, "See you soon").with_confidence(0.95)],
+        ));
+        assert_eq!(held.updates[0].kind, SubtitleUpdateKind::New);
+
+        // 0.95 decays to 0.855 and 0.770 over two contradicting cues, still above 0.7.
+        for start in 2..4 {
+            let output = worker.handle_event(ocr_event(
+                start,
+                vec![fragment(10.0, 62.0, "See you later").with_confidence(0.7)],
+            ));
+            assert_eq!(output.updates[0].kind, SubtitleUpdateKind::Updated);
+            assert_eq!(
+                output.updates[0].subtitle.as_timed().lines,
+                ["See you soon"]
+            );
+        }
+
+        // The third drops it to 0.693 and the new reading takes over.
+        let replaced = worker.handle_event(ocr_event(
+            4,
+            vec![fragment(10.0, 62.0, "See you later").with_confidence(0.7)],
+        ));
+        let timed = replaced.updates[0].subtitle.as_timed();
+        assert_eq!(timed.lines, ["See you later"]);
+        assert_eq!(timed.confidences, [Some(0.7)]);
+
+        // Without decay both readings are kept.
+        let mut worker = MergeWorker::new(SUBTITLE_CACHE_WINDOW, None);
+        worker.handle_event(ocr_event(
+            1,
+            vec![fragment(10.0, 62.0, "See you soon").with_confidence(0.95)],
+        ));
+        let output = worker.handle_event(ocr_event(
+            2,
+            vec![fragment(10.0, 62.0, "See you later").with_confidence(0.7)],
+        ));
+        assert_eq!(
+            output.updates[0].subtitle.as_timed().lines,
+            ["See you soon", "See you later"]
+        );
+    }
+
+    #[test]
+    fn empty_recognition_inside_a_held_cue_keeps_its_text() {
+        let run = |empty_hold: usize| {
+            let mut worker =
+                MergeWorker::new(SUBTITLE_CACHE_WINDOW, None).with_empty_hold(empty_hold);
+            let mut updates = Vec::new();
+            for (start, text) in [(1, "Hold on"), (2, ""), (3, "Hold on")] {
+                let texts = if text.is_empty() {
+                    Vec::new()
+                } else {
+                    vec![fragment(10.0, 62.0, text)]
+                };
+                updates.extend(worker.handle_event(ocr_event(start, texts)).updates);
+            }
+            (updates, worker.stats.clone())
+        };
+
+        let (updates, stats) = run(1);
+        assert!(
+            updates.iter().all(|update| update.subtitle.id == 0),
+            "cue split"
+        );
+        let cue = &updates.last().expect("update").subtitle;
+        assert_eq!(cue.as_timed().lines, ["Hold on"]);
+        assert_eq!(cue.start_time, Duration::from_secs(1));
+        assert_eq!(cue.end_time, Duration::from_secs(4));
+        assert_eq!((stats.cues, stats.ocr_empty), (1, 0));
+
+        // Without the hold the empty second leaves a gap and the line comes back as a new cue.
+        let (updates, stats) = run(0);
+        assert_eq!(updates.last().expect("update").subtitle.id, 1);
+        assert_eq!((stats.cues, stats.ocr_empty), (2, 1));
+    }
+
+    #[test]
+    fn identical_cues_around_a_black_gap_are_bridged() {
+        // "See you" from 1 s to 2 s, black frames until 4 s, then "See you" again until 5 s.
+        // `flash_at` puts one lit sample inside the black gap.
+        let run = |max_gap: Duration, flash_at: Option<Duration>| {
+            let mut worker =
+                MergeWorker::new(SUBTITLE_CACHE_WINDOW, None).with_black_gap_bridge(max_gap);
+            let mut updates = Vec::new();
+            for step in 4..=20u64 {
+                let time = Duration::from_millis(step * 250);
+                let black = time > Duration::from_secs(2)
+                    && time < Duration::from_secs(4)
+                    && Some(time) != flash_at;
+                worker.observe_frame(
+                    time,
+                    &VideoFrame::filled(200, 100, if black { 16 } else { 90 }).expect("frame"),
+                );
+                // Each cue completes on the first sample after it ends.
+                if time == Duration::from_millis(2250) {
+                    let event = ocr_event(1, vec![fragment(10.0, 62.0, "See you")]);
+                    updates.extend(worker.handle_event(event).updates);
+                }
+            }
+            let event = ocr_event(4, vec![fragment(10.0, 62.0, "See you")]);
+            updates.extend(worker.handle_event(event).updates);
+            updates
+        };
+
+        let updates = run(Duration::from_secs(3), None);
+        assert!(
+            updates.iter().all(|update| update.subtitle.id == 0),
+            "cue split"
+        );
+        let cue = &updates.last().expect("update").subtitle;
+        assert_eq!(cue.start_time, Duration::from_secs(1));
+        assert_eq!(cue.end_time, Duration::from_secs(5));
+
+        // Off, too short for the gap, or a lit frame inside it: the cue comes back as a new one.
+        for (max_gap, flash_at) in [
+            (Duration::ZERO, None),
+            (Duration::from_millis(1500), None),
+            (Duration::from_secs(3), Some(Duration::from_secs(3))),
+        ] {
+            let updates = run(max_gap, flash_at);
+            assert_eq!(
+                updates.last().expect("update").subtitle.id,
+                1,
+                "bridged with {max_gap:?} and a flash at {flash_at:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn rtl_cues_render_with_marker() {
+        let mut worker = MergeWorker::new(SUBTITLE_CACHE_WINDOW, Some(TextDirection::RightToLeft));
+        let update = worker
+            .apply_cue(SubtitleCue {
+                start_time: Duration::from_millis(0),
+                end_time: Duration::from_millis(900),
+                start_frame: 0,
+                end_frame: 0,
+                lines: vec![CueLine {
+                    text: "שלום עולם".to_string(),
+                    center: 0.9,
+                    confidence: None,
+                }],
+                rtl: true,
+                region: RoiConfig {
+                    x: 0.1,
+                    y: 0.85,
+                    width: 0.8,
+                    height: 0.1,
+                },
+            })
+            .expect("update");
+
+        let srt = render_srt(&[update.subtitle]);
+
+        assert!(
+            srt.contains("\u{200F}שלום עולם"),
+            "missing RTL mark: {srt:?}"
+        );
+    }
+}
@@ -1,44 +1,69 @@
 pub mod averager;
+pub mod color_range;
+mod debug_images;
 pub mod detector;
 pub mod determiner;
+pub mod exclusion;
+pub mod fixed_regions;
+pub mod heatmap;
 pub mod lifecycle;
 pub mod merge;
 pub mod ocr;
+pub mod rotation;
 pub mod sampler;
 pub mod sorter;
 
 use std::path::{Path, PathBuf};
 use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Duration;
 
 use averager::{Averager, AveragerResult};
-use detector::Detector;
-use futures_util::Stream;
+use color_range::LumaRangeNormalizer;
+use detector::{DetectionSampleResult, Detector};
+use exclusion::ExclusionZones;
+use fixed_regions::FixedRegions;
+use futures_util::{Stream, StreamExt};
+use tokio::sync::broadcast;
 use tokio_stream::wrappers::WatchStream;
 
+use crate::encoding::OutputEncoding;
+#[cfg(any(feature = "ocr-ort", all(feature = "ocr-vision", target_os = "macos")))]
+use crate::log_error;
+use crate::log_info;
+use crate::log_warn;
 #[cfg(feature = "ocr-ort")]
 use crate::model;
-use crate::settings::{DetectionSettings, EffectiveSettings};
+use crate::output_format::OutputFormat;
+use crate::settings::{ColorRange, DetectionSettings, EffectiveSettings, OcrSettings, RoiUnits};
 use determiner::{RegionDeterminer, RegionDeterminerError};
 use lifecycle::{RegionLifecycleError, RegionLifecycleTracker};
 use merge::{Merge, MergeResult};
-use ocr::{OcrStageError, SubtitleOcr};
-use sampler::FrameSampler;
+use ocr::{OcrPreviewSender, OcrStageError, SubtitleOcr};
+use rotation::FrameRotator;
+use sampler::{FrameSampler, InFlightLimiter, SamplerResult, chain_corrections};
 use sorter::FrameSorter;
-use subtitle_fast_decoder::DynDecoderProvider;
+use subtitle_fast_decoder::{
+    DecoderController, DynDecoderProvider, FrameStream, SeekInfo, SeekMode,
+};
 #[cfg(all(feature = "ocr-vision", target_os = "macos"))]
 use subtitle_fast_ocr::VisionOcrEngine;
-use subtitle_fast_ocr::{NoopOcrEngine, OcrEngine};
 #[cfg(feature = "ocr-ort")]
-use subtitle_fast_ocr::{OcrError, OrtOcrConfig, OrtOcrEngine};
-use subtitle_fast_types::DecoderError;
+use subtitle_fast_ocr::{DynamicInputWidth, OcrError, OrtOcrConfig, OrtOcrEngine};
+use subtitle_fast_ocr::{NoopOcrEngine, OcrEngine};
+use subtitle_fast_types::{
+    DecoderError, DecoderResult, FailedStage, RoiConfig, TextDirection, VideoFrame,
+};
 use subtitle_fast_validator::subtitle_detection::SubtitleDetectionError;
 
 pub use crate::subtitle::{
-    MergedSubtitle, SubtitleLine, TimedSubtitle, render_srt, sort_subtitles,
+    CueLayout, MergedSubtitle, SubtitleGap, SubtitleLine, TimedSubtitle, find_gaps,
+    merge_subtitles, render_gap_srt, render_srt, sort_subtitles, split_subtitle,
 };
 pub use merge::{SubtitleStats, SubtitleUpdate, SubtitleUpdateKind};
 
+const SUBTITLE_UPDATE_CAPACITY: usize = 256;
+
 pub struct StreamBundle<T> {
     pub stream: Pin<Box<dyn Stream<Item = T> + Send>>,
     pub total_frames: Option<u64>,
@@ -56,6 +81,17 @@ impl<T> StreamBundle<T> {
 #[derive(Clone)]
 pub struct PipelineConfig {
     pub detection: DetectionSettings,
+    /// Consecutive decode errors skipped before the run fails; zero fails on the first one.
+    pub decoder_error_tolerance: usize,
+    /// Range of the decoded luma; limited-range frames are stretched to full range.
+    pub decoder_color_range: ColorRange,
+    /// Extra attempts at initializing the decoder backend before falling back to another.
+    pub decoder_init_retries: u32,
+    /// Wait before the first initialization retry, doubling for each one after.
+    pub decoder_init_backoff: Duration,
+    /// Frame the decoder was configured to start at (`SUBFAST_START_FRAME`); skipping the intro
+    /// never seeks back before it.
+    pub decoder_start_frame: Option<u64>,
     pub ocr: OcrPipelineConfig,
     pub output: OutputPipelineConfig,
 }
@@ -63,25 +99,123 @@ pub struct PipelineConfig {
 #[derive(Clone)]
 pub struct OcrPipelineConfig {
     pub engine: Arc<dyn OcrEngine>,
+    pub text_direction: Option<TextDirection>,
+    /// Emit placeholder cues when `engine` is the noop fallback.
+    pub placeholder: bool,
+    /// Lifecycle events recognized concurrently on the blocking pool.
+    pub concurrency: usize,
+    /// Compose recognized text to Unicode NFC.
+    pub normalize_nfc: bool,
+    /// Drop cues with fewer non-whitespace characters than this; 0 keeps every cue.
+    pub min_cue_chars: usize,
+    /// Per-cue decay of a held line's confidence against a differing reading; 0 disables it.
+    pub confidence_decay: f32,
+    /// Empty recognitions in a row that may extend the cue they continue; 0 drops them all.
+    pub empty_hold: usize,
+    /// Mean confidence below which a region is recognized again from an upscaled crop.
+    pub upscale_retry_below: Option<f32>,
+    /// Regions larger than this many frame pixels are skipped instead of recognized.
+    pub max_crop_pixels: usize,
+    /// Where the OCR stage publishes its latest crop and text; `None` for CLI runs.
+    pub preview: Option<OcrPreviewSender>,
 }
 
 #[derive(Clone)]
 pub struct OutputPipelineConfig {
     pub path: PathBuf,
+    pub format: OutputFormat,
+    pub gap_markers_ms: Option<u64>,
+    pub layout: CueLayout,
+    /// Fixed regions whose cues go to separate `.trackN` files, in track order; empty writes
+    /// every cue to `path`.
+    pub tracks: Vec<RoiConfig>,
+    pub emit_metadata: bool,
+    pub timing_frames: bool,
+    pub encoding: OutputEncoding,
 }
 
 impl PipelineConfig {
     pub fn from_settings(settings: &EffectiveSettings, input: &Path) -> Result<Self, DecoderError> {
-        let engine = build_ocr_engine(settings);
+        Self::with_engine(settings, input, build_ocr_engine(settings))
+    }
+
+    /// One config per input for a batch run. `build_engine` is called once and its engine is
+    /// shared by every file, so a model is loaded only once however many files run at a time.
+    /// Outputs go next to each input; an explicit output path, or two inputs that would write
+    /// the same file, is an error.
+    pub fn for_batch(
+        settings: &EffectiveSettings,
+        inputs: &[PathBuf],
+        build_engine: impl FnOnce(&EffectiveSettings) -> Arc<dyn OcrEngine>,
+    ) -> Result<Vec<Self>, DecoderError> {
+        if inputs.len() > 1 && settings.output.path.is_some() {
+            return Err(DecoderError::configuration(
+                "an output path cannot be used with several inputs; outputs are written next to each input",
+            ));
+        }
+        let engine = build_engine(settings);
+        let mut configs: Vec<Self> = Vec::with_capacity(inputs.len());
+        for input in inputs {
+            let config = Self::with_engine(settings, input, Arc::clone(&engine))?;
+            if configs
+                .iter()
+                .any(|other| other.output.path == config.output.path)
+            {
+                return Err(DecoderError::configuration(format!(
+                    "several inputs would write {}",
+                    config.output.path.display()
+                )));
+            }
+            configs.push(config);
+        }
+        Ok(configs)
+    }
+
+    /// Like [`from_settings`](Self::from_settings), recognizing with an already built `engine`.
+    pub fn with_engine(
+        settings: &EffectiveSettings,
+        input: &Path,
+        engine: Arc<dyn OcrEngine>,
+    ) -> Result<Self, DecoderError> {
         let output_path = settings
             .output
             .path
             .clone()
-            .unwrap_or_else(|| default_output_path(input));
+            .unwrap_or_else(|| default_output_path(input, settings.output.format));
         Ok(Self {
             detection: settings.detection.clone(),
-            ocr: OcrPipelineConfig { engine },
-            output: OutputPipelineConfig { path: output_path },
+            decoder_error_tolerance: settings.decoder.error_tolerance,
+            decoder_color_range: settings.decoder.color_range,
+            decoder_init_retries: settings.decoder.init_retries,
+            decoder_init_backoff: settings.decoder.init_retry_backoff,
+            decoder_start_frame: None,
+            ocr: OcrPipelineConfig {
+                engine,
+                text_direction: settings.ocr.text_direction,
+                placeholder: settings.ocr.placeholder,
+                concurrency: settings.ocr.concurrency,
+                normalize_nfc: settings.ocr.normalize_nfc,
+                min_cue_chars: settings.ocr.min_cue_chars,
+                confidence_decay: settings.ocr.confidence_decay,
+                empty_hold: settings.ocr.empty_hold,
+                upscale_retry_below: settings.ocr.upscale_retry_below,
+                max_crop_pixels: settings.ocr.max_crop_pixels,
+                preview: None,
+            },
+            output: OutputPipelineConfig {
+                path: output_path,
+                format: settings.output.format,
+                gap_markers_ms: settings.output.gap_markers_ms,
+                layout: settings.output.cue_layout(),
+                tracks: if settings.output.split_tracks {
+                    settings.detection.fixed_regions.clone()
+                } else {
+                    Vec::new()
+                },
+                emit_metadata: settings.output.emit_metadata,
+                timing_frames: settings.output.timing_frames,
+                encoding: settings.output.encoding,
+            },
         })
     }
 }
@@ -90,6 +224,8 @@ impl PipelineConfig {
 pub struct PipelineProgress {
     pub samples_seen: u64,
     pub latest_frame_index: u64,
+    /// Timestamp of the latest sampled frame.
+    pub latest_time: Duration,
     pub total_frames: Option<u64>,
     pub fps: f64,
     pub det_ms: f64,
@@ -110,23 +246,55 @@ pub struct PipelineUpdate {
 
 pub type PipelineResult = AveragerResult;
 
+/// Why a pipeline run failed, by the stage the failure originated in.
 #[derive(Debug)]
 pub enum PipelineError {
+    /// The OCR engine failed to recognize a region.
     Ocr(OcrStageError),
+    /// The subtitle detector could not be built or failed on a frame.
+    Detection(SubtitleDetectionError),
+    /// Decoding or sampling failed.
+    Decode(DecoderError),
+}
+
+impl From<OcrStageError> for PipelineError {
+    /// Unwraps errors that only passed through the OCR stage into the category they started in.
+    fn from(err: OcrStageError) -> Self {
+        match err {
+            OcrStageError::Lifecycle(RegionLifecycleError::Determiner(
+                RegionDeterminerError::Detector(detector_err),
+            )) => match detector_err {
+                detector::DetectorError::Sampler(err) => Self::Decode(err),
+                detector::DetectorError::Detection(err) => Self::Detection(err),
+            },
+            OcrStageError::Engine(_) => Self::Ocr(err),
+        }
+    }
 }
 
 pub struct PipelineOutputs {
     pub stream: Pin<Box<dyn Stream<Item = PipelineResult> + Send>>,
     pub total_frames: Option<u64>,
+    /// Subtitle updates published as the run progresses, subscribed before any frame is decoded.
+    pub subtitle_updates: broadcast::Receiver<SubtitleUpdate>,
     pub handle: PipelineHandle,
 }
 
 #[derive(Clone)]
 pub struct PipelineHandle {
     pause_tx: tokio::sync::watch::Sender<bool>,
+    subtitle_tx: broadcast::Sender<SubtitleUpdate>,
 }
 
 impl PipelineHandle {
+    /// Subscribe to subtitle updates emitted from now on.
+    ///
+    /// Receivers that fall more than the channel capacity behind observe
+    /// `RecvError::Lagged` and skip the oldest updates.
+    pub fn subscribe_subtitles(&self) -> broadcast::Receiver<SubtitleUpdate> {
+        self.subtitle_tx.subscribe()
+    }
+
     pub fn pause_sender(&self) -> tokio::sync::watch::Sender<bool> {
         self.pause_tx.clone()
     }
@@ -140,35 +308,173 @@ pub fn build_pipeline(
     provider: DynDecoderProvider,
     pipeline: &PipelineConfig,
 ) -> Result<PipelineOutputs, DecoderError> {
-    let initial_total_frames = provider.metadata().total_frames;
-    let (_, initial_stream) = provider.open()?;
+    let metadata = provider.metadata();
+    let initial_total_frames = metadata.total_frames;
+    let mut detection = pipeline.detection.clone();
+    detection.roi = detection
+        .fractional_roi(metadata.width, metadata.height)
+        .map_err(|err| DecoderError::configuration(err.to_string()))?;
+    detection.roi_units = RoiUnits::Fraction;
+    let (controller, initial_stream) = provider.open()?;
+    let seek_target = intro_seek_target(
+        detection.skip_intro,
+        pipeline.decoder_start_frame,
+        metadata.frame_rate(),
+    );
+    let initial_stream = seek_past_intro(controller, initial_stream, seek_target);
 
     let (pause_tx, pause_rx) = tokio::sync::watch::channel(false);
+    let (subtitle_tx, subtitle_updates) = broadcast::channel(SUBTITLE_UPDATE_CAPACITY);
 
+    let tolerant_stream =
+        ErrorTolerantStream::new(initial_stream, pipeline.decoder_error_tolerance);
     let paused_stream = StreamBundle::new(
-        Box::pin(PauseStream::new(initial_stream, pause_rx.clone())),
+        Box::pin(PauseStream::new(tolerant_stream, pause_rx.clone())),
         initial_total_frames,
     );
 
-    let sorted = FrameSorter::new().attach(paused_stream);
-    let sampled = FrameSampler::new(pipeline.detection.samples_per_second).attach(sorted);
-
-    let detector_stage = Detector::new(&pipeline.detection).map_err(detection_error_to_frame)?;
-
-    let detected = detector_stage.attach(sampled);
-    let determined = RegionDeterminer::new().attach(detected);
-    let tracked = RegionLifecycleTracker::new(&pipeline.detection).attach(determined);
-    let ocred = SubtitleOcr::new(Arc::clone(&pipeline.ocr.engine)).attach(tracked);
-    let merged: StreamBundle<MergeResult> = Merge::with_default_window().attach(ocred);
-    let averaged: StreamBundle<AveragerResult> = Averager::new().attach(merged);
+    let sampled = sample_frames(paused_stream, pipeline, &detection, metadata.frame_rate());
+
+    let fixed_regions = !detection.fixed_regions.is_empty();
+    let determined = if fixed_regions {
+        FixedRegions::new(detection.fixed_regions.clone()).attach(sampled)
+    } else {
+        let detector_stage = Detector::new(&detection).map_err(detection_error_to_frame)?;
+        let detected = detector_stage.attach(sampled);
+        RegionDeterminer::new()
+            .with_full_width_guard(detection.full_width_guard, detection.roi)
+            .attach(detected)
+    };
+    // Fixed regions never disappear, so a change of content has to end the cue instead.
+    let tracked = RegionLifecycleTracker::new(&detection)
+        .with_split_on_change(fixed_regions)
+        .attach(determined);
+    let ocred = SubtitleOcr::new(Arc::clone(&pipeline.ocr.engine))
+        .with_placeholder(pipeline.ocr.placeholder)
+        .with_concurrency(pipeline.ocr.concurrency)
+        .with_nfc_normalization(pipeline.ocr.normalize_nfc)
+        .with_upscale_retry(pipeline.ocr.upscale_retry_below)
+        .with_max_crop_pixels(pipeline.ocr.max_crop_pixels)
+        .with_preview(pipeline.ocr.preview.clone())
+        .attach(tracked);
+    let merged: StreamBundle<MergeResult> = Merge::with_default_window()
+        .with_text_direction(pipeline.ocr.text_direction)
+        .with_min_cue_chars(pipeline.ocr.min_cue_chars)
+        .with_confidence_decay(pipeline.ocr.confidence_decay)
+        .with_empty_hold(pipeline.ocr.empty_hold)
+        .with_black_gap_bridge(Duration::from_millis(detection.black_gap_bridge_ms))
+        .with_separate_regions(!pipeline.output.tracks.is_empty())
+        .attach(ocred);
+    let merged = ExclusionZones::new(
+        detection.exclusion_zones.clone(),
+        detection.exclusion_overlap,
+    )
+    .attach(merged);
+    let averaged: StreamBundle<AveragerResult> = Averager::new()
+        .with_subtitle_updates(subtitle_tx.clone())
+        .attach(merged);
 
     Ok(PipelineOutputs {
         stream: averaged.stream,
         total_frames: averaged.total_frames,
-        handle: PipelineHandle { pause_tx },
+        subtitle_updates,
+        handle: PipelineHandle {
+            pause_tx,
+            subtitle_tx,
+        },
     })
 }
 
+/// Detection alone over the whole frame, ignoring the configured ROI and fixed regions; used to
+/// build an ROI heatmap.
+pub fn build_detection_stream(
+    provider: DynDecoderProvider,
+    pipeline: &PipelineConfig,
+) -> Result<StreamBundle<DetectionSampleResult>, DecoderError> {
+    let metadata = provider.metadata();
+    let mut detection = pipeline.detection.clone();
+    detection.roi = None;
+    detection.roi_units = RoiUnits::Fraction;
+    let (controller, initial_stream) = provider.open()?;
+    let seek_target = intro_seek_target(
+        detection.skip_intro,
+        pipeline.decoder_start_frame,
+        metadata.frame_rate(),
+    );
+    let initial_stream = seek_past_intro(controller, initial_stream, seek_target);
+
+    let tolerant_stream = StreamBundle::new(
+        Box::pin(ErrorTolerantStream::new(
+            initial_stream,
+            pipeline.decoder_error_tolerance,
+        )),
+        metadata.total_frames,
+    );
+    let sampled = sample_frames(tolerant_stream, pipeline, &detection, metadata.frame_rate());
+    let detector_stage = Detector::new(&detection).map_err(detection_error_to_frame)?;
+    Ok(detector_stage.attach(sampled))
+}
+
+/// Where to seek so a skipped intro of length `offset` is not decoded at all. `None` when there
+/// is no intro, or when the decoder already starts at `start_frame` past it (or it cannot tell,
+/// without a frame rate): seeking there would decode frames the start frame skips.
+fn intro_seek_target(
+    offset: Duration,
+    start_frame: Option<u64>,
+    frame_rate: Option<f64>,
+) -> Option<Duration> {
+    if offset.is_zero() {
+        return None;
+    }
+    let Some(start_frame) = start_frame.filter(|frame| *frame > 0) else {
+        return Some(offset);
+    };
+    let fps = frame_rate.filter(|fps| *fps > 0.0)?;
+    (Duration::from_secs_f64(start_frame as f64 / fps) < offset).then_some(offset)
+}
+
+/// Asks the decoder to seek to `target` so a skipped intro is not decoded at all; the sampler
+/// still discards anything earlier, for backends that land on a keyframe before it or cannot
+/// seek. The controller has to outlive the stream for the seek to be seen, so it moves into it.
+fn seek_past_intro(
+    controller: DecoderController,
+    stream: FrameStream,
+    target: Option<Duration>,
+) -> FrameStream {
+    let Some(position) = target else {
+        return stream;
+    };
+    let _ = controller.seek(SeekInfo::Time {
+        position,
+        mode: SeekMode::Fast,
+    });
+    Box::pin(stream.map(move |frame| {
+        let _ = &controller;
+        frame
+    }))
+}
+
+/// Orders, normalizes and rotates decoded frames, then samples them at the detection rate.
+fn sample_frames(
+    decoded: StreamBundle<DecoderResult<VideoFrame>>,
+    pipeline: &PipelineConfig,
+    detection: &DetectionSettings,
+    frame_rate: Option<f64>,
+) -> StreamBundle<SamplerResult> {
+    let sorted = FrameSorter::new().attach(decoded);
+    let correction = chain_corrections(
+        LumaRangeNormalizer::new(pipeline.decoder_color_range).correction(),
+        FrameRotator::new(detection.frame_rotation_deg).correction(),
+    );
+    FrameSampler::new(detection.samples_per_second)
+        .with_limiter(detection.max_in_flight.map(InFlightLimiter::new))
+        .with_frame_rate(frame_rate)
+        .with_roi(detection.roi)
+        .with_start_offset(detection.skip_intro)
+        .with_correction(correction)
+        .attach(sorted)
+}
+
 struct PauseStream<S> {
     inner: S,
     pause_updates: WatchStream<bool>,
@@ -236,31 +542,84 @@ where
     }
 }
 
+/// Drops up to `tolerance` consecutive decode errors so one corrupt packet does not end the run.
+///
+/// The count resets whenever a frame decodes successfully; the error past the limit is passed on.
+struct ErrorTolerantStream<S> {
+    inner: S,
+    tolerance: usize,
+    consecutive: usize,
+}
+
+impl<S> ErrorTolerantStream<S> {
+    fn new(inner: S, tolerance: usize) -> Self {
+        Self {
+            inner,
+            tolerance,
+            consecutive: 0,
+        }
+    }
+}
+
+impl<S> Stream for ErrorTolerantStream<S>
+where
+    S: Stream<Item = DecoderResult<VideoFrame>> + Unpin + Send,
+{
+    type Item = DecoderResult<VideoFrame>;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            match Pin::new(&mut this.inner).poll_next(cx) {
+                std::task::Poll::Ready(Some(Ok(frame))) => {
+                    this.consecutive = 0;
+                    return std::task::Poll::Ready(Some(Ok(frame)));
+                }
+                std::task::Poll::Ready(Some(Err(err))) if this.consecutive < this.tolerance => {
+                    this.consecutive += 1;
+                    log_warn!(
+                        "warning: skipping decode error ({}/{} consecutive): {err}",
+                        this.consecutive,
+                        this.tolerance
+                    );
+                }
+                other => return other,
+            }
+        }
+    }
+}
+
 fn detection_error_to_frame(err: SubtitleDetectionError) -> DecoderError {
-    DecoderError::configuration(format!("subtitle detection error: {err}"))
+    DecoderError::stage(FailedStage::Detection, err)
+}
+
+/// Maps a failure of [`build_detection_stream`]'s stream the way [`pipeline_error_to_frame`]
+/// maps a full pipeline's.
+pub fn detector_error_to_frame(err: detector::DetectorError) -> DecoderError {
+    match err {
+        detector::DetectorError::Sampler(err) => err,
+        detector::DetectorError::Detection(err) => detection_error_to_frame(err),
+    }
 }
 
+/// Decode errors come back unchanged; detection and OCR failures become
+/// [`DecoderError::Stage`] errors that keep the stage and the original error as their source.
 pub fn pipeline_error_to_frame(err: PipelineError) -> DecoderError {
     match err {
-        PipelineError::Ocr(ocr_err) => match ocr_err {
-            OcrStageError::Lifecycle(lifecycle_err) => match lifecycle_err {
-                RegionLifecycleError::Determiner(det_err) => match det_err {
-                    RegionDeterminerError::Detector(detector_err) => match detector_err {
-                        detector::DetectorError::Sampler(sampler_err) => sampler_err,
-                        detector::DetectorError::Detection(det_err) => {
-                            detection_error_to_frame(det_err)
-                        }
-                    },
-                },
-            },
-            OcrStageError::Engine(ocr_err) => {
-                DecoderError::configuration(format!("ocr error: {ocr_err}"))
-            }
-        },
+        PipelineError::Decode(err) => err,
+        PipelineError::Detection(err) => detection_error_to_frame(err),
+        PipelineError::Ocr(OcrStageError::Engine(ocr_err)) => {
+            DecoderError::stage(FailedStage::Ocr, ocr_err)
+        }
+        PipelineError::Ocr(err) => pipeline_error_to_frame(err.into()),
     }
 }
 
-fn build_ocr_engine(settings: &EffectiveSettings) -> Arc<dyn OcrEngine> {
+pub fn build_ocr_engine(settings: &EffectiveSettings) -> Arc<dyn OcrEngine> {
     if let Some(backend) = settings
         .ocr
         .backend
@@ -268,17 +627,18 @@ fn build_ocr_engine(settings: &EffectiveSettings) -> Arc<dyn OcrEngine> {
         .map(|value| value.trim().to_ascii_lowercase())
     {
         if backend == "auto" {
-            return build_ocr_engine_auto();
+            return build_ocr_engine_auto(&settings.ocr);
         }
-        if let Some(engine) = build_ocr_engine_requested(&backend) {
+        if let Some(engine) = build_ocr_engine_requested(&backend, &settings.ocr) {
             return engine;
         }
-        eprintln!("ocr backend '{backend}' unavailable, falling back to auto");
+        log_info!("ocr backend '{backend}' unavailable, falling back to auto");
     }
-    build_ocr_engine_auto()
+    build_ocr_engine_auto(&settings.ocr)
 }
 
-fn build_ocr_engine_requested(backend: &str) -> Option<Arc<dyn OcrEngine>> {
+#[cfg_attr(not(feature = "ocr-ort"), allow(unused_variables))]
+fn build_ocr_engine_requested(backend: &str, ocr: &OcrSettings) -> Option<Arc<dyn OcrEngine>> {
     match backend {
         "noop" => Some(Arc::new(NoopOcrEngine)),
         "vision" => {
@@ -287,7 +647,7 @@ fn build_ocr_engine_requested(backend: &str) -> Option<Arc<dyn OcrEngine>> {
                 return VisionOcrEngine::new()
                     .map(|engine| Arc::new(engine) as Arc<dyn OcrEngine>)
                     .map_err(|err| {
-                        eprintln!("vision OCR engine failed to initialize: {err}");
+                        log_error!("vision OCR engine failed to initialize: {err}");
                         err
                     })
                     .ok();
@@ -298,9 +658,9 @@ fn build_ocr_engine_requested(backend: &str) -> Option<Arc<dyn OcrEngine>> {
         "ort" => {
             #[cfg(feature = "ocr-ort")]
             {
-                return build_ort_engine()
+                return build_ort_engine(ocr)
                     .map_err(|err| {
-                        eprintln!("ort OCR engine failed to initialize: {err}");
+                        log_error!("ort OCR engine failed to initialize: {err}");
                         err
                     })
                     .ok();
@@ -312,22 +672,23 @@ fn build_ocr_engine_requested(backend: &str) -> Option<Arc<dyn OcrEngine>> {
     }
 }
 
-fn build_ocr_engine_auto() -> Arc<dyn OcrEngine> {
+#[cfg_attr(not(feature = "ocr-ort"), allow(unused_variables))]
+fn build_ocr_engine_auto(ocr: &OcrSettings) -> Arc<dyn OcrEngine> {
     #[cfg(all(feature = "ocr-vision", target_os = "macos"))]
     {
         match VisionOcrEngine::new() {
             Ok(engine) => return Arc::new(engine),
             Err(err) => {
-                eprintln!("vision OCR engine failed to initialize: {err}");
+                log_error!("vision OCR engine failed to initialize: {err}");
             }
         }
     }
     #[cfg(feature = "ocr-ort")]
     {
-        match build_ort_engine() {
+        match build_ort_engine(ocr) {
             Ok(engine) => return engine,
             Err(err) => {
-                eprintln!("ort OCR engine failed to initialize: {err}");
+                log_error!("ort OCR engine failed to initialize: {err}");
             }
         }
     }
@@ -335,19 +696,272 @@ fn build_ocr_engine_auto() -> Arc<dyn OcrEngine> {
 }
 
 #[cfg(feature = "ocr-ort")]
-fn build_ort_engine() -> Result<Arc<dyn OcrEngine>, OcrError> {
+fn build_ort_engine(ocr: &OcrSettings) -> Result<Arc<dyn OcrEngine>, OcrError> {
     let paths = model::ort_model_paths()
         .map_err(|err| OcrError::backend(format!("failed to resolve ORT model paths: {err}")))?;
-    let config = OrtOcrConfig {
-        model_path: paths.model_path().to_path_buf(),
-        dictionary_path: paths.dictionary_path().to_path_buf(),
-        ..OrtOcrConfig::default()
-    };
+    let config = ort_config(ocr, paths.model_path(), paths.dictionary_path());
     OrtOcrEngine::with_config(config).map(|engine| Arc::new(engine) as Arc<dyn OcrEngine>)
 }
 
-fn default_output_path(input: &Path) -> PathBuf {
+/// Engine configuration for `ocr`, warning about input sizes the bundled model family is
+/// unlikely to accept.
+#[cfg(feature = "ocr-ort")]
+fn ort_config(ocr: &OcrSettings, model_path: &Path, dictionary_path: &Path) -> OrtOcrConfig {
+    let defaults = OrtOcrConfig::default();
+    let dynamic_width = ocr.dynamic_input_width.then(DynamicInputWidth::default);
+    if let Some(height) = ocr.input_height
+        && height != defaults.input_height
+    {
+        log_warn!(
+            "warning: ocr.input_height = {height} differs from the {} px PaddleOCR recognition models \
+             expect; recognition fails or degrades unless the model was trained at that height",
+            defaults.input_height
+        );
+    }
+    if let Some(width) = ocr.input_width {
+        let stride = DynamicInputWidth::default().stride;
+        if dynamic_width.is_some() {
+            log_warn!(
+                "warning: ocr.input_width = {width} is ignored while dynamic_input_width is on"
+            );
+        } else if width % stride != 0 {
+            log_warn!(
+                "warning: ocr.input_width = {width} is not a multiple of {stride}; most recognition \
+                 models only accept widths their downsampling divides evenly"
+            );
+        }
+    }
+    OrtOcrConfig {
+        model_path: model_path.to_path_buf(),
+        dictionary_path: dictionary_path.to_path_buf(),
+        input_height: ocr.input_height.unwrap_or(defaults.input_height),
+        input_width: ocr.input_width.unwrap_or(defaults.input_width),
+        dynamic_width,
+        input_padding: ocr.input_padding,
+        preserve_blank_tokens: ocr.preserve_blank_tokens,
+        blank_index: ocr.blank_index,
+        decode: ocr.decode,
+        ..defaults
+    }
+}
+
+pub(crate) fn default_output_path(input: &Path, format: OutputFormat) -> PathBuf {
     let mut path = input.to_path_buf();
-    path.set_extension("srt");
+    path.set_extension(format.as_str());
     path
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{CliArgs, CliSources};
+    use crate::settings::resolve_settings;
+    use clap::Parser;
+
+    fn frame(idx: u64) -> DecoderResult<VideoFrame> {
+        VideoFrame::from_nv12_owned(
+            2,
+            2,
+            2,
+            2,
+            Some(Duration::from_millis(idx * 1000)),
+            None,
+            vec![idx as u8; 4],
+            vec![128; 2],
+        )
+    }
+
+    fn corrupt() -> DecoderResult<VideoFrame> {
+        Err(DecoderError::configuration("corrupt packet"))
+    }
+
+    async fn run_sampler(
+        items: Vec<DecoderResult<VideoFrame>>,
+        tolerance: usize,
+    ) -> (usize, Option<DecoderError>) {
+        let stream = ErrorTolerantStream::new(futures_util::stream::iter(items), tolerance);
+        let sorted = FrameSorter::new().attach(StreamBundle::new(Box::pin(stream), None));
+        let mut sampled = FrameSampler::new(1).attach(sorted).stream;
+        let mut frames = 0;
+        while let Some(item) = sampled.next().await {
+            match item {
+                Ok(_) => frames += 1,
+                Err(err) => return (frames, Some(err)),
+            }
+        }
+        (frames, None)
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn transient_decode_error_is_skipped() {
+        let items = vec![frame(0), corrupt(), frame(1), frame(2), corrupt(), frame(3)];
+        let (frames, err) = run_sampler(items, 1).await;
+        assert!(err.is_none(), "run should complete despite isolated errors");
+        assert_eq!(frames, 4);
+
+        let (frames, err) = run_sampler(vec![frame(0), corrupt(), frame(1)], 0).await;
+        assert!(err.is_some(), "zero tolerance keeps the first error fatal");
+        assert_eq!(frames, 1);
+
+        let items = vec![frame(0), corrupt(), corrupt(), frame(1)];
+        let (_, err) = run_sampler(items, 1).await;
+        assert!(
+            err.is_some(),
+            "errors beyond the tolerance still fail the run"
+        );
+    }
+
+    fn through_ocr_stage(err: detector::DetectorError) -> PipelineError {
+        PipelineError::from(OcrStageError::Lifecycle(RegionLifecycleError::Determiner(
+            RegionDeterminerError::Detector(err),
+        )))
+    }
+
+    #[test]
+    fn batch_inputs_share_one_ocr_engine() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config = dir.path().join("batch.toml");
+        std::fs::write(&config, "").expect("write config");
+        let flag = config.to_str().expect("utf-8 path");
+        let cli = CliArgs::try_parse_from(["subtitle-fast", "--config", flag, "a.mp4", "b.mkv"])
+            .expect("cli args");
+        let settings = resolve_settings(&cli, &CliSources::default())
+            .expect("settings")
+            .settings;
+
+        let mut built = 0;
+        let configs = PipelineConfig::for_batch(&settings, &cli.inputs, |_| {
+            built += 1;
+            Arc::new(NoopOcrEngine)
+        })
+        .expect("batch");
+        assert_eq!(built, 1);
+        assert_eq!(configs.len(), 2);
+        assert!(Arc::ptr_eq(&configs[0].ocr.engine, &configs[1].ocr.engine));
+        assert_eq!(configs[0].output.path, PathBuf::from("a.srt"));
+        assert_eq!(configs[1].output.path, PathBuf::from("b.srt"));
+
+        let clash = [PathBuf::from("a.mp4"), PathBuf::from("a.mkv")];
+        assert!(PipelineConfig::for_batch(&settings, &clash, |_| Arc::new(NoopOcrEngine)).is_err());
+    }
+
+    #[cfg(feature = "ocr-ort")]
+    #[test]
+    fn ocr_input_size_overrides_reach_the_ort_config() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let config = dir.path().join("model.toml");
+        std::fs::write(&config, "[ocr]\ninput_height = 32\ninput_width = 640\n")
+            .expect("write config");
+        let flag = config.to_str().expect("utf-8 path");
+        let resolve = |args: &[&str]| {
+            let cli =
+                CliArgs::try_parse_from(["subtitle-fast", "--config", flag].iter().chain(args))
+                    .expect("cli args");
+            let ocr = resolve_settings(&cli, &CliSources::default())
+                .expect("settings")
+                .settings
+                .ocr;
+            ort_config(&ocr, Path::new("rec.onnx"), Path::new("dict.txt"))
+        };
+
+        let from_file = resolve(&[]);
+        assert_eq!((from_file.input_height, from_file.input_width), (32, 640));
+        assert_eq!(from_file.model_path, PathBuf::from("rec.onnx"));
+
+        let from_cli = resolve(&["--ocr-input-height", "64"]);
+        assert_eq!((from_cli.input_height, from_cli.input_width), (64, 640));
+
+        std::fs::write(&config, "").expect("write config");
+        let defaults = OrtOcrConfig::default();
+        let unset = resolve(&[]);
+        assert_eq!(
+            (unset.input_height, unset.input_width),
+            (defaults.input_height, defaults.input_width)
+        );
+    }
+
+    #[test]
+    fn pipeline_errors_keep_their_category() {
+        let decode = through_ocr_stage(detector::DetectorError::Sampler(
+            DecoderError::InvalidFrame {
+                reason: "truncated plane".into(),
+            },
+        ));
+        assert!(matches!(decode, PipelineError::Decode(_)));
+        match pipeline_error_to_frame(decode) {
+            DecoderError::InvalidFrame { reason } => assert_eq!(reason, "truncated plane"),
+            other => panic!("unexpected error: {other:?}"),
+        }
+
+        let detection = through_ocr_stage(detector::DetectorError::Detection(
+            SubtitleDetectionError::EmptyRoi,
+        ));
+        assert!(matches!(
+            detection,
+            PipelineError::Detection(SubtitleDetectionError::EmptyRoi)
+        ));
+        let mapped = pipeline_error_to_frame(detection);
+        assert!(
+            mapped.to_string().starts_with("subtitle detection error"),
+            "{mapped}"
+        );
+        match mapped {
+            DecoderError::Stage {
+                stage: FailedStage::Detection,
+                source,
+            } => assert!(matches!(
+                source.downcast_ref::<SubtitleDetectionError>(),
+                Some(SubtitleDetectionError::EmptyRoi)
+            )),
+            other => panic!("unexpected error: {other:?}"),
+        }
+
+        let ocr = PipelineError::from(OcrStageError::Engine(
+            subtitle_fast_ocr::OcrError::Backend {
+                message: "model missing".into(),
+            },
+        ));
+        assert!(matches!(ocr, PipelineError::Ocr(OcrStageError::Engine(_))));
+        let mapped = pipeline_error_to_frame(ocr);
+        let message = mapped.to_string();
+        assert!(
+            message.starts_with("ocr error") && message.contains("model missing"),
+            "{message}"
+        );
+        match mapped {
+            DecoderError::Stage {
+                stage: FailedStage::Ocr,
+                source,
+            } => assert!(
+                source
+                    .downcast_ref::<subtitle_fast_ocr::OcrError>()
+                    .is_some()
+            ),
+            other => panic!("unexpected error: {other:?}"),
+        }
+
+        // A lifecycle error wrapped by hand still maps by where it started.
+        let wrapped = PipelineError::Ocr(OcrStageError::Lifecycle(
+            RegionLifecycleError::Determiner(RegionDeterminerError::Detector(
+                detector::DetectorError::Sampler(DecoderError::configuration("bad stream")),
+            )),
+        ));
+        assert!(matches!(
+            pipeline_error_to_frame(wrapped),
+            DecoderError::Configuration { message } if message == "bad stream"
+        ));
+    }
+
+    #[test]
+    fn intro_seek_never_goes_back_before_the_start_frame() {
+        let intro = Duration::from_secs(60);
+        assert_eq!(intro_seek_target(Duration::ZERO, None, Some(25.0)), None);
+        assert_eq!(intro_seek_target(intro, None, Some(25.0)), Some(intro));
+        // Starting at 0:20 still seeks on past the intro.
+        assert_eq!(intro_seek_target(intro, Some(500), Some(25.0)), Some(intro));
+        // Starting at 2:00 is already past it.
+        assert_eq!(intro_seek_target(intro, Some(3000), Some(25.0)), None);
+        // Without a frame rate the start frame cannot be placed; only the sampler skips.
+        assert_eq!(intro_seek_target(intro, Some(500), None), None);
+    }
+}
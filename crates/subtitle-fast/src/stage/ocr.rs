@@ -1,29 +1,128 @@
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::{Duration, Instant};
 
 use futures_util::{StreamExt, stream::unfold};
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, watch};
+use unicode_normalization::{UnicodeNormalization, is_nfc};
 
 use super::StreamBundle;
 use super::detector::DetectionSample;
+use super::determiner::RegionId;
 use super::lifecycle::{
     CompletedRegion, LifecycleEvent, LifecycleResult, RegionLifecycleError, RegionTimings,
 };
+use crate::{log_error, log_warn};
 use subtitle_fast_ocr::{LumaPlane, OcrEngine, OcrError, OcrRequest};
-use subtitle_fast_types::{OcrRegion, OcrResponse, RoiConfig, VideoFrame};
+use subtitle_fast_types::{OcrRegion, OcrResponse, OcrText, RoiConfig, VideoFrame};
 
 const OCR_CHANNEL_CAPACITY: usize = 4;
+/// Scale applied to a region's crop for the low-confidence retry.
+const UPSCALE_RETRY_FACTOR: usize = 2;
+/// Stem of the cue text given to regions when only the noop engine is available.
+pub const PLACEHOLDER_TEXT: &str = "subtitle";
+/// Largest region, in frame pixels, sent to the engine unless configured otherwise. About half
+/// a 4K frame, far beyond any real subtitle line.
+pub const DEFAULT_MAX_CROP_PIXELS: usize = 4_000_000;
 
 pub(crate) type RegionBounds = (usize, usize, usize, usize);
 pub type OcrStageResult = Result<OcrEvent, OcrStageError>;
+/// Latest crop and text published by the OCR stage; see [`SubtitleOcr::with_preview`].
+pub type OcrPreviewSender = watch::Sender<Option<OcrPreview>>;
+
+/// The 8-bit luma crop of the most recently recognized region and the text read from it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OcrPreview {
+    pub width: usize,
+    pub height: usize,
+    /// Row-major samples, `width * height` of them.
+    pub pixels: Vec<u8>,
+    pub text: String,
+}
+
+impl OcrPreview {
+    /// Encodes the crop as a grayscale PNG; `None` for an empty crop or mismatched buffer.
+    pub fn to_png(&self) -> Option<Vec<u8>> {
+        if self.width == 0 || self.height == 0 || self.pixels.len() != self.width * self.height {
+            return None;
+        }
+        let mut bytes = Vec::new();
+        let mut encoder = png::Encoder::new(&mut bytes, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().ok()?;
+        writer.write_image_data(&self.pixels).ok()?;
+        writer.finish().ok()?;
+        Some(bytes)
+    }
+}
 
 pub struct SubtitleOcr {
     engine: Arc<dyn OcrEngine>,
+    placeholder: bool,
+    concurrency: usize,
+    normalize_nfc: bool,
+    upscale_retry_below: Option<f32>,
+    max_crop_pixels: usize,
+    preview: Option<OcrPreviewSender>,
 }
 
 impl SubtitleOcr {
     pub fn new(engine: Arc<dyn OcrEngine>) -> Self {
-        Self { engine }
+        Self {
+            engine,
+            placeholder: false,
+            concurrency: 1,
+            normalize_nfc: false,
+            upscale_retry_below: None,
+            max_crop_pixels: DEFAULT_MAX_CROP_PIXELS,
+            preview: None,
+        }
+    }
+
+    /// Publish the crop and text of the last region of every event to `sender`, replacing the
+    /// previous one. No crop is copied while the sender has no receivers, so a viewer turns
+    /// the preview on and off by holding or dropping its receiver.
+    pub fn with_preview(mut self, sender: Option<OcrPreviewSender>) -> Self {
+        self.preview = sender;
+        self
+    }
+
+    /// Skip, with a warning, regions covering more than `limit` frame pixels instead of
+    /// cropping and recognizing them, so a runaway detection cannot balloon memory or latency.
+    pub fn with_max_crop_pixels(mut self, limit: usize) -> Self {
+        self.max_crop_pixels = limit.max(1);
+        self
+    }
+
+    /// Recognize a region once more on a 2x upscaled crop when its mean confidence is below
+    /// `threshold`, keeping whichever attempt is more confident. Results without a confidence
+    /// are never retried; `None` disables the retry.
+    pub fn with_upscale_retry(mut self, threshold: Option<f32>) -> Self {
+        self.upscale_retry_below = threshold;
+        self
+    }
+
+    /// Compose recognized text to Unicode NFC so decomposed engine output compares equal to
+    /// the precomposed form during merging and editing.
+    pub fn with_nfc_normalization(mut self, enabled: bool) -> Self {
+        self.normalize_nfc = enabled;
+        self
+    }
+
+    /// Recognize up to `concurrency` lifecycle events at once on the blocking thread pool.
+    /// Results are still emitted in input order. Engines that serialize internally gain
+    /// nothing beyond keeping recognition off the async runtime.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// When the engine reads no text, give each region a [`placeholder_text`] naming its id so
+    /// the run still produces timing-only cues, and back-to-back cues are not merged as repeats.
+    pub fn with_placeholder(mut self, enabled: bool) -> Self {
+        self.placeholder = enabled;
+        self
     }
 
     pub fn attach(self, input: StreamBundle<LifecycleResult>) -> StreamBundle<OcrStageResult> {
@@ -33,6 +132,12 @@ impl SubtitleOcr {
         } = input;
 
         let engine = self.engine;
+        let placeholder = self.placeholder;
+        let concurrency = self.concurrency;
+        let normalize_nfc = self.normalize_nfc;
+        let upscale_retry_below = self.upscale_retry_below;
+        let max_crop_pixels = self.max_crop_pixels;
+        let preview = self.preview;
         let (tx, rx) = mpsc::channel::<OcrStageResult>(OCR_CHANNEL_CAPACITY);
 
         tokio::spawn(async move {
@@ -41,25 +146,35 @@ impl SubtitleOcr {
                 return;
             }
 
-            let worker = OcrWorker::new(Arc::clone(&engine));
-            let mut upstream = stream;
-
-            while let Some(event) = upstream.next().await {
-                match event {
-                    Ok(segment_event) => {
-                        let result = worker.handle_event(segment_event);
-                        let is_err = result.is_err();
-                        if tx.send(result).await.is_err() {
-                            return;
-                        }
-                        if is_err {
-                            return;
-                        }
-                    }
-                    Err(err) => {
-                        let _ = tx.send(Err(OcrStageError::Lifecycle(err))).await;
-                        return;
+            let worker = Arc::new(
+                OcrWorker::new(Arc::clone(&engine), placeholder, normalize_nfc)
+                    .with_upscale_retry(upscale_retry_below)
+                    .with_max_crop_pixels(max_crop_pixels)
+                    .with_preview(preview),
+            );
+            let mut results = stream
+                .map(move |event| {
+                    let worker = Arc::clone(&worker);
+                    async move {
+                        let segment_event = event.map_err(OcrStageError::Lifecycle)?;
+                        tokio::task::spawn_blocking(move || worker.handle_event(segment_event))
+                            .await
+                            .unwrap_or_else(|err| {
+                                Err(OcrStageError::Engine(OcrError::backend(format!(
+                                    "OCR worker failed: {err}"
+                                ))))
+                            })
                     }
+                })
+                .buffered(concurrency);
+
+            while let Some(result) = results.next().await {
+                let is_err = result.is_err();
+                if tx.send(result).await.is_err() {
+                    return;
+                }
+                if is_err {
+                    return;
                 }
             }
         });
@@ -101,33 +216,185 @@ pub enum OcrStageError {
 
 struct OcrWorker {
     engine: Arc<dyn OcrEngine>,
+    placeholder: bool,
+    batch: bool,
+    placeholder_warnings: AtomicUsize,
+    normalize_nfc: bool,
+    upscale_retry_below: Option<f32>,
+    max_crop_pixels: usize,
+    preview: Option<OcrPreviewSender>,
 }
 
 impl OcrWorker {
-    fn new(engine: Arc<dyn OcrEngine>) -> Self {
-        Self { engine }
+    fn new(engine: Arc<dyn OcrEngine>, placeholder: bool, normalize_nfc: bool) -> Self {
+        let capabilities = engine.capabilities();
+        let placeholder = placeholder && capabilities.reads_no_text;
+        let batch = capabilities.supports_batch;
+        Self {
+            engine,
+            placeholder,
+            batch,
+            placeholder_warnings: AtomicUsize::new(0),
+            normalize_nfc,
+            upscale_retry_below: None,
+            max_crop_pixels: DEFAULT_MAX_CROP_PIXELS,
+            preview: None,
+        }
+    }
+
+    fn with_preview(mut self, sender: Option<OcrPreviewSender>) -> Self {
+        self.preview = sender;
+        self
+    }
+
+    fn with_upscale_retry(mut self, threshold: Option<f32>) -> Self {
+        self.upscale_retry_below = threshold;
+        self
+    }
+
+    fn with_max_crop_pixels(mut self, limit: usize) -> Self {
+        self.max_crop_pixels = limit;
+        self
     }
 
-    fn handle_event(&self, event: LifecycleEvent) -> Result<OcrEvent, OcrStageError> {
+    fn handle_event(&self, mut event: LifecycleEvent) -> Result<OcrEvent, OcrStageError> {
         let started = Instant::now();
-        let mut timings = OcrTimings::default();
-        let mut subtitles = Vec::with_capacity(event.completed.len());
-
-        for lifecycle in event.completed {
-            timings.intervals = timings.intervals.saturating_add(1);
-            let region = roi_to_region(&lifecycle.roi, &lifecycle.frame);
-            let Some(bounds) = region_bounds(&region, &lifecycle.frame) else {
-                continue;
+        let mut timings = OcrTimings {
+            intervals: event.completed.len() as u64,
+            ..OcrTimings::default()
+        };
+
+        let prepared: Vec<(CompletedRegion, OcrRegion, RegionBounds)> = event
+            .completed
+            .into_iter()
+            .filter_map(|lifecycle| {
+                let region = roi_to_region(&lifecycle.roi, &lifecycle.frame);
+                let bounds = region_bounds(&region, &lifecycle.frame)?;
+                let (width, height) = (bounds.2 - bounds.0, bounds.3 - bounds.1);
+                if width.saturating_mul(height) > self.max_crop_pixels {
+                    log_warn!(
+                        "warning: skipping {width}x{height} region at frame {} (over the {} pixel OCR limit)",
+                        lifecycle.start_frame,
+                        self.max_crop_pixels
+                    );
+                    return None;
+                }
+                Some((lifecycle, region, bounds))
+            })
+            .collect();
+        let responses = self.recognize_all(&prepared, &mut timings)?;
+
+        let mut subtitles = Vec::with_capacity(prepared.len());
+        for ((lifecycle, region, bounds), response) in prepared.into_iter().zip(responses) {
+            let (width, height) = (bounds.2 - bounds.0, bounds.3 - bounds.1);
+            let upscaled_pixels =
+                (width * UPSCALE_RETRY_FACTOR).saturating_mul(height * UPSCALE_RETRY_FACTOR);
+            let response = match self.upscale_retry_below {
+                // The upscaled crop goes through the same pixel limit as the first attempt.
+                Some(threshold)
+                    if upscaled_pixels <= self.max_crop_pixels
+                        && mean_confidence(&response).is_some_and(|value| value < threshold) =>
+                {
+                    let retry_started = Instant::now();
+                    let retried =
+                        self.recognize_upscaled(&LumaPlane::from_frame(&lifecycle.frame), bounds);
+                    timings.ocr_calls = timings.ocr_calls.saturating_add(1);
+                    timings.ocr_duration =
+                        timings.ocr_duration.saturating_add(retry_started.elapsed());
+                    match retried {
+                        Some(retried) if mean_confidence(&retried) > mean_confidence(&response) => {
+                            retried
+                        }
+                        _ => response,
+                    }
+                }
+                _ => response,
             };
+            let mut response = if self.placeholder && response.texts.is_empty() {
+                self.placeholder_response(region, lifecycle.id)
+            } else {
+                response
+            };
+            if self.normalize_nfc {
+                for text in &mut response.texts {
+                    if !is_nfc(&text.text) {
+                        text.text = text.text.nfc().collect();
+                    }
+                }
+            }
+            subtitles.push(OcredSubtitle {
+                lifecycle,
+                region,
+                response,
+            });
+        }
+
+        if let Some(sender) = self.preview.as_ref()
+            && sender.receiver_count() > 0
+            && let Some(preview) = subtitles.last().and_then(preview_of)
+        {
+            sender.send_replace(Some(preview));
+        }
+
+        timings.total = started.elapsed();
+        if let Some(sample) = event.sample.as_mut() {
+            sample.sample.release_in_flight();
+        }
+
+        Ok(OcrEvent {
+            sample: event.sample,
+            regions: subtitles,
+            region_timings: event.region_timings,
+            timings: Some(timings),
+        })
+    }
+
+    /// First recognition pass over every region of an event: a single `recognize_batch` call
+    /// when the engine batches, otherwise one `recognize` call per region.
+    fn recognize_all(
+        &self,
+        prepared: &[(CompletedRegion, OcrRegion, RegionBounds)],
+        timings: &mut OcrTimings,
+    ) -> Result<Vec<OcrResponse>, OcrStageError> {
+        let requests: Vec<OcrRequest<'_>> = prepared
+            .iter()
+            .map(|(lifecycle, region, _)| {
+                OcrRequest::new(
+                    LumaPlane::from_frame(&lifecycle.frame),
+                    std::slice::from_ref(region),
+                )
+            })
+            .collect();
+
+        if self.batch && requests.len() > 1 {
+            let ocr_started = Instant::now();
+            let responses = self.engine.recognize_batch(&requests).map_err(|err| {
+                log_error!(
+                    "[ocr-error-debug] batch of {} regions failed: {}",
+                    requests.len(),
+                    err
+                );
+                OcrStageError::Engine(err)
+            })?;
+            if responses.len() != requests.len() {
+                return Err(OcrStageError::Engine(OcrError::backend(format!(
+                    "OCR engine returned {} responses for a batch of {}",
+                    responses.len(),
+                    requests.len()
+                ))));
+            }
+            timings.ocr_calls = timings.ocr_calls.saturating_add(1);
+            timings.ocr_duration = timings.ocr_duration.saturating_add(ocr_started.elapsed());
+            return Ok(responses);
+        }
 
-            let plane = LumaPlane::from_frame(&lifecycle.frame);
-            let regions = [region];
-            let request = OcrRequest::new(plane, &regions);
+        let mut responses = Vec::with_capacity(requests.len());
+        for ((lifecycle, _, bounds), request) in prepared.iter().zip(&requests) {
             let ocr_started = Instant::now();
-            let response = match self.engine.recognize(&request) {
+            let response = match self.engine.recognize(request) {
                 Ok(resp) => resp,
                 Err(err) => {
-                    eprintln!(
+                    log_error!(
                         "[ocr-error-debug] frame={} roi_norm=({:.3},{:.3},{:.3},{:.3}) region_px={}x{}@({},{}) error={}",
                         lifecycle.start_frame,
                         lifecycle.roi.x,
@@ -145,24 +412,102 @@ impl OcrWorker {
             };
             timings.ocr_calls = timings.ocr_calls.saturating_add(1);
             timings.ocr_duration = timings.ocr_duration.saturating_add(ocr_started.elapsed());
-            subtitles.push(OcredSubtitle {
-                lifecycle,
-                region,
-                response,
-            });
+            responses.push(response);
         }
+        Ok(responses)
+    }
 
-        timings.total = started.elapsed();
+    /// Recognizes the region's crop scaled up by [`UPSCALE_RETRY_FACTOR`] and maps the results
+    /// back to frame pixels. A failed retry yields `None` so the first attempt stands.
+    fn recognize_upscaled(
+        &self,
+        plane: &LumaPlane<'_>,
+        bounds: RegionBounds,
+    ) -> Option<OcrResponse> {
+        let (left, top, right, bottom) = bounds;
+        let (width, height) = (right - left, bottom - top);
+        let data = plane.crop_upscaled_8bit(left, top, width, height, UPSCALE_RETRY_FACTOR)?;
+        let (scaled_width, scaled_height) =
+            (width * UPSCALE_RETRY_FACTOR, height * UPSCALE_RETRY_FACTOR);
+        let plane = LumaPlane::from_raw(
+            &data,
+            scaled_width as u32,
+            scaled_height as u32,
+            scaled_width,
+        )
+        .ok()?;
+        let regions = [OcrRegion {
+            x: 0.0,
+            y: 0.0,
+            width: scaled_width as f32,
+            height: scaled_height as f32,
+        }];
+        let mut response = self
+            .engine
+            .recognize(&OcrRequest::new(plane, &regions))
+            .ok()?;
+        let scale = UPSCALE_RETRY_FACTOR as f32;
+        for text in &mut response.texts {
+            text.region = OcrRegion {
+                x: left as f32 + text.region.x / scale,
+                y: top as f32 + text.region.y / scale,
+                width: text.region.width / scale,
+                height: text.region.height / scale,
+            };
+        }
+        Some(response)
+    }
 
-        Ok(OcrEvent {
-            sample: event.sample,
-            regions: subtitles,
-            region_timings: event.region_timings,
-            timings: Some(timings),
-        })
+    fn placeholder_response(&self, region: OcrRegion, id: RegionId) -> OcrResponse {
+        if self
+            .placeholder_warnings
+            .compare_exchange(0, 1, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
+        {
+            log_warn!(
+                "warning: no OCR engine is available; cues keep their timings but use \"[{PLACEHOLDER_TEXT} <id>]\" as text"
+            );
+        }
+        OcrResponse::new(vec![OcrText::new(region, placeholder_text(id))])
     }
 }
 
+/// Cue text standing in for region `id` when no engine reads text.
+pub fn placeholder_text(id: RegionId) -> String {
+    format!("[{PLACEHOLDER_TEXT} {id}]")
+}
+
+fn preview_of(subtitle: &OcredSubtitle) -> Option<OcrPreview> {
+    let frame = &subtitle.lifecycle.frame;
+    let (left, top, right, bottom) = region_bounds(&subtitle.region, frame)?;
+    let (width, height) = (right - left, bottom - top);
+    let pixels = LumaPlane::from_frame(frame).crop_8bit(left, top, width, height)?;
+    let text = subtitle
+        .response
+        .texts
+        .iter()
+        .map(|text| text.text.trim())
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    Some(OcrPreview {
+        width,
+        height,
+        pixels,
+        text,
+    })
+}
+
+/// Mean of the confidences the engine reported; `None` when it reported none.
+fn mean_confidence(response: &OcrResponse) -> Option<f32> {
+    let values: Vec<f32> = response
+        .texts
+        .iter()
+        .filter_map(|text| text.confidence)
+        .collect();
+    (!values.is_empty()).then(|| values.iter().sum::<f32>() / values.len() as f32)
+}
+
 fn roi_to_region(roi: &RoiConfig, frame: &VideoFrame) -> OcrRegion {
     let width = frame.width().max(1) as f32;
     let height = frame.height().max(1) as f32;
@@ -212,22 +557,392 @@ pub(crate) fn region_bounds(region: &OcrRegion, frame: &VideoFrame) -> Option<Re
 
 #[cfg(test)]
 mod tests {
-    use super::roi_to_region;
-    use subtitle_fast_types::{RoiConfig, VideoFrame};
+    use super::*;
+    use std::sync::Mutex;
+    use subtitle_fast_ocr::{NoopOcrEngine, OcrCapabilities};
+
+    fn completed(id: u64, frame: &Arc<VideoFrame>) -> CompletedRegion {
+        CompletedRegion {
+            id,
+            label: format!("region-{id}"),
+            start_time: Duration::from_secs(id),
+            end_time: Duration::from_secs(id) + Duration::from_millis(800),
+            start_frame: id * 30,
+            end_frame: id * 30 + 24,
+            roi: RoiConfig {
+                x: 0.1,
+                y: 0.7,
+                width: 0.8,
+                height: 0.2,
+            },
+            frame: Arc::clone(frame),
+        }
+    }
+
+    /// A black 100x50 frame for regions whose pixels the test does not care about.
+    fn blank_frame() -> Arc<VideoFrame> {
+        Arc::new(VideoFrame::filled(100, 50, 0).unwrap())
+    }
+
+    #[test]
+    fn noop_engine_with_placeholder_emits_timing_only_cues() {
+        let frame = blank_frame();
+        let worker = OcrWorker::new(Arc::new(NoopOcrEngine), true, false);
+
+        for batch in [vec![0, 1], vec![2]] {
+            let event = LifecycleEvent {
+                sample: None,
+                completed: batch.iter().map(|id| completed(*id, &frame)).collect(),
+                region_timings: None,
+            };
+            let ocred = worker.handle_event(event).expect("noop OCR succeeds");
+            assert_eq!(ocred.regions.len(), batch.len());
+            for subtitle in &ocred.regions {
+                let texts = &subtitle.response.texts;
+                assert_eq!(texts.len(), 1);
+                assert_eq!(texts[0].text, placeholder_text(subtitle.lifecycle.id));
+            }
+        }
+        assert_eq!(worker.placeholder_warnings.load(Ordering::Relaxed), 1);
+
+        let disabled = OcrWorker::new(Arc::new(NoopOcrEngine), false, false);
+        let event = LifecycleEvent {
+            sample: None,
+            completed: vec![completed(3, &frame)],
+            region_timings: None,
+        };
+        let ocred = disabled.handle_event(event).expect("noop OCR succeeds");
+        assert!(ocred.regions[0].response.texts.is_empty());
+        assert_eq!(disabled.placeholder_warnings.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn latest_crop_is_published_and_converts_to_a_png() {
+        let luma: Vec<u8> = (0..50)
+            .flat_map(|y| (0..100).map(move |x| (x + y * 2) as u8))
+            .collect();
+        let frame = Arc::new(
+            VideoFrame::from_nv12_owned(100, 50, 100, 100, None, None, luma, vec![128; 2500])
+                .unwrap(),
+        );
+        let (sender, receiver) = watch::channel(None);
+        let worker =
+            OcrWorker::new(Arc::new(NoopOcrEngine), true, false).with_preview(Some(sender.clone()));
+        let event = || LifecycleEvent {
+            sample: None,
+            completed: vec![completed(0, &frame)],
+            region_timings: None,
+        };
+        worker.handle_event(event()).expect("noop OCR succeeds");
+
+        let preview = receiver.borrow().clone().expect("preview published");
+        // The ROI covers x 10..90 and y 35..45 of the 100x50 frame.
+        assert_eq!((preview.width, preview.height), (80, 10));
+        assert_eq!(preview.pixels[0], 10 + 35 * 2);
+        assert_eq!(preview.pixels[80 * 9 + 79], 89 + 44 * 2);
+        assert_eq!(preview.text, "[subtitle 0]");
+
+        let png = preview.to_png().expect("png");
+        let mut reader = png::Decoder::new(std::io::Cursor::new(png))
+            .read_info()
+            .expect("png header");
+        let mut decoded = vec![0; reader.output_buffer_size().expect("buffer size")];
+        let info = reader.next_frame(&mut decoded).expect("png data");
+        assert_eq!((info.width, info.height), (80, 10));
+        assert_eq!(info.color_type, png::ColorType::Grayscale);
+        assert_eq!(decoded, preview.pixels);
+
+        let truncated = OcrPreview {
+            pixels: preview.pixels[1..].to_vec(),
+            ..preview
+        };
+        assert_eq!(truncated.to_png(), None);
+
+        // Nobody watching: nothing is copied.
+        drop(receiver);
+        sender.send_replace(None);
+        worker.handle_event(event()).expect("noop OCR succeeds");
+        assert!(sender.borrow().is_none());
+    }
+
+    /// Returns "café" with a combining acute accent (NFD).
+    struct DecomposedEngine;
+
+    impl OcrEngine for DecomposedEngine {
+        fn name(&self) -> &'static str {
+            "decomposed"
+        }
+
+        fn recognize(&self, request: &OcrRequest<'_>) -> Result<OcrResponse, OcrError> {
+            Ok(OcrResponse::new(vec![OcrText::new(
+                request.regions()[0],
+                "cafe\u{301}".to_string(),
+            )]))
+        }
+    }
+
+    #[test]
+    fn recognized_text_is_composed_to_nfc() {
+        let frame = blank_frame();
+        let event = || LifecycleEvent {
+            sample: None,
+            completed: vec![completed(0, &frame)],
+            region_timings: None,
+        };
+
+        let normalized = OcrWorker::new(Arc::new(DecomposedEngine), false, true)
+            .handle_event(event())
+            .expect("recognition succeeds");
+        assert_eq!(normalized.regions[0].response.texts[0].text, "caf\u{e9}");
+
+        let raw = OcrWorker::new(Arc::new(DecomposedEngine), false, false)
+            .handle_event(event())
+            .expect("recognition succeeds");
+        assert_eq!(raw.regions[0].response.texts[0].text, "cafe\u{301}");
+    }
+
+    /// Sleeps longer for earlier regions so concurrent calls finish out of order, recording
+    /// how many calls were ever running at once.
+    struct SlowFirstEngine {
+        finished: Mutex<Vec<u64>>,
+        active: AtomicUsize,
+        peak: AtomicUsize,
+    }
+
+    impl OcrEngine for SlowFirstEngine {
+        fn name(&self) -> &'static str {
+            "slow-first"
+        }
+
+        fn recognize(&self, request: &OcrRequest<'_>) -> Result<OcrResponse, OcrError> {
+            let running = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(running, Ordering::SeqCst);
+            let region = request.regions()[0];
+            let id = (region.x / 10.0).round() as u64;
+            std::thread::sleep(Duration::from_millis(10 * (4 - id.min(4))));
+            self.finished.lock().unwrap().push(id);
+            self.active.fetch_sub(1, Ordering::SeqCst);
+            Ok(OcrResponse::new(vec![OcrText::new(
+                region,
+                format!("cue {id}"),
+            )]))
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn concurrent_recognition_keeps_input_order() {
+        let frame = blank_frame();
+        let events: Vec<LifecycleResult> = (0..4)
+            .map(|id| {
+                let mut region = completed(id, &frame);
+                region.roi.x = id as f32 * 0.1;
+                region.roi.width = 0.05;
+                Ok(LifecycleEvent {
+                    sample: None,
+                    completed: vec![region],
+                    region_timings: None,
+                })
+            })
+            .collect();
+        let engine = Arc::new(SlowFirstEngine {
+            finished: Mutex::new(Vec::new()),
+            active: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+        });
+
+        let mut stream = SubtitleOcr::new(engine.clone())
+            .with_concurrency(4)
+            .attach(StreamBundle::new(
+                Box::pin(futures_util::stream::iter(events)),
+                None,
+            ))
+            .stream;
+        let mut texts = Vec::new();
+        while let Some(event) = stream.next().await {
+            let event = event.expect("recognition succeeds");
+            texts.extend(
+                event
+                    .regions
+                    .iter()
+                    .map(|subtitle| subtitle.response.texts[0].text.clone()),
+            );
+        }
+
+        assert_eq!(texts, ["cue 0", "cue 1", "cue 2", "cue 3"]);
+        let peak = engine.peak.load(Ordering::SeqCst);
+        assert!(peak > 1, "recognition calls never overlapped (peak {peak})");
+        let finished = engine.finished.lock().unwrap().clone();
+        assert_ne!(
+            finished,
+            [0, 1, 2, 3],
+            "slower earlier regions should finish after later ones"
+        );
+    }
+
+    /// More confident the more pixels it is given, naming the input width in its text.
+    struct ResolutionHungryEngine;
+
+    impl OcrEngine for ResolutionHungryEngine {
+        fn name(&self) -> &'static str {
+            "resolution-hungry"
+        }
+
+        fn recognize(&self, request: &OcrRequest<'_>) -> Result<OcrResponse, OcrError> {
+            let region = request.regions()[0];
+            let confidence = if region.width >= 100.0 { 0.9 } else { 0.3 };
+            Ok(OcrResponse::new(vec![
+                OcrText::new(region, format!("{}px", region.width.round()))
+                    .with_confidence(confidence),
+            ]))
+        }
+    }
+
+    #[test]
+    fn low_confidence_region_is_retried_upscaled() {
+        let frame = blank_frame();
+        let event = || LifecycleEvent {
+            sample: None,
+            completed: vec![completed(0, &frame)],
+            region_timings: None,
+        };
+
+        let retried = OcrWorker::new(Arc::new(ResolutionHungryEngine), false, false)
+            .with_upscale_retry(Some(0.5))
+            .handle_event(event())
+            .expect("recognition succeeds");
+        let text = &retried.regions[0].response.texts[0];
+        assert_eq!(text.text, "160px");
+        assert_eq!(text.confidence, Some(0.9));
+        // Mapped back onto the 80x10 frame region the first attempt saw.
+        assert!((text.region.x - 10.0).abs() < 1e-3, "{:?}", text.region);
+        assert!((text.region.y - 35.0).abs() < 1e-3, "{:?}", text.region);
+        assert!((text.region.width - 80.0).abs() < 1e-3, "{:?}", text.region);
+        assert_eq!(retried.timings.unwrap().ocr_calls, 2);
+
+        let single = OcrWorker::new(Arc::new(ResolutionHungryEngine), false, false)
+            .handle_event(event())
+            .expect("recognition succeeds");
+        assert_eq!(single.regions[0].response.texts[0].text, "80px");
+        assert_eq!(single.timings.unwrap().ocr_calls, 1);
+
+        // The 160x20 upscaled crop would exceed a 1000 pixel limit the 80x10 one fits.
+        let capped = OcrWorker::new(Arc::new(ResolutionHungryEngine), false, false)
+            .with_upscale_retry(Some(0.5))
+            .with_max_crop_pixels(1000)
+            .handle_event(event())
+            .expect("recognition succeeds");
+        assert_eq!(capped.regions[0].response.texts[0].text, "80px");
+        assert_eq!(capped.timings.unwrap().ocr_calls, 1);
+    }
+
+    #[test]
+    fn regions_over_the_pixel_limit_are_skipped() {
+        let frame = blank_frame();
+        let mut runaway = completed(1, &frame);
+        runaway.roi = RoiConfig {
+            x: 0.0,
+            y: 0.0,
+            width: 1.0,
+            height: 1.0,
+        };
+        let event = LifecycleEvent {
+            sample: None,
+            completed: vec![completed(0, &frame), runaway],
+            region_timings: None,
+        };
+
+        // The normal region is 80x10 pixels; the runaway one covers the whole 100x50 frame.
+        let ocred = OcrWorker::new(Arc::new(ResolutionHungryEngine), false, false)
+            .with_max_crop_pixels(1000)
+            .handle_event(event)
+            .expect("recognition succeeds");
+        assert_eq!(ocred.regions.len(), 1);
+        assert_eq!(ocred.regions[0].lifecycle.id, 0);
+        assert_eq!(ocred.regions[0].response.texts[0].text, "80px");
+        assert_eq!(ocred.timings.unwrap().ocr_calls, 1);
+    }
+
+    /// Advertises batching and records how it was called.
+    struct BatchingEngine {
+        batch_sizes: Mutex<Vec<usize>>,
+    }
+
+    impl OcrEngine for BatchingEngine {
+        fn name(&self) -> &'static str {
+            "batching"
+        }
+
+        fn capabilities(&self) -> OcrCapabilities {
+            OcrCapabilities {
+                supports_batch: true,
+                ..OcrCapabilities::default()
+            }
+        }
+
+        fn recognize(&self, request: &OcrRequest<'_>) -> Result<OcrResponse, OcrError> {
+            self.batch_sizes.lock().unwrap().push(1);
+            let region = request.regions()[0];
+            Ok(OcrResponse::new(vec![OcrText::new(
+                region,
+                format!("at {}", region.x.round()),
+            )]))
+        }
+
+        fn recognize_batch(
+            &self,
+            requests: &[OcrRequest<'_>],
+        ) -> Result<Vec<OcrResponse>, OcrError> {
+            self.batch_sizes.lock().unwrap().push(requests.len());
+            requests
+                .iter()
+                .map(|request| {
+                    let region = request.regions()[0];
+                    Ok(OcrResponse::new(vec![OcrText::new(
+                        region,
+                        format!("at {}", region.x.round()),
+                    )]))
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn batching_engine_gets_one_call_per_event() {
+        let frame = blank_frame();
+        let engine = Arc::new(BatchingEngine {
+            batch_sizes: Mutex::new(Vec::new()),
+        });
+        let worker = OcrWorker::new(engine.clone(), false, false);
+        let event = |ids: &[u64]| LifecycleEvent {
+            sample: None,
+            completed: ids
+                .iter()
+                .map(|id| {
+                    let mut region = completed(*id, &frame);
+                    region.roi.x = *id as f32 * 0.2;
+                    region.roi.width = 0.1;
+                    region
+                })
+                .collect(),
+            region_timings: None,
+        };
+
+        let ocred = worker.handle_event(event(&[0, 1, 2])).expect("batch");
+        let texts: Vec<_> = ocred
+            .regions
+            .iter()
+            .map(|subtitle| subtitle.response.texts[0].text.as_str())
+            .collect();
+        assert_eq!(texts, ["at 0", "at 20", "at 40"]);
+        assert_eq!(ocred.timings.unwrap().ocr_calls, 1);
+
+        worker.handle_event(event(&[3])).expect("single");
+        assert_eq!(*engine.batch_sizes.lock().unwrap(), [3, 1]);
+    }
 
     #[test]
     fn roi_to_region_clamps_to_bounds() {
-        let frame = VideoFrame::from_nv12_owned(
-            100,
-            50,
-            100,
-            100,
-            None,
-            None,
-            vec![0; 5000],
-            vec![128; 2500],
-        )
-        .unwrap();
+        let frame = VideoFrame::filled(100, 50, 0).unwrap();
         let roi = RoiConfig {
             x: -0.2,
             y: 0.5,
@@ -0,0 +1,143 @@
+use std::sync::Arc;
+
+use super::sampler::FrameCorrection;
+use subtitle_fast_types::{DecoderResult, FrameBuffer, VideoFrame};
+
+/// Rotates the luma of every frame the pipeline reads about its center, so detection,
+/// comparison and OCR all see the straightened picture. Native-handle frames pass through.
+pub struct FrameRotator {
+    angle_deg: f32,
+}
+
+impl FrameRotator {
+    /// `angle_deg` is counter-clockwise as displayed; zero leaves frames untouched.
+    pub fn new(angle_deg: f32) -> Self {
+        Self { angle_deg }
+    }
+
+    /// The sampler correction applying the rotation, or `None` when frames are kept as decoded.
+    pub fn correction(self) -> Option<FrameCorrection> {
+        if self.angle_deg == 0.0 {
+            return None;
+        }
+        let angle_deg = self.angle_deg;
+        Some(Arc::new(move |frame: &VideoFrame| {
+            rotate_frame(frame, angle_deg)
+        }))
+    }
+}
+
+/// Returns a tightly packed copy of `frame` rotated by `angle_deg`. Uncovered corners are black
+/// and the chroma plane is reset to neutral, since only luma feeds detection and OCR.
+pub(crate) fn rotate_frame(frame: &VideoFrame, angle_deg: f32) -> DecoderResult<VideoFrame> {
+    if !matches!(frame.buffer(), FrameBuffer::Nv12(_)) {
+        return Ok(frame.clone());
+    }
+    let width = frame.width() as usize;
+    let height = frame.height() as usize;
+    let stride = frame.stride();
+    let source = frame.data();
+    let (sin, cos) = angle_deg.to_radians().sin_cos();
+    let (cx, cy) = ((width as f32 - 1.0) / 2.0, (height as f32 - 1.0) / 2.0);
+
+    let sample = |x: isize, y: isize| -> f32 {
+        if x < 0 || y < 0 || x as usize >= width || y as usize >= height {
+            return 0.0;
+        }
+        f32::from(source[y as usize * stride + x as usize])
+    };
+
+    let mut y_plane = vec![0u8; width * height];
+    for (row, out) in y_plane.chunks_exact_mut(width.max(1)).enumerate() {
+        let dy = row as f32 - cy;
+        for (col, value) in out.iter_mut().enumerate() {
+            let dx = col as f32 - cx;
+            // Inverse mapping: find where this output pixel came from in the source.
+            let sx = cx + dx * cos - dy * sin;
+            let sy = cy + dx * sin + dy * cos;
+            let (x0, y0) = (sx.floor(), sy.floor());
+            let (fx, fy) = (sx - x0, sy - y0);
+            let (x0, y0) = (x0 as isize, y0 as isize);
+            let top = sample(x0, y0) * (1.0 - fx) + sample(x0 + 1, y0) * fx;
+            let bottom = sample(x0, y0 + 1) * (1.0 - fx) + sample(x0 + 1, y0 + 1) * fx;
+            *value = (top * (1.0 - fy) + bottom * fy).round().clamp(0.0, 255.0) as u8;
+        }
+    }
+
+    let uv_plane = vec![128u8; width * height.div_ceil(2)];
+    let rotated = VideoFrame::from_nv12_owned(
+        frame.width(),
+        frame.height(),
+        width,
+        width,
+        frame.pts(),
+        frame.dts(),
+        y_plane,
+        uv_plane,
+    )?;
+    Ok(rotated
+        .with_index(frame.index())
+        .with_serial(frame.serial()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use subtitle_fast_validator::{FrameValidator, FrameValidatorConfig, SubtitleDetectionOptions};
+
+    const WIDTH: usize = 640;
+    const HEIGHT: usize = 240;
+
+    /// A bright 420x24 "text line" with glyph-like gaps, centered and tilted counter-clockwise.
+    fn tilted_band(angle_deg: f32) -> VideoFrame {
+        let mut y_plane = vec![0u8; WIDTH * HEIGHT];
+        for y in 108..132 {
+            for x in 110..530 {
+                if x % 12 < 9 {
+                    y_plane[y * WIDTH + x] = 230;
+                }
+            }
+        }
+        let level = VideoFrame::from_nv12_owned(
+            WIDTH as u32,
+            HEIGHT as u32,
+            WIDTH,
+            WIDTH,
+            None,
+            None,
+            y_plane,
+            vec![128u8; WIDTH * HEIGHT / 2],
+        )
+        .expect("frame");
+        rotate_frame(&level, angle_deg).expect("rotated frame")
+    }
+
+    async fn tallest_region(frame: VideoFrame) -> Option<f32> {
+        let config = FrameValidatorConfig {
+            detection: SubtitleDetectionOptions::default(),
+        };
+        let validator = FrameValidator::new(config).expect("validator");
+        let detection = validator.process_frame(frame).await.expect("detection");
+        detection
+            .regions
+            .iter()
+            .map(|region| region.height)
+            .reduce(f32::max)
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn compensating_rotation_restores_a_flat_band() {
+        let tilted = tilted_band(8.0);
+        let before = tallest_region(tilted.clone()).await;
+        let straightened = rotate_frame(&tilted, -8.0).expect("rotated frame");
+        let after = tallest_region(straightened).await.expect("band detected");
+
+        // Line height plus the validator's 5px margin on each side, with a little slack for
+        // the resampled edges.
+        assert!(after < 40.0, "straightened band height {after}");
+        assert!(
+            before.is_none_or(|height| height > 60.0),
+            "tilted band height {before:?}"
+        );
+    }
+}
@@ -1,12 +1,14 @@
 use std::collections::VecDeque;
-use std::sync::Arc;
+use std::fmt;
+use std::hash::{DefaultHasher, Hasher};
+use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 
 use futures_util::{StreamExt, stream::unfold};
-use tokio::sync::mpsc;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore, mpsc};
 
 use super::StreamBundle;
-use subtitle_fast_types::{DecoderError, DecoderResult, VideoFrame};
+use subtitle_fast_types::{DecoderError, DecoderResult, RoiConfig, VideoFrame};
 
 const SAMPLER_CHANNEL_CAPACITY: usize = 1;
 const DEFAULT_POOL_CAPACITY: usize = 24;
@@ -15,6 +17,24 @@ const EPSILON: f64 = 1e-6;
 
 pub type SamplerResult = Result<SampledFrame, DecoderError>;
 
+/// Correction of a decoded frame, such as luma range expansion or rotation. The sampler runs it
+/// only on frames the pipeline reads: each sampled frame, and a skipped frame the first time a
+/// later stage looks it up in the history, so frames nobody reads are never copied.
+pub type FrameCorrection = Arc<dyn Fn(&VideoFrame) -> DecoderResult<VideoFrame> + Send + Sync>;
+
+/// `first` followed by `second`; `None` stands for no correction.
+pub fn chain_corrections(
+    first: Option<FrameCorrection>,
+    second: Option<FrameCorrection>,
+) -> Option<FrameCorrection> {
+    match (first, second) {
+        (Some(first), Some(second)) => {
+            Some(Arc::new(move |frame: &VideoFrame| second(&first(frame)?)))
+        }
+        (first, second) => first.or(second),
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum FrameType {
     Sampled,
@@ -24,24 +44,68 @@ pub enum FrameType {
 #[derive(Debug, Clone)]
 pub struct SamplerContext {
     estimated_fps: Option<f64>,
+    container_fps: Option<f64>,
 }
 
 impl SamplerContext {
-    fn initial() -> Self {
+    pub(crate) fn initial(container_fps: Option<f64>) -> Self {
         Self {
             estimated_fps: None,
+            container_fps,
         }
     }
 
-    fn with_estimate(estimated_fps: f64) -> Self {
+    fn with_estimate(estimated_fps: f64, container_fps: Option<f64>) -> Self {
         Self {
             estimated_fps: Some(estimated_fps),
+            container_fps,
         }
     }
 
     pub fn estimated_fps(&self) -> Option<f64> {
         self.estimated_fps
     }
+
+    /// Frame rate reported by the decoder metadata, if any.
+    pub fn container_fps(&self) -> Option<f64> {
+        self.container_fps
+    }
+
+    /// Best known frame rate: the container value when present, else the timestamp estimate.
+    pub fn frame_rate(&self) -> Option<f64> {
+        self.container_fps.or(self.estimated_fps)
+    }
+}
+
+/// Bounds the number of sampled frames travelling between the sampler and the OCR output.
+#[derive(Clone)]
+pub struct InFlightLimiter {
+    semaphore: Arc<Semaphore>,
+    limit: usize,
+}
+
+impl InFlightLimiter {
+    pub fn new(limit: usize) -> Self {
+        let limit = limit.max(1);
+        Self {
+            semaphore: Arc::new(Semaphore::new(limit)),
+            limit,
+        }
+    }
+
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Number of frames currently holding a permit.
+    pub fn in_flight(&self) -> usize {
+        self.limit
+            .saturating_sub(self.semaphore.available_permits())
+    }
+
+    async fn acquire(&self) -> Option<OwnedSemaphorePermit> {
+        Arc::clone(&self.semaphore).acquire_owned().await.ok()
+    }
 }
 
 pub struct SampledFrame {
@@ -49,10 +113,12 @@ pub struct SampledFrame {
     frame: Arc<VideoFrame>,
     history: FrameHistory,
     context: Arc<SamplerContext>,
+    permit: Option<OwnedSemaphorePermit>,
+    roi_hash: Option<u64>,
 }
 
 impl SampledFrame {
-    fn new(
+    pub(crate) fn new(
         frame_index: u64,
         frame: Arc<VideoFrame>,
         history: FrameHistory,
@@ -63,9 +129,26 @@ impl SampledFrame {
             frame,
             history,
             context,
+            permit: None,
+            roi_hash: None,
         }
     }
 
+    fn with_roi_hash(mut self, roi_hash: Option<u64>) -> Self {
+        self.roi_hash = roi_hash;
+        self
+    }
+
+    fn with_permit(mut self, permit: Option<OwnedSemaphorePermit>) -> Self {
+        self.permit = permit;
+        self
+    }
+
+    /// Releases the in-flight slot held by this frame, if any.
+    pub fn release_in_flight(&mut self) {
+        self.permit = None;
+    }
+
     pub fn frame(&self) -> &VideoFrame {
         self.frame.as_ref()
     }
@@ -85,15 +168,63 @@ impl SampledFrame {
     pub fn sampler_context(&self) -> &SamplerContext {
         &self.context
     }
+
+    /// Hash of the luma pixels inside the detection ROI; equal hashes mean identical pixels.
+    pub fn roi_hash(&self) -> Option<u64> {
+        self.roi_hash
+    }
 }
 
 pub struct FrameSampler {
     samples_per_second: u32,
+    limiter: Option<InFlightLimiter>,
+    frame_rate: Option<f64>,
+    roi: Option<RoiConfig>,
+    start_offset: Duration,
+    correction: Option<FrameCorrection>,
 }
 
 impl FrameSampler {
     pub fn new(samples_per_second: u32) -> Self {
-        Self { samples_per_second }
+        Self {
+            samples_per_second,
+            limiter: None,
+            frame_rate: None,
+            roi: None,
+            start_offset: Duration::ZERO,
+            correction: None,
+        }
+    }
+
+    /// Corrects the frames samples and their history hand out; `None` passes them as decoded.
+    pub fn with_correction(mut self, correction: Option<FrameCorrection>) -> Self {
+        self.correction = correction;
+        self
+    }
+
+    /// Discards frames presented before `offset`, so neither samples nor their history see
+    /// them. Frames without a timestamp are placed by index and the metadata frame rate, and
+    /// are kept when neither is known.
+    pub fn with_start_offset(mut self, offset: Duration) -> Self {
+        self.start_offset = offset;
+        self
+    }
+
+    /// Fractional detection ROI hashed for each sample; `None` hashes the whole frame.
+    pub fn with_roi(mut self, roi: Option<RoiConfig>) -> Self {
+        self.roi = roi;
+        self
+    }
+
+    /// Frame rate from the decoder metadata, used when frames lack timestamps.
+    pub fn with_frame_rate(mut self, frame_rate: Option<f64>) -> Self {
+        self.frame_rate = frame_rate;
+        self
+    }
+
+    pub fn with_limiter(mut self, limiter: Option<InFlightLimiter>) -> Self {
+        self.limiter = limiter;
+        self
     }
 }
 
@@ -108,11 +239,21 @@ impl FrameSampler {
         } = input;
 
         let samples_per_second = self.samples_per_second;
+        let limiter = self.limiter;
+        let frame_rate = self.frame_rate;
+        let roi = self.roi;
+        let start_offset = self.start_offset;
+        let correction = self.correction;
         let (tx, rx) = mpsc::channel::<SamplerResult>(SAMPLER_CHANNEL_CAPACITY);
 
         tokio::spawn(async move {
             let mut upstream = stream;
-            let mut worker = SamplerWorker::new(samples_per_second);
+            let mut worker = SamplerWorker::new(samples_per_second)
+                .with_limiter(limiter)
+                .with_frame_rate(frame_rate)
+                .with_roi(roi)
+                .with_start_offset(start_offset)
+                .with_correction(correction);
 
             while let Some(maybe_item) = upstream.next().await {
                 match maybe_item {
@@ -146,6 +287,10 @@ struct SamplerWorker {
     fps: FpsEstimator,
     context: Arc<SamplerContext>,
     last_sampled_index: Option<u64>,
+    limiter: Option<InFlightLimiter>,
+    roi: Option<RoiConfig>,
+    start_offset: Duration,
+    correction: Option<FrameCorrection>,
 }
 
 impl SamplerWorker {
@@ -155,8 +300,56 @@ impl SamplerWorker {
             pool: SamplerPool::new(DEFAULT_POOL_CAPACITY),
             schedule: SampleSchedule::new(samples_per_second),
             fps: FpsEstimator::new(),
-            context: Arc::new(SamplerContext::initial()),
+            context: Arc::new(SamplerContext::initial(None)),
             last_sampled_index: None,
+            limiter: None,
+            roi: None,
+            start_offset: Duration::ZERO,
+            correction: None,
+        }
+    }
+
+    fn with_correction(mut self, correction: Option<FrameCorrection>) -> Self {
+        self.correction = correction;
+        self
+    }
+
+    fn with_start_offset(mut self, offset: Duration) -> Self {
+        self.start_offset = offset;
+        self
+    }
+
+    fn before_start(&self, frame: &VideoFrame) -> bool {
+        if self.start_offset.is_zero() {
+            return false;
+        }
+        let presented = frame.pts().or_else(|| {
+            let fps = self.context.container_fps()?;
+            Some(Duration::from_secs_f64(frame.index()? as f64 / fps))
+        });
+        presented.is_some_and(|time| time < self.start_offset)
+    }
+
+    fn with_roi(mut self, roi: Option<RoiConfig>) -> Self {
+        self.roi = roi;
+        self
+    }
+
+    fn with_limiter(mut self, limiter: Option<InFlightLimiter>) -> Self {
+        self.limiter = limiter;
+        self
+    }
+
+    fn with_frame_rate(mut self, frame_rate: Option<f64>) -> Self {
+        let frame_rate = frame_rate.filter(|fps| fps.is_finite() && *fps > 0.0);
+        self.context = Arc::new(SamplerContext::initial(frame_rate));
+        self
+    }
+
+    async fn acquire_permit(&self) -> Result<Option<OwnedSemaphorePermit>, ()> {
+        match &self.limiter {
+            Some(limiter) => limiter.acquire().await.map(Some).ok_or(()),
+            None => Ok(None),
         }
     }
 
@@ -165,6 +358,9 @@ impl SamplerWorker {
         frame: VideoFrame,
         tx: &mpsc::Sender<SamplerResult>,
     ) -> Result<(), ()> {
+        if self.before_start(&frame) {
+            return Ok(());
+        }
         self.processed = self.processed.saturating_add(1);
         let processed_index = self.processed;
 
@@ -183,12 +379,9 @@ impl SamplerWorker {
             self.update_tuning(fps);
         }
 
-        let frame_arc = Arc::new(frame);
-        self.pool.push(PoolEntry::new(
-            frame_index,
-            frame_type,
-            Arc::clone(&frame_arc),
-        ));
+        let pooled = PooledFrame::new(Arc::new(frame), self.correction.clone());
+        self.pool
+            .push(PoolEntry::new(frame_index, frame_type, pooled.clone()));
 
         let history = if matches!(frame_type, FrameType::Sampled) {
             Some(self.pool.snapshot())
@@ -197,7 +390,18 @@ impl SamplerWorker {
         };
 
         if let Some(history) = history {
-            let sample = SampledFrame::new(frame_index, frame_arc, history, self.context.clone());
+            let frame_arc = match pooled.corrected() {
+                Ok(frame) => frame,
+                Err(err) => {
+                    let _ = tx.send(Err(err)).await;
+                    return Err(());
+                }
+            };
+            let permit = self.acquire_permit().await?;
+            let roi_hash = roi_luma_hash(&frame_arc, self.roi);
+            let sample = SampledFrame::new(frame_index, frame_arc, history, self.context.clone())
+                .with_roi_hash(roi_hash)
+                .with_permit(permit);
             if tx.send(Ok(sample)).await.is_err() {
                 return Err(());
             }
@@ -214,15 +418,24 @@ impl SamplerWorker {
         if self.last_sampled_index == Some(latest.frame_index) {
             return;
         }
+        let frame_index = latest.frame_index;
+        let frame = match latest.frame.corrected() {
+            Ok(frame) => frame,
+            Err(err) => {
+                let _ = tx.send(Err(err)).await;
+                return;
+            }
+        };
         let history = self.pool.snapshot();
-        let sample = SampledFrame::new(
-            latest.frame_index,
-            latest.frame_handle(),
-            history,
-            self.context.clone(),
-        );
+        let Ok(permit) = self.acquire_permit().await else {
+            return;
+        };
+        let roi_hash = roi_luma_hash(&frame, self.roi);
+        let sample = SampledFrame::new(frame_index, frame, history, self.context.clone())
+            .with_roi_hash(roi_hash)
+            .with_permit(permit);
         let _ = tx.send(Ok(sample)).await;
-        self.last_sampled_index = Some(latest.frame_index);
+        self.last_sampled_index = Some(frame_index);
     }
 
     fn update_tuning(&mut self, fps: f64) {
@@ -241,8 +454,43 @@ impl SamplerWorker {
             capacity = MAX_POOL_CAPACITY;
         }
         self.pool.set_capacity(capacity);
-        self.context = Arc::new(SamplerContext::with_estimate(fps));
+        self.context = Arc::new(SamplerContext::with_estimate(
+            fps,
+            self.context.container_fps(),
+        ));
+    }
+}
+
+/// Hashes the luma rows covered by `roi`, or `None` when the frame has no CPU-side pixels.
+fn roi_luma_hash(frame: &VideoFrame, roi: Option<RoiConfig>) -> Option<u64> {
+    let width = frame.width() as usize;
+    let height = frame.height() as usize;
+    let plane = frame.y_plane();
+    let stride = frame.y_stride();
+    if width == 0 || height == 0 || stride < width || plane.len() < stride * (height - 1) + width {
+        return None;
+    }
+
+    let (x0, y0, x1, y1) = roi
+        .map(|roi| {
+            let span = |start: f32, len: f32, size: usize| {
+                let lo = (start * size as f32).floor().clamp(0.0, size as f32) as usize;
+                let hi = ((start + len) * size as f32).ceil().clamp(0.0, size as f32) as usize;
+                (lo, hi)
+            };
+            let (x0, x1) = span(roi.x, roi.width, width);
+            let (y0, y1) = span(roi.y, roi.height, height);
+            (x0, y0, x1, y1)
+        })
+        .filter(|&(x0, y0, x1, y1)| x1 > x0 && y1 > y0)
+        .unwrap_or((0, 0, width, height));
+
+    let mut hasher = DefaultHasher::new();
+    for row in y0..y1 {
+        let start = row * stride;
+        hasher.write(&plane[start + x0..start + x1]);
     }
+    Some(hasher.finish())
 }
 
 struct SamplerPool {
@@ -279,7 +527,7 @@ impl SamplerPool {
             records.push(HistoryRecord {
                 frame_index: entry.frame_index,
                 frame_type: entry.frame_type,
-                frame: Arc::clone(&entry.frame),
+                frame: entry.frame.clone(),
             });
         }
         FrameHistory::new(records)
@@ -411,20 +659,68 @@ struct FpsObservation {
 struct PoolEntry {
     frame_index: u64,
     frame_type: FrameType,
-    frame: Arc<VideoFrame>,
+    frame: PooledFrame,
 }
 
 impl PoolEntry {
-    fn new(frame_index: u64, frame_type: FrameType, frame: Arc<VideoFrame>) -> Self {
+    fn new(frame_index: u64, frame_type: FrameType, frame: PooledFrame) -> Self {
         Self {
             frame_index,
             frame_type,
             frame,
         }
     }
+}
 
-    fn frame_handle(&self) -> Arc<VideoFrame> {
-        Arc::clone(&self.frame)
+/// A decoded frame whose correction runs the first time it is read, shared by every history
+/// snapshot holding the frame.
+#[derive(Clone)]
+struct PooledFrame {
+    decoded: Arc<VideoFrame>,
+    correction: Option<FrameCorrection>,
+    corrected: Arc<OnceLock<Arc<VideoFrame>>>,
+}
+
+impl PooledFrame {
+    fn new(decoded: Arc<VideoFrame>, correction: Option<FrameCorrection>) -> Self {
+        Self {
+            decoded,
+            correction,
+            corrected: Arc::new(OnceLock::new()),
+        }
+    }
+
+    fn corrected(&self) -> DecoderResult<Arc<VideoFrame>> {
+        let Some(correction) = &self.correction else {
+            return Ok(Arc::clone(&self.decoded));
+        };
+        if let Some(frame) = self.corrected.get() {
+            return Ok(Arc::clone(frame));
+        }
+        let frame = Arc::new(correction(&self.decoded)?);
+        Ok(Arc::clone(self.corrected.get_or_init(|| frame)))
+    }
+
+    /// Like [`corrected`](Self::corrected), falling back to the decoded frame if the correction
+    /// fails; history lookups have no way to report the error.
+    fn frame(&self) -> &Arc<VideoFrame> {
+        let Some(correction) = &self.correction else {
+            return &self.decoded;
+        };
+        self.corrected.get_or_init(|| {
+            correction(&self.decoded)
+                .map(Arc::new)
+                .unwrap_or_else(|_| Arc::clone(&self.decoded))
+        })
+    }
+}
+
+impl fmt::Debug for PooledFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PooledFrame")
+            .field("decoded", &self.decoded)
+            .field("corrected", &self.corrected.get().is_some())
+            .finish_non_exhaustive()
     }
 }
 
@@ -449,22 +745,37 @@ impl FrameHistory {
 pub struct HistoryRecord {
     pub frame_index: u64,
     pub frame_type: FrameType,
-    frame: Arc<VideoFrame>,
+    frame: PooledFrame,
 }
 
 impl HistoryRecord {
+    #[cfg(test)]
+    pub(crate) fn new(frame_index: u64, frame_type: FrameType, frame: Arc<VideoFrame>) -> Self {
+        Self {
+            frame_index,
+            frame_type,
+            frame: PooledFrame::new(frame, None),
+        }
+    }
+
     pub fn frame(&self) -> &VideoFrame {
-        &self.frame
+        self.frame.frame()
     }
 
     pub fn frame_handle(&self) -> Arc<VideoFrame> {
-        Arc::clone(&self.frame)
+        Arc::clone(self.frame.frame())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::stage::detector::DetectionSample;
+    use crate::stage::lifecycle::{CompletedRegion, LifecycleEvent};
+    use crate::stage::ocr::SubtitleOcr;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use subtitle_fast_ocr::{OcrEngine, OcrError, OcrRequest};
+    use subtitle_fast_types::{OcrResponse, SubtitleDetectionResult};
 
     #[tokio::test(flavor = "current_thread")]
     async fn sampled_history_includes_current_frame() {
@@ -502,6 +813,58 @@ mod tests {
         assert!(has_current, "sample history should include current frame");
     }
 
+    #[tokio::test(flavor = "current_thread")]
+    async fn correction_runs_only_on_frames_that_are_read() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counted = Arc::clone(&calls);
+        let brighten: FrameCorrection = Arc::new(move |frame: &VideoFrame| {
+            counted.fetch_add(1, Ordering::SeqCst);
+            Ok(VideoFrame::from_nv12_owned(
+                2,
+                2,
+                2,
+                2,
+                frame.pts(),
+                None,
+                vec![255; 4],
+                vec![128; 2],
+            )?
+            .with_index(frame.index()))
+        });
+        let mut worker = SamplerWorker::new(1).with_correction(Some(brighten));
+        let (tx, mut rx) = mpsc::channel(4);
+        for millis in [0, 250, 500, 750, 1000] {
+            let frame = VideoFrame::from_nv12_owned(
+                2,
+                2,
+                2,
+                2,
+                Some(Duration::from_millis(millis)),
+                None,
+                vec![16; 4],
+                vec![128; 2],
+            )
+            .expect("frame");
+            worker
+                .handle_frame(frame, &tx)
+                .await
+                .expect("frame handled");
+        }
+
+        let first = rx.recv().await.expect("first sample").expect("sample");
+        let second = rx.recv().await.expect("second sample").expect("sample");
+        assert_eq!(first.frame().y_plane()[0], 255);
+        assert_eq!(second.frame().y_plane()[0], 255);
+        // The three skipped frames in between were never read.
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let skipped = &second.history().records()[1];
+        assert!(matches!(skipped.frame_type, FrameType::Skipped));
+        assert_eq!(skipped.frame().y_plane()[0], 255);
+        assert_eq!(skipped.frame().y_plane()[0], 255);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
     #[tokio::test(flavor = "current_thread")]
     async fn final_sample_emitted_for_unsampled_tail() {
         let mut worker = SamplerWorker::new(1);
@@ -558,4 +921,134 @@ mod tests {
             "final history should include latest frame"
         );
     }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn frames_before_the_start_offset_are_never_sampled() {
+        let frames: Vec<DecoderResult<VideoFrame>> = (0..8u64)
+            .map(|idx| {
+                VideoFrame::from_nv12_owned(
+                    2,
+                    2,
+                    2,
+                    2,
+                    Some(Duration::from_millis(idx * 500)),
+                    None,
+                    vec![idx as u8; 4],
+                    vec![128; 2],
+                )
+                .map(|frame| frame.with_index(Some(idx)))
+            })
+            .collect();
+        let input = StreamBundle::new(Box::pin(futures_util::stream::iter(frames)), None);
+        let mut sampled = FrameSampler::new(2)
+            .with_start_offset(Duration::from_millis(1500))
+            .attach(input)
+            .stream;
+
+        let mut indices = Vec::new();
+        while let Some(sample) = sampled.next().await {
+            let sample = sample.expect("sample");
+            assert!(
+                sample
+                    .history()
+                    .records()
+                    .iter()
+                    .all(|record| record.frame_index >= 3),
+                "history reaches back before the offset"
+            );
+            indices.push(sample.frame_index());
+        }
+        assert_eq!(indices.first(), Some(&3));
+        assert!(indices.iter().all(|index| *index >= 3), "{indices:?}");
+    }
+
+    /// Takes a while per call and records how many sampled frames held a permit meanwhile.
+    struct SlowEngine {
+        limiter: InFlightLimiter,
+        peak: AtomicUsize,
+    }
+
+    impl OcrEngine for SlowEngine {
+        fn name(&self) -> &'static str {
+            "slow"
+        }
+
+        fn recognize(&self, _request: &OcrRequest<'_>) -> Result<OcrResponse, OcrError> {
+            std::thread::sleep(Duration::from_millis(5));
+            self.peak
+                .fetch_max(self.limiter.in_flight(), Ordering::Relaxed);
+            Ok(OcrResponse::new(Vec::new()))
+        }
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn limiter_bounds_frames_in_flight() {
+        const LIMIT: usize = 2;
+        let limiter = InFlightLimiter::new(LIMIT);
+        let frames: Vec<DecoderResult<VideoFrame>> = (0..12u64)
+            .map(|idx| {
+                VideoFrame::from_nv12_owned(
+                    8,
+                    8,
+                    8,
+                    8,
+                    Some(Duration::from_millis(idx * 1000)),
+                    None,
+                    vec![idx as u8; 64],
+                    vec![128; 32],
+                )
+            })
+            .collect();
+        let input = StreamBundle::new(Box::pin(futures_util::stream::iter(frames)), None);
+        let sampled = FrameSampler::new(1)
+            .with_limiter(Some(limiter.clone()))
+            .attach(input);
+        let events = sampled.stream.map(|sample| {
+            let sample = sample.expect("sample");
+            let region = CompletedRegion {
+                id: sample.frame_index(),
+                label: String::new(),
+                start_time: Duration::ZERO,
+                end_time: Duration::from_secs(1),
+                start_frame: sample.frame_index(),
+                end_frame: sample.frame_index(),
+                roi: RoiConfig {
+                    x: 0.0,
+                    y: 0.0,
+                    width: 1.0,
+                    height: 1.0,
+                },
+                frame: sample.frame_handle(),
+            };
+            Ok(LifecycleEvent {
+                sample: Some(DetectionSample {
+                    sample,
+                    detection: SubtitleDetectionResult::empty(),
+                    elapsed: Duration::ZERO,
+                    reused: false,
+                }),
+                completed: vec![region],
+                region_timings: None,
+            })
+        });
+        let engine = Arc::new(SlowEngine {
+            limiter: limiter.clone(),
+            peak: AtomicUsize::new(0),
+        });
+        let mut recognized = SubtitleOcr::new(engine.clone())
+            .attach(StreamBundle::new(Box::pin(events), None))
+            .stream;
+
+        let mut received = 0;
+        while let Some(event) = recognized.next().await {
+            event.expect("recognition succeeds");
+            assert!(limiter.in_flight() <= LIMIT, "limiter exceeded its bound");
+            received += 1;
+        }
+
+        assert_eq!(received, 12);
+        // The sampler ran ahead of the slow engine until the limiter stopped it.
+        assert_eq!(engine.peak.load(Ordering::Relaxed), LIMIT);
+        assert_eq!(limiter.in_flight(), 0);
+    }
 }
@@ -1,10 +1,36 @@
 use std::fmt::Write as _;
 use std::time::Duration;
 
+use subtitle_fast_types::RoiConfig;
+
+/// Unicode RIGHT-TO-LEFT MARK, prefixed to RTL lines so players pick the right base direction.
+const RTL_MARK: char = '\u{200F}';
+/// Cue text written for intervals without any detected subtitle.
+const GAP_MARKER_TEXT: &str = "[no subtitle]";
+/// Script header for ASS output: one bottom-centered white style named `Default`.
+const ASS_HEADER: &str = "[Script Info]
+ScriptType: v4.00+
+WrapStyle: 0
+ScaledBorderAndShadow: yes
+
+[V4+ Styles]
+Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding
+Style: Default,Arial,20,&H00FFFFFF,&H000000FF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,1,2,0,2,10,10,10,1
+
+[Events]
+Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text
+";
+
 #[derive(Clone, Debug)]
 pub struct SubtitleLine {
     pub center: f32,
     pub text: String,
+    /// Whether the line was assembled in right-to-left reading order.
+    pub rtl: bool,
+    /// Mean OCR confidence of the fragments forming the line, when the engine reports one.
+    pub confidence: Option<f32>,
+    /// Detected region the line was read from, normalized to the frame.
+    pub region: Option<RoiConfig>,
 }
 
 #[derive(Clone, Debug)]
@@ -13,24 +39,39 @@ pub struct MergedSubtitle {
     pub start_time: Duration,
     pub end_time: Duration,
     pub start_frame: u64,
+    pub end_frame: u64,
     pub lines: Vec<SubtitleLine>,
 }
 
+/// Interval of the stream during which no subtitle was detected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SubtitleGap {
+    pub start_time: Duration,
+    pub end_time: Duration,
+}
+
 #[derive(Clone, Debug)]
 pub struct TimedSubtitle {
     pub id: u64,
     pub start_ms: f64,
     pub end_ms: f64,
     pub lines: Vec<String>,
+    /// OCR confidence of each entry in `lines`; empty when unknown, e.g. for parsed SRT files.
+    pub confidences: Vec<Option<f32>>,
 }
 
 impl MergedSubtitle {
     pub fn as_timed(&self) -> TimedSubtitle {
+        let lines = ordered_lines(&self.lines);
         TimedSubtitle {
             id: self.id,
             start_ms: self.start_time.as_secs_f64() * 1000.0,
             end_ms: self.end_time.as_secs_f64() * 1000.0,
-            lines: ordered_lines(&self.lines),
+            confidences: lines.iter().map(|line| line.confidence).collect(),
+            lines: lines
+                .into_iter()
+                .map(|line| line.text.trim().to_string())
+                .collect(),
         }
     }
 }
@@ -48,14 +89,164 @@ pub fn sort_subtitles(subtitles: &mut [MergedSubtitle]) {
     });
 }
 
+/// Output hygiene applied to a track before it is rendered, whether a run writes it or the GUI
+/// exports it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CueLayout {
+    /// See [`enforce_min_gap`]; `None` leaves cue times as merged.
+    pub min_cue_gap: Option<Duration>,
+    /// See [`wrap_lines`]; `None` keeps lines as recognized.
+    pub wrap_width: Option<usize>,
+}
+
+impl CueLayout {
+    /// Sorts `subtitles` into output order, then separates touching cues and wraps long lines.
+    pub fn apply(&self, subtitles: &mut [MergedSubtitle]) {
+        sort_subtitles(subtitles);
+        if let Some(gap) = self.min_cue_gap {
+            enforce_min_gap(subtitles, gap);
+        }
+        if let Some(width) = self.wrap_width {
+            wrap_lines(subtitles, width);
+        }
+    }
+}
+
+/// Pulls back the end of every sorted cue that ends less than `min_gap` before the next one
+/// starts, so players never show two cues at once on a shared frame. Overlapping cues, such
+/// as two regions on screen together, are left alone, as is a cue the nudge would end before
+/// it starts.
+pub fn enforce_min_gap(subtitles: &mut [MergedSubtitle], min_gap: Duration) {
+    for idx in 1..subtitles.len() {
+        let next_start = subtitles[idx].start_time;
+        let cue = &mut subtitles[idx - 1];
+        if cue.end_time > next_start || next_start - cue.end_time >= min_gap {
+            continue;
+        }
+        if let Some(end) = next_start.checked_sub(min_gap)
+            && end > cue.start_time
+        {
+            cue.end_time = end;
+        }
+    }
+}
+
+/// Wraps every line of every cue to at most `width` characters with [`wrap_text`], for players
+/// that do not wrap long lines themselves.
+pub fn wrap_lines(subtitles: &mut [MergedSubtitle], width: usize) {
+    for cue in subtitles {
+        for line in &mut cue.lines {
+            line.text = wrap_text(&line.text, width);
+        }
+    }
+}
+
+/// Breaks each line of `text` longer than `width` characters at spaces, filling every line as
+/// far as it goes. A run without spaces, such as CJK text, is cut after `width` characters.
+pub fn wrap_text(text: &str, width: usize) -> String {
+    let width = width.max(1);
+    let mut wrapped = Vec::new();
+    for line in text.lines() {
+        if line.chars().count() <= width {
+            wrapped.push(line.to_string());
+            continue;
+        }
+        let mut current = String::new();
+        let mut current_len = 0;
+        for word in line.split_whitespace() {
+            let mut chars: Vec<char> = word.chars().collect();
+            if current_len > 0 && current_len + 1 + chars.len() <= width {
+                current.push(' ');
+                current.extend(&chars);
+                current_len += 1 + chars.len();
+                continue;
+            }
+            if current_len > 0 {
+                wrapped.push(std::mem::take(&mut current));
+            }
+            while chars.len() > width {
+                wrapped.push(chars.drain(..width).collect());
+            }
+            current_len = chars.len();
+            current = chars.into_iter().collect();
+        }
+        if current_len > 0 {
+            wrapped.push(current);
+        }
+    }
+    wrapped.join("\n")
+}
+
+/// Splits `subtitle` at `at` into a cue ending there and a cue starting there, both keeping
+/// every line for manual editing; the second still carries the original id until it is
+/// stored under one of its own. The frame range is divided in proportion to time. `None`
+/// when `at` is not strictly inside the cue.
+pub fn split_subtitle(
+    subtitle: &MergedSubtitle,
+    at: Duration,
+) -> Option<(MergedSubtitle, MergedSubtitle)> {
+    if at <= subtitle.start_time || at >= subtitle.end_time {
+        return None;
+    }
+    let fraction = (at - subtitle.start_time).as_secs_f64()
+        / (subtitle.end_time - subtitle.start_time).as_secs_f64();
+    let frames = subtitle.end_frame.saturating_sub(subtitle.start_frame);
+    let split_frame = subtitle.start_frame + (frames as f64 * fraction).round() as u64;
+
+    let mut first = subtitle.clone();
+    first.end_time = at;
+    first.end_frame = split_frame;
+    let mut second = subtitle.clone();
+    second.start_time = at;
+    second.start_frame = split_frame;
+    Some((first, second))
+}
+
+/// Combines two cues into one spanning both, keeping the id of the one that starts first.
+/// Its lines come first; a line repeating the text of the line before it is dropped.
+pub fn merge_subtitles(a: &MergedSubtitle, b: &MergedSubtitle) -> MergedSubtitle {
+    let (earlier, later) = if (b.start_time, b.start_frame) < (a.start_time, a.start_frame) {
+        (b, a)
+    } else {
+        (a, b)
+    };
+    let mut lines: Vec<SubtitleLine> = Vec::with_capacity(earlier.lines.len() + later.lines.len());
+    for line in earlier.lines.iter().chain(&later.lines) {
+        if lines
+            .last()
+            .is_some_and(|last| last.text.trim() == line.text.trim())
+        {
+            continue;
+        }
+        lines.push(line.clone());
+    }
+    MergedSubtitle {
+        id: earlier.id,
+        start_time: earlier.start_time,
+        end_time: earlier.end_time.max(later.end_time),
+        start_frame: earlier.start_frame,
+        end_frame: earlier.end_frame.max(later.end_frame),
+        lines,
+    }
+}
+
 pub fn render_srt(subtitles: &[MergedSubtitle]) -> String {
+    render_srt_where(subtitles, |_| true)
+}
+
+/// Like [`render_srt`], but writes only the cues `keep` accepts, each numbered as it is in the
+/// full track.
+pub fn render_srt_where(
+    subtitles: &[MergedSubtitle],
+    keep: impl Fn(&MergedSubtitle) -> bool,
+) -> String {
     let mut output = String::new();
     for (idx, cue) in subtitles.iter().enumerate() {
         let lines = ordered_lines(&cue.lines);
-        if lines.is_empty() {
+        if lines.is_empty() || !keep(cue) {
             continue;
         }
-        if idx > 0 {
+        if !output.is_empty() {
             output.push('\n');
         }
         let _ = writeln!(&mut output, "{}", idx + 1);
@@ -66,41 +257,464 @@ pub fn render_srt(subtitles: &[MergedSubtitle]) -> String {
             format_timestamp(cue.end_time)
         );
         for line in lines {
-            let _ = writeln!(&mut output, "{line}");
+            for text in line.text.trim().lines() {
+                if line.rtl {
+                    let _ = writeln!(&mut output, "{RTL_MARK}{text}");
+                } else {
+                    let _ = writeln!(&mut output, "{text}");
+                }
+            }
+        }
+    }
+    output
+}
+
+pub fn render_vtt(subtitles: &[MergedSubtitle]) -> String {
+    let mut output = String::from("WEBVTT\n");
+    for cue in subtitles {
+        let lines = ordered_lines(&cue.lines);
+        if lines.is_empty() {
+            continue;
+        }
+        output.push('\n');
+        let _ = writeln!(
+            &mut output,
+            "{} --> {}",
+            format_clock(cue.start_time, '.'),
+            format_clock(cue.end_time, '.')
+        );
+        for line in lines {
+            for text in line.text.trim().lines() {
+                if line.rtl {
+                    let _ = writeln!(&mut output, "{RTL_MARK}{text}");
+                } else {
+                    let _ = writeln!(&mut output, "{text}");
+                }
+            }
         }
     }
     output
 }
 
-fn ordered_lines(lines: &[SubtitleLine]) -> Vec<String> {
+/// Advanced SubStation Alpha script with every cue in the `Default` style; lines are joined
+/// with `\N` hard breaks.
+pub fn render_ass(subtitles: &[MergedSubtitle]) -> String {
+    let mut output = String::from(ASS_HEADER);
+    for cue in subtitles {
+        let lines = ordered_lines(&cue.lines);
+        if lines.is_empty() {
+            continue;
+        }
+        let mut texts = Vec::new();
+        for line in lines {
+            for text in line.text.trim().lines() {
+                if line.rtl {
+                    texts.push(format!("{RTL_MARK}{text}"));
+                } else {
+                    texts.push(text.to_string());
+                }
+            }
+        }
+        let text = texts.join("\\N");
+        let _ = writeln!(
+            &mut output,
+            "Dialogue: 0,{},{},Default,,0,0,0,,{text}",
+            format_ass_timestamp(cue.start_time),
+            format_ass_timestamp(cue.end_time)
+        );
+    }
+    output
+}
+
+/// One JSON object per line for each cue: `index` (1-based, matching the SRT cue number), `id`,
+/// `start_ms`, `end_ms` and `lines` in display order.
+pub fn render_jsonl(subtitles: &[MergedSubtitle]) -> String {
+    render_jsonl_where(subtitles, |_| true)
+}
+
+/// Like [`render_jsonl`], but writes only the cues `keep` accepts; `index` still counts every
+/// cue of the full track.
+pub fn render_jsonl_where(
+    subtitles: &[MergedSubtitle],
+    keep: impl Fn(&MergedSubtitle) -> bool,
+) -> String {
+    let mut output = String::new();
+    for (idx, cue) in subtitles.iter().enumerate() {
+        let timed = cue.as_timed();
+        if timed.lines.is_empty() || !keep(cue) {
+            continue;
+        }
+        let record = serde_json::json!({
+            "index": idx + 1,
+            "id": timed.id,
+            "start_ms": duration_millis(cue.start_time),
+            "end_ms": duration_millis(cue.end_time),
+            "lines": timed.lines,
+        });
+        let _ = writeln!(&mut output, "{record}");
+    }
+    output
+}
+
+/// Intervals of at least `min_gap` that no cue covers, from the start of the stream to
+/// `stream_end`, for sorted cues.
+///
+/// Gaps are inferred from the cues alone, so an interval where a region was detected but its
+/// cue was dropped (by `min_cue_chars`, an exclusion zone or empty OCR) counts as a gap too.
+pub fn find_gaps(
+    subtitles: &[MergedSubtitle],
+    min_gap: Duration,
+    stream_end: Duration,
+) -> Vec<SubtitleGap> {
+    let mut gaps = Vec::new();
+    let mut covered_until = Duration::ZERO;
+    let mut push_gap = |start_time: Duration, end_time: Duration| {
+        if end_time > start_time && end_time - start_time >= min_gap {
+            gaps.push(SubtitleGap {
+                start_time,
+                end_time,
+            });
+        }
+    };
+    for cue in subtitles {
+        push_gap(covered_until, cue.start_time);
+        covered_until = covered_until.max(cue.end_time);
+    }
+    push_gap(covered_until, stream_end);
+    gaps
+}
+
+pub fn render_gap_srt(gaps: &[SubtitleGap]) -> String {
+    let mut output = String::new();
+    for (idx, gap) in gaps.iter().enumerate() {
+        if idx > 0 {
+            output.push('\n');
+        }
+        let _ = writeln!(&mut output, "{}", idx + 1);
+        let _ = writeln!(
+            &mut output,
+            "{} --> {}",
+            format_timestamp(gap.start_time),
+            format_timestamp(gap.end_time)
+        );
+        let _ = writeln!(&mut output, "{GAP_MARKER_TEXT}");
+    }
+    output
+}
+
+/// Parses SRT text into cues numbered in file order, skipping blocks without a valid timing line.
+pub fn parse_srt(text: &str) -> Vec<TimedSubtitle> {
+    let text = text.trim_start_matches('\u{FEFF}').replace("\r\n", "\n");
+    let mut cues = Vec::new();
+    for block in text.split("\n\n") {
+        let mut lines = block
+            .lines()
+            .map(str::trim_end)
+            .skip_while(|line| line.is_empty());
+        let Some(mut timing) = lines.next() else {
+            continue;
+        };
+        if !timing.contains("-->") {
+            let Some(next) = lines.next() else {
+                continue;
+            };
+            timing = next;
+        }
+        let Some((start, end)) = timing.split_once("-->") else {
+            continue;
+        };
+        let (Some(start), Some(end)) = (parse_timestamp(start), parse_timestamp(end)) else {
+            continue;
+        };
+        cues.push(TimedSubtitle {
+            id: cues.len() as u64,
+            start_ms: start.as_secs_f64() * 1000.0,
+            end_ms: end.as_secs_f64() * 1000.0,
+            lines: lines
+                .map(|line| line.trim_start_matches(RTL_MARK).trim().to_string())
+                .filter(|line| !line.is_empty())
+                .collect(),
+            confidences: Vec::new(),
+        });
+    }
+    cues
+}
+
+fn ordered_lines(lines: &[SubtitleLine]) -> Vec<&SubtitleLine> {
     let mut refs: Vec<&SubtitleLine> = lines.iter().collect();
     refs.sort_by(|a, b| {
         a.center
             .partial_cmp(&b.center)
             .unwrap_or(std::cmp::Ordering::Equal)
     });
-    let mut ordered = Vec::new();
+    let mut ordered: Vec<&SubtitleLine> = Vec::new();
     for line in refs {
         let text = line.text.trim();
         if text.is_empty() {
             continue;
         }
-        if ordered.last().is_some_and(|last: &String| last == text) {
+        if ordered.last().is_some_and(|last| last.text.trim() == text) {
             continue;
         }
-        ordered.push(text.to_string());
+        ordered.push(line);
     }
     ordered
 }
 
 fn format_timestamp(time: Duration) -> String {
-    let millis = time
-        .as_secs()
-        .saturating_mul(1000)
-        .saturating_add(u64::from(time.subsec_millis()));
+    format_clock(time, ',')
+}
+
+/// `HH:MM:SS` plus milliseconds after `separator`: `,` for SRT, `.` for WebVTT.
+fn format_clock(time: Duration, separator: char) -> String {
+    let millis = duration_millis(time);
     let hours = millis / 3_600_000;
     let minutes = (millis % 3_600_000) / 60_000;
     let seconds = (millis % 60_000) / 1000;
     let remain_ms = millis % 1000;
-    format!("{hours:02}:{minutes:02}:{seconds:02},{remain_ms:03}")
+    format!("{hours:02}:{minutes:02}:{seconds:02}{separator}{remain_ms:03}")
+}
+
+/// ASS `H:MM:SS.cc`, truncated to centiseconds.
+fn format_ass_timestamp(time: Duration) -> String {
+    let centis = duration_millis(time) / 10;
+    let hours = centis / 360_000;
+    let minutes = (centis % 360_000) / 6_000;
+    let seconds = (centis % 6_000) / 100;
+    let remain_cs = centis % 100;
+    format!("{hours}:{minutes:02}:{seconds:02}.{remain_cs:02}")
+}
+
+fn duration_millis(time: Duration) -> u64 {
+    time.as_secs()
+        .saturating_mul(1000)
+        .saturating_add(u64::from(time.subsec_millis()))
+}
+
+/// Parses `HH:MM:SS,mmm`; a `.` millisecond separator and trailing cue settings are accepted.
+fn parse_timestamp(value: &str) -> Option<Duration> {
+    let value = value.split_whitespace().next()?;
+    let (clock, millis) = value.split_once([',', '.'])?;
+    let mut parts = clock.split(':').map(|part| part.parse::<u64>().ok());
+    let (hours, minutes, seconds) = (parts.next()??, parts.next()??, parts.next()??);
+    if parts.next().is_some() || minutes >= 60 || seconds >= 60 {
+        return None;
+    }
+    let millis: u64 = millis.parse().ok()?;
+    Some(Duration::from_millis(
+        ((hours * 60 + minutes) * 60 + seconds) * 1000 + millis,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cue(id: u64, start_ms: u64, end_ms: u64) -> MergedSubtitle {
+        MergedSubtitle {
+            id,
+            start_time: Duration::from_millis(start_ms),
+            end_time: Duration::from_millis(end_ms),
+            start_frame: 0,
+            end_frame: 0,
+            lines: vec![SubtitleLine {
+                center: 0.5,
+                text: format!("cue {id}"),
+                rtl: false,
+                confidence: None,
+                region: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn parse_srt_reads_rendered_output() {
+        let mut second = cue(2, 1_500, 2_250);
+        second.lines[0].rtl = true;
+        let srt = render_srt(&[cue(1, 0, 1_000), second]);
+
+        let parsed = parse_srt(&format!("\u{FEFF}{}", srt.replace('\n', "\r\n")));
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].start_ms, 0.0);
+        assert_eq!(parsed[0].end_ms, 1_000.0);
+        assert_eq!(parsed[0].lines, vec!["cue 1"]);
+        assert_eq!(parsed[1].start_ms, 1_500.0);
+        assert_eq!(parsed[1].end_ms, 2_250.0);
+        assert_eq!(parsed[1].lines, vec!["cue 2"]);
+
+        assert!(parse_srt("1\nnot a timing line\ntext\n").is_empty());
+    }
+
+    #[test]
+    fn cue_layout_sorts_before_separating_and_wrapping() {
+        let mut subtitles = [cue(2, 1_000, 2_000), cue(1, 0, 1_000)];
+        subtitles[0].lines[0].text = "second cue".to_string();
+        let layout = CueLayout {
+            min_cue_gap: Some(Duration::from_millis(40)),
+            wrap_width: Some(6),
+        };
+        layout.apply(&mut subtitles);
+        assert_eq!(subtitles[0].id, 1);
+        assert_eq!(subtitles[0].end_time, Duration::from_millis(960));
+        assert_eq!(subtitles[1].lines[0].text, "second\ncue");
+    }
+
+    #[test]
+    fn touching_cues_are_separated_by_the_minimum_gap() {
+        let mut subtitles = [
+            cue(1, 0, 1_000),
+            cue(2, 1_000, 2_000),
+            cue(3, 2_001, 3_000),
+            cue(4, 2_500, 4_000),
+            cue(5, 4_000, 4_020),
+            cue(6, 4_020, 5_000),
+        ];
+        enforce_min_gap(&mut subtitles, Duration::from_millis(40));
+        let ends: Vec<_> = subtitles
+            .iter()
+            .map(|cue| cue.end_time.as_millis())
+            .collect();
+        // Cues 3 and 4 overlap and stay put; cue 5 is too short to end 40 ms early.
+        assert_eq!(ends, [960, 1_961, 3_000, 3_960, 4_020, 5_000]);
+        assert_eq!(subtitles[1].start_time, Duration::from_millis(1_000));
+        assert_eq!(subtitles[1].lines[0].text, "cue 2");
+
+        let srt = render_srt(&subtitles[..2]);
+        assert!(
+            srt.contains("00:00:00,000 --> 00:00:00,960\ncue 1\n\n2\n00:00:01,000 --> "),
+            "{srt}"
+        );
+        let vtt = render_vtt(&subtitles[..2]);
+        assert!(vtt.contains("00:00:00.000 --> 00:00:00.960\n"), "{vtt}");
+    }
+
+    #[test]
+    fn long_lines_wrap_at_spaces_and_cjk_runs_are_cut() {
+        assert_eq!(
+            wrap_text("the quick brown fox jumps over", 12),
+            "the quick\nbrown fox\njumps over"
+        );
+        assert_eq!(wrap_text("short\nlines stay", 12), "short\nlines stay");
+        assert_eq!(
+            wrap_text("今天天气很好我们出去走走", 5),
+            "今天天气很\n好我们出去\n走走"
+        );
+        assert_eq!(wrap_text("ok 一二三四五六", 4), "ok\n一二三四\n五六");
+    }
+
+    #[test]
+    fn split_divides_a_cue_at_the_given_time() {
+        let mut merged = cue(4, 1_000, 3_000);
+        merged.start_frame = 25;
+        merged.end_frame = 75;
+        merged.lines.push(SubtitleLine {
+            center: 0.8,
+            text: "second line".to_string(),
+            rtl: false,
+            confidence: Some(0.9),
+            region: None,
+        });
+
+        let (first, second) = split_subtitle(&merged, Duration::from_millis(1_500)).expect("split");
+        assert_eq!(
+            (first.start_time, first.end_time),
+            (Duration::from_millis(1_000), Duration::from_millis(1_500))
+        );
+        assert_eq!((first.start_frame, first.end_frame), (25, 38));
+        assert_eq!(
+            (second.start_time, second.end_time),
+            (Duration::from_millis(1_500), Duration::from_millis(3_000))
+        );
+        assert_eq!((second.start_frame, second.end_frame), (38, 75));
+        assert_eq!(first.as_timed().lines, merged.as_timed().lines);
+        assert_eq!(second.as_timed().lines, merged.as_timed().lines);
+
+        // The split point has to leave both halves non-empty.
+        for at in [0, 1_000, 3_000, 4_000] {
+            assert!(split_subtitle(&merged, Duration::from_millis(at)).is_none());
+        }
+    }
+
+    #[test]
+    fn merge_combines_two_cues_into_one() {
+        let mut earlier = cue(3, 1_000, 2_000);
+        earlier.start_frame = 25;
+        earlier.end_frame = 50;
+        earlier.lines[0].text = "Where were you".to_string();
+        let mut later = cue(8, 2_040, 3_500);
+        later.start_frame = 51;
+        later.end_frame = 87;
+        later.lines[0].text = "Where were you".to_string();
+        later.lines.push(SubtitleLine {
+            center: 0.5,
+            text: "last night?".to_string(),
+            rtl: false,
+            confidence: None,
+            region: None,
+        });
+
+        for merged in [
+            merge_subtitles(&earlier, &later),
+            merge_subtitles(&later, &earlier),
+        ] {
+            assert_eq!(merged.id, 3);
+            assert_eq!(
+                (merged.start_time, merged.end_time),
+                (Duration::from_millis(1_000), Duration::from_millis(3_500))
+            );
+            assert_eq!((merged.start_frame, merged.end_frame), (25, 87));
+            let texts: Vec<&str> = merged.lines.iter().map(|line| line.text.as_str()).collect();
+            assert_eq!(texts, ["Where were you", "last night?"]);
+        }
+
+        // A cue lying inside the other keeps the outer bounds.
+        let inner = cue(9, 1_200, 1_800);
+        let merged = merge_subtitles(&earlier, &inner);
+        assert_eq!(
+            (merged.start_time, merged.end_time),
+            (Duration::from_millis(1_000), Duration::from_millis(2_000))
+        );
+        assert_eq!(merged.as_timed().lines, ["Where were you", "cue 9"]);
+    }
+
+    #[test]
+    fn long_absence_between_cues_becomes_gap_marker() {
+        let subtitles = [cue(1, 0, 1_000), cue(2, 1_500, 2_000), cue(3, 6_000, 7_000)];
+        let gaps = find_gaps(
+            &subtitles,
+            Duration::from_secs(2),
+            Duration::from_millis(8_000),
+        );
+        assert_eq!(
+            gaps,
+            vec![SubtitleGap {
+                start_time: Duration::from_millis(2_000),
+                end_time: Duration::from_millis(6_000),
+            }]
+        );
+
+        let srt = render_gap_srt(&gaps);
+        assert_eq!(srt, "1\n00:00:02,000 --> 00:00:06,000\n[no subtitle]\n");
+    }
+
+    #[test]
+    fn absence_before_the_first_and_after_the_last_cue_becomes_gap_markers() {
+        let gap = |start_ms, end_ms| SubtitleGap {
+            start_time: Duration::from_millis(start_ms),
+            end_time: Duration::from_millis(end_ms),
+        };
+        let subtitles = [cue(1, 3_000, 4_000), cue(2, 4_500, 5_000)];
+        assert_eq!(
+            find_gaps(
+                &subtitles,
+                Duration::from_secs(2),
+                Duration::from_millis(9_000)
+            ),
+            vec![gap(0, 3_000), gap(5_000, 9_000)]
+        );
+        assert_eq!(
+            find_gaps(&[], Duration::from_secs(2), Duration::from_millis(9_000)),
+            vec![gap(0, 9_000)]
+        );
+    }
 }
@@ -0,0 +1,474 @@
+//! Subtitle store with an optional cap on how many cues stay in memory.
+//!
+//! The merge stage mostly revises the newest cues, so once the resident count exceeds the cap
+//! the oldest cues are appended to a JSON-lines spill file and dropped from memory. They can
+//! still change: with `separate_regions` the merge stage may extend an older cue of another
+//! region, and the editor can edit or remove any cue. Such changes append a new record for the
+//! same id; when reloading, the last record per id wins.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use subtitle_fast_types::RoiConfig;
+
+use crate::stage::{SubtitleUpdate, SubtitleUpdateKind};
+use crate::subtitle::{MergedSubtitle, SubtitleLine, sort_subtitles};
+
+pub struct SubtitleBuffer {
+    resident: Vec<MergedSubtitle>,
+    limit: Option<NonZeroUsize>,
+    spill: Option<SpillLog>,
+    next_id: u64,
+}
+
+struct SpillLog {
+    path: PathBuf,
+    file: Option<File>,
+    ids: HashSet<u64>,
+}
+
+impl Default for SubtitleBuffer {
+    fn default() -> Self {
+        Self::unbounded()
+    }
+}
+
+impl SubtitleBuffer {
+    pub fn unbounded() -> Self {
+        Self {
+            resident: Vec::new(),
+            limit: None,
+            spill: None,
+            next_id: 0,
+        }
+    }
+
+    /// Keep at most `limit` cues in memory, spilling older ones to `spill_path`.
+    ///
+    /// The spill file is created on the first flush and removed when the buffer is cleared or
+    /// dropped.
+    pub fn bounded(limit: NonZeroUsize, spill_path: PathBuf) -> Self {
+        Self {
+            resident: Vec::new(),
+            limit: Some(limit),
+            spill: Some(SpillLog {
+                path: spill_path,
+                file: None,
+                ids: HashSet::new(),
+            }),
+            next_id: 0,
+        }
+    }
+
+    /// Cues currently held in memory, in arrival order.
+    pub fn resident(&self) -> &[MergedSubtitle] {
+        &self.resident
+    }
+
+    pub fn flushed_len(&self) -> usize {
+        self.spill.as_ref().map_or(0, |spill| spill.ids.len())
+    }
+
+    pub fn len(&self) -> usize {
+        self.resident.len() + self.flushed_len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn spill_path(&self) -> Option<&Path> {
+        self.spill.as_ref().map(|spill| spill.path.as_path())
+    }
+
+    /// Apply a pipeline update, returning the ids of cues flushed out of memory as a result.
+    ///
+    /// A revision of a cue that was already spilled is recorded in the spill file rather than
+    /// brought back into memory.
+    pub fn apply(&mut self, update: &SubtitleUpdate) -> io::Result<Vec<u64>> {
+        self.reserve_id(update.subtitle.id);
+        let id = update.subtitle.id;
+        match update.kind {
+            SubtitleUpdateKind::New => self.resident.push(update.subtitle.clone()),
+            SubtitleUpdateKind::Updated => {
+                if let Some(existing) = self.resident.iter_mut().find(|subtitle| subtitle.id == id)
+                {
+                    *existing = update.subtitle.clone();
+                } else if let Some(spill) =
+                    self.spill.as_mut().filter(|spill| spill.ids.contains(&id))
+                {
+                    spill.append(&SpillRecord::Cue(SpilledCue::from(&update.subtitle)))?;
+                } else {
+                    self.resident.push(update.subtitle.clone());
+                }
+            }
+        }
+        self.enforce_limit()
+    }
+
+    /// Add a cue that did not come from the pipeline, such as one split off in the editor,
+    /// returning the ids of cues flushed out of memory as a result.
+    pub fn insert(&mut self, subtitle: MergedSubtitle) -> io::Result<Vec<u64>> {
+        self.reserve_id(subtitle.id);
+        self.resident.push(subtitle);
+        self.enforce_limit()
+    }
+
+    /// An id no cue stored since the last clear has used, even if that cue was removed since.
+    pub fn next_id(&self) -> u64 {
+        self.next_id
+    }
+
+    /// The cue with `id`, wherever it lives.
+    pub fn get(&self, id: u64) -> io::Result<Option<MergedSubtitle>> {
+        if let Some(existing) = self.resident.iter().find(|subtitle| subtitle.id == id) {
+            return Ok(Some(existing.clone()));
+        }
+        match self.spill.as_ref().filter(|spill| spill.ids.contains(&id)) {
+            Some(spill) => Ok(spill.load()?.remove(&id)),
+            None => Ok(None),
+        }
+    }
+
+    /// Edit the cue with `id`, wherever it lives, and return its new state.
+    pub fn update(
+        &mut self,
+        id: u64,
+        edit: impl FnOnce(&mut MergedSubtitle),
+    ) -> io::Result<Option<MergedSubtitle>> {
+        if let Some(existing) = self.resident.iter_mut().find(|subtitle| subtitle.id == id) {
+            edit(existing);
+            return Ok(Some(existing.clone()));
+        }
+        let Some(spill) = self.spill.as_mut().filter(|spill| spill.ids.contains(&id)) else {
+            return Ok(None);
+        };
+        let Some(mut subtitle) = spill.load()?.remove(&id) else {
+            return Ok(None);
+        };
+        edit(&mut subtitle);
+        spill.append(&SpillRecord::Cue(SpilledCue::from(&subtitle)))?;
+        Ok(Some(subtitle))
+    }
+
+    /// Remove the cue with `id`, returning it if it existed.
+    pub fn remove(&mut self, id: u64) -> io::Result<Option<MergedSubtitle>> {
+        if let Some(index) = self.resident.iter().position(|subtitle| subtitle.id == id) {
+            return Ok(Some(self.resident.remove(index)));
+        }
+        let Some(spill) = self.spill.as_mut().filter(|spill| spill.ids.contains(&id)) else {
+            return Ok(None);
+        };
+        let Some(removed) = spill.load()?.remove(&id) else {
+            return Ok(None);
+        };
+        spill.append(&SpillRecord::Removed { id })?;
+        spill.ids.remove(&id);
+        Ok(Some(removed))
+    }
+
+    /// Every cue, including ones reloaded from the spill file, in presentation order.
+    pub fn all(&self) -> io::Result<Vec<MergedSubtitle>> {
+        let mut subtitles = match self.spill.as_ref() {
+            Some(spill) => spill.load()?.into_values().collect(),
+            None => Vec::new(),
+        };
+        subtitles.extend(self.resident.iter().cloned());
+        sort_subtitles(&mut subtitles);
+        Ok(subtitles)
+    }
+
+    pub fn clear(&mut self) {
+        self.resident.clear();
+        self.next_id = 0;
+        if let Some(spill) = self.spill.as_mut() {
+            spill.discard();
+        }
+    }
+
+    fn reserve_id(&mut self, id: u64) {
+        self.next_id = self.next_id.max(id.saturating_add(1));
+    }
+
+    fn enforce_limit(&mut self) -> io::Result<Vec<u64>> {
+        let Some(limit) = self.limit else {
+            return Ok(Vec::new());
+        };
+        let Some(spill) = self.spill.as_mut() else {
+            return Ok(Vec::new());
+        };
+        let excess = self.resident.len().saturating_sub(limit.get());
+        if excess == 0 {
+            return Ok(Vec::new());
+        }
+        let mut flushed = Vec::with_capacity(excess);
+        for subtitle in self.resident.drain(..excess) {
+            spill.append(&SpillRecord::Cue(SpilledCue::from(&subtitle)))?;
+            spill.ids.insert(subtitle.id);
+            flushed.push(subtitle.id);
+        }
+        Ok(flushed)
+    }
+}
+
+impl Drop for SubtitleBuffer {
+    fn drop(&mut self) {
+        if let Some(spill) = self.spill.as_mut() {
+            spill.discard();
+        }
+    }
+}
+
+impl SpillLog {
+    fn append(&mut self, record: &SpillRecord) -> io::Result<()> {
+        if self.file.is_none() {
+            let file = OpenOptions::new()
+                .create(true)
+                .write(true)
+                .truncate(true)
+                .open(&self.path)?;
+            self.file = Some(file);
+        }
+        let Some(file) = self.file.as_mut() else {
+            return Ok(());
+        };
+        let mut line = serde_json::to_string(record).map_err(io::Error::other)?;
+        line.push('\n');
+        file.write_all(line.as_bytes())?;
+        file.flush()
+    }
+
+    fn load(&self) -> io::Result<HashMap<u64, MergedSubtitle>> {
+        let mut subtitles = HashMap::new();
+        if self.file.is_none() {
+            return Ok(subtitles);
+        }
+        let reader = BufReader::new(File::open(&self.path)?);
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(&line).map_err(io::Error::other)? {
+                SpillRecord::Cue(cue) => {
+                    subtitles.insert(cue.id, cue.into());
+                }
+                SpillRecord::Removed { id } => {
+                    subtitles.remove(&id);
+                }
+            }
+        }
+        Ok(subtitles)
+    }
+
+    fn discard(&mut self) {
+        if self.file.take().is_some() {
+            let _ = fs::remove_file(&self.path);
+        }
+        self.ids.clear();
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "record", rename_all = "snake_case")]
+enum SpillRecord {
+    Cue(SpilledCue),
+    Removed { id: u64 },
+}
+
+#[derive(Serialize, Deserialize)]
+struct SpilledCue {
+    id: u64,
+    start_time: Duration,
+    end_time: Duration,
+    start_frame: u64,
+    end_frame: u64,
+    lines: Vec<SpilledLine>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SpilledLine {
+    center: f32,
+    text: String,
+    rtl: bool,
+    confidence: Option<f32>,
+    region: Option<[f32; 4]>,
+}
+
+impl From<&MergedSubtitle> for SpilledCue {
+    fn from(subtitle: &MergedSubtitle) -> Self {
+        Self {
+            id: subtitle.id,
+            start_time: subtitle.start_time,
+            end_time: subtitle.end_time,
+            start_frame: subtitle.start_frame,
+            end_frame: subtitle.end_frame,
+            lines: subtitle
+                .lines
+                .iter()
+                .map(|line| SpilledLine {
+                    center: line.center,
+                    text: line.text.clone(),
+                    rtl: line.rtl,
+                    confidence: line.confidence,
+                    region: line.region.map(|roi| [roi.x, roi.y, roi.width, roi.height]),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<SpilledCue> for MergedSubtitle {
+    fn from(cue: SpilledCue) -> Self {
+        Self {
+            id: cue.id,
+            start_time: cue.start_time,
+            end_time: cue.end_time,
+            start_frame: cue.start_frame,
+            end_frame: cue.end_frame,
+            lines: cue
+                .lines
+                .into_iter()
+                .map(|line| SubtitleLine {
+                    center: line.center,
+                    text: line.text,
+                    rtl: line.rtl,
+                    confidence: line.confidence,
+                    region: line.region.map(|[x, y, width, height]| RoiConfig {
+                        x,
+                        y,
+                        width,
+                        height,
+                    }),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::subtitle::render_srt;
+
+    fn cue(id: u64) -> MergedSubtitle {
+        MergedSubtitle {
+            id,
+            start_time: Duration::from_secs(id * 2),
+            end_time: Duration::from_secs(id * 2 + 1),
+            start_frame: id * 60,
+            end_frame: id * 60 + 30,
+            lines: vec![SubtitleLine {
+                center: 0.9,
+                text: format!("cue {id}"),
+                rtl: false,
+                confidence: Some(0.8),
+                region: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn resident_cues_stay_bounded_while_spill_keeps_everything() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let spill_path = dir.path().join("cues.jsonl");
+        let limit = NonZeroUsize::new(3).unwrap();
+        let mut buffer = SubtitleBuffer::bounded(limit, spill_path.clone());
+
+        let mut flushed = Vec::new();
+        for id in 0..10 {
+            let update = SubtitleUpdate {
+                kind: SubtitleUpdateKind::New,
+                subtitle: cue(id),
+            };
+            flushed.extend(buffer.apply(&update).expect("apply"));
+            assert!(buffer.resident().len() <= limit.get());
+        }
+        assert_eq!(flushed, (0..7).collect::<Vec<_>>());
+        assert_eq!(buffer.len(), 10);
+
+        let spilled = fs::read_to_string(&spill_path).expect("spill file");
+        assert_eq!(spilled.lines().count(), 7);
+
+        let all = buffer.all().expect("reload");
+        let ids: Vec<u64> = all.iter().map(|subtitle| subtitle.id).collect();
+        assert_eq!(ids, (0..10).collect::<Vec<_>>());
+        assert_eq!(all[0].lines[0].text, "cue 0");
+        assert_eq!(all[0].lines[0].confidence, Some(0.8));
+        assert_eq!(render_srt(&all).matches(" --> ").count(), 10);
+
+        let edited = buffer
+            .update(1, |subtitle| subtitle.lines[0].text = "edited".to_string())
+            .expect("update")
+            .expect("spilled cue found");
+        assert_eq!(edited.lines[0].text, "edited");
+        assert_eq!(
+            buffer.remove(2).expect("remove").map(|removed| removed.id),
+            Some(2)
+        );
+        assert!(buffer.remove(2).expect("remove twice").is_none());
+        assert_eq!(buffer.next_id(), 10);
+        assert_eq!(
+            buffer
+                .get(1)
+                .expect("get")
+                .map(|subtitle| subtitle.lines[0].text.clone()),
+            Some("edited".to_string())
+        );
+
+        // Removing the newest cue does not hand its id out again.
+        assert_eq!(
+            buffer.remove(9).expect("remove").map(|removed| removed.id),
+            Some(9)
+        );
+        assert_eq!(buffer.next_id(), 10);
+        assert!(buffer.get(9).expect("get removed").is_none());
+
+        let all = buffer.all().expect("reload");
+        assert_eq!(all.len(), 8);
+        assert_eq!(all[1].lines[0].text, "edited");
+        assert!(all.iter().all(|subtitle| subtitle.id != 2));
+
+        buffer.clear();
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.next_id(), 0);
+        assert!(!spill_path.exists());
+    }
+
+    #[test]
+    fn updates_to_spilled_cues_are_recorded_in_the_spill_file() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let limit = NonZeroUsize::new(1).unwrap();
+        let mut buffer = SubtitleBuffer::bounded(limit, dir.path().join("cues.jsonl"));
+        for id in 0..3 {
+            let update = SubtitleUpdate {
+                kind: SubtitleUpdateKind::New,
+                subtitle: cue(id),
+            };
+            buffer.apply(&update).expect("apply");
+        }
+        assert_eq!(buffer.flushed_len(), 2);
+
+        let mut revised = cue(0);
+        revised.end_time = Duration::from_secs(3);
+        revised.lines[0].text = "revised".to_string();
+        let update = SubtitleUpdate {
+            kind: SubtitleUpdateKind::Updated,
+            subtitle: revised,
+        };
+        assert!(buffer.apply(&update).expect("apply").is_empty());
+        assert_eq!(buffer.resident().len(), 1);
+        assert_eq!(buffer.len(), 3);
+
+        let all = buffer.all().expect("reload");
+        let ids: Vec<u64> = all.iter().map(|subtitle| subtitle.id).collect();
+        assert_eq!(ids, [0, 1, 2]);
+        assert_eq!(all[0].lines[0].text, "revised");
+        assert_eq!(all[0].end_time, Duration::from_secs(3));
+    }
+}
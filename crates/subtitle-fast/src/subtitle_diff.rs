@@ -0,0 +1,144 @@
+//! Classifies cues against a reference track, such as an SRT exported by an earlier run.
+//!
+//! Cues are paired one-to-one by temporal overlap, largest overlap first. A paired cue is
+//! unchanged when its text matches after trimming, changed otherwise; unpaired current cues are
+//! new and unpaired reference cues are removed.
+
+use std::collections::HashMap;
+
+use crate::subtitle::TimedSubtitle;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CueDiff {
+    Unchanged,
+    Changed,
+    New,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct SubtitleDiff {
+    statuses: HashMap<u64, CueDiff>,
+    removed: Vec<TimedSubtitle>,
+}
+
+impl SubtitleDiff {
+    /// Status of the current cue with `id`, if it took part in the diff.
+    pub fn status(&self, id: u64) -> Option<CueDiff> {
+        self.statuses.get(&id).copied()
+    }
+
+    /// Reference cues with no counterpart in the current track, in reference order.
+    pub fn removed(&self) -> &[TimedSubtitle] {
+        &self.removed
+    }
+
+    pub fn count(&self, status: CueDiff) -> usize {
+        self.statuses
+            .values()
+            .filter(|value| **value == status)
+            .count()
+    }
+}
+
+pub fn diff_subtitles(current: &[TimedSubtitle], reference: &[TimedSubtitle]) -> SubtitleDiff {
+    let mut by_start: Vec<usize> = (0..reference.len()).collect();
+    by_start.sort_by(|a, b| reference[*a].start_ms.total_cmp(&reference[*b].start_ms));
+    let longest = reference
+        .iter()
+        .map(|cue| cue.end_ms - cue.start_ms)
+        .fold(0.0, f64::max);
+
+    let mut pairs = Vec::new();
+    for (current_idx, cue) in current.iter().enumerate() {
+        let lo = by_start.partition_point(|idx| reference[*idx].start_ms < cue.start_ms - longest);
+        let hi = by_start.partition_point(|idx| reference[*idx].start_ms < cue.end_ms);
+        for &reference_idx in &by_start[lo..hi.max(lo)] {
+            let other = &reference[reference_idx];
+            let overlap = cue.end_ms.min(other.end_ms) - cue.start_ms.max(other.start_ms);
+            if overlap > 0.0 {
+                pairs.push((overlap, current_idx, reference_idx));
+            }
+        }
+    }
+    pairs.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)).then(a.2.cmp(&b.2)));
+
+    let mut matched = vec![None; current.len()];
+    let mut reference_used = vec![false; reference.len()];
+    for (_, current_idx, reference_idx) in pairs {
+        if matched[current_idx].is_some() || reference_used[reference_idx] {
+            continue;
+        }
+        matched[current_idx] = Some(reference_idx);
+        reference_used[reference_idx] = true;
+    }
+
+    let statuses = current
+        .iter()
+        .zip(matched)
+        .map(|(cue, reference_idx)| {
+            let status = match reference_idx {
+                None => CueDiff::New,
+                Some(idx) if normalized_text(cue) == normalized_text(&reference[idx]) => {
+                    CueDiff::Unchanged
+                }
+                Some(_) => CueDiff::Changed,
+            };
+            (cue.id, status)
+        })
+        .collect();
+    let removed = reference
+        .iter()
+        .zip(reference_used)
+        .filter(|(_, used)| !used)
+        .map(|(cue, _)| cue.clone())
+        .collect();
+    SubtitleDiff { statuses, removed }
+}
+
+fn normalized_text(cue: &TimedSubtitle) -> Vec<&str> {
+    cue.lines
+        .iter()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::subtitle::parse_srt;
+
+    fn cue(id: u64, start_ms: f64, end_ms: f64, text: &str) -> TimedSubtitle {
+        TimedSubtitle {
+            id,
+            start_ms,
+            end_ms,
+            lines: text.lines().map(str::to_string).collect(),
+            confidences: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn cues_are_classified_by_overlap_and_text() {
+        let reference = parse_srt(
+            "1\n00:00:01,000 --> 00:00:02,000\nHello\n\n\
+             2\n00:00:03,000 --> 00:00:04,000\nOld wording\n\n\
+             3\n00:00:05,000 --> 00:00:06,000\nDropped line\n",
+        );
+        let current = vec![
+            cue(10, 1_100.0, 2_050.0, " Hello "),
+            cue(11, 2_900.0, 3_900.0, "New wording"),
+            cue(12, 3_950.0, 4_200.0, "Old wording"),
+            cue(13, 7_000.0, 8_000.0, "Brand new"),
+        ];
+
+        let diff = diff_subtitles(&current, &reference);
+        assert_eq!(diff.status(10), Some(CueDiff::Unchanged));
+        assert_eq!(diff.status(11), Some(CueDiff::Changed));
+        assert_eq!(diff.status(12), Some(CueDiff::New));
+        assert_eq!(diff.status(13), Some(CueDiff::New));
+        assert_eq!(diff.count(CueDiff::New), 2);
+        assert_eq!(diff.removed().len(), 1);
+        assert_eq!(diff.removed()[0].lines, vec!["Dropped line"]);
+    }
+}
@@ -0,0 +1,151 @@
+//! Directory polling behind `--watch`, for capture setups that keep dropping recordings into a
+//! folder.
+//!
+//! A file counts as complete once its size and modification time are unchanged between two
+//! polls, so a recording still being written is left alone. A video is handed out again after
+//! it next settles until it is marked done, and videos that already have a subtitle file beside
+//! them are skipped.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::log_warn;
+use crate::output_format::OutputFormat;
+use crate::stage::default_output_path;
+
+/// Video file extensions accepted by `--watch` and the GUI's file picker.
+pub const SUPPORTED_VIDEO_EXTENSIONS: &[&str] = &[
+    "mp4", "mov", "mkv", "webm", "avi", "m4v", "mpg", "mpeg", "ts",
+];
+
+/// Time between two scans of a watched directory; also how long a file must stay unchanged.
+pub const WATCH_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+pub fn is_supported_video_path(path: &Path) -> bool {
+    let Some(ext) = path.extension().and_then(|ext| ext.to_str()) else {
+        return false;
+    };
+    SUPPORTED_VIDEO_EXTENSIONS
+        .iter()
+        .any(|allowed| allowed.eq_ignore_ascii_case(ext))
+}
+
+/// Size and modification time a file had at the last poll.
+type FileState = (u64, Option<SystemTime>);
+
+pub struct WatchedDir {
+    dir: PathBuf,
+    format: OutputFormat,
+    /// Files seen growing or for the first time at the last poll.
+    pending: HashMap<PathBuf, FileState>,
+    /// Files marked done or found with a subtitle file, never returned again.
+    done: HashSet<PathBuf>,
+}
+
+impl WatchedDir {
+    /// Watches `dir` for videos whose `format` subtitle file does not exist yet.
+    pub fn new(dir: PathBuf, format: OutputFormat) -> Self {
+        Self {
+            dir,
+            format,
+            pending: HashMap::new(),
+            done: HashSet::new(),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Videos that have stopped changing since the previous poll, in path order. Fails only when
+    /// the directory itself cannot be read; an unreadable entry is skipped.
+    pub fn poll(&mut self) -> io::Result<Vec<PathBuf>> {
+        let mut ready = Vec::new();
+        let mut pending = HashMap::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = match entry {
+                Ok(entry) => entry.path(),
+                Err(err) => {
+                    log_warn!(
+                        "warning: skipping an unreadable entry in {}: {err}",
+                        self.dir.display()
+                    );
+                    continue;
+                }
+            };
+            if !is_supported_video_path(&path) || self.done.contains(&path) {
+                continue;
+            }
+            // A file removed or locked mid-scan is looked at again next time.
+            let Ok(metadata) = fs::metadata(&path) else {
+                continue;
+            };
+            if !metadata.is_file() {
+                continue;
+            }
+            if default_output_path(&path, self.format).exists() {
+                self.done.insert(path);
+                continue;
+            }
+            let state = (metadata.len(), metadata.modified().ok());
+            if state.0 > 0 && self.pending.get(&path) == Some(&state) {
+                ready.push(path);
+            } else {
+                pending.insert(path, state);
+            }
+        }
+        self.pending = pending;
+        ready.sort();
+        Ok(ready)
+    }
+
+    /// Stops handing out `video`, once it has been processed.
+    pub fn mark_done(&mut self, video: &Path) {
+        self.done.insert(video.to_path_buf());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::OpenOptions;
+    use std::io::Write;
+
+    #[test]
+    fn new_video_is_handed_out_once_it_stops_growing() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut watched = WatchedDir::new(dir.path().to_path_buf(), OutputFormat::Srt);
+        assert!(watched.poll().expect("poll").is_empty());
+
+        let video = dir.path().join("capture.mkv");
+        fs::write(&video, b"first chunk").expect("write video");
+        fs::write(dir.path().join("notes.txt"), b"not a video").expect("write notes");
+        // Already subtitled before the watch saw it.
+        fs::write(dir.path().join("old.mp4"), b"done").expect("write old video");
+        fs::write(dir.path().join("old.srt"), b"").expect("write old subtitles");
+
+        // First sighting, then still being written.
+        assert!(watched.poll().expect("poll").is_empty());
+        OpenOptions::new()
+            .append(true)
+            .open(&video)
+            .and_then(|mut file| file.write_all(b", second chunk"))
+            .expect("append");
+        assert!(watched.poll().expect("poll").is_empty());
+
+        // Unchanged since the last poll: complete.
+        assert_eq!(watched.poll().expect("poll"), std::slice::from_ref(&video));
+
+        // Not marked done, say because the run was interrupted: handed out again once settled.
+        assert!(watched.poll().expect("poll").is_empty());
+        assert_eq!(watched.poll().expect("poll"), std::slice::from_ref(&video));
+
+        // Marked done: never returned again.
+        watched.mark_done(&video);
+        assert!(watched.poll().expect("poll").is_empty());
+        assert!(watched.poll().expect("poll").is_empty());
+    }
+}